@@ -13,9 +13,10 @@ use anyhow::bail;
 use anyhow::ensure;
 use holochain_conductor_api::AdminRequest;
 use holochain_conductor_api::AdminResponse;
+use holochain_conductor_api::AllowedOrigins;
+use holochain_conductor_api::AppInfo;
 use holochain_conductor_api::AppStatusFilter;
 use holochain_conductor_api::InterfaceDriver;
-use holochain_conductor_api::{AdminInterfaceConfig, AppInfo};
 use holochain_p2p::kitsune_p2p::agent_store::AgentInfoSigned;
 use holochain_types::prelude::DnaHash;
 use holochain_types::prelude::DnaModifiersOpt;
@@ -43,6 +44,15 @@ pub struct Call {
     #[arg(short, long, conflicts_with_all = &["existing_paths", "indices"], value_delimiter = ',')]
     pub running: Vec<u16>,
 
+    /// Paths to unix domain sockets of running conductor admin interfaces.
+    /// Cannot be combined with running ports or existing sandboxes.
+    #[arg(
+        long,
+        conflicts_with_all = &["running", "existing_paths", "indices"],
+        value_delimiter = ','
+    )]
+    pub unix_sockets: Vec<PathBuf>,
+
     #[command(flatten)]
     pub existing: Existing,
 
@@ -58,6 +68,7 @@ pub struct Call {
 pub enum AdminRequestCli {
     AddAdminWs(AddAdminWs),
     AddAppWs(AddAppWs),
+    AddAppUnixSocket(AddAppUnixSocket),
     RegisterDna(RegisterDna),
     InstallApp(InstallApp),
     /// Calls AdminRequest::UninstallApp.
@@ -97,6 +108,19 @@ pub struct AddAppWs {
     /// Optional port number.
     /// Defaults to assigned by OS.
     pub port: Option<u16>,
+
+    /// Origins that may connect to the interface, as a comma separated list.
+    /// Defaults to any origin.
+    #[arg(long, default_value = "*")]
+    pub allowed_origins: AllowedOrigins,
+}
+
+/// Calls AdminRequest::AttachAppUnixSocketInterface
+/// and adds another app interface listening on a unix domain socket.
+#[derive(Debug, Parser, Clone)]
+pub struct AddAppUnixSocket {
+    /// Path to create the socket at.
+    pub path: PathBuf,
 }
 
 /// Calls AdminRequest::RegisterDna
@@ -214,9 +238,16 @@ pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
     let Call {
         existing,
         running,
+        unix_sockets,
         call,
     } = req;
-    let cmds = if running.is_empty() {
+    let cmds = if !unix_sockets.is_empty() {
+        let mut cmds = Vec::with_capacity(unix_sockets.len());
+        for path in unix_sockets {
+            cmds.push((unix_socket_cmd(path).await?, None, None));
+        }
+        cmds
+    } else if running.is_empty() {
         let paths = if existing.is_empty() {
             crate::save::load(std::env::current_dir()?)?
         } else {
@@ -259,6 +290,16 @@ pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(unix)]
+async fn unix_socket_cmd(path: PathBuf) -> anyhow::Result<CmdRunner> {
+    Ok(CmdRunner::try_new_unix_socket(path).await?)
+}
+
+#[cfg(not(unix))]
+async fn unix_socket_cmd(_path: PathBuf) -> anyhow::Result<CmdRunner> {
+    bail!("Unix domain sockets are not supported on this platform")
+}
+
 async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Result<()> {
     match call {
         AdminRequestCli::AddAdminWs(args) => {
@@ -269,9 +310,13 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             let port = attach_app_interface(cmd, args).await?;
             msg!("Added app port {}", port);
         }
+        AdminRequestCli::AddAppUnixSocket(args) => {
+            let path = attach_app_unix_socket_interface(cmd, args).await?;
+            msg!("Added app unix socket {}", path.display());
+        }
         AdminRequestCli::ListAppWs => {
-            let ports = list_app_ws(cmd).await?;
-            msg!("Attached app interfaces {:?}", ports);
+            let drivers = list_app_ws(cmd).await?;
+            msg!("Attached app interfaces {:?}", drivers);
         }
         AdminRequestCli::RegisterDna(args) => {
             let dnas = register_dna(cmd, args).await?;
@@ -379,9 +424,7 @@ pub async fn add_admin_interface(cmd: &mut CmdRunner, args: AddAdminWs) -> anyho
     let port = args.port.unwrap_or(0);
     let resp = cmd
         .command(AdminRequest::AddAdminInterfaces(vec![
-            AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port },
-            },
+            crate::ports::sandbox_admin_interface(port),
         ]))
         .await?;
     ensure!(
@@ -482,7 +525,7 @@ pub async fn uninstall_app(cmd: &mut CmdRunner, args: UninstallApp) -> anyhow::R
 }
 
 /// Calls [`AdminRequest::ListAppInterfaces`].
-pub async fn list_app_ws(cmd: &mut CmdRunner) -> anyhow::Result<Vec<u16>> {
+pub async fn list_app_ws(cmd: &mut CmdRunner) -> anyhow::Result<Vec<InterfaceDriver>> {
     let resp = cmd.command(AdminRequest::ListAppInterfaces).await?;
     Ok(expect_match!(resp => AdminResponse::AppInterfacesListed, "Failed to list app interfaces"))
 }
@@ -546,7 +589,10 @@ pub async fn disable_app(cmd: &mut CmdRunner, args: DisableApp) -> anyhow::Resul
 /// Calls [`AdminRequest::AttachAppInterface`] and adds another app interface.
 pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow::Result<u16> {
     let resp = cmd
-        .command(AdminRequest::AttachAppInterface { port: args.port })
+        .command(AdminRequest::AttachAppInterface {
+            port: args.port,
+            allowed_origins: args.allowed_origins,
+        })
         .await?;
     tracing::debug!(?resp);
     match resp {
@@ -559,6 +605,28 @@ pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow
     }
 }
 
+/// Calls [`AdminRequest::AttachAppUnixSocketInterface`] and adds another app interface
+/// listening on a unix domain socket.
+pub async fn attach_app_unix_socket_interface(
+    cmd: &mut CmdRunner,
+    args: AddAppUnixSocket,
+) -> anyhow::Result<PathBuf> {
+    let resp = cmd
+        .command(AdminRequest::AttachAppUnixSocketInterface {
+            path: args.path.clone(),
+        })
+        .await?;
+    tracing::debug!(?resp);
+    match resp {
+        AdminResponse::AppUnixSocketInterfaceAttached { path } => Ok(path),
+        _ => Err(anyhow!(
+            "Failed to attach app unix socket interface {:?}, got: {:?}",
+            args.path,
+            resp
+        )),
+    }
+}
+
 /// Calls [`AdminRequest::DumpState`] and dumps the current cell's state.
 // TODO: Add pretty print.
 // TODO: Default to dumping all cell state.
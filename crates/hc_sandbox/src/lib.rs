@@ -73,6 +73,14 @@ impl CmdRunner {
         Ok(Self { client })
     }
 
+    /// Create a new connection for calling admin interface commands
+    /// over a unix domain socket.
+    #[cfg(unix)]
+    pub async fn try_new_unix_socket(path: PathBuf) -> WebsocketResult<Self> {
+        let client = ports::get_admin_api_unix_socket(path).await?;
+        Ok(Self { client })
+    }
+
     /// Create a command runner from a sandbox path.
     /// This expects holochain to be on the path.
     pub async fn from_sandbox(
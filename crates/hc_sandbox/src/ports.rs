@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use holochain_conductor_api::{
-    config::conductor::ConductorConfig, AdminInterfaceConfig, InterfaceDriver,
+    config::conductor::ConductorConfig, AdminInterfaceAuth, AdminInterfaceConfig, InterfaceDriver,
 };
 use holochain_websocket::{self as ws, WebsocketConfig, WebsocketReceiver, WebsocketSender};
 use url2::prelude::*;
@@ -33,7 +33,8 @@ pub async fn get_admin_ports(paths: Vec<PathBuf>) -> anyhow::Result<Vec<u16>> {
         if let Some(config) = read_config(p)? {
             if let Some(ai) = config.admin_interfaces {
                 if let Some(AdminInterfaceConfig {
-                    driver: InterfaceDriver::Websocket { port },
+                    driver: InterfaceDriver::Websocket { port, .. },
+                    ..
                 }) = ai.get(0)
                 {
                     ports.push(*port)
@@ -59,29 +60,44 @@ async fn websocket_client_by_port(
     .await
 }
 
+/// Connect to an admin interface that is listening on a unix domain socket.
+#[cfg(unix)]
+pub(crate) async fn get_admin_api_unix_socket(path: PathBuf) -> WebsocketResult<WebsocketSender> {
+    tracing::debug!(?path);
+    ws::connect_unix(path, Arc::new(WebsocketConfig::default()))
+        .await
+        .map(|p| p.0)
+}
+
 pub(crate) fn random_admin_port(config: &mut ConductorConfig) {
     match config.admin_interfaces.as_mut().and_then(|i| i.first_mut()) {
         Some(AdminInterfaceConfig {
-            driver: InterfaceDriver::Websocket { port },
+            driver: InterfaceDriver::Websocket { port, .. },
+            ..
         }) => {
             if *port != 0 {
                 *port = 0;
             }
         }
+        // A unix socket path is chosen by the user so it is left alone.
+        Some(_) => {}
         None => {
             let port = 0;
-            config.admin_interfaces = Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port },
-            }]);
+            config.admin_interfaces = Some(vec![sandbox_admin_interface(port)]);
         }
     }
 }
 
+/// The admin interface that sandboxes are given. Sandboxes are for local
+/// development, and their clients only know the admin port, so connections
+/// to it don't authenticate.
+pub(crate) fn sandbox_admin_interface(port: u16) -> AdminInterfaceConfig {
+    AdminInterfaceConfig::websocket(port).with_auth(AdminInterfaceAuth::None)
+}
+
 pub(crate) fn set_admin_port(config: &mut ConductorConfig, port: u16) {
     let p = port;
-    let port = AdminInterfaceConfig {
-        driver: InterfaceDriver::Websocket { port },
-    };
+    let port = sandbox_admin_interface(port);
     match config
         .admin_interfaces
         .as_mut()
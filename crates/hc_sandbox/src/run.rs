@@ -4,6 +4,7 @@ use std::path::Path;
 use std::{path::PathBuf, process::Stdio};
 
 use holochain_conductor_api::conductor::{ConductorConfig, KeystoreConfig};
+use holochain_conductor_api::AllowedOrigins;
 use tokio::io::AsyncBufReadExt;
 use tokio::io::BufReader;
 use tokio::process::{Child, Command};
@@ -48,6 +49,7 @@ pub async fn run(
             &mut cmd,
             AddAppWs {
                 port: Some(app_port),
+                allowed_origins: AllowedOrigins::Any,
             },
         )
         .await?;
@@ -1,7 +1,11 @@
 use assert_cmd::prelude::*;
 use holochain_cli_sandbox::cli::LaunchInfo;
+use holochain_conductor_api::AdminRequest;
+use holochain_conductor_api::AdminResponse;
+use holochain_conductor_api::AppAuthenticationRequest;
 use holochain_conductor_api::AppRequest;
 use holochain_conductor_api::AppResponse;
+use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
 use holochain_websocket::{self as ws, WebsocketConfig, WebsocketReceiver, WebsocketSender};
 use matches::assert_matches;
 use once_cell::sync::Lazy;
@@ -64,18 +68,39 @@ async fn new_websocket_client_for_port(
     .await?)
 }
 
-async fn get_app_info(port: u16) {
+async fn get_app_info(admin_port: u16, port: u16) {
     tracing::debug!(calling_app_interface = ?port);
+    let (mut admin_tx, _) = new_websocket_client_for_port(admin_port)
+        .await
+        .expect(&format!(
+            "Failed to connect to conductor on port [{}]",
+            admin_port
+        ));
+    let request = AdminRequest::IssueAppAuthenticationToken(
+        IssueAppAuthenticationTokenPayload::for_apps(vec!["test-app".to_string()]),
+    );
+    let response = admin_tx.request(request);
+    let r: AdminResponse = check_timeout(response).await;
+    let token = match r {
+        AdminResponse::AppAuthenticationTokenIssued(issued) => issued.token,
+        _ => panic!("Failed to issue app authentication token: {:?}", r),
+    };
+
     let (mut app_tx, _) = new_websocket_client_for_port(port).await.expect(&format!(
         "Failed to connect to conductor on port [{}]",
         port
     ));
+    let request = AppRequest::Authenticate(AppAuthenticationRequest { token });
+    let response = app_tx.request(request);
+    let r: AppResponse = check_timeout(response).await;
+    assert_matches!(r, AppResponse::Authenticated(_));
+
     let request = AppRequest::AppInfo {
-        installed_app_id: "Stub".to_string(),
+        installed_app_id: "test-app".to_string(),
     };
     let response = app_tx.request(request);
     let r: AppResponse = check_timeout(response).await;
-    assert_matches!(r, AppResponse::AppInfo(None));
+    assert_matches!(r, AppResponse::AppInfo(Some(_)));
 }
 
 async fn check_timeout<T>(response: impl Future<Output = Result<T, ws::WebsocketError>>) -> T {
@@ -154,7 +179,11 @@ async fn generate_sandbox_and_connect() {
     let launch_info = get_launch_info(hc_admin.stdout.take().unwrap()).await;
 
     // - Make a call to list app info to the port
-    get_app_info(*launch_info.app_ports.first().expect("No app ports found")).await;
+    get_app_info(
+        launch_info.admin_port,
+        *launch_info.app_ports.first().expect("No app ports found"),
+    )
+    .await;
 }
 
 /// Generates a new sandbox with a single app deployed and tries to list DNA
@@ -4,7 +4,8 @@
 use holo_hash::{ActionHash, AgentPubKey, AnyLinkableHash, DnaHash, EntryHash};
 use holochain_integrity_types::{
     AgentValidationPkg, CloseChain, Create, CreateLink, Delete, DeleteLink, Dna,
-    EntryCreationAction, InitZomesComplete, LinkTag, MembraneProof, OpenChain, UnitEnum, Update,
+    EntryCreationAction, InitZomesComplete, LinkTag, MembraneProof, MigrationTarget, OpenChain,
+    UnitEnum, Update,
 };
 
 mod flat_op_activity;
@@ -151,19 +151,19 @@ pub enum OpActivity<UnitType, LT> {
     },
     /// This operation registers the [`Action`] for an
     /// [`Action::OpenChain`] to the author's chain
-    /// and contains the previous chains's [`DnaHash`].
+    /// and contains the previous chain's [`MigrationTarget`].
     OpenChain {
-        /// Hash of the prevous DNA that we are migrating from
-        previous_dna_hash: DnaHash,
+        /// The DNA or agent key that we are migrating from
+        previous_target: MigrationTarget,
         /// The [`OpenChain`] action
         action: OpenChain,
     },
     /// This operation registers the [`Action`] for an
     /// [`Action::CloseChain`] to the author's chain
-    /// and contains the new chains's [`DnaHash`].
+    /// and contains the new chain's [`MigrationTarget`].
     CloseChain {
-        /// Hash of the new DNA that we are migrating to
-        new_dna_hash: DnaHash,
+        /// The DNA or agent key that we are migrating to
+        new_target: MigrationTarget,
         /// The [`CloseChain`] action
         action: CloseChain,
     },
@@ -149,19 +149,19 @@ pub enum OpRecord<ET: UnitEnum, LT> {
     },
     /// This operation stores the [`Record`] for an
     /// [`Action::OpenChain`] and contains the previous
-    /// chains's [`DnaHash`].
+    /// chain's [`MigrationTarget`].
     OpenChain {
-        /// Hash of the prevous DNA that we are migrating from
-        previous_dna_hash: DnaHash,
+        /// The DNA or agent key that we are migrating from
+        previous_target: MigrationTarget,
         /// The [`OpenChain`] action
         action: OpenChain,
     },
     /// This operation stores the [`Record`] for an
     /// [`Action::CloseChain`] and contains the new
-    /// chains's [`DnaHash`].
+    /// chain's [`MigrationTarget`].
     CloseChain {
-        /// Hash of the new DNA that we are migrating to
-        new_dna_hash: DnaHash,
+        /// The DNA or agent key that we are migrating to
+        new_target: MigrationTarget,
         /// The [`CloseChain`] action
         action: CloseChain,
     },
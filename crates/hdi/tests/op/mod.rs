@@ -140,8 +140,8 @@ fn op_errors(op: Op) -> WasmErrorInner {
 #[test_case(FlatOp::RegisterAgentActivity(OpActivity::DeleteLink{ action: dl(ah(0)), original_action_hash: ah(0), base_address: eh(0).into()}))]
 // Action's without entries
 #[test_case(FlatOp::RegisterAgentActivity(OpActivity::Dna { action: dna(dh(0)), dna_hash: dh(0)}))]
-#[test_case(FlatOp::RegisterAgentActivity(OpActivity::OpenChain { previous_dna_hash: dh(0), action: oc(dh(0))}))]
-#[test_case(FlatOp::RegisterAgentActivity(OpActivity::CloseChain { new_dna_hash: dh(0), action: cc(dh(0))}))]
+#[test_case(FlatOp::RegisterAgentActivity(OpActivity::OpenChain { previous_target: MigrationTarget::Dna(dh(0)), action: oc(MigrationTarget::Dna(dh(0)))}))]
+#[test_case(FlatOp::RegisterAgentActivity(OpActivity::CloseChain { new_target: MigrationTarget::Agent(ak(0)), action: cc(MigrationTarget::Agent(ak(0)))}))]
 #[test_case(FlatOp::RegisterAgentActivity(OpActivity::InitZomesComplete { action: izc()}))]
 #[test_case(FlatOp::RegisterAgentActivity(OpActivity::AgentValidationPkg{ membrane_proof: None, action: avp(None) }))]
 #[test_case(FlatOp::RegisterAgentActivity(OpActivity::AgentValidationPkg{ membrane_proof: Some(mp()), action: avp(Some(mp())) }))]
@@ -169,8 +169,8 @@ fn op_errors(op: Op) -> WasmErrorInner {
 #[test_case(FlatOp::StoreRecord(OpRecord::DeleteLink { action: dl(ah(0)), original_action_hash: ah(0), base_address: eh(0).into() }))]
 // Action's without entries
 #[test_case(FlatOp::StoreRecord(OpRecord::Dna{ action: dna(dh(0)), dna_hash: dh(0)}))]
-#[test_case(FlatOp::StoreRecord(OpRecord::OpenChain{ action: oc(dh(0)), previous_dna_hash: dh(0)}))]
-#[test_case(FlatOp::StoreRecord(OpRecord::CloseChain{ action: cc(dh(1)), new_dna_hash: dh(1)}))]
+#[test_case(FlatOp::StoreRecord(OpRecord::OpenChain{ action: oc(MigrationTarget::Dna(dh(0))), previous_target: MigrationTarget::Dna(dh(0))}))]
+#[test_case(FlatOp::StoreRecord(OpRecord::CloseChain{ action: cc(MigrationTarget::Dna(dh(1))), new_target: MigrationTarget::Dna(dh(1))}))]
 #[test_case(FlatOp::StoreRecord(OpRecord::InitZomesComplete { action: izc() }))]
 #[test_case(FlatOp::StoreRecord(OpRecord::AgentValidationPkg { action: avp(None), membrane_proof: None}))]
 #[test_case(FlatOp::StoreRecord(OpRecord::AgentValidationPkg { action: avp(Some(mp())), membrane_proof: Some(mp())}))]
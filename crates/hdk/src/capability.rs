@@ -198,3 +198,42 @@ pub fn update_cap_grant(
         chain_top_ordering: ChainTopOrdering::default(),
     })
 }
+
+/// List the capability grants committed to the local source chain.
+///
+/// Grants are returned in the order they were committed, each with the hash of the action that
+/// committed it and when it was revoked, if it has been updated or deleted.
+/// Revoked grants are only returned if [`CapGrantsInput::include_revoked`] is set.
+/// Set [`CapGrantsInput::tag`] to only return grants with that tag.
+///
+/// ```ignore
+/// let active_grants = capability_grants(CapGrantsInput::default())?;
+/// ```
+pub fn capability_grants(input: CapGrantsInput) -> ExternResult<Vec<CapGrantInfo>> {
+    HDK.with(|h| h.borrow().capability_grants(input))
+}
+
+/// List the capability claims committed to the local source chain.
+///
+/// Claims which have been deleted are not returned.
+/// Set [`CapClaimsInput::grantor`] and [`CapClaimsInput::tag`] to find the claims for a given
+/// agent and purpose, e.g. to get the secret to pass to [`crate::p2p::call_remote`].
+///
+/// ```ignore
+/// let claims = capability_claims(CapClaimsInput {
+///     grantor: Some(bob),
+///     tag: Some("foo".into()),
+/// })?;
+/// ```
+pub fn capability_claims(input: CapClaimsInput) -> ExternResult<Vec<CapClaim>> {
+    HDK.with(|h| h.borrow().capability_claims(input))
+}
+
+/// Get the capability that authorized the current zome call.
+///
+/// The [`CapabilityInfo`] contains the agent who made the call, the [`CapGrant`] that allowed it,
+/// and the hash of the action that committed the grant. When the caller is the chain author the
+/// grant is the implicit [`CapGrant::ChainAuthor`] which has no action.
+pub fn capability_info() -> ExternResult<CapabilityInfo> {
+    HDK.with(|h| h.borrow().capability_info(()))
+}
@@ -52,6 +52,7 @@ fn root_ensures() {
             base_address: root_hash().unwrap(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: Some(Path::from("foo").make_tag().unwrap()),
+            filter: GetLinksFilter::default(),
         }]))
         .returning(|_| {
             Ok(vec![vec![Link {
@@ -212,6 +213,7 @@ fn paths_exists() {
             base_address: Path::from("foo").path_entry_hash().unwrap().into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: Some(Path::from("bar").make_tag().unwrap()),
+            filter: GetLinksFilter::default(),
         }]))
         .returning(|_| {
             Ok(vec![vec![Link {
@@ -230,6 +232,7 @@ fn paths_exists() {
             base_address: Path::from("foo.bar").path_entry_hash().unwrap().into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: Some(Path::from("baz").make_tag().unwrap()),
+            filter: GetLinksFilter::default(),
         }]))
         .returning(|_| {
             Ok(vec![vec![Link {
@@ -324,6 +327,7 @@ fn children() {
             base_address: root_hash().unwrap(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: Some(Path::from("foo").make_tag().unwrap()),
+            filter: GetLinksFilter::default(),
         }]))
         .returning({
             let foo = foo.clone();
@@ -335,6 +339,7 @@ fn children() {
             base_address: Path::from("foo").path_entry_hash().unwrap().into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: Some(Path::from("bar").make_tag().unwrap()),
+            filter: GetLinksFilter::default(),
         }]))
         .returning({
             let foo_bar = foo_bar.clone();
@@ -346,6 +351,7 @@ fn children() {
             base_address: Path::from("foo").path_entry_hash().unwrap().into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: Some(Path::from("bar2").make_tag().unwrap()),
+            filter: GetLinksFilter::default(),
         }]))
         .returning({
             let foo_bar2 = foo_bar2.clone();
@@ -357,6 +363,7 @@ fn children() {
             base_address: Path::from("foo.bar").path_entry_hash().unwrap().into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: Some(Path::from("baz").make_tag().unwrap()),
+            filter: GetLinksFilter::default(),
         }]))
         .returning({
             let foo_bar_baz = foo_bar_baz.clone();
@@ -368,6 +375,7 @@ fn children() {
             base_address: Path::from("foo.bar2").path_entry_hash().unwrap().into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: Some(Path::from("baz2").make_tag().unwrap()),
+            filter: GetLinksFilter::default(),
         }]))
         .returning({
             let foo_bar2_baz2 = foo_bar2_baz2.clone();
@@ -379,6 +387,7 @@ fn children() {
             base_address: Path::from("foo").path_entry_hash().unwrap().into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: None,
+            filter: GetLinksFilter::default(),
         }]))
         .returning(move |_| Ok(vec![vec![foo_bar.clone(), foo_bar2.clone()]]));
     // foo.bar -[]-> foo.bar.baz
@@ -387,6 +396,7 @@ fn children() {
             base_address: Path::from("foo.bar").path_entry_hash().unwrap().into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: None,
+            filter: GetLinksFilter::default(),
         }]))
         .returning(move |_| Ok(vec![vec![foo_bar_baz.clone()]]));
     // foo.bar2 -[]-> foo.bar2.baz2
@@ -395,6 +405,7 @@ fn children() {
             base_address: Path::from("foo.bar2").path_entry_hash().unwrap().into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: None,
+            filter: GetLinksFilter::default(),
         }]))
         .returning(move |_| Ok(vec![vec![foo_bar2_baz2.clone()]]));
     // foo.bar.baz -[]-> ()
@@ -403,6 +414,7 @@ fn children() {
             base_address: Path::from("foo.bar.baz").path_entry_hash().unwrap().into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: None,
+            filter: GetLinksFilter::default(),
         }]))
         .returning(|_| Ok(vec![vec![]]));
     // foo.bar2.baz2 -[]-> ()
@@ -414,6 +426,7 @@ fn children() {
                 .into(),
             link_type: LinkTypeFilter::single_type(0.into(), 0.into()),
             tag_prefix: None,
+            filter: GetLinksFilter::default(),
         }]))
         .returning(|_| Ok(vec![vec![]]));
     set_hdk(mock);
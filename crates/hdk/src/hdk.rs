@@ -27,6 +27,10 @@ pub trait HdkT: HdiT {
         get_agent_activity_input: GetAgentActivityInput,
     ) -> ExternResult<AgentActivity>;
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>>;
+    // Capability
+    fn capability_claims(&self, input: CapClaimsInput) -> ExternResult<Vec<CapClaim>>;
+    fn capability_grants(&self, input: CapGrantsInput) -> ExternResult<Vec<CapGrantInfo>>;
+    fn capability_info(&self, capability_info_input: ()) -> ExternResult<CapabilityInfo>;
     // Ed25519
     fn sign(&self, sign: Sign) -> ExternResult<Signature>;
     fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures>;
@@ -58,6 +62,7 @@ pub trait HdkT: HdiT {
     fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
     fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
     fn remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
+    fn stream_chunk(&self, chunk: ExternIO) -> ExternResult<()>;
     // Random
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
     // Time
@@ -99,6 +104,10 @@ mockall::mock! {
             get_agent_activity_input: GetAgentActivityInput,
         ) -> ExternResult<AgentActivity>;
         fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>>;
+        // Capability
+        fn capability_claims(&self, input: CapClaimsInput) -> ExternResult<Vec<CapClaim>>;
+        fn capability_grants(&self, input: CapGrantsInput) -> ExternResult<Vec<CapGrantInfo>>;
+        fn capability_info(&self, capability_info_input: ()) -> ExternResult<CapabilityInfo>;
         // Ed25519
         fn sign(&self, sign: Sign) -> ExternResult<Signature>;
         fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures>;
@@ -130,6 +139,7 @@ mockall::mock! {
         fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
         fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
         fn remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
+        fn stream_chunk(&self, chunk: ExternIO) -> ExternResult<()>;
         // Random
         fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
         // Time
@@ -280,6 +290,15 @@ impl HdkT for ErrHdk {
     fn query(&self, _: ChainQueryFilter) -> ExternResult<Vec<Record>> {
         Self::err()
     }
+    fn capability_claims(&self, _: CapClaimsInput) -> ExternResult<Vec<CapClaim>> {
+        Self::err()
+    }
+    fn capability_grants(&self, _: CapGrantsInput) -> ExternResult<Vec<CapGrantInfo>> {
+        Self::err()
+    }
+    fn capability_info(&self, _: ()) -> ExternResult<CapabilityInfo> {
+        Self::err()
+    }
     fn sign(&self, _: Sign) -> ExternResult<Signature> {
         Self::err()
     }
@@ -343,6 +362,9 @@ impl HdkT for ErrHdk {
     fn remote_signal(&self, _: RemoteSignal) -> ExternResult<()> {
         Self::err()
     }
+    fn stream_chunk(&self, _: ExternIO) -> ExternResult<()> {
+        Self::err()
+    }
     // Random
     fn random_bytes(&self, _: u32) -> ExternResult<Bytes> {
         Self::err()
@@ -477,6 +499,15 @@ impl HdkT for HostHdk {
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
         host_call::<ChainQueryFilter, Vec<Record>>(__hc__query_1, filter)
     }
+    fn capability_claims(&self, input: CapClaimsInput) -> ExternResult<Vec<CapClaim>> {
+        host_call::<CapClaimsInput, Vec<CapClaim>>(__hc__capability_claims_1, input)
+    }
+    fn capability_grants(&self, input: CapGrantsInput) -> ExternResult<Vec<CapGrantInfo>> {
+        host_call::<CapGrantsInput, Vec<CapGrantInfo>>(__hc__capability_grants_1, input)
+    }
+    fn capability_info(&self, _: ()) -> ExternResult<CapabilityInfo> {
+        host_call::<(), CapabilityInfo>(__hc__capability_info_1, ())
+    }
     fn sign(&self, sign: Sign) -> ExternResult<Signature> {
         host_call::<Sign, Signature>(__hc__sign_1, sign)
     }
@@ -544,6 +575,9 @@ impl HdkT for HostHdk {
     fn remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()> {
         host_call::<RemoteSignal, ()>(__hc__remote_signal_1, remote_signal)
     }
+    fn stream_chunk(&self, chunk: ExternIO) -> ExternResult<()> {
+        host_call::<ExternIO, ()>(__hc__stream_chunk_1, chunk)
+    }
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes> {
         host_call::<u32, Bytes>(__hc__random_bytes_1, number_of_bytes)
     }
@@ -160,7 +160,8 @@
 //!   - Failure overrides retry.
 //!   - See [`create_cap_grant`](crate::capability::create_cap_grant) for an explanation of how to set up capabilities in `init`.
 //! - `fn migrate_agent_{{ open|close }} -> ExternResult<MigrateAgentCallbackResult>`:
-//!   - Allows the guest to pass/fail a migration attempt to/from another DNA.
+//!   - Allows the guest to pass/fail a migration attempt to/from another DNA or agent key.
+//!   - Migrations are triggered by the conductor admin API, and end in a `CloseChain` action on the old chain and an `OpenChain` action on the new one.
 //!   - Open runs when an agent is starting a new source chain from an old one.
 //!   - Close runs when an agent is deprecating an old source chain in favour of a new one.
 //!   - All zomes in a DNA migrate at the same time.
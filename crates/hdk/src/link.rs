@@ -156,6 +156,39 @@ pub fn get_links(
         .unwrap())
 }
 
+/// Returns the links that reference a base hash, like [ `get_links` ], further narrowed by a
+/// [ `GetLinksFilter` ].
+///
+/// The filter can restrict links to a creation time range or a single author, and can page through
+/// large link sets with a limit and a cursor taken from the last link of the previous page.
+///
+/// ```ignore
+/// let filter = GetLinksFilter::new().author(agent).limit(20);
+/// let page = get_links_filtered(base.clone(), LinkTypes::Post, None, filter.clone())?;
+/// if let Some(last) = page.last() {
+///     let next = get_links_filtered(base, LinkTypes::Post, None, filter.cursor(last.into()))?;
+/// }
+/// ```
+///
+/// See [ `get_links` ].
+pub fn get_links_filtered(
+    base: impl Into<AnyLinkableHash>,
+    link_type: impl LinkTypeFilterExt,
+    link_tag: Option<LinkTag>,
+    filter: GetLinksFilter,
+) -> ExternResult<Vec<Link>> {
+    let link_type = link_type.try_into_filter()?;
+    Ok(HDK
+        .with(|h| {
+            h.borrow().get_links(vec![
+                GetLinksInput::new(base.into(), link_type, link_tag).filter(filter)
+            ])
+        })?
+        .into_iter()
+        .next()
+        .unwrap())
+}
+
 /// Get all link creates and deletes that reference a base hash, optionally filtered by type or tag.
 ///
 /// Type can be filtered by providing a variant of the link types, or a range of them. To get links of
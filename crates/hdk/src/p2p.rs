@@ -156,3 +156,33 @@ where
         })
     })
 }
+
+/// Send a chunk of output to the client of a streaming zome call.
+///
+/// A client that makes a streaming zome call receives each chunk as soon as
+/// it is sent, ahead of the return value of the zome function. This lets a
+/// zome function return large results piece by piece instead of building
+/// them up in memory.
+///
+/// The call waits while the client is falling behind in receiving chunks,
+/// and returns an error if the client cancels the stream or the zome call
+/// isn't streaming.
+///
+/// ```ignore
+/// #[hdk_extern]
+/// fn all_posts(_: ()) -> ExternResult<()> {
+///     for record in query(ChainQueryFilter::new().include_entries(true))? {
+///         stream_chunk(&record)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn stream_chunk<I>(chunk: I) -> ExternResult<()>
+where
+    I: serde::Serialize + std::fmt::Debug,
+{
+    HDK.with(|h| {
+        h.borrow()
+            .stream_chunk(ExternIO::encode(chunk).map_err(|e| wasm_error!(e))?)
+    })
+}
@@ -1,3 +1,6 @@
+pub use crate::capability::capability_claims;
+pub use crate::capability::capability_grants;
+pub use crate::capability::capability_info;
 pub use crate::capability::create_cap_claim;
 pub use crate::capability::create_cap_grant;
 pub use crate::capability::delete_cap_grant;
@@ -44,6 +47,7 @@ pub use crate::link::create_link;
 pub use crate::link::delete_link;
 pub use crate::link::get_link_details;
 pub use crate::link::get_links;
+pub use crate::link::get_links_filtered;
 pub use crate::link::LinkTypeFilterExt;
 pub use crate::map_extern;
 pub use crate::map_extern::ExternResult;
@@ -51,6 +55,7 @@ pub use crate::p2p::call;
 pub use crate::p2p::call_remote;
 pub use crate::p2p::emit_signal;
 pub use crate::p2p::remote_signal;
+pub use crate::p2p::stream_chunk;
 pub use crate::random::*;
 pub use crate::time::schedule;
 pub use crate::time::sleep;
@@ -142,6 +147,7 @@ macro_rules! holochain_externs {
             create:1,
             emit_signal:1,
             remote_signal:1,
+            stream_chunk:1,
             create_link:1,
             delete_link:1,
             update:1,
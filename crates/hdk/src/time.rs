@@ -136,7 +136,14 @@ pub fn schedule(scheduled_fn: &str) -> ExternResult<()> {
     HDK.with(|h| h.borrow().schedule(String::from(scheduled_fn)))
 }
 
-/// @todo Not implemented
+/// Pause the current zome call for the given duration.
+///
+/// Sleeping doesn't suspend the zome call: it keeps its wasm instance and a
+/// conductor thread until it wakes, so other calls can't use them meanwhile.
+/// The conductor only lets as many zome calls sleep at once as its
+/// `max_sleeping_zome_calls` config allows, and refuses sleeps longer than its
+/// `max_sleep_ms` config. It returns an error if the zome call expires before
+/// the sleep ends or too many other zome calls are sleeping.
 pub fn sleep(wake_after: std::time::Duration) -> ExternResult<()> {
     HDK.with(|h| h.borrow().sleep(wake_after))
 }
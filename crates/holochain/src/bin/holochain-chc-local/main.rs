@@ -0,0 +1,41 @@
+//! A local Chain Head Coordinator server, backed by in-memory chains.
+//!
+//! Point a conductor at it by setting `chc_namespace` to the printed URL.
+//! Intended for testing only: all data is lost when the server stops.
+
+use std::net::ToSocketAddrs;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "holochain-chc-local",
+    about = "A local Chain Head Coordinator server, for testing."
+)]
+struct Opt {
+    /// The interface and port to bind to
+    #[structopt(short, long, default_value = "127.0.0.1:0")]
+    interface: String,
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() {
+    if let Err(err) = run(Opt::from_args()).await {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+async fn run(opt: Opt) -> Result<(), String> {
+    let addr = opt
+        .interface
+        .as_str()
+        .to_socket_addrs()
+        .map_err(|e| format!("invalid interface {}: {}", opt.interface, e))?
+        .next()
+        .ok_or_else(|| format!("interface {} did not resolve to an address", opt.interface))?;
+
+    let (driver, addr, _shutdown) = holochain::conductor::chc::run_chc_server(addr)?;
+    println!("http://{}", addr);
+    driver.await;
+    Ok(())
+}
@@ -86,7 +86,7 @@ async fn async_main() {
         std::env::set_var("CUSTOM_FILTER", t);
     }
 
-    holochain_trace::init_fmt(opt.structured.clone()).expect("Failed to start contextual logging");
+    init_tracing(&opt, &config).expect("Failed to start contextual logging");
     debug!("holochain_trace initialized");
 
     kitsune_p2p_types::metrics::init_sys_info_poll();
@@ -113,9 +113,32 @@ async fn async_main() {
         .unwrap_or_else(|e| tracing::error!("Could not handle termination signal: {:?}", e));
     tracing::info!("Gracefully shutting down conductor...");
     let shutdown_result = conductor.shutdown().await;
+    #[cfg(feature = "otlp")]
+    holochain_trace::shutdown_otlp();
     handle_shutdown(shutdown_result);
 }
 
+/// Start logging, and sending traces to an OTLP collector if one is configured.
+fn init_tracing(
+    opt: &Opt,
+    config: &ConductorConfig,
+) -> Result<(), holochain_trace::errors::TracingError> {
+    match config
+        .metrics
+        .as_ref()
+        .and_then(|metrics| metrics.otlp_endpoint.as_ref())
+    {
+        #[cfg(feature = "otlp")]
+        Some(endpoint) => holochain_trace::init_fmt_with_otlp(opt.structured.clone(), endpoint),
+        #[cfg(not(feature = "otlp"))]
+        Some(_) => {
+            eprintln!("Not sending traces to the configured OTLP endpoint, as this build of holochain doesn't have the `otlp` feature");
+            holochain_trace::init_fmt(opt.structured.clone())
+        }
+        None => holochain_trace::init_fmt(opt.structured.clone()),
+    }
+}
+
 fn get_conductor_config(opt: &Opt) -> ConductorConfig {
     let config_path = opt.config_path.clone();
     let config_path_default = config_path.is_none();
@@ -194,6 +217,13 @@ fn load_config(config_path: &ConfigFilePath, config_path_default: bool) -> Condu
             display_friendly_malformed_config_message(config_path, err);
             std::process::exit(ERROR_CODE);
         }
+        Err(ConductorConfigError::InvalidNetworkConfig(err)) => {
+            println!(
+                "Error: The conductor config file at {} has an invalid network config: {}",
+                config_path, err
+            );
+            std::process::exit(ERROR_CODE);
+        }
         result => result.expect("Could not load conductor config"),
     }
 }
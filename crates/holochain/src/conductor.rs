@@ -29,6 +29,7 @@ pub mod interactive;
 pub mod interface;
 pub mod kitsune_host_impl;
 pub mod manager;
+pub(crate) mod metrics;
 pub mod p2p_agent_store;
 pub mod paths;
 #[allow(missing_docs)]
@@ -217,6 +217,13 @@ pub trait CellConductorReadHandleT: Send + Sync {
 
     /// Expose is_blocked functionality to zomes.
     async fn is_blocked(&self, input: BlockTargetId, timestamp: Timestamp) -> DatabaseResult<bool>;
+
+    /// The longest a zome call can sleep for at a time.
+    fn max_sleep_duration(&self) -> std::time::Duration;
+
+    /// Take a permit for a zome call to sleep, if fewer than the configured
+    /// maximum are already sleeping.
+    fn try_start_sleep(&self) -> Option<tokio::sync::OwnedSemaphorePermit>;
 }
 
 #[async_trait]
@@ -280,4 +287,12 @@ impl CellConductorReadHandleT for CellConductorApi {
     async fn is_blocked(&self, input: BlockTargetId, timestamp: Timestamp) -> DatabaseResult<bool> {
         self.conductor_handle.is_blocked(input, timestamp).await
     }
+
+    fn max_sleep_duration(&self) -> std::time::Duration {
+        self.conductor_handle.get_config().max_sleep_duration()
+    }
+
+    fn try_start_sleep(&self) -> Option<tokio::sync::OwnedSemaphorePermit> {
+        self.conductor_handle.try_start_zome_call_sleep()
+    }
 }
@@ -171,6 +171,9 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .clone()
                     .new_sign_keypair_random()
                     .await?;
+                self.conductor_handle
+                    .register_agent_key_with_dpki(agent_pub_key.clone())
+                    .await?;
                 Ok(AdminResponse::AgentPubKeyGenerated(agent_pub_key))
             }
             ListCellIds => {
@@ -220,15 +223,47 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::AppDisabled)
             }
-            AttachAppInterface { port } => {
+            AttachAppInterface {
+                port,
+                allowed_origins,
+            } => {
                 let port = port.unwrap_or(0);
                 let port = self
                     .conductor_handle
                     .clone()
-                    .add_app_interface(either::Either::Left(port))
+                    .add_app_interface(either::Either::Left(port), allowed_origins)
                     .await?;
                 Ok(AdminResponse::AppInterfaceAttached { port })
             }
+            AttachAppUnixSocketInterface { path } => {
+                let path = self
+                    .conductor_handle
+                    .clone()
+                    .add_app_unix_socket_interface(path)
+                    .await?;
+                Ok(AdminResponse::AppUnixSocketInterfaceAttached { path })
+            }
+            Authenticate(_) => Err(ConductorApiError::Other(
+                "Only an admin interface connection can be authenticated".into(),
+            )),
+            IssueAppAuthenticationToken(payload) => {
+                for installed_app_id in payload.installed_app_ids.iter() {
+                    // Make sure the app exists before handing out access to it
+                    self.conductor_handle
+                        .get_app_info(installed_app_id)
+                        .await?
+                        .ok_or_else(|| ConductorError::AppNotInstalled(installed_app_id.clone()))?;
+                }
+                let issued = self
+                    .conductor_handle
+                    .issue_app_authentication_token(payload)?;
+                Ok(AdminResponse::AppAuthenticationTokenIssued(issued))
+            }
+            RevokeAppAuthenticationToken(token) => {
+                self.conductor_handle
+                    .revoke_app_authentication_token(&token)?;
+                Ok(AdminResponse::AppAuthenticationTokenRevoked)
+            }
             ListAppInterfaces => {
                 let interfaces = self.conductor_handle.list_app_interfaces().await?;
                 Ok(AdminResponse::AppInterfacesListed(interfaces))
@@ -255,6 +290,10 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 let stats = self.conductor_handle.dump_network_stats().await?;
                 Ok(AdminResponse::NetworkStatsDumped(stats))
             }
+            DumpFetchPool { dna_hash } => {
+                let dump = self.conductor_handle.dump_fetch_pool(&dna_hash).await?;
+                Ok(AdminResponse::FetchPoolDumped(dump))
+            }
             AddAgentInfo { agent_infos } => {
                 self.conductor_handle.add_agent_infos(agent_infos).await?;
                 Ok(AdminResponse::AgentInfoAdded)
@@ -263,6 +302,10 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 let r = self.conductor_handle.get_agent_infos(cell_id).await?;
                 Ok(AdminResponse::AgentInfo(r))
             }
+            SetArcPin { cell_id, pin } => {
+                self.conductor_handle.set_arc_pin(&cell_id, pin).await?;
+                Ok(AdminResponse::ArcPinSet)
+            }
             GraftRecords {
                 cell_id,
                 validate,
@@ -288,9 +331,57 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::CloneCellDeleted)
             }
+            ProvisionDeferredCell(payload) => {
+                let provisioned_cell = self
+                    .conductor_handle
+                    .clone()
+                    .provision_deferred_cell(*payload)
+                    .await?;
+                Ok(AdminResponse::DeferredCellProvisioned(provisioned_cell))
+            }
             StorageInfo => Ok(AdminResponse::StorageInfo(
                 self.conductor_handle.storage_info().await?,
             )),
+            MigrateChain { from, to } => {
+                let (close_hash, open_hash) = self.conductor_handle.migrate_chain(from, to).await?;
+                Ok(AdminResponse::ChainMigrated(close_hash, open_hash))
+            }
+            ListScheduledFunctions { cell_id } => Ok(AdminResponse::ScheduledFunctionsListed(
+                self.conductor_handle.list_scheduled_fns(&cell_id).await?,
+            )),
+            PauseScheduledFunction {
+                cell_id,
+                zome_name,
+                fn_name,
+            } => {
+                self.conductor_handle
+                    .set_scheduled_fn_paused(&cell_id, ScheduledFn::new(zome_name, fn_name), true)
+                    .await?;
+                Ok(AdminResponse::ScheduledFunctionPaused)
+            }
+            ResumeScheduledFunction {
+                cell_id,
+                zome_name,
+                fn_name,
+            } => {
+                self.conductor_handle
+                    .set_scheduled_fn_paused(&cell_id, ScheduledFn::new(zome_name, fn_name), false)
+                    .await?;
+                Ok(AdminResponse::ScheduledFunctionResumed)
+            }
+            CancelScheduledFunction {
+                cell_id,
+                zome_name,
+                fn_name,
+            } => {
+                self.conductor_handle
+                    .cancel_scheduled_fn(&cell_id, ScheduledFn::new(zome_name, fn_name))
+                    .await?;
+                Ok(AdminResponse::ScheduledFunctionCancelled)
+            }
+            DumpZomeCallRateLimitMetrics => Ok(AdminResponse::ZomeCallRateLimitMetricsDumped(
+                self.conductor_handle.zome_call_rate_limit_metrics(),
+            )),
         }
     }
 }
@@ -1,14 +1,20 @@
 use super::InterfaceApi;
+use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::ExternalApiWireError;
 use crate::conductor::api::error::SerializationError;
+use crate::conductor::conductor::measure_committed_bytes;
+use crate::conductor::conductor::AppAuthSession;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
+use crate::conductor::CellError;
 use crate::conductor::ConductorHandle;
+use crate::core::workflow::ZomeCallResult;
 
 use holochain_serialized_bytes::prelude::*;
 
 use holochain_types::prelude::*;
+use std::collections::HashSet;
 
 pub use holochain_conductor_api::*;
 
@@ -21,6 +27,43 @@ pub trait AppInterfaceApi: 'static + Send + Sync + Clone {
         request: AppRequest,
     ) -> ConductorApiResult<AppResponse>;
 
+    /// Check the token of an [`AppRequest::Authenticate`] request, returning
+    /// the session of the authenticated connection
+    async fn authenticate(
+        &self,
+        token: &AppAuthenticationToken,
+    ) -> ConductorApiResult<AppAuthSession>;
+
+    /// Check that a connection which can access the given apps may make
+    /// the request
+    async fn authorize(
+        &self,
+        allowed_apps: &HashSet<InstalledAppId>,
+        request: &AppRequest,
+    ) -> ConductorApiResult<()>;
+
+    /// Whether the cell belongs to any of the given running apps
+    async fn is_cell_in_apps(
+        &self,
+        allowed_apps: &HashSet<InstalledAppId>,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<bool>;
+
+    /// Whether any of the given running apps has a cell of the DNA
+    async fn is_dna_in_apps(
+        &self,
+        allowed_apps: &HashSet<InstalledAppId>,
+        dna_hash: &DnaHash,
+    ) -> ConductorApiResult<bool>;
+
+    /// Call a zome function, sending the chunks it streams to the given
+    /// channel
+    async fn call_zome_streaming(
+        &self,
+        call: ZomeCall,
+        stream: tokio::sync::mpsc::Sender<ExternIO>,
+    ) -> ConductorApiResult<AppResponse>;
+
     // -- provided -- //
 
     /// Deal with error cases produced by `handle_app_request_inner`
@@ -64,26 +107,86 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                     .await?,
             )),
             AppRequest::CallZome(call) => {
-                match self.conductor_handle.call_zome(*call.clone()).await? {
-                    Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCalled(Box::new(output))),
-                    Ok(ZomeCallResponse::Unauthorized(zome_call_authorization, _, zome_name, fn_name, _)) => Ok(AppResponse::Error(
-                        ExternalApiWireError::ZomeCallUnauthorized(format!(
-                            "Call was not authorized with reason {:?}, cap secret {:?} to call the function {} in zome {}",
-                            zome_call_authorization, call.cap_secret, fn_name, zome_name
-                        )),
-                    )),
-                    Ok(ZomeCallResponse::NetworkError(e)) => unreachable!(
-                        "Interface zome calls should never be routed to the network. This is a bug. Got {}",
-                        e
-                    ),
-                    Ok(ZomeCallResponse::CountersigningSession(e)) => Ok(AppResponse::Error(
-                        ExternalApiWireError::CountersigningSessionError(format!(
-                            "A countersigning session has failed to start on this zome call because: {}",
-                            e
-                        )),
-                    )),
-                    Err(e) => Ok(AppResponse::Error(e.into())),
+                let costs = self
+                    .conductor_handle
+                    .check_zome_call_rate_limits(std::slice::from_ref(call.as_ref()))
+                    .await?;
+                let (result, committed_bytes) =
+                    measure_committed_bytes(self.conductor_handle.call_zome(*call.clone())).await;
+                let refunded = match &result {
+                    Ok(result) => refused_zome_calls(std::slice::from_ref(result)),
+                    Err(_) => vec![],
+                };
+                self.conductor_handle.settle_zome_call_rate_limits(
+                    costs,
+                    &refunded,
+                    committed_bytes,
+                );
+                Ok(zome_call_response(&call, result?))
+            }
+            AppRequest::CallZomeBatch(calls) => {
+                let costs = self
+                    .conductor_handle
+                    .check_zome_call_rate_limits(&calls)
+                    .await?;
+                let (results, committed_bytes) =
+                    measure_committed_bytes(self.conductor_handle.call_zome_batch(calls.clone()))
+                        .await;
+                // The calls after the one the batch stopped at were never made.
+                let refunded: Vec<usize> = match &results {
+                    Ok(batch) => refused_zome_calls(&batch.results)
+                        .into_iter()
+                        .chain(batch.results.len()..calls.len())
+                        .collect(),
+                    Err(ConductorApiError::CellError(CellError::ZomeCallBatchFailed {
+                        index,
+                        ..
+                    })) => (index + 1..calls.len()).collect(),
+                    Err(_) => vec![],
+                };
+                self.conductor_handle.settle_zome_call_rate_limits(
+                    costs,
+                    &refunded,
+                    committed_bytes,
+                );
+                let batch = match results {
+                    Err(ConductorApiError::CellError(CellError::ZomeCallBatchFailed {
+                        index,
+                        source,
+                    })) => {
+                        return Ok(AppResponse::Error(
+                            ExternalApiWireError::ZomeCallBatchFailed {
+                                index,
+                                error: Box::new(ExternalApiWireError::internal(source)),
+                            },
+                        ))
+                    }
+                    results => results?,
+                };
+                let mut outputs = Vec::with_capacity(batch.results.len());
+                for (index, (call, result)) in calls.iter().zip(batch.results).enumerate() {
+                    match zome_call_output(call, result) {
+                        Ok(output) => outputs.push(output),
+                        Err(error) => {
+                            return Ok(AppResponse::Error(
+                                ExternalApiWireError::ZomeCallBatchFailed {
+                                    index,
+                                    error: Box::new(error),
+                                },
+                            ))
+                        }
+                    }
                 }
+                // The batch was committed, so this is reported with the
+                // outputs rather than as a failure of the batch.
+                let post_commit_error = batch.post_commit_error.and_then(|response| {
+                    let call = calls.last()?;
+                    zome_call_output(call, Ok(response)).err()
+                });
+                Ok(AppResponse::ZomeBatchCalled {
+                    outputs,
+                    post_commit_error,
+                })
             }
             AppRequest::CreateCloneCell(payload) => {
                 let clone_cell = self
@@ -115,8 +218,189 @@ impl AppInterfaceApi for RealAppInterfaceApi {
             AppRequest::ListWasmHostFunctions => Ok(AppResponse::ListWasmHostFunctions(
                 self.conductor_handle.list_wasm_host_functions().await?,
             )),
+            AppRequest::EnzymaticCountersigningSessions(cell_id) => {
+                let sessions = self
+                    .conductor_handle
+                    .enzymatic_countersigning_sessions(&cell_id)
+                    .await?;
+                Ok(AppResponse::EnzymaticCountersigningSessions(sessions))
+            }
+            AppRequest::CallZomeStreaming(_) | AppRequest::CancelZomeCallStream(_) => {
+                Err(ConductorApiError::Other(
+                    "Streaming zome calls can only be made on an app interface connection".into(),
+                ))
+            }
+            AppRequest::SignalSubscription(_) => Err(ConductorApiError::Other(
+                "Signal subscriptions can only be set on an app interface connection".into(),
+            )),
+            AppRequest::Authenticate(_) => Err(ConductorApiError::Other(
+                "Only an app interface connection can be authenticated".into(),
+            )),
         }
     }
+
+    async fn call_zome_streaming(
+        &self,
+        call: ZomeCall,
+        stream: tokio::sync::mpsc::Sender<ExternIO>,
+    ) -> ConductorApiResult<AppResponse> {
+        let costs = self
+            .conductor_handle
+            .check_zome_call_rate_limits(std::slice::from_ref(&call))
+            .await?;
+        let (result, committed_bytes) = measure_committed_bytes(
+            self.conductor_handle
+                .call_zome_streaming(call.clone(), stream),
+        )
+        .await;
+        let refunded = match &result {
+            Ok(result) => refused_zome_calls(std::slice::from_ref(result)),
+            Err(_) => vec![],
+        };
+        self.conductor_handle
+            .settle_zome_call_rate_limits(costs, &refunded, committed_bytes);
+        Ok(zome_call_response(&call, result?))
+    }
+
+    async fn authenticate(
+        &self,
+        token: &AppAuthenticationToken,
+    ) -> ConductorApiResult<AppAuthSession> {
+        self.conductor_handle
+            .authenticate_app_token(token)
+            .ok_or_else(|| {
+                ConductorApiError::AuthenticationFailed(
+                    "The token is invalid, expired or has already been used".to_string(),
+                )
+            })
+    }
+
+    async fn authorize(
+        &self,
+        allowed_apps: &HashSet<InstalledAppId>,
+        request: &AppRequest,
+    ) -> ConductorApiResult<()> {
+        let authorized = match request {
+            AppRequest::AppInfo { installed_app_id } => allowed_apps.contains(installed_app_id),
+            AppRequest::CallZome(call) => self.is_cell_in_apps(allowed_apps, &call.cell_id).await?,
+            AppRequest::CallZomeBatch(calls) => {
+                let mut authorized = true;
+                for call in calls.iter() {
+                    if !self.is_cell_in_apps(allowed_apps, &call.cell_id).await? {
+                        authorized = false;
+                        break;
+                    }
+                }
+                authorized
+            }
+            AppRequest::CallZomeStreaming(payload) => {
+                self.is_cell_in_apps(allowed_apps, &payload.call.cell_id)
+                    .await?
+            }
+            AppRequest::EnzymaticCountersigningSessions(cell_id) => {
+                self.is_cell_in_apps(allowed_apps, cell_id).await?
+            }
+            AppRequest::CreateCloneCell(payload) => allowed_apps.contains(&payload.app_id),
+            AppRequest::DisableCloneCell(payload) | AppRequest::EnableCloneCell(payload) => {
+                allowed_apps.contains(&payload.app_id)
+            }
+            AppRequest::NetworkInfo(payload) => {
+                let mut authorized = true;
+                for dna_hash in payload.dnas.iter() {
+                    if !self.is_dna_in_apps(allowed_apps, dna_hash).await? {
+                        authorized = false;
+                        break;
+                    }
+                }
+                authorized
+            }
+            AppRequest::SignalSubscription(subscription) => {
+                allowed_apps.contains(&subscription.installed_app_id)
+            }
+            AppRequest::Authenticate(_)
+            | AppRequest::CancelZomeCallStream(_)
+            | AppRequest::ListWasmHostFunctions => true,
+        };
+        if authorized {
+            Ok(())
+        } else {
+            Err(ConductorApiError::Unauthorized(format!(
+                "This connection can only access the apps {:?}",
+                allowed_apps
+            )))
+        }
+    }
+
+    async fn is_cell_in_apps(
+        &self,
+        allowed_apps: &HashSet<InstalledAppId>,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<bool> {
+        Ok(!self
+            .conductor_handle
+            .list_running_apps_for_dependent_cell_id(cell_id)
+            .await?
+            .is_disjoint(allowed_apps))
+    }
+
+    async fn is_dna_in_apps(
+        &self,
+        allowed_apps: &HashSet<InstalledAppId>,
+        dna_hash: &DnaHash,
+    ) -> ConductorApiResult<bool> {
+        Ok(!self
+            .conductor_handle
+            .list_running_apps_for_dependent_dna_hash(dna_hash)
+            .await?
+            .is_disjoint(allowed_apps))
+    }
+}
+
+/// The response to send to the client for the result of its zome call
+fn zome_call_response(call: &ZomeCall, result: ZomeCallResult) -> AppResponse {
+    match zome_call_output(call, result) {
+        Ok(output) => AppResponse::ZomeCalled(Box::new(output)),
+        Err(error) => AppResponse::Error(error),
+    }
+}
+
+/// The indices of the zome calls which were refused because their signature
+/// or cap grant couldn't be verified, and so don't count against the rate
+/// limits.
+fn refused_zome_calls(results: &[ZomeCallResult]) -> Vec<usize> {
+    results
+        .iter()
+        .enumerate()
+        .filter(|(_, result)| matches!(result, Ok(ZomeCallResponse::Unauthorized(..))))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// The output of a successful zome call, or the error to send to the client
+fn zome_call_output(
+    call: &ZomeCall,
+    result: ZomeCallResult,
+) -> Result<ExternIO, ExternalApiWireError> {
+    match result {
+        Ok(ZomeCallResponse::Ok(output)) => Ok(output),
+        Ok(ZomeCallResponse::Unauthorized(zome_call_authorization, _, zome_name, fn_name, _)) => Err(
+            ExternalApiWireError::ZomeCallUnauthorized(format!(
+                "Call was not authorized with reason {:?}, cap secret {:?} to call the function {} in zome {}",
+                zome_call_authorization, call.cap_secret, fn_name, zome_name
+            )),
+        ),
+        Ok(ZomeCallResponse::NetworkError(e)) => unreachable!(
+            "Interface zome calls should never be routed to the network. This is a bug. Got {}",
+            e
+        ),
+        Ok(ZomeCallResponse::CountersigningSession(e)) => Err(
+            ExternalApiWireError::CountersigningSessionError(format!(
+                "A countersigning session has failed to start on this zome call because: {}",
+                e
+            )),
+        ),
+        Err(e) => Err(e.into()),
+    }
 }
 
 #[async_trait::async_trait]
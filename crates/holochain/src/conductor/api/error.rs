@@ -68,6 +68,22 @@ pub enum ConductorApiError {
     #[error("The Dna file path provided was invalid")]
     DnaReadError(String),
 
+    /// An app interface connection could not be authenticated
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+
+    /// An app interface connection is not allowed to make this request
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// A zome call was rejected because it is over a rate limit
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    /// Zome calls were rejected because they could never fit within a rate limit
+    #[error("Rate limit exceeded: {0}")]
+    RateLimitExceeded(String),
+
     /// KeystoreError
     #[error("KeystoreError: {0}")]
     KeystoreError(#[from] holochain_keystore::KeystoreError),
@@ -150,6 +166,12 @@ impl From<ConductorApiError> for ExternalApiWireError {
     fn from(err: ConductorApiError) -> Self {
         match err {
             ConductorApiError::DnaReadError(e) => ExternalApiWireError::DnaReadError(e),
+            ConductorApiError::AuthenticationFailed(e) => {
+                ExternalApiWireError::AuthenticationFailed(e)
+            }
+            ConductorApiError::Unauthorized(e) => ExternalApiWireError::Unauthorized(e),
+            ConductorApiError::RateLimited(e) => ExternalApiWireError::RateLimited(e),
+            ConductorApiError::RateLimitExceeded(e) => ExternalApiWireError::RateLimitExceeded(e),
             e => ExternalApiWireError::internal(e),
         }
     }
@@ -16,11 +16,19 @@ use crate::core::queue_consumer::QueueTriggers;
 use crate::core::ribosome::guest_callback::init::InitResult;
 use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::ribosome::ZomeCallInvocation;
+use crate::core::ribosome::ZomeCallStream;
 use crate::core::workflow::call_zome_workflow;
+use crate::core::workflow::call_zome_workflow::flush_zome_call_workspace;
 use crate::core::workflow::countersigning_workflow::countersigning_success;
 use crate::core::workflow::countersigning_workflow::incoming_countersigning;
+use crate::core::workflow::countersigning_workflow::resume_countersigning_sessions;
+use crate::core::workflow::countersigning_workflow::RESUME_SESSIONS_RETRY_INTERVAL;
 use crate::core::workflow::genesis_workflow::genesis_workflow;
 use crate::core::workflow::initialize_zomes_workflow;
+use crate::core::workflow::migrate_agent_workflow::migrate_agent_workflow;
+use crate::core::workflow::migrate_agent_workflow::ChainMigration;
+use crate::core::workflow::migrate_agent_workflow::MigrateAgentWorkflowArgs;
+use crate::core::workflow::migrate_agent_workflow::PendingChainMigration;
 use crate::core::workflow::CallZomeWorkflowArgs;
 use crate::core::workflow::GenesisWorkflowArgs;
 use crate::core::workflow::GenesisWorkspace;
@@ -32,6 +40,7 @@ use futures::future::FutureExt;
 use holo_hash::*;
 use holochain_cascade::authority;
 use holochain_conductor_api::ZomeCall;
+use holochain_keystore::AgentPubKeyExt;
 use holochain_p2p::event::CountersigningSessionNegotiationMessage;
 use holochain_p2p::ChcImpl;
 use holochain_p2p::HolochainP2pDna;
@@ -41,13 +50,18 @@ use holochain_state::host_fn_workspace::SourceChainWorkspace;
 use holochain_state::nonce::fresh_nonce;
 use holochain_state::prelude::*;
 use holochain_state::schedule::live_scheduled_fns;
+use holochain_state::schedule::ScheduledFnExecution;
+use holochain_state::schedule::ScheduledFnOutcome;
 use holochain_types::db_cache::DhtDbQueryCache;
 use holochain_types::prelude::*;
 use rusqlite::OptionalExtension;
 use rusqlite::Transaction;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Weak;
 use tracing::*;
 use tracing_futures::Instrument;
 
@@ -103,6 +117,29 @@ pub struct Cell {
     holochain_p2p_cell: HolochainP2pDna,
     queue_triggers: QueueTriggers,
     init_mutex: tokio::sync::Mutex<()>,
+    resume_countersigning: Arc<ResumeCountersigning>,
+}
+
+/// The outcome of a batch of zome calls made with [`Cell::call_zome_batch`].
+#[derive(Debug, Default)]
+pub struct ZomeCallBatchResults {
+    /// The result of each zome call which was made, in order.
+    pub results: Vec<ZomeCallResult>,
+    /// Set if the batch was committed, but a countersigning session one of
+    /// the calls started couldn't be published.
+    pub post_commit_error: Option<ZomeCallResponse>,
+}
+
+/// Tracks the task which finds out what became of a cell's interrupted
+/// countersigning sessions, so that only one runs at a time.
+#[derive(Default)]
+struct ResumeCountersigning {
+    /// Whether the task is running.
+    running: AtomicBool,
+    /// Whether the sessions should be checked again before the task ends.
+    requested: AtomicBool,
+    /// Wakes the task up to check the sessions before its retry interval is up.
+    wake: tokio::sync::Notify,
 }
 
 impl Cell {
@@ -148,6 +185,7 @@ impl Cell {
                     holochain_p2p_cell,
                     queue_triggers,
                     init_mutex: Default::default(),
+                    resume_countersigning: Default::default(),
                 },
                 initial_queue_triggers,
             ))
@@ -319,18 +357,30 @@ impl Cell {
                     .authored_db
                     .async_commit(move |txn: &mut Transaction| {
                         for ((scheduled_fn, _), result) in lives.iter().zip(results.iter()) {
-                            match result {
+                            let outcome = match result {
                                 Ok(Ok(ZomeCallResponse::Ok(extern_io))) => {
-                                    let next_schedule: Schedule = match extern_io.decode() {
-                                        Ok(Some(v)) => v,
-                                        Ok(None) => {
-                                            continue;
-                                        }
-                                        Err(e) => {
-                                            error!("{}", e.to_string());
-                                            continue;
-                                        }
-                                    };
+                                    match extern_io.decode::<Option<Schedule>>() {
+                                        Ok(next_schedule) => ScheduledFnOutcome::Ok(next_schedule),
+                                        Err(e) => ScheduledFnOutcome::Error(e.to_string()),
+                                    }
+                                }
+                                errorish => ScheduledFnOutcome::Error(format!("{:?}", errorish)),
+                            };
+                            // Failing to record history must not stop the
+                            // function from being rescheduled.
+                            if let Err(e) = record_scheduled_fn_execution(
+                                txn,
+                                &author,
+                                scheduled_fn,
+                                ScheduledFnExecution {
+                                    fired_at: now,
+                                    outcome: outcome.clone(),
+                                },
+                            ) {
+                                error!("{}", e.to_string());
+                            }
+                            match outcome {
+                                ScheduledFnOutcome::Ok(Some(next_schedule)) => {
                                     // Ignore errors so that failing to schedule
                                     // one function doesn't error others.
                                     // For example if a zome returns a bad cron.
@@ -342,10 +392,10 @@ impl Cell {
                                         now,
                                     ) {
                                         error!("{}", e.to_string());
-                                        continue;
                                     }
                                 }
-                                errorish => error!("{:?}", errorish),
+                                ScheduledFnOutcome::Ok(None) => {}
+                                ScheduledFnOutcome::Error(e) => error!("{}", e),
                             }
                         }
                         Result::<(), DatabaseError>::Ok(())
@@ -370,7 +420,8 @@ impl Cell {
             | QueryAgentInfoSignedNearBasis { .. }
             | QueryPeerDensity { .. }
             | Publish { .. }
-            | FetchOpData { .. } => {
+            | FetchOpData { .. }
+            | WarrantReceived { .. } => {
                 // These events are aggregated over a set of cells, so need to be handled at the conductor level.
                 unreachable!()
             }
@@ -554,6 +605,23 @@ impl Cell {
     ) -> CellResult<()> {
         match message {
             CountersigningSessionNegotiationMessage::EnzymePush(dht_op) => {
+                // Only coordinate sessions this agent is the enzyme for,
+                // and only collect actions that were signed by their author.
+                if dht_op.enzymatic_countersigning_enzyme() != Some(self.id.agent_pubkey()) {
+                    warn!("Ignoring countersigning op pushed to an agent that isn't its enzyme");
+                    return Ok(());
+                }
+                let action = dht_op.action();
+                if !action
+                    .author()
+                    .verify_signature(dht_op.signature(), &action)
+                    .await
+                {
+                    warn!(
+                        "Ignoring countersigning op pushed to the enzyme with an invalid signature"
+                    );
+                    return Ok(());
+                }
                 let ops = vec![*dht_op]
                     .into_iter()
                     .map(|op| {
@@ -581,6 +649,55 @@ impl Cell {
                 .await
                 .map_err(Box::new)?)
             }
+            CountersigningSessionNegotiationMessage::EnzymeSessionTimeout {
+                entry_hash,
+                signature,
+            } => {
+                // Only the enzyme of a session this agent is in may say it timed out.
+                let author = self.id.agent_pubkey().clone();
+                let enzyme = self
+                    .space
+                    .authored_db
+                    .async_reader(move |txn| get_countersigning_sessions(&txn, &author))
+                    .await?
+                    .into_iter()
+                    .find(|(session_entry_hash, _)| *session_entry_hash == entry_hash)
+                    .and_then(|(_, session_data)| {
+                        let preflight_request = session_data.preflight_request();
+                        if preflight_request.enzymatic {
+                            preflight_request
+                                .signing_agents
+                                .first()
+                                .map(|(enzyme, _)| enzyme.clone())
+                        } else {
+                            None
+                        }
+                    });
+                let enzyme = match enzyme {
+                    Some(enzyme) => enzyme,
+                    None => {
+                        warn!("Ignoring timeout of a countersigning session that isn't enzymatic or that this agent isn't in");
+                        return Ok(());
+                    }
+                };
+                if !enzyme
+                    .verify_signature_raw(&signature, entry_hash.get_raw_39().into())
+                    .await
+                {
+                    warn!("Ignoring countersigning session timeout not signed by the session's enzyme");
+                    return Ok(());
+                }
+                // Even the enzyme isn't trusted with the outcome, which is checked with
+                // the other signers' authorities the same way as for sessions
+                // interrupted by a restart.
+                let cell = self
+                    .conductor_handle
+                    .cell_by_id(&self.id, false)
+                    .await
+                    .map_err(Box::new)?;
+                cell.resume_interrupted_countersigning();
+                Ok(())
+            }
         }
     }
 
@@ -745,6 +862,22 @@ impl Cell {
             crate::core::workflow::publish_dht_ops_workflow::DEFAULT_RECEIPT_BUNDLE_SIZE,
         );
 
+        // Let the app know that something this cell published was rejected.
+        let rejection = match &action {
+            Some(action)
+                if receipt.receipt.validation_status == ValidationStatus::Rejected
+                    && action.0.author() == self.id.agent_pubkey() =>
+            {
+                Some(SystemSignal::PublishRejected {
+                    cell_id: self.id.clone(),
+                    dht_op_hash: receipt.receipt.dht_op_hash.clone(),
+                    action_hash: ActionHash::with_data_sync(&action.0),
+                    validators: receipt.receipt.validators.clone(),
+                })
+            }
+            _ => None,
+        };
+
         self.space
             .dht_db
             .async_commit(move |txn| {
@@ -767,6 +900,11 @@ impl Cell {
             })
             .await?;
 
+        if let Some(rejection) = rejection {
+            // It's fine for nobody to be listening.
+            let _ = self.signal_broadcaster().send(rejection.into());
+        }
+
         Ok(())
     }
 
@@ -804,16 +942,25 @@ impl Cell {
         // double ? because
         // - ConductorApiResult
         // - ZomeCallResult
-        Ok(self.call_zome(invocation, None).await??.try_into()?)
+        Ok(self.call_zome(invocation, None, None).await??.try_into()?)
     }
 
-    /// Function called by the Conductor
-    // #[instrument(skip(self, call, workspace_lock))]
+    /// Function called by the Conductor.
+    ///
+    /// Chunks the zome function yields with `stream_chunk` are sent to the
+    /// `stream`, if it is given.
+    // #[instrument(skip(self, call, workspace_lock, stream))]
     pub async fn call_zome(
         &self,
         call: ZomeCall,
         workspace_lock: Option<SourceChainWorkspace>,
+        stream: ZomeCallStream,
     ) -> CellResult<ZomeCallResult> {
+        // Observed when dropped, however the call ends.
+        let _timer = super::metrics::ZOME_CALL_DURATION
+            .with_label_values(&[&call.zome_name.to_string(), &call.fn_name.to_string()])
+            .start_timer();
+
         // Only check if init has run if this call is not coming from
         // an already running init call.
         if workspace_lock
@@ -859,6 +1006,7 @@ impl Cell {
             signal_tx,
             conductor_handle,
             is_root_zome_call,
+            stream,
         };
         Ok(call_zome_workflow(
             workspace_lock,
@@ -872,6 +1020,79 @@ impl Cell {
         .map_err(Box::new)?)
     }
 
+    /// Make several zome calls which share one source chain workspace, then
+    /// write everything they added to the source chain at once.
+    ///
+    /// The calls are made in order and stop at the first one which doesn't
+    /// succeed, in which case nothing is written. The results are returned up
+    /// to and including that call.
+    ///
+    /// Once the batch is written, it stays written, so a failure to publish a
+    /// countersigning session after that is reported alongside the results
+    /// rather than in place of any of them.
+    pub async fn call_zome_batch(&self, calls: Vec<ZomeCall>) -> CellResult<ZomeCallBatchResults> {
+        // Init commits to the source chain, so it must run before the
+        // workspace of the batch is taken.
+        self.check_or_run_zome_init().await?;
+
+        let keystore = self.conductor_api.keystore().clone();
+        let ribosome = self.get_ribosome()?;
+        let workspace = SourceChainWorkspace::new(
+            self.authored_db().clone(),
+            self.dht_db().clone(),
+            self.space.dht_query_cache.clone(),
+            self.cache().clone(),
+            keystore.clone(),
+            self.id.agent_pubkey().clone(),
+            Arc::new(ribosome.dna_def().as_content().clone()),
+        )
+        .await?;
+
+        let mut coordinator_zomes: Vec<CoordinatorZome> = Vec::new();
+        let mut results = Vec::with_capacity(calls.len());
+        for (index, call) in calls.into_iter().enumerate() {
+            if let Ok(zome) = ribosome.dna_def().get_coordinator_zome(&call.zome_name) {
+                if !coordinator_zomes
+                    .iter()
+                    .any(|z| z.zome_name() == zome.zome_name())
+                {
+                    coordinator_zomes.push(zome);
+                }
+            }
+            let result = self
+                .call_zome(call, Some(workspace.clone()), None)
+                .await
+                .map_err(|e| CellError::ZomeCallBatchFailed {
+                    index,
+                    source: Box::new(e),
+                })?;
+            let succeeded = matches!(result, Ok(ZomeCallResponse::Ok(_)));
+            results.push(result);
+            if !succeeded {
+                return Ok(ZomeCallBatchResults {
+                    results,
+                    post_commit_error: None,
+                });
+            }
+        }
+
+        let post_commit_error = flush_zome_call_workspace(
+            workspace,
+            self.holochain_p2p_cell.clone(),
+            keystore,
+            self.conductor_handle.clone(),
+            coordinator_zomes,
+            self.queue_triggers.publish_dht_ops.clone(),
+            self.queue_triggers.integrate_dht_ops.clone(),
+        )
+        .await
+        .map_err(Box::new)?;
+        Ok(ZomeCallBatchResults {
+            results,
+            post_commit_error,
+        })
+    }
+
     /// Check if each Zome's init callback has been run, and if not, run it.
     #[tracing::instrument(skip(self))]
     async fn check_or_run_zome_init(&self) -> CellResult<()> {
@@ -932,6 +1153,120 @@ impl Cell {
         Ok(())
     }
 
+    /// Prepare to close or open this cell's source chain as one end of a
+    /// chain migration.
+    ///
+    /// The `migrate_agent` callbacks are run first, and the new action is only
+    /// validated if they all pass. The chain isn't touched until the returned
+    /// migration is passed to [`Cell::commit_chain_migration`].
+    pub async fn prepare_chain_migration(
+        &self,
+        migration: ChainMigration,
+    ) -> CellResult<PendingChainMigration> {
+        let keystore = self.conductor_api.keystore().clone();
+        let ribosome = self.get_ribosome()?;
+        let workspace = SourceChainWorkspace::new(
+            self.authored_db().clone(),
+            self.dht_db().clone(),
+            self.space.dht_query_cache.clone(),
+            self.cache().clone(),
+            keystore,
+            self.id.agent_pubkey().clone(),
+            Arc::new(ribosome.dna_def().as_content().clone()),
+        )
+        .await?;
+
+        let args = MigrateAgentWorkflowArgs {
+            ribosome,
+            conductor_handle: self.conductor_handle.clone(),
+            migration,
+        };
+        Ok(
+            migrate_agent_workflow(workspace, self.holochain_p2p_cell.clone(), args)
+                .await
+                .map_err(Box::new)?,
+        )
+    }
+
+    /// Write a migration action prepared by [`Cell::prepare_chain_migration`]
+    /// to this cell's source chain. Returns the hash of the action.
+    pub async fn commit_chain_migration(
+        &self,
+        pending: PendingChainMigration,
+    ) -> CellResult<ActionHash> {
+        let action_hash = pending.flush().await.map_err(Box::new)?;
+
+        self.queue_triggers
+            .publish_dht_ops
+            .trigger(&"migrate_agent_workflow");
+        self.queue_triggers
+            .integrate_dht_ops
+            .trigger(&"migrate_agent_workflow");
+        Ok(action_hash)
+    }
+
+    /// Find out the outcome of countersigning sessions that were interrupted,
+    /// e.g. by the conductor restarting, so the chain doesn't stay locked.
+    ///
+    /// Sessions that can't be decided yet are checked again periodically
+    /// until they are resolved or the cell is removed. Only one task does this
+    /// per cell, and asking again while it runs makes it check straight away.
+    pub(super) fn resume_interrupted_countersigning(self: &Arc<Self>) {
+        let resume = self.resume_countersigning.clone();
+        resume.requested.store(true, Ordering::SeqCst);
+        if resume.running.swap(true, Ordering::SeqCst) {
+            // Have the task that is already running check the sessions again.
+            resume.wake.notify_one();
+        } else {
+            tokio::spawn(Self::resume_countersigning_task(
+                Arc::downgrade(self),
+                resume,
+            ));
+        }
+    }
+
+    async fn resume_countersigning_task(cell: Weak<Self>, resume: Arc<ResumeCountersigning>) {
+        loop {
+            resume.requested.store(false, Ordering::SeqCst);
+            let unresolved = {
+                let cell = match cell.upgrade() {
+                    Some(cell) => cell,
+                    None => return,
+                };
+                match resume_countersigning_sessions(
+                    cell.space.clone(),
+                    &cell.holochain_p2p_cell,
+                    cell.id.agent_pubkey().clone(),
+                    cell.queue_triggers.clone(),
+                    cell.signal_broadcaster(),
+                )
+                .await
+                {
+                    Ok(unresolved) => unresolved,
+                    Err(error) => {
+                        error!(?error, cell_id = ?cell.id, "Failed to resume countersigning sessions");
+                        true
+                    }
+                }
+            };
+            if !unresolved {
+                resume.running.store(false, Ordering::SeqCst);
+                // Check again if that was asked for during this check,
+                // unless another task has already started to.
+                if resume.requested.load(Ordering::SeqCst)
+                    && !resume.running.swap(true, Ordering::SeqCst)
+                {
+                    continue;
+                }
+                return;
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(RESUME_SESSIONS_RETRY_INTERVAL) => {}
+                _ = resume.wake.notified() => {}
+            }
+        }
+    }
+
     /// Clean up long-running managed tasks.
     pub async fn cleanup(&self) -> CellResult<()> {
         use holochain_p2p::HolochainP2pDnaT;
@@ -48,6 +48,13 @@ pub enum CellError {
     SourceChainError(#[from] SourceChainError),
     #[error("The cell tried to run the initialize zomes callback but failed because {0:?}")]
     InitFailed(InitResult),
+    #[error("Zome call {index} of the batch failed: {source}")]
+    ZomeCallBatchFailed {
+        /// The position of the zome call in the batch
+        index: usize,
+        /// Why the zome call failed
+        source: Box<CellError>,
+    },
     #[error(
         "Another zome function has triggered the `init()` callback, which has been blocking this zome call for longer than {} seconds. Giving up.",
         INIT_MUTEX_TIMEOUT_SECS
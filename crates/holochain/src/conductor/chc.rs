@@ -1,6 +1,7 @@
 //! Types for Chain Head Coordination
 
 use holochain_p2p::ChcImpl;
+use holochain_types::chc::ChcResult;
 use holochain_zome_types::CellId;
 use once_cell::sync::Lazy;
 use std::{collections::HashMap, sync::Arc};
@@ -11,6 +12,9 @@ pub use chc_local::*;
 mod chc_remote;
 pub use chc_remote::*;
 
+mod chc_server;
+pub use chc_server::*;
+
 static CHC_LOCAL_MAP: Lazy<parking_lot::Mutex<HashMap<CellId, Arc<ChcLocal>>>> =
     Lazy::new(|| parking_lot::Mutex::new(HashMap::new()));
 
@@ -21,19 +25,21 @@ const CHC_LOCAL_MAGIC_STRING: &'static str = "#LOCAL#";
 /// In particular, if the namespace is the magic string "#LOCAL#", then a [`ChcLocal`]
 /// implementation will be used. Otherwise, if the namespace is set, and the CellId
 /// is "CHC-enabled", then a [`ChcRemote`] will be produced.
-pub fn build_chc(namespace: Option<&String>, cell_id: &CellId) -> Option<ChcImpl> {
+pub fn build_chc(namespace: Option<&String>, cell_id: &CellId) -> ChcResult<Option<ChcImpl>> {
     // TODO: check if the agent key is Holo-hosted, otherwise return none
     let is_holo_agent = true;
     if is_holo_agent {
-        namespace.map(|ns| {
-            if ns == CHC_LOCAL_MAGIC_STRING {
-                chc_local(cell_id.clone())
-            } else {
-                chc_remote(ns, cell_id)
-            }
-        })
+        namespace
+            .map(|ns| {
+                if ns == CHC_LOCAL_MAGIC_STRING {
+                    Ok(chc_local(cell_id.clone()))
+                } else {
+                    chc_remote(ns, cell_id)
+                }
+            })
+            .transpose()
     } else {
-        None
+        Ok(None)
     }
 }
 
@@ -44,6 +50,6 @@ fn chc_local(cell_id: CellId) -> ChcImpl {
         .clone()
 }
 
-fn chc_remote(namespace: &str, cell_id: &CellId) -> ChcImpl {
-    Arc::new(ChcRemote::new(namespace, cell_id))
+fn chc_remote(namespace: &str, cell_id: &CellId) -> ChcResult<ChcImpl> {
+    Ok(Arc::new(ChcRemote::new(namespace, cell_id)?))
 }
@@ -2,15 +2,28 @@
 //!
 //! **NOTE** this API is not set in stone. Do not design a CHC against this API yet,
 //! as it will change!
+//!
+//! All endpoints for a given cell live under `{namespace}/{dna_hash}/{agent_pubkey}`.
+//! Request and response bodies are msgpack-encoded. A `409 Conflict` response
+//! carries an encoded `(Option<u32>, String)` describing an [`ChcError::InvalidChain`],
+//! and a `404 Not Found` from `/get_entries` carries the encoded set of missing hashes.
 
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 use ::bytes::Bytes;
 use holo_hash::{ActionHash, EntryHash};
 use holochain_serialized_bytes::{decode, encode};
 use holochain_types::chc::{ChainHeadCoordinator, ChcError, ChcResult};
 use holochain_zome_types::prelude::*;
-use reqwest::Url;
+use reqwest::{StatusCode, Url};
+
+/// How many times a request is retried after a transient failure
+/// before giving up.
+const MAX_RETRIES: u32 = 3;
+
+/// The delay before the first retry. Doubles with each subsequent retry.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
 
 /// An HTTP client which can talk to a remote CHC implementation
 pub struct ChcRemote {
@@ -47,52 +60,235 @@ impl ChainHeadCoordinator for ChcRemote {
 
     async fn get_entries(
         &self,
-        _hashes: HashSet<&EntryHash>,
+        hashes: HashSet<&EntryHash>,
     ) -> ChcResult<HashMap<EntryHash, Entry>> {
-        todo!()
+        let body = encode(&hashes)?;
+        let response = self.entries.post("/get_entries", body).await?;
+        let entries: HashMap<EntryHash, Entry> = decode(&response)?;
+        let missing: HashSet<EntryHash> = hashes
+            .into_iter()
+            .filter(|h| !entries.contains_key(*h))
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            Ok(entries)
+        } else {
+            Err(ChcError::MissingEntries(missing))
+        }
     }
 }
 
 impl ChcRemote {
-    /// Constructor
-    pub fn new(_namespace: &str, _cell_id: &CellId) -> Self {
-        todo!("Implement remote CHC client")
+    /// Constructor.
+    ///
+    /// The namespace is the base URL of the CHC service. The URL for this
+    /// particular cell is built by appending the DNA hash and agent key to it.
+    pub fn new(namespace: &str, cell_id: &CellId) -> ChcResult<Self> {
+        let base_url = cell_url(namespace, cell_id)?;
+        Ok(Self {
+            actions: ChcRemoteClient::new(base_url.clone()),
+            entries: ChcRemoteClient::new(base_url),
+        })
     }
 }
 
+/// Build the base URL for a cell's CHC endpoints
+fn cell_url(namespace: &str, cell_id: &CellId) -> ChcResult<Url> {
+    let url = format!(
+        "{}/{}/{}",
+        namespace.trim_end_matches('/'),
+        cell_id.dna_hash(),
+        cell_id.agent_pubkey()
+    );
+    Url::parse(&url).map_err(|e| ChcError::InvalidUrl(format!("{}: {}", url, e)))
+}
+
 /// Client for a single CHC server
 pub struct ChcRemoteClient {
-    base_url: url::Url,
+    base_url: Url,
+    client: reqwest::Client,
 }
 
 impl ChcRemoteClient {
-    fn url(&self, path: &str) -> Url {
-        assert!(path.chars().nth(0) == Some('/'));
-        Url::parse(&format!("{}{}", self.base_url, path)).expect("invalid URL")
+    fn new(base_url: Url) -> Self {
+        Self {
+            base_url,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn url(&self, path: &str) -> ChcResult<Url> {
+        if !path.starts_with('/') {
+            return Err(ChcError::InvalidUrl(format!(
+                "CHC path must start with '/': {}",
+                path
+            )));
+        }
+        let url = format!("{}{}", self.base_url, path);
+        Url::parse(&url).map_err(|e| ChcError::InvalidUrl(format!("{}: {}", url, e)))
     }
 
     async fn get(&self, path: &str) -> ChcResult<Bytes> {
-        let bytes = reqwest::get(self.url(path))
-            .await
-            .map_err(extract_string)?
-            .bytes()
-            .await
-            .map_err(extract_string)?;
-        Ok(bytes)
+        let url = self.url(path)?;
+        self.send_with_retry(|| self.client.get(url.clone())).await
     }
 
     async fn post(&self, path: &str, body: Vec<u8>) -> ChcResult<Bytes> {
-        let client = reqwest::Client::new();
-        let response = client
-            .post(self.url(path))
-            .body(body)
-            .send()
+        let url = self.url(path)?;
+        self.send_with_retry(|| self.client.post(url.clone()).body(body.clone()))
             .await
-            .map_err(extract_string)?;
-        Ok(response.bytes().await.map_err(extract_string)?)
+    }
+
+    /// Send a request, retrying with exponential backoff if the failure
+    /// looks transient (connection problems, timeouts, a 5xx status, or
+    /// a 429 asking us to slow down).
+    async fn send_with_retry(
+        &self,
+        request: impl Fn() -> reqwest::RequestBuilder,
+    ) -> ChcResult<Bytes> {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            let result = match request().send().await {
+                Ok(response) => handle_response(response).await,
+                Err(e) => Err(Attempt::Transient(extract_string(e))),
+            };
+            match result {
+                Ok(bytes) => return Ok(bytes),
+                Err(Attempt::Fatal(e)) => return Err(e),
+                Err(Attempt::Transient(e)) => {
+                    if attempt >= MAX_RETRIES {
+                        return Err(e);
+                    }
+                    tracing::warn!(?e, attempt, "Transient CHC failure, retrying");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// The outcome of a failed request attempt
+enum Attempt {
+    /// The request may succeed if retried
+    Transient(ChcError),
+    /// Retrying will not help
+    Fatal(ChcError),
+}
+
+async fn handle_response(response: reqwest::Response) -> Result<Bytes, Attempt> {
+    let status = response.status();
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| Attempt::Transient(extract_string(e)))?;
+    match status {
+        s if s.is_success() => Ok(bytes),
+        StatusCode::CONFLICT => {
+            let (seq, msg): (Option<u32>, String) =
+                decode(&bytes).map_err(|e| Attempt::Fatal(e.into()))?;
+            Err(Attempt::Fatal(ChcError::InvalidChain(seq, msg)))
+        }
+        StatusCode::NOT_FOUND => match decode::<_, HashSet<EntryHash>>(&bytes) {
+            Ok(missing) => Err(Attempt::Fatal(ChcError::MissingEntries(missing))),
+            Err(_) => Err(Attempt::Fatal(service_error(status, &bytes))),
+        },
+        s if s.is_server_error() || s == StatusCode::TOO_MANY_REQUESTS => {
+            Err(Attempt::Transient(service_error(status, &bytes)))
+        }
+        _ => Err(Attempt::Fatal(service_error(status, &bytes))),
     }
 }
 
+fn service_error(status: StatusCode, body: &[u8]) -> ChcError {
+    ChcError::ServiceError(status.as_u16(), String::from_utf8_lossy(body).into_owned())
+}
+
 fn extract_string(e: reqwest::Error) -> ChcError {
     ChcError::ServiceUnreachable(e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use holochain_conductor_api::conductor::ConductorConfig;
+
+    use crate::{conductor::chc::run_chc_server, sweettest::*};
+
+    use super::*;
+
+    use ::fixt::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn remote_chc_genesis_and_fork() {
+        use holochain::test_utils::inline_zomes::simple_crud_zome;
+
+        let (driver, addr, shutdown) = run_chc_server(([127, 0, 0, 1], 0)).unwrap();
+        tokio::spawn(driver);
+        let namespace = format!("http://{}", addr);
+
+        let mut config = ConductorConfig::default();
+        config.chc_namespace = Some(namespace.clone());
+        let mut conductors =
+            SweetConductorBatch::from_configs([config.clone(), config.clone()]).await;
+
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(simple_crud_zome()).await;
+        let (agent, _) = SweetAgents::alice_and_bob();
+
+        let (cell,) = conductors[0]
+            .setup_app_for_agent("app", agent.clone(), [&dna_file])
+            .await
+            .unwrap()
+            .into_tuple();
+        let cell_id = cell.cell_id();
+
+        // The genesis actions were recorded by the remote CHC
+        let chc = ChcRemote::new(&namespace, cell_id).unwrap();
+        let actions = chc.get_actions_since_hash(None).await.unwrap();
+        assert_eq!(actions.len(), 3);
+        assert_eq!(
+            chc.head().await.unwrap().as_ref(),
+            Some(actions[2].action_address())
+        );
+
+        let entry_hashes: HashSet<&EntryHash> = actions
+            .iter()
+            .filter_map(|a| a.hashed.entry_hash())
+            .collect();
+        assert_eq!(entry_hashes.len(), 1);
+        let entries = chc.get_entries(entry_hashes).await.unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let missing = EntryHash::from_raw_36(vec![0; 36]);
+        assert!(matches!(
+            chc.get_entries([&missing].into_iter().collect()).await,
+            Err(ChcError::MissingEntries(_))
+        ));
+
+        // Trying to run genesis for the same agent elsewhere forks the chain
+        let install_result = conductors[1]
+            .setup_app_for_agent("app", agent.clone(), [&dna_file])
+            .await;
+        assert!(format!("{:?}", install_result).contains("InvalidChain(Some(2)"));
+
+        shutdown();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn remote_chc_unreachable() {
+        let chc = ChcRemote::new("http://127.0.0.1:1", &fixt!(CellId)).unwrap();
+        assert!(matches!(
+            chc.head().await,
+            Err(ChcError::ServiceUnreachable(_))
+        ));
+    }
+
+    #[test]
+    fn remote_chc_invalid_namespace() {
+        assert!(matches!(
+            ChcRemote::new("not a url", &fixt!(CellId)),
+            Err(ChcError::InvalidUrl(_))
+        ));
+    }
+}
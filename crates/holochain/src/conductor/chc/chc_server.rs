@@ -0,0 +1,168 @@
+//! An HTTP server exposing [`ChcLocal`] instances over the protocol spoken
+//! by [`ChcRemote`](super::ChcRemote), for testing purposes only.
+//!
+//! Each `{dna_hash}/{agent_pubkey}` path prefix gets its own in-memory chain,
+//! which is lost when the server stops.
+
+// Fixes some warnings introduced by `warp`
+#![allow(opaque_hidden_inferred_bound)]
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use holochain_serialized_bytes::{decode, encode};
+use holochain_types::prelude::*;
+use warp::http::{Response, StatusCode};
+use warp::hyper::body::Bytes;
+use warp::Filter;
+
+use super::ChcLocal;
+
+/// The set of chains served, keyed by the `(dna_hash, agent_pubkey)` path segments
+type ChcServerChains = Arc<parking_lot::Mutex<HashMap<(String, String), Arc<ChcLocal>>>>;
+
+/// A future which drives the server until shutdown
+pub type ChcServerDriver = futures::future::BoxFuture<'static, ()>;
+
+/// Call to gracefully stop the server
+pub type ChcServerShutdown = Box<dyn FnOnce() + 'static + Send + Sync>;
+
+/// Run a local CHC server bound to the given address.
+///
+/// Returns the driver future, which must be awaited or spawned for the
+/// server to make progress, along with the actual bound address.
+pub fn run_chc_server(
+    addr: impl Into<SocketAddr> + 'static,
+) -> Result<(ChcServerDriver, SocketAddr, ChcServerShutdown), String> {
+    let chains: ChcServerChains = Default::default();
+
+    let head = warp::get()
+        .and(warp::path!(String / String / "head"))
+        .and(with_chains(chains.clone()))
+        .and_then(
+            |dna: String, agent: String, chains: ChcServerChains| async move {
+                let chc = get_chc(&chains, dna, agent);
+                respond(chc.head().await)
+            },
+        );
+
+    let add_actions = post("add_actions", chains.clone()).and_then(
+        |dna: String, agent: String, body: Bytes, chains: ChcServerChains| async move {
+            let chc = get_chc(&chains, dna, agent);
+            match decode::<_, Vec<SignedActionHashed>>(&body) {
+                Ok(actions) => respond(chc.add_actions(actions).await),
+                Err(e) => respond::<()>(Err(e.into())),
+            }
+        },
+    );
+
+    let add_entries = post("add_entries", chains.clone()).and_then(
+        |dna: String, agent: String, body: Bytes, chains: ChcServerChains| async move {
+            let chc = get_chc(&chains, dna, agent);
+            match decode::<_, Vec<EntryHashed>>(&body) {
+                Ok(entries) => respond(chc.add_entries(entries).await),
+                Err(e) => respond::<()>(Err(e.into())),
+            }
+        },
+    );
+
+    let get_actions_since_hash = post("get_actions_since_hash", chains.clone()).and_then(
+        |dna: String, agent: String, body: Bytes, chains: ChcServerChains| async move {
+            let chc = get_chc(&chains, dna, agent);
+            match decode::<_, Option<ActionHash>>(&body) {
+                Ok(hash) => respond(chc.get_actions_since_hash(hash).await),
+                Err(e) => respond::<()>(Err(e.into())),
+            }
+        },
+    );
+
+    let get_entries = post("get_entries", chains).and_then(
+        |dna: String, agent: String, body: Bytes, chains: ChcServerChains| async move {
+            let chc = get_chc(&chains, dna, agent);
+            match decode::<_, HashSet<EntryHash>>(&body) {
+                Ok(hashes) => respond(chc.get_entries(hashes.iter().collect()).await),
+                Err(e) => respond::<()>(Err(e.into())),
+            }
+        },
+    );
+
+    let routes = head
+        .or(add_actions)
+        .or(add_entries)
+        .or(get_actions_since_hash)
+        .or(get_entries);
+
+    let (s, r) = tokio::sync::oneshot::channel();
+    let shutdown = Box::new(move || {
+        let _ = s.send(());
+    });
+
+    match warp::serve(routes).try_bind_with_graceful_shutdown(addr, async move {
+        let _ = r.await;
+    }) {
+        Ok((addr, server)) => {
+            let driver = futures::future::FutureExt::boxed(server);
+            Ok((driver, addr, shutdown))
+        }
+        Err(e) => Err(format!("Failed to bind socket: {:?}", e)),
+    }
+}
+
+fn post(
+    method: &'static str,
+    chains: ChcServerChains,
+) -> impl Filter<Extract = (String, String, Bytes, ChcServerChains), Error = warp::Rejection> + Clone
+{
+    warp::post()
+        .and(warp::path!(String / String / String))
+        .and_then(move |dna: String, agent: String, m: String| async move {
+            if m == method {
+                Ok((dna, agent))
+            } else {
+                Err(warp::reject::not_found())
+            }
+        })
+        .untuple_one()
+        .and(warp::body::bytes())
+        .and(with_chains(chains))
+}
+
+fn with_chains(
+    chains: ChcServerChains,
+) -> impl Filter<Extract = (ChcServerChains,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || chains.clone())
+}
+
+fn get_chc(chains: &ChcServerChains, dna: String, agent: String) -> Arc<ChcLocal> {
+    chains
+        .lock()
+        .entry((dna, agent))
+        .or_insert_with(|| Arc::new(ChcLocal::new()))
+        .clone()
+}
+
+/// Map a CHC result onto the status codes understood by [`ChcRemote`](super::ChcRemote).
+fn respond<T: serde::Serialize + std::fmt::Debug>(
+    result: ChcResult<T>,
+) -> Result<Response<Vec<u8>>, warp::Rejection> {
+    let (status, body) = match result {
+        Ok(val) => (StatusCode::OK, encode(&val)),
+        Err(ChcError::InvalidChain(seq, msg)) => (StatusCode::CONFLICT, encode(&(seq, msg))),
+        Err(ChcError::MissingEntries(hashes)) => (StatusCode::NOT_FOUND, encode(&hashes)),
+        Err(e @ ChcError::DeserializationError(_)) => {
+            (StatusCode::BAD_REQUEST, Ok(e.to_string().into_bytes()))
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Ok(e.to_string().into_bytes()),
+        ),
+    };
+    let response = match body {
+        Ok(body) => Response::builder().status(status).body(body),
+        Err(e) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(e.to_string().into_bytes()),
+    };
+    Ok(response.expect("response is well-formed"))
+}
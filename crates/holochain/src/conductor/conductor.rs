@@ -32,11 +32,15 @@
 pub use self::share::RwShare;
 use super::api::RealAppInterfaceApi;
 use super::api::ZomeCall;
+use super::config::AdminInterfaceAuth;
 use super::config::AdminInterfaceConfig;
+use super::config::AllowedOrigins;
 use super::config::InterfaceDriver;
+use super::config::ADMIN_AUTH_TOKEN_FILE;
 use super::entry_def_store::get_entry_defs;
 use super::error::ConductorError;
 use super::interface::error::InterfaceResult;
+use super::interface::websocket::admin_token_hash;
 use super::interface::websocket::spawn_admin_interface_tasks;
 use super::interface::websocket::spawn_app_interface_task;
 use super::interface::websocket::spawn_websocket_listener;
@@ -57,6 +61,7 @@ use super::state::ConductorState;
 use super::CellError;
 use super::{api::RealAdminInterfaceApi, manager::TaskManagerClient};
 use crate::conductor::cell::Cell;
+use crate::conductor::cell::ZomeCallBatchResults;
 use crate::conductor::config::ConductorConfig;
 use crate::conductor::error::ConductorResult;
 use crate::conductor::p2p_agent_store::get_single_agent_info;
@@ -68,6 +73,9 @@ use crate::core::ribosome::guest_callback::post_commit::PostCommitArgs;
 use crate::core::ribosome::guest_callback::post_commit::POST_COMMIT_CHANNEL_BOUND;
 use crate::core::ribosome::guest_callback::post_commit::POST_COMMIT_CONCURRENT_LIMIT;
 use crate::core::ribosome::RibosomeT;
+use crate::core::workflow::app_validation_workflow::confirm_warrant;
+use crate::core::workflow::app_validation_workflow::AppValidationWorkspace;
+use crate::core::workflow::migrate_agent_workflow::ChainMigration;
 use crate::core::workflow::ZomeCallResult;
 use crate::{
     conductor::api::error::ConductorApiResult, core::ribosome::real_ribosome::RealRibosome,
@@ -79,13 +87,17 @@ use futures::future::TryFutureExt;
 use futures::stream::StreamExt;
 use holo_hash::DnaHash;
 use holochain_conductor_api::conductor::KeystoreConfig;
+use holochain_conductor_api::AppAuthenticationToken;
+use holochain_conductor_api::AppAuthenticationTokenIssued;
 use holochain_conductor_api::AppInfo;
 use holochain_conductor_api::AppStatusFilter;
 use holochain_conductor_api::ClonedCell;
 use holochain_conductor_api::FullIntegrationStateDump;
 use holochain_conductor_api::FullStateDump;
 use holochain_conductor_api::IntegrationStateDump;
+use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
 use holochain_conductor_api::JsonDump;
+use holochain_conductor_api::ProvisionedCell;
 use holochain_keystore::lair_keystore::spawn_lair_keystore;
 use holochain_keystore::lair_keystore::spawn_lair_keystore_in_proc;
 use holochain_keystore::test_keystore::spawn_test_keystore;
@@ -119,6 +131,14 @@ use crate::core::queue_consumer::QueueTriggers;
 
 pub use holochain_types::share;
 
+mod app_auth_token_store;
+
+pub use app_auth_token_store::*;
+
+mod zome_call_rate_limiter;
+
+pub use zome_call_rate_limiter::*;
+
 mod builder;
 
 pub use builder::*;
@@ -182,6 +202,9 @@ struct CellItem {
 pub(crate) type StopBroadcaster = task_motel::StopBroadcaster;
 pub(crate) type StopReceiver = task_motel::StopListener;
 
+/// How many warrants received from the network may be checked at once
+pub const MAX_CONCURRENT_WARRANT_CHECKS: usize = 4;
+
 /// A Conductor is a group of [Cell]s
 pub struct Conductor {
     /// The collection of available, running cells associated with this Conductor
@@ -205,6 +228,28 @@ pub struct Conductor {
     /// Collection app interface data, keyed by id
     app_interfaces: RwShare<HashMap<AppInterfaceId, AppInterfaceRuntime>>,
 
+    /// The token generated for admin interfaces which authenticate with
+    /// [`AdminInterfaceAuth::GeneratedToken`]
+    admin_auth_token: String,
+
+    /// The tokens that clients can use to authenticate app interface connections
+    app_auth_token_store: RwShare<AppAuthTokenStore>,
+
+    /// Limits on how fast app interface clients can call zome functions
+    zome_call_rate_limiter: RwShare<ZomeCallRateLimiter>,
+
+    /// A permit for each zome call which may be sleeping at once
+    sleeping_zome_calls: Arc<tokio::sync::Semaphore>,
+
+    /// A permit for each received warrant which may be checked at once
+    warrant_checks: Arc<tokio::sync::Semaphore>,
+
+    /// Held while a deferred cell is provisioned, so that only one is at a time
+    deferred_provisioning: tokio::sync::Mutex<()>,
+
+    /// False while a configured DPKI service has yet to replace its stand-in
+    dpki_ready: AtomicBool,
+
     /// The interface to the task manager
     task_manager: TaskManagerClient,
 
@@ -238,7 +283,7 @@ impl Conductor {
 
 /// Methods related to conductor startup/shutdown
 mod startup_shutdown_impls {
-    use kitsune_p2p_types::box_fut_plain;
+    use std::path::PathBuf;
 
     use crate::conductor::manager::{spawn_task_outcome_handler, OutcomeReceiver, OutcomeSender};
 
@@ -258,12 +303,32 @@ mod startup_shutdown_impls {
             post_commit: tokio::sync::mpsc::Sender<PostCommitArgs>,
             outcome_sender: OutcomeSender,
         ) -> Self {
+            let zome_call_rate_limiter =
+                ZomeCallRateLimiter::new(config.zome_call_rate_limits.clone().unwrap_or_default());
+            let sleeping_zome_calls = Arc::new(tokio::sync::Semaphore::new(
+                config.max_sleeping_zome_calls(),
+            ));
+            let dpki_ready = AtomicBool::new(config.dpki.is_none());
             Self {
                 spaces,
                 running_cells: RwShare::new(HashMap::new()),
                 config,
                 shutting_down: Arc::new(AtomicBool::new(false)),
                 app_interfaces: RwShare::new(HashMap::new()),
+                admin_auth_token: {
+                    use rand::RngCore;
+                    let mut token = [0; 32];
+                    rand::thread_rng().fill_bytes(&mut token);
+                    token.iter().map(|b| format!("{:02x}", b)).collect()
+                },
+                app_auth_token_store: RwShare::new(AppAuthTokenStore::default()),
+                zome_call_rate_limiter: RwShare::new(zome_call_rate_limiter),
+                sleeping_zome_calls,
+                warrant_checks: Arc::new(tokio::sync::Semaphore::new(
+                    MAX_CONCURRENT_WARRANT_CHECKS,
+                )),
+                deferred_provisioning: tokio::sync::Mutex::new(()),
+                dpki_ready,
                 task_manager: TaskManagerClient::new(outcome_sender),
                 // Must be initialized later, since it requires an Arc<Conductor>
                 outcomes_task: RwShare::new(None),
@@ -329,35 +394,162 @@ mod startup_shutdown_impls {
                 *lock = Some(task);
             });
 
+            // Until the real services are running, use permissive stand-ins,
+            // so that the cells which back the services can themselves be created.
             self.services.share_mut(|services| {
-                let mut dpki = MockDpkiService::new();
-                dpki.expect_is_key_valid()
-                    .returning(|_, _| box_fut_plain(Ok(true)));
-                dpki.expect_key_mutation()
-                    .returning(|_, _| box_fut_plain(Ok(())));
-
-                let app_store = MockAppStoreService::new();
-
                 *services = Some(ConductorServices {
-                    dpki: Arc::new(dpki),
-                    app_store: Arc::new(app_store),
+                    dpki: Arc::new(mock_dpki()),
+                    app_store: Arc::new(mock_app_store()),
                 });
             });
 
             self.clone().add_admin_interfaces(admin_configs).await?;
             self.clone().startup_app_interfaces().await?;
 
+            if let Some(port) = self
+                .config
+                .metrics
+                .as_ref()
+                .and_then(|metrics| metrics.prometheus_port)
+            {
+                crate::conductor::metrics::spawn_prometheus_endpoint(self.task_manager(), port)
+                    .await?;
+            }
+
             // We don't care what fx are returned here, since all cells need to
             // be spun up
             let _ = self.start_paused_apps().await?;
 
-            self.process_app_status_fx(AppStatusFx::SpinUp, None).await
+            let errors = self
+                .clone()
+                .process_app_status_fx(AppStatusFx::SpinUp, None)
+                .await?;
+
+            self.initialize_services().await?;
+
+            Ok(errors)
+        }
+
+        /// Swap in the real conductor services for any services which are configured,
+        /// making sure the apps which back them are installed and running.
+        async fn initialize_services(self: Arc<Self>) -> ConductorResult<()> {
+            if let Some(config) = self.config.dpki.clone() {
+                let properties = if config.init_params.is_empty() {
+                    None
+                } else {
+                    Some(YamlProperties::new(serde_yaml::from_str(
+                        &config.init_params,
+                    )?))
+                };
+                let cell_id = self
+                    .clone()
+                    .install_service_app(
+                        config.instance_id,
+                        DPKI_ROLE_NAME,
+                        config.dna_path,
+                        properties,
+                    )
+                    .await?;
+                let dpki = DeepkeyBuiltin::new(self.clone(), cell_id);
+                self.services.share_mut(|services| {
+                    if let Some(services) = services {
+                        services.dpki = dpki;
+                    }
+                });
+                self.dpki_ready
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+
+            if let Some(config) = self.config.app_store.clone() {
+                let cell_id = self
+                    .clone()
+                    .install_service_app(
+                        config.instance_id,
+                        APP_STORE_ROLE_NAME,
+                        config.dna_path,
+                        None,
+                    )
+                    .await?;
+                let app_store = AppStoreBuiltin::new(self.clone(), cell_id);
+                self.services.share_mut(|services| {
+                    if let Some(services) = services {
+                        services.app_store = app_store;
+                    }
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Make sure the single-DNA app which backs a conductor service is installed
+        /// and enabled, installing it from the given DNA bundle path if necessary,
+        /// and return the CellId of its one cell.
+        async fn install_service_app(
+            self: Arc<Self>,
+            app_id: InstalledAppId,
+            role_name: &str,
+            dna_path: Option<PathBuf>,
+            properties: Option<YamlProperties>,
+        ) -> ConductorResult<CellId> {
+            let state = self.get_state().await?;
+            if state.get_app(&app_id).is_err() {
+                let dna_path = dna_path.ok_or_else(|| {
+                    ConductorError::ConfigError(format!(
+                        "The service app '{}' is not installed, and no dna_path was given to install it from",
+                        app_id
+                    ))
+                })?;
+                let dna_bundle = DnaBundle::read_from_file(&dna_path).await?;
+                let bundle_path = PathBuf::from(format!("{}.dna", role_name));
+                let manifest = AppManifestCurrentBuilder::default()
+                    .name(app_id.clone())
+                    .description(None)
+                    .roles(vec![AppRoleManifest {
+                        name: role_name.into(),
+                        dna: AppRoleDnaManifest {
+                            location: Some(DnaLocation::Bundled(bundle_path.clone())),
+                            modifiers: DnaModifiersOpt {
+                                properties,
+                                ..DnaModifiersOpt::none()
+                            },
+                            installed_hash: None,
+                            clone_limit: 0,
+                        },
+                        provisioning: Some(CellProvisioning::Create { deferred: false }),
+                    }])
+                    .build()
+                    .map_err(ConductorError::other)?;
+                let bundle = AppBundle::new(
+                    manifest.into(),
+                    vec![(bundle_path, dna_bundle)],
+                    PathBuf::from("."),
+                )
+                .await?;
+                let agent_key = self.keystore.new_sign_keypair_random().await?;
+                self.clone()
+                    .install_app_bundle(InstallAppPayload {
+                        source: AppBundleSource::Bundle(bundle),
+                        agent_key,
+                        installed_app_id: Some(app_id.clone()),
+                        membrane_proofs: HashMap::new(),
+                        network_seed: None,
+                    })
+                    .await?;
+            }
+
+            let (app, _) = self.enable_app(app_id).await?;
+            Ok(app.role(&role_name.to_string())?.cell_id().clone())
         }
     }
 }
 
 /// Methods related to conductor interfaces
 mod interface_impls {
+    use std::path::PathBuf;
+
+    use crate::conductor::api::error::ConductorApiError;
+    use holochain_conductor_api::ZomeCallRateLimitMetrics;
+
     use super::*;
 
     impl Conductor {
@@ -370,27 +562,46 @@ mod interface_impls {
             let admin_api = RealAdminInterfaceApi::new(self.clone());
             let tm = self.task_manager();
 
+            if configs
+                .iter()
+                .any(|config| config.auth == AdminInterfaceAuth::GeneratedToken)
+            {
+                self.write_admin_auth_token()?;
+            }
+
             // Closure to process each admin config item
-            let spawn_from_config = |AdminInterfaceConfig { driver, .. }| {
+            let spawn_from_config = |AdminInterfaceConfig {
+                                         driver,
+                                         session_duration_seconds,
+                                         auth,
+                                     }| {
                 let admin_api = admin_api.clone();
                 let tm = tm.clone();
+                let auth_token_hash = match auth {
+                    AdminInterfaceAuth::GeneratedToken => {
+                        Some(admin_token_hash(&self.admin_auth_token))
+                    }
+                    AdminInterfaceAuth::TokenHash { hash } => Some(hash.to_lowercase()),
+                    AdminInterfaceAuth::None => None,
+                };
                 async move {
-                    match driver {
-                        InterfaceDriver::Websocket { port } => {
-                            let (listener_handle, listener) =
-                                spawn_websocket_listener(port).await?;
-                            let port = listener_handle.local_addr().port().unwrap_or(port);
-                            spawn_admin_interface_tasks(
-                                tm.clone(),
-                                listener_handle,
-                                listener,
-                                admin_api.clone(),
-                                port,
-                            );
-
-                            InterfaceResult::Ok(port)
+                    let (listener_handle, listener) = spawn_websocket_listener(&driver).await?;
+                    let port = match driver {
+                        InterfaceDriver::Websocket { port, .. } => {
+                            Some(listener_handle.local_addr().port().unwrap_or(port))
                         }
-                    }
+                        InterfaceDriver::UnixSocket { .. } => None,
+                    };
+                    spawn_admin_interface_tasks(
+                        tm.clone(),
+                        listener_handle,
+                        listener,
+                        admin_api.clone(),
+                        session_duration_seconds.map(|s| std::time::Duration::from_secs(s as u64)),
+                        auth_token_hash,
+                    );
+
+                    InterfaceResult::Ok(port)
                 }
             };
 
@@ -404,19 +615,49 @@ mod interface_impls {
             // Exit if the admin interfaces fail to be created
             let ports = ports.map_err(Box::new)?;
 
-            for p in ports {
+            // Unix domain socket interfaces don't have a port
+            for p in ports.into_iter().flatten() {
                 self.add_admin_port(p);
             }
             Ok(())
         }
 
+        /// Write the generated admin auth token to [`ADMIN_AUTH_TOKEN_FILE`] in
+        /// the environment path, readable only by the user the conductor runs as.
+        fn write_admin_auth_token(&self) -> ConductorResult<()> {
+            use std::io::Write;
+
+            let path = self
+                .config
+                .environment_path
+                .as_ref()
+                .join(ADMIN_AUTH_TOKEN_FILE);
+            // The file is created anew, since its permissions are only set
+            // when it's created.
+            match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => (),
+            }
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            options
+                .open(&path)?
+                .write_all(self.admin_auth_token.as_bytes())?;
+            tracing::info!(?path, "Wrote the admin interface auth token");
+            Ok(())
+        }
+
         /// Spawn a new app interface task, register it with the TaskManager,
         /// and modify the conductor accordingly, based on the config passed in
-        /// which is just a networking port number (or 0 to auto-select one).
+        /// which is a networking port number (or 0 to auto-select one) and the
+        /// origins that may connect to it.
         /// Returns the given or auto-chosen port number if giving an Ok Result
         pub async fn add_app_interface(
             self: Arc<Self>,
             port: either::Either<u16, AppInterfaceId>,
+            allowed_origins: AllowedOrigins,
         ) -> ConductorResult<u16> {
             let interface_id = match port {
                 either::Either::Left(port) => AppInterfaceId::new(port),
@@ -424,6 +665,43 @@ mod interface_impls {
             };
             let port = interface_id.port();
             tracing::debug!("Attaching interface {}", port);
+            let driver = InterfaceDriver::Websocket {
+                port,
+                allowed_origins,
+            };
+            let driver = self.spawn_app_interface(interface_id, driver).await?;
+            let port = driver
+                .port()
+                .ok_or_else(|| ConductorError::other("Websocket app interface has no port"))?;
+            tracing::debug!("App interface added at port: {}", port);
+            Ok(port)
+        }
+
+        /// Spawn a new app interface task listening on a unix domain socket
+        /// at the given path, register it with the TaskManager, and modify
+        /// the conductor accordingly.
+        pub async fn add_app_unix_socket_interface(
+            self: Arc<Self>,
+            path: PathBuf,
+        ) -> ConductorResult<PathBuf> {
+            tracing::debug!("Attaching interface {}", path.display());
+            self.spawn_app_interface(
+                AppInterfaceId::default(),
+                InterfaceDriver::unix_socket(path.clone()),
+            )
+            .await?;
+            tracing::debug!("App interface added at path: {}", path.display());
+            Ok(path)
+        }
+
+        /// Spawn an app interface task for the driver and record the interface
+        /// in the conductor state.
+        /// Returns the driver with the port that was bound if one was auto-selected.
+        async fn spawn_app_interface(
+            self: Arc<Self>,
+            interface_id: AppInterfaceId,
+            driver: InterfaceDriver,
+        ) -> ConductorResult<InterfaceDriver> {
             let app_api = RealAppInterfaceApi::new(self.clone());
             // This receiver is thrown away because we can produce infinite new
             // receivers from the Sender
@@ -432,7 +710,7 @@ mod interface_impls {
             let tm = self.task_manager();
 
             // TODO: RELIABILITY: Handle this task by restarting it if it fails and log the error
-            let port = spawn_app_interface_task(tm.clone(), port, app_api, signal_tx.clone())
+            let driver = spawn_app_interface_task(tm.clone(), driver, app_api, signal_tx.clone())
                 .await
                 .map_err(Box::new)?;
             let interface = AppInterfaceRuntime::Websocket { signal_tx };
@@ -447,14 +725,13 @@ mod interface_impls {
                 app_interfaces.insert(interface_id.clone(), interface);
                 Ok(())
             })?;
-            let config = AppInterfaceConfig::websocket(port);
+            let config = AppInterfaceConfig::new(driver.clone());
             self.update_state(|mut state| {
                 state.app_interfaces.insert(interface_id, config);
                 Ok(state)
             })
             .await?;
-            tracing::debug!("App interface added at port: {}", port);
-            Ok(port)
+            Ok(driver)
         }
 
         /// Returns a port which is guaranteed to have a websocket listener with an Admin interface
@@ -463,14 +740,14 @@ mod interface_impls {
             self.admin_websocket_ports.share_ref(|p| p.first().copied())
         }
 
-        /// Give a list of networking ports taken up as running app interface tasks
-        pub async fn list_app_interfaces(&self) -> ConductorResult<Vec<u16>> {
+        /// Give a list of the drivers of the running app interface tasks
+        pub async fn list_app_interfaces(&self) -> ConductorResult<Vec<InterfaceDriver>> {
             Ok(self
                 .get_state()
                 .await?
                 .app_interfaces
-                .values()
-                .map(|config| config.driver.port())
+                .into_values()
+                .map(|config| config.driver)
                 .collect())
         }
 
@@ -478,12 +755,138 @@ mod interface_impls {
         /// This should only be run at conductor initialization.
         #[allow(irrefutable_let_patterns)]
         pub(crate) async fn startup_app_interfaces(self: Arc<Self>) -> ConductorResult<()> {
-            for id in self.get_state().await?.app_interfaces.keys().cloned() {
+            for (id, config) in self.get_state().await?.app_interfaces {
                 tracing::debug!("Starting up app interface: {:?}", id);
-                let _ = self.clone().add_app_interface(either::Right(id)).await?;
+                match config.driver {
+                    InterfaceDriver::Websocket {
+                        allowed_origins, ..
+                    } => {
+                        let _ = self
+                            .clone()
+                            .add_app_interface(either::Right(id), allowed_origins)
+                            .await?;
+                    }
+                    driver @ InterfaceDriver::UnixSocket { .. } => {
+                        let _ = self.clone().spawn_app_interface(id, driver).await?;
+                    }
+                }
             }
             Ok(())
         }
+
+        /// Issue a token which a client can use to authenticate a connection
+        /// to an app interface for the given apps.
+        pub fn issue_app_authentication_token(
+            &self,
+            payload: IssueAppAuthenticationTokenPayload,
+        ) -> ConductorResult<AppAuthenticationTokenIssued> {
+            if payload.installed_app_ids.is_empty() {
+                return Err(ConductorError::other(
+                    "An app authentication token must be issued for at least one app",
+                ));
+            }
+            Ok(self
+                .app_auth_token_store
+                .share_mut(|store| store.issue_token(payload)))
+        }
+
+        /// Revoke an app authentication token so that it can't be used anymore,
+        /// closing the connections authenticated with it.
+        pub fn revoke_app_authentication_token(
+            &self,
+            token: &AppAuthenticationToken,
+        ) -> ConductorResult<()> {
+            if self
+                .app_auth_token_store
+                .share_mut(|store| store.revoke_token(token))
+            {
+                Ok(())
+            } else {
+                Err(ConductorError::other("No such app authentication token"))
+            }
+        }
+
+        /// Use an app authentication token, starting the session of the
+        /// authenticated connection if the token is valid.
+        pub fn authenticate_app_token(
+            &self,
+            token: &AppAuthenticationToken,
+        ) -> Option<AppAuthSession> {
+            self.app_auth_token_store
+                .share_mut(|store| store.authenticate_token(token))
+        }
+
+        /// Count zome calls made by an app interface client against the
+        /// zome call rate limits, rejecting them all if any is over a limit.
+        ///
+        /// Returns what the calls cost, if there are any limits, which must be
+        /// passed to [`Self::settle_zome_call_rate_limits`] once they are made.
+        pub async fn check_zome_call_rate_limits(
+            &self,
+            calls: &[ZomeCall],
+        ) -> ConductorApiResult<Option<Vec<ZomeCallCost>>> {
+            if self.config.zome_call_rate_limits.is_none() {
+                return Ok(None);
+            }
+            let mut costs = Vec::with_capacity(calls.len());
+            for call in calls {
+                costs.push(ZomeCallCost {
+                    installed_app_ids: self
+                        .list_running_apps_for_dependent_cell_id(&call.cell_id)
+                        .await?
+                        .into_iter()
+                        .collect(),
+                    cap_secret: call.cap_secret,
+                    provenance: call.provenance.clone(),
+                });
+            }
+            self.zome_call_rate_limiter
+                .share_mut(|limiter| limiter.try_acquire(&costs, std::time::Instant::now()))
+                .map_err(|error| {
+                    warn!(?error, "Rejected zome calls over a rate limit");
+                    match error {
+                        ZomeCallRateLimitError::OverLimit(reason) => {
+                            ConductorApiError::RateLimited(reason)
+                        }
+                        ZomeCallRateLimitError::ExceedsCapacity(reason) => {
+                            ConductorApiError::RateLimitExceeded(reason)
+                        }
+                    }
+                })?;
+            Ok(Some(costs))
+        }
+
+        /// Settle the rate limits for zome calls allowed by
+        /// [`Self::check_zome_call_rate_limits`] once they have been made.
+        ///
+        /// The calls at the `refunded` indices, which were refused because
+        /// their signature or cap grant couldn't be verified or were never
+        /// made, don't count against the limits. Every other call does,
+        /// whatever its outcome, as do the bytes committed by the calls.
+        pub fn settle_zome_call_rate_limits(
+            &self,
+            costs: Option<Vec<ZomeCallCost>>,
+            refunded: &[usize],
+            committed_bytes: u64,
+        ) {
+            if let Some(costs) = costs {
+                self.zome_call_rate_limiter.share_mut(|limiter| {
+                    limiter.settle(&costs, refunded, committed_bytes, std::time::Instant::now())
+                });
+            }
+        }
+
+        /// The number of zome calls rejected by the zome call rate limits.
+        pub fn zome_call_rate_limit_metrics(&self) -> ZomeCallRateLimitMetrics {
+            self.zome_call_rate_limiter
+                .share_ref(|limiter| limiter.metrics())
+        }
+
+        /// Take a permit for a zome call to sleep, if fewer than the configured
+        /// maximum are already sleeping. The call may sleep while it holds it.
+        pub fn try_start_zome_call_sleep(&self) -> Option<tokio::sync::OwnedSemaphorePermit> {
+            self.sleeping_zome_calls.clone().try_acquire_owned().ok()
+        }
     }
 }
 
@@ -524,6 +927,12 @@ mod dna_impls {
                 let dna_def = ribosome.dna_def();
                 dna_defs.insert(cell_id.to_owned(), dna_def.to_owned());
             }
+            // Deferred cells don't exist yet, but their DNA may already be registered
+            for role in app.roles().values().filter(|role| role.is_deferred()) {
+                if let Ok(ribosome) = self.get_ribosome(role.dna_hash()) {
+                    dna_defs.insert(role.cell_id().to_owned(), ribosome.dna_def().to_owned());
+                }
+            }
             Ok(dna_defs)
         }
 
@@ -652,26 +1061,32 @@ mod dna_impls {
 
         /// Restart every paused app
         pub(crate) async fn start_paused_apps(&self) -> ConductorResult<AppStatusFx> {
-            let (_, delta) = self
+            let (_, (ids, delta)) = self
                 .update_state_prime(|mut state| {
                     let ids = state.paused_apps().map(first).cloned().collect::<Vec<_>>();
                     if !ids.is_empty() {
                         tracing::info!("Restarting {} paused apps: {:#?}", ids.len(), ids);
                     }
                     let deltas: Vec<AppStatusFx> = ids
-                        .into_iter()
+                        .iter()
                         .map(|id| {
                             state
-                                .transition_app_status(&id, AppStatusTransition::Start)
+                                .transition_app_status(id, AppStatusTransition::Start)
                                 .map(second)
                         })
                         .collect::<Result<Vec<_>, _>>()?;
                     let delta = deltas
                         .into_iter()
                         .fold(AppStatusFx::default(), AppStatusFx::combine);
-                    Ok((state, delta))
+                    Ok((state, (ids, delta)))
                 })
                 .await?;
+            for installed_app_id in ids {
+                self.emit_system_signal(SystemSignal::AppStatusChanged {
+                    installed_app_id,
+                    status: AppStatus::Running,
+                });
+            }
             Ok(delta)
         }
 
@@ -819,6 +1234,103 @@ mod network_impls {
             self.spaces.is_blocked(input, timestamp).await
         }
 
+        /// Store a warrant received from the network, pass it on to the other
+        /// authorities for the warranted action, and block the agent it is
+        /// against. Warrants which fail verification are dropped, as are those
+        /// against actions which our own validation doesn't reject. Our own
+        /// agents are never blocked.
+        ///
+        /// Only warrants for DNAs with a running cell are accepted. Checking a
+        /// warrant means fetching and validating its action, so warrants against
+        /// actions which are already warranted aren't checked again, and when
+        /// [`MAX_CONCURRENT_WARRANT_CHECKS`] are being checked, further warrants
+        /// are dropped. They will arrive again from the other authorities.
+        pub(crate) async fn handle_warrant(
+            self: Arc<Self>,
+            dna_hash: &DnaHash,
+            warrant: SignedWarrant,
+        ) -> ConductorResult<()> {
+            if !self
+                .running_cell_ids(None)
+                .iter()
+                .any(|cell_id| cell_id.dna_hash() == dna_hash)
+            {
+                return Ok(());
+            }
+            let dna_def = match self.get_dna_def(dna_hash) {
+                Some(dna_def) => dna_def,
+                None => return Ok(()),
+            };
+            let space = self.get_or_create_space(dna_hash)?;
+            let already_warranted = space
+                .dht_db
+                .async_reader({
+                    let warrant = warrant.warrant.clone();
+                    move |txn| contains_warrant_for_action(&txn, &warrant)
+                })
+                .await?;
+            if already_warranted {
+                return Ok(());
+            }
+            let _permit = match self.warrant_checks.clone().try_acquire_owned() {
+                Ok(permit) => permit,
+                Err(_) => {
+                    tracing::warn!(?warrant, "Dropping a warrant, too many are being checked");
+                    return Ok(());
+                }
+            };
+            if !verify_warrant(&warrant).await {
+                tracing::warn!(?warrant, "Dropping a warrant which failed verification");
+                return Ok(());
+            }
+            let workspace = AppValidationWorkspace::new(
+                space.authored_db.clone().into(),
+                space.dht_db.clone(),
+                space.dht_query_cache.clone(),
+                space.cache_db.clone(),
+                self.keystore().clone(),
+                Arc::new(dna_def),
+            );
+            let network = self.holochain_p2p.to_dna(dna_hash.clone(), None);
+            if !confirm_warrant(
+                Arc::new(dna_hash.clone()),
+                &warrant.warrant,
+                &self,
+                &workspace,
+                &network,
+            )
+            .await
+            {
+                tracing::warn!(?warrant, "Dropping a warrant which we could not confirm");
+                return Ok(());
+            }
+            let cell_id = CellId::new(dna_hash.clone(), warrant.warrant.warrantee().clone());
+            let action_hash = ActionHash::with_data_sync(warrant.warrant.action.action());
+            space
+                .dht_db
+                .async_commit({
+                    let warrant = warrant.clone();
+                    move |txn| insert_warrant(txn, warrant)
+                })
+                .await?;
+
+            // Each authority passes on a warrant the first time it confirms it,
+            // so it spreads to those who missed it when it was first published.
+            if let Err(e) = network.publish_warrant(warrant).await {
+                tracing::info!(failed_to_forward_warrant = ?e);
+            }
+
+            if self.running_cell_ids(None).contains(&cell_id) {
+                return Ok(());
+            }
+            self.block(Block::new(
+                BlockTarget::Cell(cell_id, CellBlockReason::Warrant(action_hash)),
+                InclusiveTimestampInterval::try_new(Timestamp::MIN, Timestamp::MAX)?,
+            ))
+            .await?;
+            Ok(())
+        }
+
         pub(crate) async fn prune_p2p_agents_db(&self) -> ConductorResult<()> {
             use holochain_p2p::AgentPubKeyExt;
 
@@ -1026,9 +1538,57 @@ mod network_impls {
             }))
         }
 
+        /// Compare incoming agent infos with the peer store to find the peers
+        /// that came online in, or announced they are leaving, a space.
+        /// Agents of cells on this conductor are not peers.
+        async fn peer_membership_signals(
+            &self,
+            dna_hash: &DnaHash,
+            peer_data: &[AgentInfoSigned],
+        ) -> Vec<SystemSignal> {
+            use holochain_p2p::AgentPubKeyExt;
+
+            let local_cells = self.running_cell_ids(None);
+            let db = self.p2p_agents_db(dna_hash);
+            let mut signals = vec![];
+            for info in peer_data {
+                let agent = AgentPubKey::from_kitsune(&info.agent);
+                if local_cells.contains(&CellId::new(dna_hash.clone(), agent.clone())) {
+                    continue;
+                }
+                let previous =
+                    match get_single_agent_info(db.clone().into(), dna_hash.clone(), agent.clone())
+                        .await
+                    {
+                        Ok(previous) => previous,
+                        Err(e) => {
+                            tracing::warn!(?e, "Failed to read the peer store");
+                            continue;
+                        }
+                    };
+                // Stale infos don't replace what we already know.
+                if previous
+                    .as_ref()
+                    .map(|p| p.signed_at_ms >= info.signed_at_ms)
+                    .unwrap_or(false)
+                {
+                    continue;
+                }
+                let was_online = previous.map(|p| !p.url_list.is_empty()).unwrap_or(false);
+                let is_online = !info.url_list.is_empty();
+                let dna_hash = dna_hash.clone();
+                match (was_online, is_online) {
+                    (false, true) => signals.push(SystemSignal::PeerJoined { dna_hash, agent }),
+                    (true, false) => signals.push(SystemSignal::PeerLeft { dna_hash, agent }),
+                    _ => (),
+                }
+            }
+            signals
+        }
+
         #[instrument(skip(self))]
         pub(crate) async fn dispatch_holochain_p2p_event(
-            &self,
+            self: Arc<Self>,
             event: holochain_p2p::event::HolochainP2pEvent,
         ) -> ConductorApiResult<()> {
             use HolochainP2pEvent::*;
@@ -1038,6 +1598,7 @@ mod network_impls {
                 PutAgentInfoSigned {
                     peer_data, respond, ..
                 } => {
+                    let peer_signals = self.peer_membership_signals(&dna_hash, &peer_data).await;
                     let sender = self.p2p_batch_sender(&dna_hash);
                     let (result_sender, response) = tokio::sync::oneshot::channel();
                     let _ = sender
@@ -1050,6 +1611,11 @@ mod network_impls {
                         Ok(r) => r.map_err(holochain_p2p::HolochainP2pError::other),
                         Err(e) => Err(holochain_p2p::HolochainP2pError::other(e)),
                     };
+                    if res.is_ok() {
+                        for signal in peer_signals {
+                            self.emit_system_signal(signal);
+                        }
+                    }
                     respond.respond(Ok(async move { res }.boxed().into()));
                 }
                 QueryAgentInfoSigned {
@@ -1178,6 +1744,23 @@ mod network_impls {
                     .instrument(debug_span!("handle_publish"))
                     .await;
                 }
+                WarrantReceived {
+                    dna_hash,
+                    respond,
+                    warrant,
+                    ..
+                } => {
+                    async {
+                        let res = self
+                            .clone()
+                            .handle_warrant(&dna_hash, warrant)
+                            .await
+                            .map_err(holochain_p2p::HolochainP2pError::other);
+                        respond.respond(Ok(async move { res }.boxed().into()));
+                    }
+                    .instrument(debug_span!("handle_warrant"))
+                    .await;
+                }
                 FetchOpData {
                     respond,
                     query,
@@ -1225,7 +1808,43 @@ mod network_impls {
         /// Invoke a zome function on a Cell
         pub async fn call_zome(&self, call: ZomeCall) -> ConductorApiResult<ZomeCallResult> {
             let cell = self.cell_by_id(&call.cell_id, true).await?;
-            Ok(cell.call_zome(call, None).await?)
+            Ok(cell.call_zome(call, None, None).await?)
+        }
+
+        /// Invoke a zome function on a Cell, sending the chunks it yields
+        /// with `stream_chunk` to the given channel.
+        ///
+        /// The zome function waits while the channel is full, and fails
+        /// if the receiver is dropped.
+        pub async fn call_zome_streaming(
+            &self,
+            call: ZomeCall,
+            stream: tokio::sync::mpsc::Sender<ExternIO>,
+        ) -> ConductorApiResult<ZomeCallResult> {
+            let cell = self.cell_by_id(&call.cell_id, true).await?;
+            Ok(cell.call_zome(call, None, Some(stream)).await?)
+        }
+
+        /// Invoke several zome functions on a Cell, writing what they all
+        /// commit to the source chain together or not at all.
+        ///
+        /// The calls must all be made to the same Cell. See
+        /// [`Cell::call_zome_batch`] for how the results are returned.
+        pub async fn call_zome_batch(
+            &self,
+            calls: Vec<ZomeCall>,
+        ) -> ConductorApiResult<ZomeCallBatchResults> {
+            let cell_id = match calls.first() {
+                Some(call) => call.cell_id.clone(),
+                None => return Ok(ZomeCallBatchResults::default()),
+            };
+            if calls.iter().any(|call| call.cell_id != cell_id) {
+                return Err(ConductorApiError::other(
+                    "All zome calls in a batch must be made to the same cell",
+                ));
+            }
+            let cell = self.cell_by_id(&cell_id, true).await?;
+            Ok(cell.call_zome_batch(calls).await?)
         }
 
         pub(crate) async fn call_zome_with_workspace(
@@ -1235,7 +1854,7 @@ mod network_impls {
         ) -> ConductorApiResult<ZomeCallResult> {
             debug!(cell_id = ?call.cell_id);
             let cell = self.cell_by_id(&call.cell_id, true).await?;
-            Ok(cell.call_zome(call, Some(workspace_lock)).await?)
+            Ok(cell.call_zome(call, Some(workspace_lock), None).await?)
         }
 
         /// Make a zome call with deserialization and some error unwrapping built in
@@ -1322,7 +1941,14 @@ mod app_impls {
             } = payload;
 
             let bundle = {
-                let original_bundle = source.resolve().await?;
+                let original_bundle = match source {
+                    AppBundleSource::AppStore(app_hash) => self
+                        .app_store()?
+                        .get_app_bundle(app_hash.clone())
+                        .await?
+                        .ok_or(ConductorError::AppNotInAppStore(app_hash))?,
+                    source => source.resolve().await?,
+                };
                 if let Some(network_seed) = network_seed {
                     let mut manifest = original_bundle.manifest().to_owned();
                     manifest.set_network_seed(network_seed);
@@ -1340,14 +1966,26 @@ mod app_impls {
             let local_dnas = self
                 .ribosome_store()
                 .share_ref(|store| bundle.get_all_dnas_from_store(store));
+            let state = self.get_state().await?;
+            let running_cells = self.running_cell_ids(None);
+            let existing_cells: Vec<_> = state
+                .installed_apps()
+                .values()
+                .flat_map(|app| app.owned_cells().cloned())
+                .filter(|cell_id| running_cells.contains(cell_id))
+                .collect();
             let ops = bundle
-                .resolve_cells(&local_dnas, agent_key.clone(), membrane_proofs)
+                .resolve_cells(
+                    &local_dnas,
+                    agent_key.clone(),
+                    membrane_proofs,
+                    &existing_cells,
+                )
                 .await?;
 
             let cells_to_create = ops.cells_to_create();
 
             // check if cells_to_create contains a cell identical to an existing one
-            let state = self.get_state().await?;
             let all_cells: HashSet<_> = state
                 .installed_apps()
                 .values()
@@ -1383,17 +2021,173 @@ mod app_impls {
             self: Arc<Self>,
             installed_app_id: &InstalledAppId,
         ) -> ConductorResult<()> {
-            let self_clone = self.clone();
+            use holochain_p2p::AgentPubKeyExt;
             let app = self.remove_app_from_db(installed_app_id).await?;
             tracing::debug!(msg = "Removed app from db.", app = ?app);
 
             // Remove cells which may now be dangling due to the removed app
-            self_clone
+            self.clone()
                 .process_app_status_fx(AppStatusFx::SpinDown, None)
                 .await?;
+
+            // Forget the arc pins of the cells which no other app uses
+            let state = self.get_state().await?;
+            let remaining_cells: HashSet<_> = state
+                .installed_apps()
+                .values()
+                .flat_map(|app| app.all_cells())
+                .collect();
+            for cell_id in app.all_cells() {
+                if !remaining_cells.contains(cell_id) {
+                    let db = self.spaces.p2p_agents_db(cell_id.dna_hash())?;
+                    holochain_sqlite::db::p2p_put_arc_pin(
+                        &db,
+                        cell_id.agent_pubkey().to_kitsune(),
+                        None,
+                    )
+                    .await?;
+                }
+            }
             Ok(())
         }
 
+        /// Provision the cell of a role whose provisioning was deferred when the
+        /// app was installed.
+        ///
+        /// If the role's provisioning strategy allows it, the role is bound to
+        /// a running cell of another app. Otherwise a new cell is created with
+        /// the given membrane proof, and started if the app is running.
+        pub async fn provision_deferred_cell(
+            self: Arc<Self>,
+            payload: ProvisionDeferredCellPayload,
+        ) -> ConductorResult<ProvisionedCell> {
+            let ProvisionDeferredCellPayload {
+                app_id,
+                role_name,
+                membrane_proof,
+            } = payload;
+
+            // Two calls for the same role would otherwise both find it deferred
+            // and both create a cell for it.
+            let _guard = self.deferred_provisioning.lock().await;
+
+            let state = self.get_state().await?;
+            let app = state.get_app(&app_id)?;
+            let role = app.role(&role_name)?;
+            if !role.is_deferred() {
+                return Err(AppError::RoleNotDeferred(role_name).into());
+            }
+            let (provisioning, installed_hash) = app
+                .manifest()
+                .app_roles()
+                .into_iter()
+                .find(|r| r.name == role_name)
+                .map(|r| (r.provisioning.unwrap_or_default(), r.dna.installed_hash))
+                .unwrap_or_default();
+
+            let existing_cell_id = match (&provisioning, installed_hash) {
+                (
+                    CellProvisioning::UseExisting { .. }
+                    | CellProvisioning::CreateIfNotExists { .. },
+                    Some(installed_hash),
+                ) => {
+                    let running_cells = self.running_cell_ids(None);
+                    let existing_cells: Vec<_> = state
+                        .installed_apps()
+                        .iter()
+                        .filter(|(id, _)| **id != app_id)
+                        .flat_map(|(_, app)| app.owned_cells().cloned())
+                        .filter(|cell_id| running_cells.contains(cell_id))
+                        .collect();
+                    find_existing_cell(&installed_hash.into(), app.agent_key(), &existing_cells)
+                }
+                _ => None,
+            };
+
+            let (cell_id, is_dependency) = match (existing_cell_id, provisioning) {
+                (Some(cell_id), _) => (cell_id, true),
+                (None, CellProvisioning::UseExisting { .. }) => {
+                    return Err(AppBundleError::CellResolutionFailure(
+                        role_name,
+                        format!(
+                            "No existing cell of agent {} with DNA hash {}",
+                            app.agent_key(),
+                            role.dna_hash()
+                        ),
+                    )
+                    .into());
+                }
+                (None, _) => {
+                    let cell_id = role.cell_id().clone();
+                    // A cell of another app which isn't running must not have
+                    // genesis run on its chain again.
+                    if state
+                        .installed_apps()
+                        .values()
+                        .any(|app| app.all_cells().any(|id| *id == cell_id))
+                    {
+                        return Err(ConductorError::CellAlreadyExists(cell_id));
+                    }
+                    let cells = vec![(cell_id.clone(), membrane_proof)];
+                    crate::conductor::conductor::genesis_cells(self.clone(), cells).await?;
+                    (cell_id, false)
+                }
+            };
+
+            let provisioned = self
+                .update_state({
+                    let app_id = app_id.clone();
+                    let cell_id = cell_id.clone();
+                    move |mut state| {
+                        state.get_app_mut(&app_id)?.provision_deferred_cell(
+                            &role_name,
+                            cell_id,
+                            is_dependency,
+                        )?;
+                        Ok(state)
+                    }
+                })
+                .await;
+            if let Err(e) = provisioned {
+                if !is_dependency {
+                    // No app refers to the new cell, so its chain is removed
+                    // to let the role be provisioned again.
+                    let author = cell_id.agent_pubkey().clone();
+                    let removed = match self.spaces.authored_db(cell_id.dna_hash()) {
+                        Ok(db) => {
+                            db.async_commit(move |txn| {
+                                txn.execute(
+                                    "DELETE FROM Action WHERE author = :author",
+                                    rusqlite::named_params! { ":author": author },
+                                )?;
+                                DatabaseResult::Ok(())
+                            })
+                            .await
+                        }
+                        Err(err) => Err(err),
+                    };
+                    if let Err(err) = removed {
+                        tracing::error!(
+                            ?cell_id,
+                            ?err,
+                            "Failed to remove the chain of a cell which couldn't be provisioned"
+                        );
+                    }
+                }
+                return Err(e);
+            }
+            self.clone()
+                .create_and_add_initialized_cells_for_running_apps(Some(&app_id))
+                .await?;
+
+            let dna_def = self.get_ribosome(cell_id.dna_hash())?.dna_def().clone();
+            Ok(ProvisionedCell {
+                cell_id,
+                dna_modifiers: dna_def.modifiers.clone(),
+                name: dna_def.name.clone(),
+            })
+        }
+
         /// List active AppIds
         pub async fn list_running_apps(&self) -> ConductorResult<Vec<InstalledAppId>> {
             let state = self.get_state().await?;
@@ -1540,6 +2334,20 @@ mod cell_impls {
             }
         }
 
+        /// The countersigning sessions that a running cell is coordinating as
+        /// the enzyme, along with the ones it recently finished.
+        pub(crate) async fn enzymatic_countersigning_sessions(
+            &self,
+            cell_id: &CellId,
+        ) -> ConductorResult<Vec<holochain_conductor_api::EnzymaticCountersigningSessionInfo>>
+        {
+            self.cell_by_id(cell_id, false).await?;
+            let space = self.get_or_create_space(cell_id.dna_hash())?;
+            Ok(space
+                .countersigning_workspace
+                .enzymatic_sessions(cell_id.agent_pubkey()))
+        }
+
         /// Iterator over only the cells which are fully "live", meaning they have been
         /// fully initialized and are registered with the kitsune network layer.
         /// Generally used to handle conductor interface requests.
@@ -1615,6 +2423,11 @@ mod clone_cell_impls {
             crate::conductor::conductor::genesis_cells(self.clone(), cells).await?;
             self.create_and_add_initialized_cells_for_running_apps(Some(&app_id))
                 .await?;
+            self.emit_system_signal(SystemSignal::CloneCellCreated {
+                installed_app_id: app_id,
+                clone_id: clone_cell.clone_id.clone(),
+                cell_id: clone_cell.cell_id.clone(),
+            });
             Ok(clone_cell)
         }
 
@@ -1626,7 +2439,7 @@ mod clone_cell_impls {
                 clone_cell_id,
             }: &DisableCloneCellPayload,
         ) -> ConductorResult<()> {
-            let (_, removed_cell_id) = self
+            let (_, (clone_id, removed_cell_id)) = self
                 .update_state_prime({
                     let app_id = app_id.to_owned();
                     let clone_cell_id = clone_cell_id.to_owned();
@@ -1635,11 +2448,16 @@ mod clone_cell_impls {
                         let clone_id = app.get_clone_id(&clone_cell_id)?;
                         let cell_id = app.get_clone_cell_id(&clone_cell_id)?;
                         app.disable_clone_cell(&clone_id)?;
-                        Ok((state, cell_id))
+                        Ok((state, (clone_id, cell_id)))
                     }
                 })
                 .await?;
-            self.remove_cells(&[removed_cell_id]).await;
+            self.remove_cells(&[removed_cell_id.clone()]).await;
+            self.emit_system_signal(SystemSignal::CloneCellDisabled {
+                installed_app_id: app_id.clone(),
+                clone_id,
+                cell_id: removed_cell_id,
+            });
             Ok(())
         }
 
@@ -1678,6 +2496,11 @@ mod clone_cell_impls {
 
             self.create_and_add_initialized_cells_for_running_apps(Some(&payload.app_id))
                 .await?;
+            self.emit_system_signal(SystemSignal::CloneCellEnabled {
+                installed_app_id: payload.app_id.clone(),
+                clone_id: enabled_cell.clone_id.clone(),
+                cell_id: enabled_cell.cell_id.clone(),
+            });
             Ok(enabled_cell)
         }
 
@@ -1689,18 +2512,29 @@ mod clone_cell_impls {
                 clone_cell_id,
             }: &DeleteCloneCellPayload,
         ) -> ConductorResult<()> {
-            self.update_state_prime({
-                let app_id = app_id.clone();
-                let clone_cell_id = clone_cell_id.clone();
-                move |mut state| {
-                    let app = state.get_app_mut(&app_id)?;
-                    let clone_id = app.get_disabled_clone_id(&clone_cell_id)?;
-                    app.delete_clone_cell(&clone_id)?;
-                    Ok((state, ()))
-                }
-            })
-            .await?;
+            let (_, (clone_id, cell_id)) = self
+                .update_state_prime({
+                    let app_id = app_id.clone();
+                    let clone_cell_id = clone_cell_id.clone();
+                    move |mut state| {
+                        let app = state.get_app_mut(&app_id)?;
+                        let clone_id = app.get_disabled_clone_id(&clone_cell_id)?;
+                        let cell_id = app
+                            .disabled_clone_cells()
+                            .find(|(id, _)| **id == clone_id)
+                            .map(|(_, cell_id)| cell_id.clone())
+                            .ok_or_else(|| AppError::CloneCellNotFound(clone_cell_id.clone()))?;
+                        app.delete_clone_cell(&clone_id)?;
+                        Ok((state, (clone_id, cell_id)))
+                    }
+                })
+                .await?;
             self.remove_dangling_cells().await?;
+            self.emit_system_signal(SystemSignal::CloneCellDeleted {
+                installed_app_id: app_id.clone(),
+                clone_id,
+                cell_id,
+            });
             Ok(())
         }
     }
@@ -1792,14 +2626,21 @@ mod app_status_impls {
             app_id: InstalledAppId,
             transition: AppStatusTransition,
         ) -> ConductorResult<(InstalledApp, AppStatusFx)> {
-            Ok(self
+            let (app, delta) = self
                 .update_state_prime(move |mut state| {
                     let (app, delta) = state.transition_app_status(&app_id, transition)?.clone();
                     let app = app.clone();
                     Ok((state, (app, delta)))
                 })
                 .await?
-                .1)
+                .1;
+            if delta != AppStatusFx::NoChange {
+                self.emit_system_signal(SystemSignal::AppStatusChanged {
+                    installed_app_id: app.id().clone(),
+                    status: app.status().clone(),
+                });
+            }
+            Ok((app, delta))
         }
 
         /// Pause an app
@@ -1888,7 +2729,12 @@ mod app_status_impls {
                             tracing::error!(cell_id = ?cell_id, "Timed out trying to join the network");
                             Err(cell_id)
                         }
-                        Ok(Ok(_)) => Ok(cell_id),
+                        Ok(Ok(_)) => {
+                            // Now that the network can be reached, find out what became of
+                            // any countersigning sessions that were interrupted.
+                            cell.resume_interrupted_countersigning();
+                            Ok(cell_id)
+                        }
                     }
                 });
 
@@ -1939,7 +2785,7 @@ mod app_status_impls {
             // possible, and let ourselves be optimistic that all cells will join soon after
             // the app starts.
             let cell_ids: HashSet<CellId> = self.live_cell_ids();
-            let (_, delta) = self
+            let (_, (changed, delta)) = self
                 .update_state_prime(move |mut state| {
                     #[allow(deprecated)]
                     let apps = state.installed_apps_mut().iter_mut().filter(|(id, _)| {
@@ -1948,10 +2794,11 @@ mod app_status_impls {
                             .map(|ids| ids.contains(&**id))
                             .unwrap_or(true)
                     });
+                    let mut changed = vec![];
                     let delta = apps
                         .into_iter()
-                        .map(|(_app_id, app)| {
-                            match app.status().clone() {
+                        .map(|(app_id, app)| {
+                            let delta = match app.status().clone() {
                                 Running => {
                                     // If not all required cells are running, pause the app
                                     let missing: Vec<_> = app
@@ -1980,12 +2827,22 @@ mod app_status_impls {
                                     // Disabled status should never automatically change.
                                     AppStatusFx::NoChange
                                 }
+                            };
+                            if delta != AppStatusFx::NoChange {
+                                changed.push((app_id.clone(), app.status().clone()));
                             }
+                            delta
                         })
                         .fold(AppStatusFx::default(), AppStatusFx::combine);
-                    Ok((state, delta))
+                    Ok((state, (changed, delta)))
                 })
                 .await?;
+            for (installed_app_id, status) in changed {
+                self.emit_system_signal(SystemSignal::AppStatusChanged {
+                    installed_app_id,
+                    status,
+                });
+            }
             Ok(delta)
         }
 
@@ -2094,6 +2951,69 @@ mod scheduler_impls {
                 .map(|cell_arc| cell_arc.dispatch_scheduled_fns(now));
             futures::future::join_all(tasks).await;
         }
+
+        /// The functions scheduled for a cell, with their recent executions.
+        pub async fn list_scheduled_fns(
+            &self,
+            cell_id: &CellId,
+        ) -> ConductorResult<Vec<holochain_state::schedule::ScheduledFnInfo>> {
+            self.cell_by_id(cell_id, false).await?;
+            let author = cell_id.agent_pubkey().clone();
+            Ok(self
+                .get_or_create_authored_db(cell_id.dna_hash())?
+                .async_reader(move |txn| {
+                    holochain_state::schedule::list_scheduled_fns(&txn, &author)
+                })
+                .await?)
+        }
+
+        /// Pause a scheduled function so that it is no longer dispatched, or
+        /// resume it so that it is.
+        pub async fn set_scheduled_fn_paused(
+            &self,
+            cell_id: &CellId,
+            scheduled_fn: ScheduledFn,
+            paused: bool,
+        ) -> ConductorResult<()> {
+            self.cell_by_id(cell_id, false).await?;
+            let author = cell_id.agent_pubkey().clone();
+            let target = scheduled_fn.clone();
+            let found = self
+                .get_or_create_authored_db(cell_id.dna_hash())?
+                .async_commit(move |txn| set_scheduled_fn_paused(txn, &author, &target, paused))
+                .await?;
+            if found {
+                Ok(())
+            } else {
+                Err(ConductorError::ScheduledFnNotFound(
+                    cell_id.clone(),
+                    scheduled_fn,
+                ))
+            }
+        }
+
+        /// Unschedule a function and forget its execution history.
+        pub async fn cancel_scheduled_fn(
+            &self,
+            cell_id: &CellId,
+            scheduled_fn: ScheduledFn,
+        ) -> ConductorResult<()> {
+            self.cell_by_id(cell_id, false).await?;
+            let author = cell_id.agent_pubkey().clone();
+            let target = scheduled_fn.clone();
+            let found = self
+                .get_or_create_authored_db(cell_id.dna_hash())?
+                .async_commit(move |txn| cancel_scheduled_fn(txn, &author, &target))
+                .await?;
+            if found {
+                Ok(())
+            } else {
+                Err(ConductorError::ScheduledFnNotFound(
+                    cell_id.clone(),
+                    scheduled_fn,
+                ))
+            }
+        }
     }
 }
 
@@ -2142,6 +3062,59 @@ mod misc_impls {
             Ok(())
         }
 
+        /// Migrate an agent's source chain from one running cell to another,
+        /// closing the chain of `from` and opening the chain of `to`.
+        ///
+        /// The cells must differ in exactly one of DNA hash or agent key.
+        /// Returns the hashes of the `CloseChain` and `OpenChain` actions.
+        ///
+        /// The two actions are written to different chains, so they can't be
+        /// written together. If the migration fails after the old chain was
+        /// closed, running it again opens the new chain as the successor of
+        /// the `CloseChain` action which is already there.
+        pub async fn migrate_chain(
+            &self,
+            from: CellId,
+            to: CellId,
+        ) -> ConductorResult<(ActionHash, ActionHash)> {
+            let (new_target, prev_target) = match (
+                from.dna_hash() == to.dna_hash(),
+                from.agent_pubkey() == to.agent_pubkey(),
+            ) {
+                (false, true) => (
+                    MigrationTarget::Dna(to.dna_hash().clone()),
+                    MigrationTarget::Dna(from.dna_hash().clone()),
+                ),
+                (true, false) => (
+                    MigrationTarget::Agent(to.agent_pubkey().clone()),
+                    MigrationTarget::Agent(from.agent_pubkey().clone()),
+                ),
+                _ => return Err(ConductorError::InvalidChainMigration(from, to)),
+            };
+
+            let from_cell = self.cell_by_id(&from, false).await?;
+            let to_cell = self.cell_by_id(&to, false).await?;
+
+            // Both ends run their callbacks and validate their actions before
+            // either is written, so a migration refused by the new chain
+            // doesn't leave the old one closed with no successor. An end
+            // which was written by an earlier attempt is reused as it is.
+            let close = from_cell
+                .prepare_chain_migration(ChainMigration::Close(new_target))
+                .await?;
+            let open = to_cell
+                .prepare_chain_migration(ChainMigration::Open {
+                    prev_target,
+                    close_hash: close.action_hash.clone(),
+                })
+                .await?;
+
+            let close_hash = from_cell.commit_chain_migration(close).await?;
+            let open_hash = to_cell.commit_chain_migration(open).await?;
+
+            Ok((close_hash, open_hash))
+        }
+
         /// Create a JSON dump of the cell's state
         pub async fn dump_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<String> {
             let cell = self.cell_by_id(cell_id, false).await?;
@@ -2215,6 +3188,39 @@ mod misc_impls {
                 .map_err(crate::conductor::api::error::ConductorApiError::other)
         }
 
+        /// Dump a summary of the fetch pool for a DNA
+        pub async fn dump_fetch_pool(&self, dna_hash: &DnaHash) -> ConductorApiResult<String> {
+            use holochain_p2p::HolochainP2pSender;
+            let diagnostics = self
+                .holochain_p2p()
+                .get_diagnostics(dna_hash.clone())
+                .await
+                .map_err(crate::conductor::api::error::ConductorApiError::other)?;
+            Ok(diagnostics
+                .fetch_pool
+                .summary([dna_hash.to_kitsune()].into_iter().collect()))
+        }
+
+        /// Pin the arc of a cell's agent at a fixed size, or unpin it with `None`,
+        /// and publish its agent info with the new arc. The pin is persisted,
+        /// so it's applied again whenever the cell joins the network.
+        pub async fn set_arc_pin(
+            &self,
+            cell_id: &CellId,
+            pin: Option<holochain_p2p::dht_arc::ArcPin>,
+        ) -> ConductorApiResult<()> {
+            use holochain_p2p::AgentPubKeyExt;
+            use holochain_p2p::HolochainP2pSender;
+            self.cell_by_id(cell_id, false).await?;
+            let db = self.spaces.p2p_agents_db(cell_id.dna_hash())?;
+            holochain_sqlite::db::p2p_put_arc_pin(&db, cell_id.agent_pubkey().to_kitsune(), pin)
+                .await?;
+            self.holochain_p2p()
+                .refresh_agent_info(cell_id.dna_hash().clone(), cell_id.agent_pubkey().clone())
+                .await
+                .map_err(crate::conductor::api::error::ConductorApiError::other)
+        }
+
         /// Add signed agent info to the conductor
         pub async fn add_agent_infos(
             &self,
@@ -2235,6 +3241,17 @@ mod misc_impls {
             Ok(())
         }
 
+        /// Register a newly generated agent key with DPKI.
+        pub async fn register_agent_key_with_dpki(
+            &self,
+            agent_key: AgentPubKey,
+        ) -> ConductorResult<()> {
+            if let Some(dpki) = self.dpki() {
+                dpki.register_key(agent_key).await?;
+            }
+            Ok(())
+        }
+
         /// Inject records into a source chain for a cell.
         /// If the records form a chain segment that can be "grafted" onto the existing chain, it will be.
         /// Otherwise, a new chain will be formed using the specified records.
@@ -2294,6 +3311,28 @@ mod accessor_impls {
     use super::*;
 
     impl Conductor {
+        /// Access the DPKI service, if conductor services have been initialized
+        pub(crate) fn dpki(&self) -> Option<Arc<dyn DpkiService>> {
+            self.services
+                .share_ref(|s| s.as_ref().map(|s| s.dpki.clone()))
+        }
+
+        /// Whether author keys can be checked against DPKI yet. While a
+        /// configured DPKI service is starting up, [`Conductor::dpki`] returns
+        /// a stand-in which treats every key as valid.
+        pub(crate) fn is_dpki_ready(&self) -> bool {
+            self.dpki_ready.load(std::sync::atomic::Ordering::Relaxed)
+        }
+
+        /// Access the app store service, if conductor services have been initialized
+        pub(crate) fn app_store(&self) -> ConductorResult<Arc<dyn AppStoreService>> {
+            self.services.share_ref(|s| {
+                s.as_ref()
+                    .map(|s| s.app_store.clone())
+                    .ok_or(ConductorError::ServicesNotInitialized)
+            })
+        }
+
         pub(crate) fn ribosome_store(&self) -> &RwShare<RibosomeStore> {
             &self.ribosome_store
         }
@@ -2311,6 +3350,14 @@ mod accessor_impls {
             SignalBroadcaster::new(senders)
         }
 
+        /// Send a system signal out over every app interface.
+        pub(crate) fn emit_system_signal(&self, signal: SystemSignal) {
+            // It's fine for nobody to be listening.
+            if let Err(e) = self.signal_broadcaster().send(signal.into()) {
+                tracing::debug!(?e, "No app interface received the system signal");
+            }
+        }
+
         /// Instantiate a Ribosome for use with a DNA
         pub(crate) fn get_ribosome(&self, dna_hash: &DnaHash) -> ConductorResult<RealRibosome> {
             self.ribosome_store
@@ -2565,8 +3612,10 @@ impl Conductor {
 
         let tasks = app_cells.difference(&on_cells).map(|cell_id| {
             let handle = self.clone();
-            let chc = handle.chc(cell_id);
             async move {
+                let chc = handle
+                    .chc(cell_id)
+                    .map_err(|err| (cell_id.clone(), CellError::from(Box::new(err))))?;
                 let holochain_p2p_cell =
                     handle.holochain_p2p.to_dna(cell_id.dna_hash().clone(), chc);
 
@@ -2837,7 +3886,7 @@ pub(crate) async fn genesis_cells(
             let dht_db = space.dht_db;
             let dht_db_cache = space.dht_query_cache;
             let conductor = conductor.clone();
-            let chc = conductor.chc(&cell_id);
+            let chc = conductor.chc(&cell_id).map_err(Box::new)?;
             let cell_id_inner = cell_id.clone();
             let ribosome = conductor
                 .get_ribosome(cell_id.dna_hash())
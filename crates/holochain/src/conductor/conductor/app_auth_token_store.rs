@@ -0,0 +1,233 @@
+//! Tokens that clients use to authenticate connections to app interfaces.
+
+use holochain_conductor_api::AppAuthenticationToken;
+use holochain_conductor_api::AppAuthenticationTokenIssued;
+use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
+use holochain_types::prelude::*;
+use rand::RngCore;
+use std::collections::HashMap;
+
+/// Number of random bytes in an issued token.
+const TOKEN_LENGTH: usize = 32;
+
+#[derive(Debug)]
+struct TokenEntry {
+    installed_app_ids: Vec<InstalledAppId>,
+    expires_at: Option<Timestamp>,
+    single_use: bool,
+    used: bool,
+    /// Dropped when the token is revoked or expires, which ends the sessions
+    /// of the connections authenticated with it.
+    sessions: tokio::sync::watch::Sender<()>,
+}
+
+impl TokenEntry {
+    fn is_expired(&self, now: Timestamp) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+    }
+
+    /// Whether the token can't be used again, and no connection which used
+    /// it is still open, so there is nothing left to revoke.
+    fn is_spent(&self) -> bool {
+        self.used && self.sessions.receiver_count() == 0
+    }
+}
+
+/// The session of a connection authenticated with an app authentication token,
+/// which lasts as long as the token.
+#[derive(Debug)]
+pub struct AppAuthSession {
+    /// The apps the connection can access.
+    pub installed_app_ids: Vec<InstalledAppId>,
+    expires_at: Option<Timestamp>,
+    revoked: tokio::sync::watch::Receiver<()>,
+}
+
+impl AppAuthSession {
+    /// Resolves once the token of this session has expired or been revoked.
+    pub async fn ended(mut self) {
+        let expired = async {
+            match self.expires_at {
+                Some(expires_at) => {
+                    let remaining = expires_at.as_micros() - Timestamp::now().as_micros();
+                    tokio::time::sleep(std::time::Duration::from_micros(remaining.max(0) as u64))
+                        .await
+                }
+                None => futures::future::pending().await,
+            }
+        };
+        // Nothing is ever sent, so this only returns once the sender is dropped.
+        let revoked = self.revoked.changed();
+        tokio::select! {
+            _ = expired => {}
+            _ = revoked => {}
+        }
+    }
+}
+
+/// The app authentication tokens that have been issued and have not expired
+/// or been revoked.
+///
+/// Single use tokens are kept after they have been used for as long as the
+/// connection which used them is open, so that revoking them ends its
+/// session. Expired tokens and used tokens whose connection has closed are
+/// removed whenever a token is issued or used.
+/// Tokens are only held in memory, so they don't survive a conductor restart.
+#[derive(Debug, Default)]
+pub struct AppAuthTokenStore {
+    tokens: HashMap<AppAuthenticationToken, TokenEntry>,
+}
+
+impl AppAuthTokenStore {
+    /// Issue a new random token for the apps in the payload.
+    pub fn issue_token(
+        &mut self,
+        payload: IssueAppAuthenticationTokenPayload,
+    ) -> AppAuthenticationTokenIssued {
+        let now = Timestamp::now();
+        self.prune(now);
+
+        let mut token = vec![0; TOKEN_LENGTH];
+        rand::thread_rng().fill_bytes(&mut token);
+        let expires_at = match payload.expiry_seconds {
+            0 => None,
+            s => Some((now + std::time::Duration::from_secs(s)).unwrap_or(Timestamp::MAX)),
+        };
+        self.tokens.insert(
+            token.clone(),
+            TokenEntry {
+                installed_app_ids: payload.installed_app_ids,
+                expires_at,
+                single_use: payload.single_use,
+                used: false,
+                sessions: tokio::sync::watch::channel(()).0,
+            },
+        );
+        AppAuthenticationTokenIssued { token, expires_at }
+    }
+
+    /// Use a token, starting a session for the apps it grants access to if
+    /// it is valid.
+    ///
+    /// Tokens that have expired are removed, and single use tokens can't be
+    /// used again.
+    pub fn authenticate_token(&mut self, token: &AppAuthenticationToken) -> Option<AppAuthSession> {
+        self.prune(Timestamp::now());
+        let entry = self.tokens.get_mut(token)?;
+        if entry.used {
+            return None;
+        }
+        entry.used = entry.single_use;
+        Some(AppAuthSession {
+            installed_app_ids: entry.installed_app_ids.clone(),
+            expires_at: entry.expires_at,
+            revoked: entry.sessions.subscribe(),
+        })
+    }
+
+    /// Remove the tokens which have expired or been spent.
+    fn prune(&mut self, now: Timestamp) {
+        self.tokens
+            .retain(|_, entry| !entry.is_expired(now) && !entry.is_spent());
+    }
+
+    /// Revoke a token, ending the sessions of the connections authenticated
+    /// with it. Returns false if there was no such token.
+    pub fn revoke_token(&mut self, token: &AppAuthenticationToken) -> bool {
+        self.tokens.remove(token).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_use_token_can_only_be_used_once() {
+        let mut store = AppAuthTokenStore::default();
+        let issued = store.issue_token(IssueAppAuthenticationTokenPayload::for_apps(vec![
+            "app".to_string()
+        ]));
+        assert!(issued.expires_at.is_some());
+        let session = store.authenticate_token(&issued.token).unwrap();
+        assert_eq!(session.installed_app_ids, vec!["app".to_string()]);
+        assert!(store.authenticate_token(&issued.token).is_none());
+        // A used token can still be revoked while its connection is open
+        assert!(store.revoke_token(&issued.token));
+    }
+
+    #[test]
+    fn used_token_is_pruned_once_its_connection_closes() {
+        let mut store = AppAuthTokenStore::default();
+        let issued = store.issue_token(
+            IssueAppAuthenticationTokenPayload::for_apps(vec!["app".to_string()]).expiry_seconds(0),
+        );
+        let session = store.authenticate_token(&issued.token).unwrap();
+
+        // Kept while the connection holds its session, so it can be revoked
+        store.issue_token(IssueAppAuthenticationTokenPayload::for_apps(vec![]));
+        assert!(store.tokens.contains_key(&issued.token));
+
+        drop(session);
+        store.issue_token(IssueAppAuthenticationTokenPayload::for_apps(vec![]));
+        assert!(!store.tokens.contains_key(&issued.token));
+    }
+
+    #[test]
+    fn reusable_token_until_revoked() {
+        let mut store = AppAuthTokenStore::default();
+        let issued = store.issue_token(
+            IssueAppAuthenticationTokenPayload::for_apps(vec!["app".to_string()])
+                .single_use(false)
+                .expiry_seconds(0),
+        );
+        assert_eq!(issued.expires_at, None);
+        assert!(store.authenticate_token(&issued.token).is_some());
+        assert!(store.authenticate_token(&issued.token).is_some());
+        assert!(store.revoke_token(&issued.token));
+        assert!(store.authenticate_token(&issued.token).is_none());
+        assert!(!store.revoke_token(&issued.token));
+    }
+
+    #[test]
+    fn expired_token_is_rejected() {
+        let mut store = AppAuthTokenStore::default();
+        let issued = store.issue_token(
+            IssueAppAuthenticationTokenPayload::for_apps(vec!["app".to_string()]).expiry_seconds(1),
+        );
+        store.tokens.get_mut(&issued.token).unwrap().expires_at = Some(Timestamp::now());
+        assert!(store.authenticate_token(&issued.token).is_none());
+        assert!(store.tokens.is_empty());
+    }
+
+    #[tokio::test]
+    async fn session_ends_when_token_is_revoked() {
+        let mut store = AppAuthTokenStore::default();
+        let issued = store.issue_token(
+            IssueAppAuthenticationTokenPayload::for_apps(vec!["app".to_string()]).expiry_seconds(0),
+        );
+        let session = store.authenticate_token(&issued.token).unwrap();
+        let ended = tokio::spawn(session.ended());
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        assert!(!ended.is_finished());
+
+        store.revoke_token(&issued.token);
+        tokio::time::timeout(std::time::Duration::from_secs(1), ended)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn session_ends_when_token_expires() {
+        let mut store = AppAuthTokenStore::default();
+        let issued = store.issue_token(
+            IssueAppAuthenticationTokenPayload::for_apps(vec!["app".to_string()]).expiry_seconds(1),
+        );
+        let session = store.authenticate_token(&issued.token).unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), session.ended())
+            .await
+            .unwrap();
+    }
+}
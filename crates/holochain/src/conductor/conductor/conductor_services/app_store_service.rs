@@ -21,50 +21,246 @@ pub trait AppStoreService: Send + Sync {
 
 /// The errors which can be produced by the AppStoreService
 #[derive(thiserror::Error, Debug)]
-pub enum AppStoreServiceError {}
+#[allow(missing_docs)]
+pub enum AppStoreServiceError {
+    #[error("App store DNA could not be called: {0}")]
+    ZomeCallFailed(String),
+
+    #[error("The app store returned a bundle which could not be decoded: {0}")]
+    InvalidBundle(String),
+
+    #[error(
+        "The app store returned a DNA bundle with hash {actual}, but {expected} was requested"
+    )]
+    DnaHashMismatch { expected: DnaHash, actual: DnaHash },
+
+    #[error(
+        "The app store returned an app bundle with hash {actual}, but {expected} was requested"
+    )]
+    AppHashMismatch { expected: AppHash, actual: AppHash },
+}
 /// Alias
 pub type AppStoreServiceResult<T> = Result<T, AppStoreServiceError>;
 
-/// This doesn't exist yet. We need to define it.
-pub enum AppHash {}
+/// The role name under which the conductor installs the app store DNA
+pub const APP_STORE_ROLE_NAME: &str = "app_store";
+
+/// The zome in the app store DNA which serves bundles to [`AppStoreBuiltin`].
+///
+/// An app store DNA must expose these functions in this zome:
+/// - `get_dna_bundle`: takes a [`DnaHash`], returns the encoded [`DnaBundle`] as `Option<Vec<u8>>`
+/// - `get_app_bundle`: takes an [`AppHash`], returns the encoded [`AppBundle`] as `Option<Vec<u8>>`
+///
+/// Returned bundles are verified against the requested hash, so the store need not be trusted.
+pub const APP_STORE_ZOME_NAME: &str = "app_store";
 
 /// The built-in implementation of the app store service, which runs a DNA
 pub struct AppStoreBuiltin {
-    _conductor: ConductorHandle,
-    _cell_id: CellId,
+    conductor: ConductorHandle,
+    cell_id: CellId,
 }
 
 impl AppStoreBuiltin {
     /// Constructor
     pub fn new(conductor: ConductorHandle, cell_id: CellId) -> Arc<Self> {
-        Arc::new(Self {
-            _conductor: conductor,
-            _cell_id: cell_id,
-        })
+        Arc::new(Self { conductor, cell_id })
+    }
+
+    async fn fetch<I>(&self, fn_name: &str, payload: I) -> AppStoreServiceResult<Option<Vec<u8>>>
+    where
+        I: serde::Serialize + std::fmt::Debug,
+    {
+        let provenance = self.cell_id.agent_pubkey().clone();
+        let bytes: Option<Bytes> = self
+            .conductor
+            .easy_call_zome(
+                &provenance,
+                None,
+                self.cell_id.clone(),
+                APP_STORE_ZOME_NAME,
+                fn_name,
+                payload,
+            )
+            .await
+            .map_err(|e| AppStoreServiceError::ZomeCallFailed(e.to_string()))?;
+        Ok(bytes.map(|b| b.into_vec()))
     }
 }
 
 #[async_trait::async_trait]
 #[allow(clippy::needless_lifetimes)]
 impl AppStoreService for AppStoreBuiltin {
-    async fn get_dna_bundle(&self, _dna_hash: DnaHash) -> AppStoreServiceResult<Option<DnaBundle>> {
-        todo!("placeholder")
+    async fn get_dna_bundle(&self, dna_hash: DnaHash) -> AppStoreServiceResult<Option<DnaBundle>> {
+        let bytes = match self.fetch("get_dna_bundle", dna_hash.clone()).await? {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+        let decode = || {
+            DnaBundle::decode(&bytes)
+                .map_err(|e| AppStoreServiceError::InvalidBundle(e.to_string()))
+        };
+        let (_, actual) = decode()?
+            .into_dna_file(DnaModifiersOpt::none())
+            .await
+            .map_err(|e| AppStoreServiceError::InvalidBundle(e.to_string()))?;
+        if actual != dna_hash {
+            return Err(AppStoreServiceError::DnaHashMismatch {
+                expected: dna_hash,
+                actual,
+            });
+        }
+        Ok(Some(decode()?))
     }
 
-    async fn get_app_bundle(&self, _app_hash: AppHash) -> AppStoreServiceResult<Option<AppBundle>> {
-        todo!("placeholder")
+    async fn get_app_bundle(&self, app_hash: AppHash) -> AppStoreServiceResult<Option<AppBundle>> {
+        let bytes = match self.fetch("get_app_bundle", app_hash.clone()).await? {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+        let actual = AppHash::from_bundle_bytes(&bytes);
+        if actual != app_hash {
+            return Err(AppStoreServiceError::AppHashMismatch {
+                expected: app_hash,
+                actual,
+            });
+        }
+        let bundle = AppBundle::decode(&bytes)
+            .map_err(|e| AppStoreServiceError::InvalidBundle(e.to_string()))?;
+        Ok(Some(bundle))
     }
 
     fn cell_ids<'a>(&'a self) -> std::collections::HashSet<&'a CellId> {
-        [&self._cell_id].into_iter().collect()
+        [&self.cell_id].into_iter().collect()
     }
 }
 
-/// Create a minimal usable mock of the app store
+/// Create a minimal usable mock of the app store, which never has anything in it
 pub fn mock_app_store() -> MockAppStoreService {
+    use futures::FutureExt;
     let mut app_store = MockAppStoreService::new();
+    app_store
+        .expect_get_dna_bundle()
+        .returning(|_| async move { Ok(None) }.boxed());
+    app_store
+        .expect_get_app_bundle()
+        .returning(|_| async move { Ok(None) }.boxed());
     app_store
         .expect_cell_ids()
         .return_const(std::collections::HashSet::new());
     app_store
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    use holochain_wasm_test_utils::TestWasm;
+
+    use super::*;
+    use crate::conductor::error::ConductorError;
+    use crate::sweettest::*;
+    use holochain_types::inline_zome::InlineZomeSet;
+
+    /// An inline app store DNA which serves a fixed set of encoded app bundles
+    fn inline_app_store(apps: HashMap<AppHash, Vec<u8>>) -> InlineZomeSet {
+        InlineZomeSet::new_unique_single("integrity", APP_STORE_ZOME_NAME, vec![], 0)
+            .function(
+                APP_STORE_ZOME_NAME,
+                "get_app_bundle",
+                move |_, hash: AppHash| Ok(apps.get(&hash).cloned().map(Bytes::from)),
+            )
+            .function(APP_STORE_ZOME_NAME, "get_dna_bundle", |_, _: DnaHash| {
+                Ok(Option::<Bytes>::None)
+            })
+    }
+
+    async fn app_bundle() -> AppBundle {
+        let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+        let path = PathBuf::from(format!("{}", dna.dna_hash()));
+        let roles = vec![AppRoleManifest {
+            name: "role".into(),
+            dna: AppRoleDnaManifest {
+                location: Some(DnaLocation::Bundled(path.clone())),
+                modifiers: DnaModifiersOpt::none(),
+                installed_hash: None,
+                clone_limit: 0,
+            },
+            provisioning: Some(CellProvisioning::Create { deferred: false }),
+        }];
+        let manifest = AppManifestCurrentBuilder::default()
+            .name("test_app".into())
+            .description(None)
+            .roles(roles)
+            .build()
+            .unwrap();
+        let dna_bundle = DnaBundle::from_dna_file(dna).await.unwrap();
+        AppBundle::new(
+            manifest.into(),
+            vec![(path, dna_bundle)],
+            PathBuf::from("."),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn install_app_from_app_store() {
+        let mut conductor = SweetConductor::from_standard_config().await;
+
+        let bytes = app_bundle().await.encode().unwrap();
+        let app_hash = AppHash::from_bundle_bytes(&bytes);
+
+        // A store which serves some other bytes under a hash it doesn't match
+        let bogus_hash = AppHash::from_bundle_bytes(b"bogus");
+        let mut apps = HashMap::new();
+        apps.insert(app_hash.clone(), bytes);
+        apps.insert(bogus_hash.clone(), b"not the bogus bundle".to_vec());
+
+        let (dna, _, _) = SweetDnaFile::unique_from_inline_zomes(inline_app_store(apps)).await;
+        let (cell,) = conductor
+            .setup_app("app_store", [&dna])
+            .await
+            .unwrap()
+            .into_tuple();
+        let app_store = AppStoreBuiltin::new(conductor.raw_handle(), cell.cell_id().clone());
+        conductor.raw_handle().services.share_mut(|s| {
+            s.as_mut().unwrap().app_store = app_store;
+        });
+
+        let agent = SweetAgents::one(conductor.keystore()).await;
+        let payload = |source| InstallAppPayload {
+            agent_key: agent.clone(),
+            source,
+            installed_app_id: Some("app".into()),
+            network_seed: None,
+            membrane_proofs: HashMap::new(),
+        };
+
+        let unknown_hash = AppHash::from_bundle_bytes(b"unknown");
+        assert!(matches!(
+            conductor
+                .raw_handle()
+                .install_app_bundle(payload(AppBundleSource::AppStore(unknown_hash)))
+                .await,
+            Err(ConductorError::AppNotInAppStore(_))
+        ));
+
+        assert!(matches!(
+            conductor
+                .raw_handle()
+                .install_app_bundle(payload(AppBundleSource::AppStore(bogus_hash)))
+                .await,
+            Err(ConductorError::AppStoreServiceError(
+                AppStoreServiceError::AppHashMismatch { .. }
+            ))
+        ));
+
+        let app = conductor
+            .raw_handle()
+            .install_app_bundle(payload(AppBundleSource::AppStore(app_hash)))
+            .await
+            .unwrap();
+        assert_eq!(app.all_cells().count(), 1);
+    }
+}
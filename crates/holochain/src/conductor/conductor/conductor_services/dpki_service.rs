@@ -1,7 +1,9 @@
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use holo_hash::AgentPubKey;
-use holochain_zome_types::{CellId, Timestamp};
+use holochain_keystore::AgentPubKeyExt;
+use holochain_zome_types::{CellId, Signature, Timestamp};
 
 use crate::conductor::ConductorHandle;
 
@@ -10,9 +12,13 @@ use crate::conductor::ConductorHandle;
 #[mockall::automock]
 #[allow(clippy::needless_lifetimes)]
 pub trait DpkiService: Send + Sync {
-    /// Check if the key is valid (properly created and not revoked) as-at the given Timestamp
-    async fn is_key_valid(&self, key: AgentPubKey, timestamp: Timestamp)
-        -> DpkiServiceResult<bool>;
+    /// Get the state of the key (not yet known, valid, or updated or revoked)
+    /// as-at the given Timestamp
+    async fn key_state(
+        &self,
+        key: AgentPubKey,
+        timestamp: Timestamp,
+    ) -> DpkiServiceResult<KeyState>;
 
     /// Defines the different ways that keys can be created and destroyed:
     /// If an old key is specified, it will be destroyed
@@ -35,6 +41,9 @@ pub trait DpkiService: Send + Sync {
 pub enum DpkiServiceError {
     #[error("DPKI DNA could not be called: {0}")]
     ZomeCallFailed(String),
+
+    #[error("Could not sign with the key being registered: {0}")]
+    Keystore(String),
 }
 /// Alias
 pub type DpkiServiceResult<T> = Result<T, DpkiServiceError>;
@@ -42,6 +51,15 @@ pub type DpkiServiceResult<T> = Result<T, DpkiServiceError>;
 /// Some more helpful methods built around the methods provided by the service
 #[async_trait::async_trait]
 pub trait DpkiServiceExt: DpkiService {
+    /// Check if the key is valid (properly created and not revoked) as-at the given Timestamp
+    async fn is_key_valid(
+        &self,
+        key: AgentPubKey,
+        timestamp: Timestamp,
+    ) -> DpkiServiceResult<bool> {
+        Ok(self.key_state(key, timestamp).await? == KeyState::Valid)
+    }
+
     /// Register a newly created key with DPKI
     async fn register_key(&self, key: AgentPubKey) -> DpkiServiceResult<()> {
         self.key_mutation(None, Some(key)).await
@@ -63,49 +81,178 @@ pub trait DpkiServiceExt: DpkiService {
     }
 }
 
+impl<T: DpkiService + ?Sized> DpkiServiceExt for T {}
+
+/// The role name under which the conductor installs the DPKI DNA
+pub const DPKI_ROLE_NAME: &str = "dpki";
+
+/// The zome in the DPKI DNA which exposes the key management functions
+/// called by [`DeepkeyBuiltin`].
+///
+/// A DPKI DNA must expose these functions in this zome:
+/// - `key_state`: takes a [`KeyStateInput`], returns a [`KeyState`]
+/// - `register_key`, `update_key`, `revoke_key`: take a [`KeyMutationInput`], return `()`
+pub const DPKI_ZOME_NAME: &str = "deepkey";
+
+/// Input to the DPKI `key_state` function
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct KeyStateInput {
+    /// The key to look up
+    pub key: AgentPubKey,
+    /// The time at which the key's state is being queried
+    pub timestamp: Timestamp,
+}
+
+/// The state of a key as-at some timestamp, as reported by DPKI
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum KeyState {
+    /// The key was never registered, or its registration hasn't reached
+    /// this DPKI cell yet
+    NotFound,
+    /// The key was registered, but had been updated or revoked by this time
+    Invalidated,
+    /// The key was registered and not yet invalidated at this time
+    Valid,
+}
+
+/// Input to the DPKI `register_key`, `update_key` and `revoke_key` functions
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct KeyMutationInput {
+    /// The key being updated or revoked, if any
+    pub old_key: Option<AgentPubKey>,
+    /// The key being registered, if any
+    pub new_key: Option<AgentPubKey>,
+    /// A signature by the new key over the DPKI cell's own agent key,
+    /// proving that the conductor controls the private half of the new key
+    pub new_key_signature: Option<Signature>,
+}
+
+/// The most keys whose states [`DeepkeyBuiltin`] remembers. Past this, the
+/// least recently used key is forgotten.
+const KEY_STATE_CACHE_SIZE: usize = 10_000;
+
+/// What has been learned so far about when a key was valid.
+///
+/// A key only ever moves from not found, to valid, to invalidated, so any
+/// timestamp between two at which the key was seen to be valid was valid
+/// too, and any timestamp after it was seen to be invalidated is too.
+/// `NotFound` is never remembered, since the key may be registered in a
+/// part of the DPKI DHT we haven't heard from yet.
+#[derive(Debug, Default)]
+struct KnownKeyState {
+    valid: Option<(Timestamp, Timestamp)>,
+    invalidated_from: Option<Timestamp>,
+}
+
+impl KnownKeyState {
+    fn get(&self, timestamp: Timestamp) -> Option<KeyState> {
+        match (self.valid, self.invalidated_from) {
+            (_, Some(from)) if timestamp >= from => Some(KeyState::Invalidated),
+            (Some((from, until)), _) if timestamp >= from && timestamp <= until => {
+                Some(KeyState::Valid)
+            }
+            _ => None,
+        }
+    }
+
+    fn record(&mut self, timestamp: Timestamp, state: &KeyState) {
+        match state {
+            KeyState::NotFound => (),
+            KeyState::Valid => {
+                self.valid = Some(match self.valid {
+                    Some((from, until)) => (from.min(timestamp), until.max(timestamp)),
+                    None => (timestamp, timestamp),
+                })
+            }
+            KeyState::Invalidated => {
+                self.invalidated_from = Some(match self.invalidated_from {
+                    Some(from) => from.min(timestamp),
+                    None => timestamp,
+                })
+            }
+        }
+    }
+}
+
 /// The built-in implementation of the DPKI service contract, which runs a DNA
 pub struct DeepkeyBuiltin {
     conductor: ConductorHandle,
     cell_id: CellId,
+    key_states: parking_lot::Mutex<lru::LruCache<AgentPubKey, KnownKeyState>>,
 }
 
 impl DeepkeyBuiltin {
     /// Constructor
     pub fn new(conductor: ConductorHandle, cell_id: CellId) -> Arc<Self> {
-        Arc::new(Self { conductor, cell_id })
+        Arc::new(Self {
+            conductor,
+            cell_id,
+            key_states: parking_lot::Mutex::new(lru::LruCache::new(
+                NonZeroUsize::new(KEY_STATE_CACHE_SIZE).expect("cache size is not zero"),
+            )),
+        })
+    }
+
+    async fn call_dpki<I, O>(&self, fn_name: &str, payload: I) -> DpkiServiceResult<O>
+    where
+        I: serde::Serialize + std::fmt::Debug,
+        O: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        let provenance = self.cell_id.agent_pubkey().clone();
+        self.conductor
+            .easy_call_zome(
+                &provenance,
+                None,
+                self.cell_id.clone(),
+                DPKI_ZOME_NAME,
+                fn_name,
+                payload,
+            )
+            .await
+            .map_err(|e| DpkiServiceError::ZomeCallFailed(e.to_string()))
     }
 }
 
-#[allow(unreachable_code)]
-#[allow(unused_variables)]
 #[allow(clippy::needless_lifetimes)]
 #[async_trait::async_trait]
 impl DpkiService for DeepkeyBuiltin {
-    async fn is_key_valid(
+    async fn key_state(
         &self,
         key: AgentPubKey,
         timestamp: Timestamp,
-    ) -> DpkiServiceResult<bool> {
-        let keystore = self.conductor.keystore();
-        let cell_id = self.cell_id.clone();
-        let zome_name: String = "TODO: depends on dna implementation".to_string();
-        let fn_name: String = "TODO: depends on dna implementation".to_string();
-        let payload = "TODO: depends on dna implementation".to_string();
-        let cap_secret = None;
-        let provenance = cell_id.agent_pubkey().clone();
-        let is_valid: bool = self
-            .conductor
-            .easy_call_zome(
-                &provenance,
-                cap_secret,
-                cell_id,
-                zome_name,
-                fn_name,
-                payload,
+    ) -> DpkiServiceResult<KeyState> {
+        // The DPKI cell's own key cannot be vouched for by DPKI itself
+        if &key == self.cell_id.agent_pubkey() {
+            return Ok(KeyState::Valid);
+        }
+        if let Some(state) = self
+            .key_states
+            .lock()
+            .get(&key)
+            .and_then(|known| known.get(timestamp))
+        {
+            return Ok(state);
+        }
+        let state: KeyState = self
+            .call_dpki(
+                "key_state",
+                KeyStateInput {
+                    key: key.clone(),
+                    timestamp,
+                },
             )
-            .await
-            .map_err(|e| DpkiServiceError::ZomeCallFailed(e.to_string()))?;
-        Ok(is_valid)
+            .await?;
+        let mut key_states = self.key_states.lock();
+        match key_states.get_mut(&key) {
+            Some(known) => known.record(timestamp, &state),
+            None if state != KeyState::NotFound => {
+                let mut known = KnownKeyState::default();
+                known.record(timestamp, &state);
+                key_states.put(key, known);
+            }
+            None => (),
+        }
+        Ok(state)
     }
 
     async fn key_mutation(
@@ -113,7 +260,32 @@ impl DpkiService for DeepkeyBuiltin {
         old_key: Option<AgentPubKey>,
         new_key: Option<AgentPubKey>,
     ) -> DpkiServiceResult<()> {
-        todo!()
+        let fn_name = match (&old_key, &new_key) {
+            (None, None) => return Ok(()),
+            (None, Some(_)) => "register_key",
+            (Some(_), Some(_)) => "update_key",
+            (Some(_), None) => "revoke_key",
+        };
+        let new_key_signature = match &new_key {
+            Some(key) => {
+                let data: Arc<[u8]> = self.cell_id.agent_pubkey().get_raw_39().into();
+                Some(
+                    key.sign_raw(self.conductor.keystore(), data)
+                        .await
+                        .map_err(|e| DpkiServiceError::Keystore(e.to_string()))?,
+                )
+            }
+            None => None,
+        };
+        self.call_dpki(
+            fn_name,
+            KeyMutationInput {
+                old_key,
+                new_key,
+                new_key_signature,
+            },
+        )
+        .await
     }
 
     fn cell_ids<'a>(&'a self) -> std::collections::HashSet<&'a CellId> {
@@ -125,9 +297,143 @@ impl DpkiService for DeepkeyBuiltin {
 pub fn mock_dpki() -> MockDpkiService {
     use futures::FutureExt;
     let mut dpki = MockDpkiService::new();
-    dpki.expect_is_key_valid()
-        .returning(|_, _| async move { Ok(true) }.boxed());
+    dpki.expect_key_state()
+        .returning(|_, _| async move { Ok(KeyState::Valid) }.boxed());
+    dpki.expect_key_mutation()
+        .returning(|_, _| async move { Ok(()) }.boxed());
     dpki.expect_cell_ids()
         .return_const(std::collections::HashSet::new());
     dpki
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use holochain_types::prelude::*;
+
+    use super::*;
+    use crate::sweettest::*;
+    use holochain_types::inline_zome::InlineZomeSet;
+
+    /// An inline DPKI DNA which tracks key registrations in memory
+    fn inline_dpki() -> InlineZomeSet {
+        // For each key: when it was registered, and when it was invalidated
+        let keys: Arc<parking_lot::Mutex<HashMap<AgentPubKey, (Timestamp, Option<Timestamp>)>>> =
+            Default::default();
+        let k0 = keys.clone();
+        let k1 = keys.clone();
+        let k2 = keys.clone();
+        let k3 = keys;
+        InlineZomeSet::new_unique_single("integrity", DPKI_ZOME_NAME, vec![], 0)
+            .function(
+                DPKI_ZOME_NAME,
+                "key_state",
+                move |_, KeyStateInput { key, timestamp }| {
+                    Ok(match k0.lock().get(&key) {
+                        None => KeyState::NotFound,
+                        Some((from, _)) if timestamp < *from => KeyState::NotFound,
+                        Some((_, Some(until))) if timestamp >= *until => KeyState::Invalidated,
+                        Some(_) => KeyState::Valid,
+                    })
+                },
+            )
+            .function(
+                DPKI_ZOME_NAME,
+                "register_key",
+                move |_, input: KeyMutationInput| {
+                    assert!(input.new_key_signature.is_some());
+                    k1.lock()
+                        .insert(input.new_key.unwrap(), (Timestamp::now(), None));
+                    Ok(())
+                },
+            )
+            .function(
+                DPKI_ZOME_NAME,
+                "update_key",
+                move |_, input: KeyMutationInput| {
+                    let now = Timestamp::now();
+                    let mut keys = k2.lock();
+                    keys.get_mut(&input.old_key.unwrap()).unwrap().1 = Some(now);
+                    keys.insert(input.new_key.unwrap(), (now, None));
+                    Ok(())
+                },
+            )
+            .function(
+                DPKI_ZOME_NAME,
+                "revoke_key",
+                move |_, input: KeyMutationInput| {
+                    k3.lock().get_mut(&input.old_key.unwrap()).unwrap().1 = Some(Timestamp::now());
+                    Ok(())
+                },
+            )
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn deepkey_builtin_key_lifecycle() {
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let (dna, _, _) = SweetDnaFile::unique_from_inline_zomes(inline_dpki()).await;
+        let (cell,) = conductor
+            .setup_app("dpki", [&dna])
+            .await
+            .unwrap()
+            .into_tuple();
+        let dpki = DeepkeyBuiltin::new(conductor.raw_handle(), cell.cell_id().clone());
+
+        let key1 = SweetAgents::one(conductor.keystore()).await;
+        let key2 = SweetAgents::one(conductor.keystore()).await;
+
+        // The DPKI agent itself is always valid
+        assert!(dpki
+            .is_key_valid(cell.agent_pubkey().clone(), Timestamp::now())
+            .await
+            .unwrap());
+
+        assert!(!dpki
+            .is_key_valid(key1.clone(), Timestamp::now())
+            .await
+            .unwrap());
+        dpki.register_key(key1.clone()).await.unwrap();
+        assert!(dpki
+            .is_key_valid(key1.clone(), Timestamp::now())
+            .await
+            .unwrap());
+
+        let before_update = Timestamp::now();
+        dpki.update_key(key1.clone(), key2.clone()).await.unwrap();
+        assert!(!dpki
+            .is_key_valid(key1.clone(), Timestamp::now())
+            .await
+            .unwrap());
+        assert!(dpki
+            .is_key_valid(key1.clone(), before_update)
+            .await
+            .unwrap());
+        assert!(dpki
+            .is_key_valid(key2.clone(), Timestamp::now())
+            .await
+            .unwrap());
+
+        dpki.remove_key(key2.clone()).await.unwrap();
+        assert!(!dpki.is_key_valid(key2, Timestamp::now()).await.unwrap());
+    }
+
+    #[test]
+    fn known_key_state_only_remembers_settled_states() {
+        let t = |micros| Timestamp::from_micros(micros);
+        let mut known = KnownKeyState::default();
+
+        known.record(t(10), &KeyState::NotFound);
+        assert_eq!(known.get(t(10)), None);
+
+        known.record(t(20), &KeyState::Valid);
+        known.record(t(40), &KeyState::Valid);
+        assert_eq!(known.get(t(30)), Some(KeyState::Valid));
+        assert_eq!(known.get(t(10)), None);
+        assert_eq!(known.get(t(50)), None);
+
+        known.record(t(60), &KeyState::Invalidated);
+        assert_eq!(known.get(t(70)), Some(KeyState::Invalidated));
+        assert_eq!(known.get(t(50)), None);
+    }
+}
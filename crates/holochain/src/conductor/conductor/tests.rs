@@ -221,19 +221,6 @@ async fn common_genesis_test_app(
 ) -> ConductorApiResult<SweetApp> {
     let hardcoded_zome = InlineIntegrityZome::new_unique(Vec::new(), 0);
 
-    // Just a strong reminder that we need to be careful once we start using existing Cells:
-    // When a Cell panics or fails validation in general, we want to disable all Apps touching that Cell.
-    // However, if the panic/failure happens during Genesis, we want to completely
-    // destroy the app which is attempting to Create that Cell, but *NOT* any other apps
-    // which might be touching that Cell.
-    //
-    // It probably works out to be the same either way, since if we are creating a Cell,
-    // no other app could be possibly referencing it, but just in case we have some kind of complex
-    // behavior like installing two apps which reference each others' Cells at the same time,
-    // we need to be aware of this distinction.
-    holochain_types::app::we_must_remember_to_rework_cell_panic_handling_after_implementing_use_existing_cell_resolution(
-    );
-
     // Create one DNA which always works, and another from a zome that gets passed in
     let (dna_hardcoded, _, _) = mk_dna(("hardcoded", hardcoded_zome)).await;
     let (dna_custom, _, _) = mk_dna(custom_zomes).await;
@@ -620,6 +607,94 @@ async fn test_enable_disable_enable_app() {
     assert_eq!(inactive_apps.len(), 0);
 }
 
+/// Wait for the given system signal, skipping any other signals.
+async fn await_system_signal(signals: &mut SignalStream, expected: SystemSignal) {
+    use tokio_stream::StreamExt;
+    tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        while let Some(signal) = signals.next().await {
+            if signal == Signal::System(expected.clone()) {
+                return;
+            }
+        }
+        panic!("The signal stream ended");
+    })
+    .await
+    .unwrap_or_else(|_| panic!("Timed out waiting for {:?}", expected));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn app_status_and_clone_cell_changes_emit_system_signals() {
+    holochain_trace::test_run().ok();
+    let zome = simple_create_entry_zome();
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let mut signals = conductor.signals();
+    let app = common_genesis_test_app(&mut conductor, ("zome", zome))
+        .await
+        .unwrap();
+    let app_id = app.installed_app_id().clone();
+    let (_, cell) = app.into_tuple();
+
+    conductor
+        .disable_app(app_id.clone(), DisabledAppReason::User)
+        .await
+        .unwrap();
+    await_system_signal(
+        &mut signals,
+        SystemSignal::AppStatusChanged {
+            installed_app_id: app_id.clone(),
+            status: AppStatus::Disabled(DisabledAppReason::User),
+        },
+    )
+    .await;
+
+    conductor.enable_app(app_id.clone()).await.unwrap();
+    await_system_signal(
+        &mut signals,
+        SystemSignal::AppStatusChanged {
+            installed_app_id: app_id.clone(),
+            status: AppStatus::Running,
+        },
+    )
+    .await;
+
+    let clone = conductor
+        .create_clone_cell(CreateCloneCellPayload {
+            app_id: app_id.clone(),
+            role_name: cell.cell_id().dna_hash().to_string(),
+            modifiers: DnaModifiersOpt::default().with_network_seed("new seed".into()),
+            membrane_proof: None,
+            name: None,
+        })
+        .await
+        .unwrap();
+    await_system_signal(
+        &mut signals,
+        SystemSignal::CloneCellCreated {
+            installed_app_id: app_id.clone(),
+            clone_id: clone.clone_id.clone(),
+            cell_id: clone.cell_id.clone(),
+        },
+    )
+    .await;
+
+    conductor
+        .disable_clone_cell(&DisableCloneCellPayload {
+            app_id: app_id.clone(),
+            clone_cell_id: CloneCellId::CloneId(clone.clone_id.clone()),
+        })
+        .await
+        .unwrap();
+    await_system_signal(
+        &mut signals,
+        SystemSignal::CloneCellDisabled {
+            installed_app_id: app_id.clone(),
+            clone_id: clone.clone_id.clone(),
+            cell_id: clone.cell_id.clone(),
+        },
+    )
+    .await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_enable_disable_enable_clone_cell() {
     holochain_trace::test_run().ok();
@@ -1171,3 +1246,187 @@ async fn test_init_concurrency() {
     assert_eq!(num_calls_clone.fetch_add(0, Ordering::SeqCst), 100);
     assert_eq!(num_inits_clone.fetch_add(0, Ordering::SeqCst), 1);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn zome_call_batch_commits_all_or_nothing() {
+    holochain_trace::test_run().ok();
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Validate]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let keystore = conductor.keystore();
+    let calls = |fn_names: &'static [&'static str]| {
+        let keystore = keystore.clone();
+        let cell_id = cell.cell_id().clone();
+        async move {
+            let mut calls = Vec::new();
+            for fn_name in fn_names {
+                calls.push(
+                    crate::test_utils::new_zome_call(
+                        &keystore,
+                        &cell_id,
+                        fn_name,
+                        (),
+                        TestWasm::Validate,
+                    )
+                    .await
+                    .unwrap(),
+                );
+            }
+            calls
+        }
+    };
+
+    // The invalid entry fails the batch, so the valid one before it isn't
+    // committed either.
+    let result = conductor
+        .raw_handle()
+        .call_zome_batch(calls(&["always_validates", "never_validates", "always_validates"]).await)
+        .await;
+    assert_matches!(
+        result,
+        Err(ConductorApiError::CellError(
+            CellError::ZomeCallBatchFailed { index: 1, .. }
+        ))
+    );
+
+    let batch = conductor
+        .raw_handle()
+        .call_zome_batch(calls(&["always_validates", "always_validates"]).await)
+        .await
+        .unwrap();
+    assert!(batch.post_commit_error.is_none());
+    let mut action_seqs = Vec::new();
+    for result in batch.results {
+        let action_hash: ActionHash = match result {
+            Ok(ZomeCallResponse::Ok(output)) => output.decode().unwrap(),
+            other => panic!("unexpected result {:?}", other),
+        };
+        let record: Record = conductor
+            .call(
+                &cell.zome(TestWasm::Validate),
+                "must_get_valid_record",
+                action_hash,
+            )
+            .await;
+        action_seqs.push(record.action().action_seq());
+    }
+    // Both entries come straight after genesis and init, with nothing left
+    // behind by the failed batch.
+    assert_eq!(action_seqs, vec![4, 5]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn zome_calls_over_a_rate_limit_are_rejected() {
+    use crate::conductor::api::AppInterfaceApi;
+    use crate::conductor::api::RealAppInterfaceApi;
+    use holochain_conductor_api::conductor::RateLimit;
+    use holochain_conductor_api::conductor::ZomeCallRateLimitConfig;
+    use holochain_conductor_api::AppRequest;
+    use holochain_conductor_api::AppResponse;
+    use holochain_conductor_api::ExternalApiWireError;
+
+    holochain_trace::test_run().ok();
+    let mut config = SweetConductorConfig::standard();
+    config.zome_call_rate_limits = Some(ZomeCallRateLimitConfig {
+        per_provenance: Some(RateLimit {
+            calls_per_second: Some(1),
+            bytes_per_minute: None,
+        }),
+        ..Default::default()
+    });
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+    let mut conductor = SweetConductor::from_config(config).await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let api = RealAppInterfaceApi::new(conductor.raw_handle());
+
+    let mut responses = Vec::new();
+    for _ in 0..2 {
+        let call = crate::test_utils::new_zome_call(
+            &conductor.keystore(),
+            cell.cell_id(),
+            "foo",
+            (),
+            TestWasm::Foo,
+        )
+        .await
+        .unwrap();
+        responses.push(
+            api.handle_app_request(AppRequest::CallZome(Box::new(call)))
+                .await,
+        );
+    }
+    assert_matches!(responses[0], AppResponse::ZomeCalled(_));
+    assert_matches!(
+        responses[1],
+        AppResponse::Error(ExternalApiWireError::RateLimited(_))
+    );
+
+    let metrics = conductor.raw_handle().zome_call_rate_limit_metrics();
+    assert_eq!(
+        metrics.rejected_by_provenance.get(cell.agent_pubkey()),
+        Some(&1)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pinned_arc_is_used_for_agent_info_and_survives_restart() {
+    use holochain_p2p::dht_arc::ArcPin;
+    use holochain_p2p::AgentPubKeyExt;
+
+    holochain_trace::test_run().ok();
+    let (dna, _, _) = SweetDnaFile::unique_from_inline_zomes(simple_crud_zome()).await;
+    // With dynamic arcs, an unpinned arc would grow again
+    let mut config = SweetConductorConfig::standard();
+    if let Some(network) = config.network.as_mut() {
+        *network = network.clone().tune(|mut tp| {
+            tp.gossip_dynamic_arcs = true;
+            tp
+        });
+    }
+    let mut conductor = SweetConductor::from_config(config).await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let cell_id = cell.cell_id().clone();
+
+    conductor
+        .set_arc_pin(&cell_id, Some(ArcPin::Empty))
+        .await
+        .unwrap();
+    let info = conductor
+        .get_agent_infos(Some(cell_id.clone()))
+        .await
+        .unwrap()[0]
+        .clone();
+    assert!(info.storage_arc.is_empty());
+
+    // The pin is persisted, so it's picked up again when the agent
+    // rejoins the network and signs new agent info.
+    conductor.shutdown().await;
+    conductor.startup().await;
+    assert_eq_retry_10s!(
+        conductor
+            .get_agent_infos(Some(cell_id.clone()))
+            .await
+            .unwrap()
+            .first()
+            .filter(|new_info| new_info.signed_at_ms > info.signed_at_ms)
+            .map(|new_info| new_info.storage_arc.is_empty()),
+        Some(true)
+    );
+
+    // The pin goes with the app
+    conductor
+        .raw_handle()
+        .uninstall_app(&"app".to_string())
+        .await
+        .unwrap();
+    let pin = holochain_sqlite::db::p2p_get_arc_pin(
+        &conductor.get_p2p_db(dna.dna_hash()),
+        cell_id.agent_pubkey().to_kitsune(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(pin, None);
+}
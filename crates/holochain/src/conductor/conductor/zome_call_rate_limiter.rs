@@ -0,0 +1,525 @@
+//! Limits on how fast clients of the app interfaces can call zome functions.
+
+use holochain_conductor_api::config::conductor::RateLimit;
+use holochain_conductor_api::config::conductor::ZomeCallRateLimitConfig;
+use holochain_conductor_api::ZomeCallRateLimitMetrics;
+use holochain_types::prelude::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::future::Future;
+use std::time::Duration;
+use std::time::Instant;
+
+/// How many buckets are kept before idle ones are dropped.
+const MAX_BUCKETS: usize = 1024;
+
+tokio::task_local! {
+    /// The bytes committed to source chains by the zome calls being measured
+    /// with [`measure_committed_bytes`].
+    static COMMITTED_BYTES: std::cell::Cell<u64>;
+}
+
+/// Run zome calls, returning their output along with how many bytes they
+/// committed to source chains.
+pub async fn measure_committed_bytes<F: Future>(calls: F) -> (F::Output, u64) {
+    COMMITTED_BYTES
+        .scope(std::cell::Cell::new(0), async move {
+            let output = calls.await;
+            (output, COMMITTED_BYTES.with(|bytes| bytes.get()))
+        })
+        .await
+}
+
+/// Count bytes written to a source chain towards the zome calls being
+/// measured, if there are any.
+pub(crate) fn record_committed_bytes(bytes: u64) {
+    let _ = COMMITTED_BYTES.try_with(|committed| committed.set(committed.get() + bytes));
+}
+
+/// A bucket of capacity which is used up by zome calls and refills at a
+/// constant rate.
+///
+/// The bytes committed by a call are only known once it has been made, so
+/// a bucket can go into debt, in which case it must refill past empty before
+/// any more calls are allowed.
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    available: f64,
+    refill_per_sec: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn new(capacity: f64, refill_period: Duration, now: Instant) -> Self {
+        Self {
+            capacity,
+            available: capacity,
+            refill_per_sec: capacity / refill_period.as_secs_f64(),
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.available =
+            (self.available + elapsed.as_secs_f64() * self.refill_per_sec).min(self.capacity);
+        self.refilled_at = now;
+    }
+
+    fn give_back(&mut self, amount: f64) {
+        self.available = (self.available + amount).min(self.capacity);
+    }
+
+    fn fullness(&self) -> f64 {
+        self.available / self.capacity
+    }
+}
+
+/// The buckets for the limits of a single app, cap secret or provenance.
+#[derive(Debug)]
+struct Buckets {
+    calls: Option<Bucket>,
+    bytes: Option<Bucket>,
+}
+
+impl Buckets {
+    fn new(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            calls: limit
+                .calls_per_second
+                .map(|calls| Bucket::new(calls as f64, Duration::from_secs(1), now)),
+            bytes: limit
+                .bytes_per_minute
+                .map(|bytes| Bucket::new(bytes as f64, Duration::from_secs(60), now)),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        self.calls.iter_mut().for_each(|b| b.refill(now));
+        self.bytes.iter_mut().for_each(|b| b.refill(now));
+    }
+
+    /// How full the emptiest of the buckets is, as a fraction of its capacity.
+    fn fullness(&self) -> f64 {
+        self.calls
+            .iter()
+            .chain(self.bytes.iter())
+            .map(Bucket::fullness)
+            .fold(1.0, f64::min)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum RateLimitKey {
+    App(InstalledAppId),
+    /// The bytes of the cap secret, which isn't itself hashable
+    CapSecret(Vec<u8>),
+    Provenance(AgentPubKey),
+}
+
+/// What a zome call counts against the rate limits.
+#[derive(Debug, Clone)]
+pub struct ZomeCallCost {
+    /// The apps which the called cell belongs to
+    pub installed_app_ids: Vec<InstalledAppId>,
+    /// The cap secret the call is made with
+    pub cap_secret: Option<CapSecret>,
+    /// The provenance of the call
+    pub provenance: AgentPubKey,
+}
+
+/// Why zome calls were rejected by the rate limits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZomeCallRateLimitError {
+    /// The calls are over a limit for now, and may succeed once it recovers.
+    OverLimit(String),
+    /// The calls could never fit within a limit, so retrying them won't help.
+    ExceedsCapacity(String),
+}
+
+/// Tracks zome calls against the configured rate limits, and how many have
+/// been rejected.
+///
+/// Buckets are only held in memory, so they don't survive a conductor restart.
+#[derive(Debug, Default)]
+pub struct ZomeCallRateLimiter {
+    config: ZomeCallRateLimitConfig,
+    buckets: HashMap<RateLimitKey, Buckets>,
+    metrics: ZomeCallRateLimitMetrics,
+}
+
+impl ZomeCallRateLimiter {
+    /// Create a limiter for the given limits.
+    pub fn new(config: ZomeCallRateLimitConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// The keys of the limits that apply to a zome call.
+    fn keys(&self, cost: &ZomeCallCost) -> Vec<RateLimitKey> {
+        let mut keys = Vec::new();
+        if self.config.per_app.is_some() {
+            keys.extend(
+                cost.installed_app_ids
+                    .iter()
+                    .map(|id| RateLimitKey::App(id.clone())),
+            );
+        }
+        if let (Some(_), Some(secret)) = (&self.config.per_cap_secret, &cost.cap_secret) {
+            keys.push(RateLimitKey::CapSecret(secret.as_ref().to_vec()));
+        }
+        if self.config.per_provenance.is_some() {
+            keys.push(RateLimitKey::Provenance(cost.provenance.clone()));
+        }
+        keys
+    }
+
+    fn limit<'a>(config: &'a ZomeCallRateLimitConfig, key: &RateLimitKey) -> &'a RateLimit {
+        match key {
+            RateLimitKey::App(_) => config.per_app.as_ref(),
+            RateLimitKey::CapSecret(_) => config.per_cap_secret.as_ref(),
+            RateLimitKey::Provenance(_) => config.per_provenance.as_ref(),
+        }
+        .expect("Only keys with a limit are used")
+    }
+
+    /// Count zome calls against the call limits before they are made.
+    ///
+    /// The calls are either all allowed, or all rejected without counting
+    /// against any limit. Calls are also rejected while a limit on bytes is
+    /// used up. A rejection returns which limit the calls are over.
+    ///
+    /// Only the number of calls can exceed the capacity of a limit up front.
+    /// The bytes a call commits aren't known until it has been made, so a
+    /// call which commits more than a whole byte limit is allowed and then
+    /// leaves the limit in debt.
+    ///
+    /// Once the calls have been made, [`Self::settle`] must be called to give
+    /// back the calls which were refused and count the bytes committed.
+    pub fn try_acquire(
+        &mut self,
+        costs: &[ZomeCallCost],
+        now: Instant,
+    ) -> Result<(), ZomeCallRateLimitError> {
+        let mut demand: HashMap<RateLimitKey, f64> = HashMap::new();
+        for cost in costs {
+            for key in self.keys(cost) {
+                *demand.entry(key).or_default() += 1.0;
+            }
+        }
+
+        self.evict_idle_buckets(now);
+
+        let mut rejected = None;
+        for (key, calls) in demand.iter() {
+            let limit = Self::limit(&self.config, key);
+            let exceeds_calls = limit
+                .calls_per_second
+                .map_or(false, |capacity| *calls > capacity as f64);
+            if exceeds_calls {
+                rejected = Some((key.clone(), true, true));
+                break;
+            }
+            let buckets = self
+                .buckets
+                .entry(key.clone())
+                .or_insert_with(|| Buckets::new(limit, now));
+            buckets.refill(now);
+            let over_calls = buckets
+                .calls
+                .as_ref()
+                .map_or(false, |b| b.available < *calls);
+            let over_bytes = buckets.bytes.as_ref().map_or(false, |b| b.available <= 0.0);
+            if over_calls || over_bytes {
+                rejected = Some((key.clone(), over_calls, false));
+                break;
+            }
+        }
+
+        if let Some((key, over_calls, exceeds_capacity)) = rejected {
+            return Err(self.reject(key, over_calls, exceeds_capacity));
+        }
+
+        for (key, calls) in demand {
+            let buckets = self
+                .buckets
+                .get_mut(&key)
+                .expect("Buckets were created while checking the demand");
+            buckets.calls.iter_mut().for_each(|b| b.available -= calls);
+        }
+        Ok(())
+    }
+
+    /// Settle the cost of zome calls allowed by [`Self::try_acquire`] once
+    /// they have been made.
+    ///
+    /// The calls at the `refunded` indices, which were refused because their
+    /// signature or cap grant couldn't be verified or were never made, are
+    /// given back to the call limits. Every other call stays counted, whatever
+    /// its outcome. The bytes the calls committed to the source chain are
+    /// counted against the byte limits.
+    pub fn settle(
+        &mut self,
+        costs: &[ZomeCallCost],
+        refunded: &[usize],
+        committed_bytes: u64,
+        now: Instant,
+    ) {
+        let mut unverified: HashMap<RateLimitKey, f64> = HashMap::new();
+        let mut keys = HashSet::new();
+        for (index, cost) in costs.iter().enumerate() {
+            for key in self.keys(cost) {
+                if refunded.contains(&index) {
+                    *unverified.entry(key.clone()).or_default() += 1.0;
+                }
+                keys.insert(key);
+            }
+        }
+        for key in keys {
+            let limit = Self::limit(&self.config, &key);
+            let buckets = self
+                .buckets
+                .entry(key.clone())
+                .or_insert_with(|| Buckets::new(limit, now));
+            buckets.refill(now);
+            if let Some(calls) = unverified.get(&key) {
+                buckets.calls.iter_mut().for_each(|b| b.give_back(*calls));
+            }
+            buckets
+                .bytes
+                .iter_mut()
+                .for_each(|b| b.available -= committed_bytes as f64);
+        }
+    }
+
+    /// Drop the buckets which have refilled, and if there are still too many
+    /// then also the fullest of the rest, which gives back the least capacity.
+    fn evict_idle_buckets(&mut self, now: Instant) {
+        if self.buckets.len() <= MAX_BUCKETS {
+            return;
+        }
+        self.buckets.retain(|_, buckets| {
+            buckets.refill(now);
+            buckets.fullness() < 1.0
+        });
+        if self.buckets.len() > MAX_BUCKETS {
+            let mut by_fullness: Vec<_> = self
+                .buckets
+                .iter()
+                .map(|(key, buckets)| (buckets.fullness(), key.clone()))
+                .collect();
+            by_fullness.sort_by(|a, b| b.0.total_cmp(&a.0));
+            for (_, key) in by_fullness
+                .into_iter()
+                .take(self.buckets.len() - MAX_BUCKETS / 2)
+            {
+                self.buckets.remove(&key);
+            }
+        }
+    }
+
+    fn reject(
+        &mut self,
+        key: RateLimitKey,
+        over_calls: bool,
+        exceeds_capacity: bool,
+    ) -> ZomeCallRateLimitError {
+        let limit = if over_calls {
+            "calls per second"
+        } else {
+            "bytes per minute"
+        };
+        let over = if exceeds_capacity {
+            "More than the whole"
+        } else {
+            "Over the"
+        };
+        let reason = match key {
+            RateLimitKey::App(id) => {
+                let reason = format!("{} {} limit for app {}", over, limit, id);
+                *self.metrics.rejected_by_app.entry(id).or_default() += 1;
+                reason
+            }
+            RateLimitKey::CapSecret(_) => {
+                self.metrics.rejected_by_cap_secret += 1;
+                format!("{} {} limit for the cap secret", over, limit)
+            }
+            RateLimitKey::Provenance(agent) => {
+                let reason = format!("{} {} limit for provenance {}", over, limit, agent);
+                *self
+                    .metrics
+                    .rejected_by_provenance
+                    .entry(agent)
+                    .or_default() += 1;
+                reason
+            }
+        };
+        if exceeds_capacity {
+            ZomeCallRateLimitError::ExceedsCapacity(reason)
+        } else {
+            ZomeCallRateLimitError::OverLimit(reason)
+        }
+    }
+
+    /// The number of zome calls that have been rejected so far.
+    pub fn metrics(&self) -> ZomeCallRateLimitMetrics {
+        self.metrics.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use matches::assert_matches;
+
+    fn cost(provenance: &AgentPubKey) -> ZomeCallCost {
+        ZomeCallCost {
+            installed_app_ids: vec!["app".to_string()],
+            cap_secret: None,
+            provenance: provenance.clone(),
+        }
+    }
+
+    #[test]
+    fn calls_per_second_limit_recovers_over_time() {
+        let mut limiter = ZomeCallRateLimiter::new(ZomeCallRateLimitConfig {
+            per_app: Some(RateLimit {
+                calls_per_second: Some(2),
+                bytes_per_minute: None,
+            }),
+            ..Default::default()
+        });
+        let agent = fixt!(AgentPubKey);
+        let now = Instant::now();
+        assert!(limiter.try_acquire(&[cost(&agent)], now).is_ok());
+        assert!(limiter.try_acquire(&[cost(&agent)], now).is_ok());
+        assert!(limiter.try_acquire(&[cost(&agent)], now).is_err());
+        let later = now + Duration::from_millis(500);
+        assert!(limiter.try_acquire(&[cost(&agent)], later).is_ok());
+        assert!(limiter.try_acquire(&[cost(&agent)], later).is_err());
+        assert_eq!(limiter.metrics().rejected_by_app.get("app"), Some(&2));
+    }
+
+    #[test]
+    fn rejected_calls_do_not_count() {
+        let mut limiter = ZomeCallRateLimiter::new(ZomeCallRateLimitConfig {
+            per_provenance: Some(RateLimit {
+                calls_per_second: Some(2),
+                bytes_per_minute: None,
+            }),
+            ..Default::default()
+        });
+        let alice = fixt!(AgentPubKey);
+        let bob = fixt!(AgentPubKey);
+        let now = Instant::now();
+        assert!(limiter.try_acquire(&[cost(&alice)], now).is_ok());
+        // A batch over the limit is rejected as a whole, and none of it counts.
+        assert_matches!(
+            limiter.try_acquire(&[cost(&alice), cost(&alice)], now),
+            Err(ZomeCallRateLimitError::OverLimit(_))
+        );
+        assert!(limiter.try_acquire(&[cost(&alice)], now).is_ok());
+        assert!(limiter.try_acquire(&[cost(&alice)], now).is_err());
+        // Each provenance has its own limit.
+        assert!(limiter.try_acquire(&[cost(&bob)], now).is_ok());
+        let metrics = limiter.metrics();
+        assert_eq!(metrics.rejected_by_provenance.get(&alice), Some(&2));
+        assert_eq!(metrics.rejected_by_provenance.get(&bob), None);
+        assert!(metrics.rejected_by_app.is_empty());
+    }
+
+    #[test]
+    fn only_refused_calls_are_given_back() {
+        let mut limiter = ZomeCallRateLimiter::new(ZomeCallRateLimitConfig {
+            per_provenance: Some(RateLimit {
+                calls_per_second: Some(2),
+                bytes_per_minute: None,
+            }),
+            ..Default::default()
+        });
+        let agent = fixt!(AgentPubKey);
+        let now = Instant::now();
+        let batch = [cost(&agent), cost(&agent)];
+        assert!(limiter.try_acquire(&batch, now).is_ok());
+        // The second call of the batch was refused.
+        limiter.settle(&batch, &[1], 0, now);
+        assert!(limiter.try_acquire(&[cost(&agent)], now).is_ok());
+        assert!(limiter.try_acquire(&[cost(&agent)], now).is_err());
+        // A call which failed for any other reason stays counted.
+        let later = now + Duration::from_secs(1);
+        let call = [cost(&agent)];
+        assert!(limiter.try_acquire(&call, later).is_ok());
+        limiter.settle(&call, &[], 0, later);
+        assert!(limiter.try_acquire(&call, later).is_ok());
+        assert!(limiter.try_acquire(&call, later).is_err());
+    }
+
+    #[test]
+    fn committed_bytes_count_against_the_byte_limit() {
+        let mut limiter = ZomeCallRateLimiter::new(ZomeCallRateLimitConfig {
+            per_provenance: Some(RateLimit {
+                calls_per_second: None,
+                bytes_per_minute: Some(120),
+            }),
+            ..Default::default()
+        });
+        let agent = fixt!(AgentPubKey);
+        let now = Instant::now();
+        // A call can commit more than the whole limit.
+        let call = [cost(&agent)];
+        assert!(limiter.try_acquire(&call, now).is_ok());
+        limiter.settle(&call, &[], 180, now);
+        assert_matches!(
+            limiter.try_acquire(&call, now),
+            Err(ZomeCallRateLimitError::OverLimit(_))
+        );
+        // The limit has to recover from the debt before calls are allowed again.
+        let later = now + Duration::from_secs(20);
+        assert!(limiter.try_acquire(&call, later).is_err());
+        let later = now + Duration::from_secs(31);
+        assert!(limiter.try_acquire(&call, later).is_ok());
+    }
+
+    #[test]
+    fn calls_which_could_never_fit_exceed_capacity() {
+        let mut limiter = ZomeCallRateLimiter::new(ZomeCallRateLimitConfig {
+            per_app: Some(RateLimit {
+                calls_per_second: Some(2),
+                bytes_per_minute: Some(100),
+            }),
+            ..Default::default()
+        });
+        let agent = fixt!(AgentPubKey);
+        let now = Instant::now();
+        assert_matches!(
+            limiter.try_acquire(&[cost(&agent), cost(&agent), cost(&agent)], now),
+            Err(ZomeCallRateLimitError::ExceedsCapacity(_))
+        );
+        assert!(limiter
+            .try_acquire(&[cost(&agent), cost(&agent)], now)
+            .is_ok());
+        assert_eq!(limiter.metrics().rejected_by_app.get("app"), Some(&1));
+    }
+
+    #[test]
+    fn busy_buckets_are_evicted_when_there_are_too_many() {
+        let mut limiter = ZomeCallRateLimiter::new(ZomeCallRateLimitConfig {
+            per_provenance: Some(RateLimit {
+                calls_per_second: Some(2),
+                bytes_per_minute: None,
+            }),
+            ..Default::default()
+        });
+        let now = Instant::now();
+        // None of these buckets refill, so none of them are idle.
+        for _ in 0..MAX_BUCKETS * 2 {
+            let agent = fixt!(AgentPubKey);
+            assert!(limiter.try_acquire(&[cost(&agent)], now).is_ok());
+        }
+        assert!(limiter.buckets.len() <= MAX_BUCKETS + 1);
+    }
+}
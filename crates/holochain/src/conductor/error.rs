@@ -96,6 +96,9 @@ pub enum ConductorError {
     #[error("Tried to perform an operation on an app that was not running: {0}")]
     AppNotRunning(InstalledAppId),
 
+    #[error("Tried to uninstall app '{0}', whose cells are used by other apps: {1:?}")]
+    AppHasDependents(InstalledAppId, Vec<InstalledAppId>),
+
     #[error(transparent)]
     HolochainP2pError(#[from] holochain_p2p::HolochainP2pError),
 
@@ -120,6 +123,9 @@ pub enum ConductorError {
     #[error(transparent)]
     StateMutationError(#[from] holochain_state::mutations::StateMutationError),
 
+    #[error(transparent)]
+    TimestampError(#[from] holochain_zome_types::TimestampError),
+
     #[error(transparent)]
     JoinError(#[from] tokio::task::JoinError),
 
@@ -129,6 +135,24 @@ pub enum ConductorError {
     #[error(transparent)]
     RibosomeError(#[from] crate::core::ribosome::error::RibosomeError),
 
+    #[error(transparent)]
+    DpkiServiceError(#[from] crate::conductor::conductor::DpkiServiceError),
+
+    #[error(transparent)]
+    AppStoreServiceError(#[from] crate::conductor::conductor::AppStoreServiceError),
+
+    #[error("Conductor services have not been initialized yet")]
+    ServicesNotInitialized,
+
+    #[error("The app {0} was not found in the app store")]
+    AppNotInAppStore(AppHash),
+
+    #[error("Cannot migrate the chain of cell {0:?} to cell {1:?}: exactly one of the DNA hash or agent key must differ")]
+    InvalidChainMigration(CellId, CellId),
+
+    #[error("The function {1:?} is not scheduled for cell {0:?}")]
+    ScheduledFnNotFound(CellId, holochain_zome_types::ScheduledFn),
+
     /// Other
     #[error("Other: {0}")]
     Other(Box<dyn std::error::Error + Send + Sync>),
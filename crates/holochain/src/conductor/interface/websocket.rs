@@ -3,21 +3,43 @@
 
 use super::error::InterfaceError;
 use super::error::InterfaceResult;
+use crate::conductor::api::error::ConductorApiError;
+use crate::conductor::api::error::ConductorApiResult;
+use crate::conductor::api::AppInterfaceApi;
+use crate::conductor::conductor::AppAuthSession;
 use crate::conductor::interface::*;
 use crate::conductor::manager::ManagedTaskResult;
 use crate::conductor::manager::TaskManagerClient;
 use futures::FutureExt;
+use holo_hash::DnaHash;
+use holochain_conductor_api::config::InterfaceDriver;
+use holochain_conductor_api::signal_subscription::SignalFilterSet;
+use holochain_conductor_api::AdminRequest;
+use holochain_conductor_api::AdminResponse;
+use holochain_conductor_api::AppRequest;
+use holochain_conductor_api::AppResponse;
+use holochain_conductor_api::CallZomeStreamingPayload;
 use holochain_serialized_bytes::SerializedBytes;
+use holochain_types::app::InstalledAppId;
 use holochain_types::signal::Signal;
+use holochain_types::signal::SystemSignalScope;
+use holochain_types::signal::ZomeCallStreamId;
 use holochain_websocket::ListenerHandle;
 use holochain_websocket::ListenerItem;
+use holochain_websocket::ListenerStream;
 use holochain_websocket::WebsocketConfig;
 use holochain_websocket::WebsocketListener;
 use holochain_websocket::WebsocketMessage;
 use holochain_websocket::WebsocketReceiver;
 use holochain_websocket::WebsocketSender;
+use holochain_zome_types::cell::CellId;
+use holochain_zome_types::ExternIO;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::convert::TryFrom;
 
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicIsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -30,40 +52,66 @@ use url2::url2;
 /// Number of signals in buffer before applying
 /// back pressure.
 pub(crate) const SIGNAL_BUFFER_SIZE: usize = 50;
+/// Number of chunks of streaming zome calls in buffer before applying
+/// back pressure.
+const ZOME_CALL_STREAM_BUFFER_SIZE: usize = 16;
 const MAX_CONNECTIONS: isize = 400;
 
-/// Create a WebsocketListener to be used in interfaces
+/// Create a WebsocketListener to be used in interfaces, listening on either
+/// a local port or a unix domain socket depending on the driver
 pub async fn spawn_websocket_listener(
-    port: u16,
-) -> InterfaceResult<(
-    ListenerHandle,
-    impl futures::stream::Stream<Item = ListenerItem>,
-)> {
-    trace!("Initializing Admin interface");
-    let listener = WebsocketListener::bind_with_handle(
-        url2!("ws://127.0.0.1:{}", port),
-        Arc::new(WebsocketConfig::default()),
-    )
-    .await?;
-    trace!("LISTENING AT: {}", listener.0.local_addr());
-    Ok(listener)
+    driver: &InterfaceDriver,
+) -> InterfaceResult<(ListenerHandle, ListenerStream)> {
+    trace!("Initializing interface listener");
+    let listener = match driver {
+        InterfaceDriver::Websocket {
+            port,
+            allowed_origins,
+        } => {
+            WebsocketListener::bind(
+                url2!("ws://127.0.0.1:{}", port),
+                Arc::new(WebsocketConfig::default().allowed_origins(allowed_origins.clone())),
+            )
+            .await?
+        }
+        #[cfg(unix)]
+        InterfaceDriver::UnixSocket { path } => {
+            WebsocketListener::bind_unix(path, Arc::new(WebsocketConfig::default())).await?
+        }
+        #[cfg(not(unix))]
+        InterfaceDriver::UnixSocket { .. } => {
+            return Err(InterfaceError::Other(
+                "Unix domain sockets are not supported on this platform".to_string(),
+            ))
+        }
+    };
+    trace!("LISTENING AT: {}", listener.local_addr());
+    Ok(listener.into_handle_and_stream())
 }
 
 /// Create an Admin Interface, which only receives AdminRequest messages
-/// from the external client
-pub fn spawn_admin_interface_tasks<A: InterfaceApi>(
+/// from the external client.
+/// If a session duration is given, connections are closed once it has passed.
+/// If the hash of an auth token is given, connections have to authenticate
+/// with the token before making any other request.
+pub fn spawn_admin_interface_tasks<
+    A: InterfaceApi<ApiRequest = AdminRequest, ApiResponse = AdminResponse>,
+>(
     tm: TaskManagerClient,
     handle: ListenerHandle,
     listener: impl futures::stream::Stream<Item = ListenerItem> + Send + 'static,
     api: A,
-    port: u16,
+    session_duration: Option<std::time::Duration>,
+    auth_token_hash: Option<String>,
 ) {
+    let auth_token_hash = auth_token_hash.map(Arc::new);
+    let local_addr = handle.local_addr().clone();
     // Task that will kill the listener and all child connections.
     tm.add_conductor_task_ignored("admin interface websocket closer", |stop| {
         handle.close_on(stop.map(|_| true)).map(Ok)
     });
 
-    tm.add_conductor_task_ignored(&format!("admin interface, port {}", port), |_stop| {
+    tm.add_conductor_task_ignored(&format!("admin interface, {}", local_addr), |_stop| {
         async move {
             let num_connections = Arc::new(AtomicIsize::new(0));
             futures::pin_mut!(listener);
@@ -80,6 +128,8 @@ pub fn spawn_admin_interface_tasks<A: InterfaceApi>(
                             api.clone(),
                             rx_from_iface,
                             num_connections.clone(),
+                            session_duration,
+                            auth_token_hash.clone(),
                         ));
                     }
                     Err(err) => {
@@ -93,24 +143,30 @@ pub fn spawn_admin_interface_tasks<A: InterfaceApi>(
 }
 
 /// Create an App Interface, which includes the ability to receive signals
-/// from Cells via a broadcast channel
-pub async fn spawn_app_interface_task<A: InterfaceApi>(
+/// from Cells via a broadcast channel.
+/// Returns the driver with the port that was bound if one was auto-selected.
+pub async fn spawn_app_interface_task<
+    A: InterfaceApi<ApiRequest = AppRequest, ApiResponse = AppResponse> + AppInterfaceApi,
+>(
     tm: TaskManagerClient,
-    port: u16,
+    driver: InterfaceDriver,
     api: A,
     signal_broadcaster: broadcast::Sender<Signal>,
-) -> InterfaceResult<u16> {
+) -> InterfaceResult<InterfaceDriver> {
     trace!("Initializing App interface");
-    let (handle, mut listener) = WebsocketListener::bind_with_handle(
-        url2!("ws://127.0.0.1:{}", port),
-        Arc::new(WebsocketConfig::default()),
-    )
-    .await?;
-    trace!("LISTENING AT: {}", handle.local_addr());
-    let port = handle
-        .local_addr()
-        .port()
-        .ok_or(InterfaceError::PortError)?;
+    let (handle, mut listener) = spawn_websocket_listener(&driver).await?;
+    let driver = match driver {
+        InterfaceDriver::Websocket {
+            allowed_origins, ..
+        } => InterfaceDriver::Websocket {
+            port: handle
+                .local_addr()
+                .port()
+                .ok_or(InterfaceError::PortError)?,
+            allowed_origins,
+        },
+        driver => driver,
+    };
     // Task that will kill the listener and all child connections.
     tm.add_conductor_task_ignored("app interface websocket closer", |stop| {
         handle.close_on(stop.map(|_| true)).map(Ok)
@@ -138,35 +194,98 @@ pub async fn spawn_app_interface_task<A: InterfaceApi>(
             ManagedTaskResult::Ok(())
         }
     });
-    Ok(port)
+    Ok(driver)
 }
 
 /// Polls for messages coming in from the external client.
 /// Used by Admin interface.
-async fn recv_incoming_admin_msgs<A: InterfaceApi>(
+async fn recv_incoming_admin_msgs<
+    A: InterfaceApi<ApiRequest = AdminRequest, ApiResponse = AdminResponse>,
+>(
     api: A,
     rx_from_iface: WebsocketReceiver,
     num_connections: Arc<AtomicIsize>,
+    session_duration: Option<std::time::Duration>,
+    auth_token_hash: Option<Arc<String>>,
 ) {
     use futures::stream::StreamExt;
 
-    rx_from_iface
-        .for_each_concurrent(4096, move |msg| {
-            let api = api.clone();
-            async move {
-                if let Err(e) = handle_incoming_message(msg, api.clone()).await {
-                    error!(error = &e as &dyn std::error::Error)
-                }
+    let remote_addr = rx_from_iface.remote_addr().clone();
+    // Connections to an interface without a token don't need to authenticate.
+    let authenticated = Arc::new(AtomicBool::new(auth_token_hash.is_none()));
+    let handle_msgs = rx_from_iface.for_each_concurrent(4096, move |msg| {
+        let api = api.clone();
+        let auth_token_hash = auth_token_hash.clone();
+        let authenticated = authenticated.clone();
+        async move {
+            if let Err(e) =
+                handle_incoming_admin_message(msg, api, auth_token_hash, authenticated).await
+            {
+                error!(error = &e as &dyn std::error::Error)
             }
-        })
-        .await;
+        }
+    });
+    match session_duration {
+        Some(session_duration) => {
+            // Dropping the receiver when the session is over closes the connection.
+            if tokio::time::timeout(session_duration, handle_msgs)
+                .await
+                .is_err()
+            {
+                debug!(%remote_addr, "Admin interface session expired");
+            }
+        }
+        None => handle_msgs.await,
+    }
     num_connections.fetch_sub(1, Ordering::SeqCst);
 }
 
+/// The authentication state of an app interface connection.
+#[derive(Clone)]
+struct ConnectionAuth {
+    /// The apps the connection has been authenticated for, or `None` until
+    /// it has been authenticated.
+    allowed_apps: Arc<parking_lot::RwLock<Option<HashSet<InstalledAppId>>>>,
+    /// Passes the session of the connection on once it has been
+    /// authenticated, so that the connection is closed when it ends.
+    sessions: tokio::sync::mpsc::UnboundedSender<AppAuthSession>,
+}
+
+impl ConnectionAuth {
+    /// The state of a new connection, and the receiver of its session.
+    fn new() -> (Self, tokio::sync::mpsc::UnboundedReceiver<AppAuthSession>) {
+        let (sessions, rx_sessions) = tokio::sync::mpsc::unbounded_channel();
+        let auth = Self {
+            allowed_apps: Arc::new(parking_lot::RwLock::new(None)),
+            sessions,
+        };
+        (auth, rx_sessions)
+    }
+
+    fn allowed_apps(&self) -> Option<HashSet<InstalledAppId>> {
+        self.allowed_apps.read().clone()
+    }
+}
+
+/// The signal filters set on an app interface connection for each app with
+/// [`AppRequest::SignalSubscription`]. Signals about an app without a
+/// subscription are not filtered.
+type SignalSubscriptions = Arc<parking_lot::RwLock<HashMap<InstalledAppId, SignalFilterSet>>>;
+
 /// Polls for messages coming in from the external client while simultaneously
 /// polling for signals being broadcast from the Cells associated with this
 /// App interface.
-fn spawn_recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
+///
+/// Signals are only sent once the connection has been authenticated, if they
+/// come from the cells of an app the connection can access and if they pass
+/// the filters the client has set for that app on this connection with
+/// [`AppRequest::SignalSubscription`].
+///
+/// The connection is closed once the token it was authenticated with
+/// expires or is revoked.
+fn spawn_recv_incoming_msgs_and_outgoing_signals<
+    A: InterfaceApi<ApiRequest = AppRequest, ApiResponse = AppResponse> + AppInterfaceApi,
+>(
     api: A,
     rx_from_iface: WebsocketReceiver,
     rx_from_cell: broadcast::Receiver<Signal>,
@@ -174,16 +293,68 @@ fn spawn_recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
 ) {
     use futures::stream::StreamExt;
 
-    trace!("CONNECTION: {}", rx_from_iface.remote_addr());
+    let remote_addr = rx_from_iface.remote_addr().clone();
+    trace!("CONNECTION: {}", remote_addr);
 
-    let rx_from_cell = futures::stream::unfold(rx_from_cell, |mut rx_from_cell| async move {
-        if let Ok(item) = rx_from_cell.recv().await {
-            Some((item, rx_from_cell))
-        } else {
-            None
+    let subscriptions: SignalSubscriptions = Default::default();
+    let (auth, mut rx_sessions) = ConnectionAuth::new();
+    let (streams, mut rx_chunks) = ZomeCallStreams::new();
+
+    let mut tx_chunks_to_iface = tx_to_iface.clone();
+    tokio::task::spawn(async move {
+        while let Some(chunk) = rx_chunks.recv().await {
+            if let Err(err) = async {
+                let bytes = SerializedBytes::try_from(chunk)?;
+                tx_chunks_to_iface.signal(bytes).await?;
+                InterfaceResult::Ok(())
+            }
+            .await
+            {
+                // Dropping the receiver stops every stream on this connection.
+                error!(?err, "error sending zome call stream chunk");
+                break;
+            }
         }
     });
 
+    let signal_subscriptions = subscriptions.clone();
+    let signal_auth = auth.clone();
+    let signal_api = api.clone();
+    // Which cells and DNAs of each app this connection can receive signals
+    // about, so the conductor state only has to be checked the first time
+    // each is seen.
+    let access = SignalAccess::default();
+    let rx_from_cell = futures::stream::unfold(
+        (rx_from_cell, access),
+        move |(mut rx_from_cell, mut access)| {
+            let subscriptions = signal_subscriptions.clone();
+            let auth = signal_auth.clone();
+            let api = signal_api.clone();
+            async move {
+                while let Ok(item) = rx_from_cell.recv().await {
+                    let allowed_apps = match auth.allowed_apps() {
+                        Some(allowed_apps) => allowed_apps,
+                        None => continue,
+                    };
+                    for installed_app_id in allowed_apps.iter() {
+                        if !access.can_receive(&api, installed_app_id, &item).await {
+                            continue;
+                        }
+                        let allowed = subscriptions
+                            .read()
+                            .get(installed_app_id)
+                            .map(|filters| filters.allows(&item))
+                            .unwrap_or(true);
+                        if allowed {
+                            return Some((item, (rx_from_cell, access)));
+                        }
+                    }
+                }
+                None
+            }
+        },
+    );
+
     tokio::task::spawn(rx_from_cell.for_each_concurrent(4096, move |signal| {
         let mut tx_to_iface = tx_to_iface.clone();
         async move {
@@ -200,25 +371,343 @@ fn spawn_recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
         }
     }));
 
-    tokio::task::spawn(rx_from_iface.for_each_concurrent(4096, move |msg| {
+    let session_auth = auth.clone();
+    let handle_msgs = rx_from_iface.for_each_concurrent(4096, move |msg| {
         let api = api.clone();
+        let subscriptions = subscriptions.clone();
+        let auth = auth.clone();
+        let streams = streams.clone();
         async move {
-            if let Err(err) = handle_incoming_message(msg, api).await {
+            if let Err(err) =
+                handle_incoming_app_message(msg, api, subscriptions, auth, streams).await
+            {
                 error!(?err, "error handling websocket message");
             }
         }
-    }));
+    });
+    let session_ended = async move {
+        match rx_sessions.recv().await {
+            Some(session) => session.ended().await,
+            None => futures::future::pending().await,
+        }
+    };
+    tokio::task::spawn(async move {
+        // Dropping the receiver when the session is over closes the connection.
+        tokio::select! {
+            _ = handle_msgs => {}
+            _ = session_ended => {
+                *session_auth.allowed_apps.write() = None;
+                debug!(%remote_addr, "App interface session ended");
+            }
+        }
+    });
+}
+
+/// The streaming zome calls in progress on an app interface connection.
+#[derive(Clone)]
+struct ZomeCallStreams {
+    /// Carries the chunks of every stream to the connection.
+    tx_chunks: tokio::sync::mpsc::Sender<Signal>,
+    /// Cancels each stream in progress.
+    cancel: Arc<parking_lot::Mutex<HashMap<ZomeCallStreamId, tokio::sync::oneshot::Sender<()>>>>,
 }
 
-/// Handles messages on all interfaces
-async fn handle_incoming_message<A>(ws_msg: WebsocketMessage, api: A) -> InterfaceResult<()>
+impl ZomeCallStreams {
+    /// Create the streams of a connection along with the receiver of
+    /// their chunks, which are to be sent on the connection.
+    fn new() -> (Self, tokio::sync::mpsc::Receiver<Signal>) {
+        let (tx_chunks, rx_chunks) = tokio::sync::mpsc::channel(ZOME_CALL_STREAM_BUFFER_SIZE);
+        let streams = Self {
+            tx_chunks,
+            cancel: Default::default(),
+        };
+        (streams, rx_chunks)
+    }
+
+    /// Make a streaming zome call, passing on each chunk the zome function
+    /// yields as it arrives.
+    ///
+    /// The chunks are only received as fast as the connection takes them,
+    /// which holds up the zome function once the buffers between them are
+    /// full. Cancelling the stream or losing the connection drops the
+    /// receiver, so the zome function fails on the next chunk it yields.
+    async fn call_zome<A: AppInterfaceApi>(
+        &self,
+        api: &A,
+        payload: CallZomeStreamingPayload,
+    ) -> ConductorApiResult<AppResponse> {
+        let CallZomeStreamingPayload { stream_id, call } = payload;
+        let (cancel, mut cancelled) = tokio::sync::oneshot::channel();
+        match self.cancel.lock().entry(stream_id) {
+            Entry::Occupied(_) => {
+                return Err(ConductorApiError::Other(
+                    format!("A zome call stream with id {} is in progress", stream_id).into(),
+                ))
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(cancel);
+            }
+        }
+
+        let (stream, rx_chunks) = tokio::sync::mpsc::channel(ZOME_CALL_STREAM_BUFFER_SIZE);
+        let mut rx_chunks = Some(rx_chunks);
+        let mut is_cancelled = false;
+        let call = api.call_zome_streaming(call, stream);
+        futures::pin_mut!(call);
+        let response = loop {
+            tokio::select! {
+                response = &mut call => break response,
+                _ = &mut cancelled, if !is_cancelled => {
+                    is_cancelled = true;
+                    rx_chunks = None;
+                }
+                chunk = async { rx_chunks.as_mut()?.recv().await }, if rx_chunks.is_some() => {
+                    let sent = match chunk {
+                        Some(chunk) => self.send_chunk(stream_id, chunk).await,
+                        None => false,
+                    };
+                    if !sent {
+                        rx_chunks = None;
+                    }
+                }
+            }
+        };
+
+        // Pass on the chunks yielded just before the zome function returned.
+        if let Some(mut rx_chunks) = rx_chunks {
+            rx_chunks.close();
+            while let Some(chunk) = rx_chunks.recv().await {
+                if !self.send_chunk(stream_id, chunk).await {
+                    break;
+                }
+            }
+        }
+
+        // A cancelled stream has already been removed, and its id may have
+        // been reused since.
+        let mut streams = self.cancel.lock();
+        if !is_cancelled
+            && matches!(
+                cancelled.try_recv(),
+                Err(tokio::sync::oneshot::error::TryRecvError::Empty)
+            )
+        {
+            streams.remove(&stream_id);
+        }
+        response
+    }
+
+    /// Cancel the stream with this id. Returns whether it was in progress.
+    fn cancel(&self, stream_id: ZomeCallStreamId) -> bool {
+        let cancel = self.cancel.lock().remove(&stream_id);
+        match cancel {
+            Some(cancel) => {
+                cancel.send(()).ok();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pass on a chunk of a stream. Returns whether the connection can
+    /// still take chunks.
+    async fn send_chunk(&self, stream_id: ZomeCallStreamId, chunk: ExternIO) -> bool {
+        self.tx_chunks
+            .send(Signal::ZomeCallStream { stream_id, chunk })
+            .await
+            .is_ok()
+    }
+}
+
+/// Remembers which cells and DNAs of each app a connection may receive
+/// signals about.
+#[derive(Default)]
+struct SignalAccess {
+    cells: HashMap<(InstalledAppId, CellId), bool>,
+    dnas: HashMap<(InstalledAppId, DnaHash), bool>,
+}
+
+impl SignalAccess {
+    /// Whether the signal concerns the given app. App signals and system
+    /// signals about a cell concern the apps with that cell, other system
+    /// signals concern the app or DNA they are about.
+    async fn can_receive<A: AppInterfaceApi>(
+        &mut self,
+        api: &A,
+        installed_app_id: &InstalledAppId,
+        signal: &Signal,
+    ) -> bool {
+        let app = || std::iter::once(installed_app_id.clone()).collect::<HashSet<_>>();
+        let scope = match signal {
+            Signal::App { cell_id, .. } => SystemSignalScope::Cell(cell_id),
+            Signal::System(system_signal) => system_signal.scope(),
+            // Chunks of streaming zome calls are only sent to the connection
+            // that made the call, so they should never be broadcast.
+            Signal::ZomeCallStream { .. } => return false,
+        };
+        match scope {
+            SystemSignalScope::Conductor => true,
+            SystemSignalScope::App(app_id) => app_id == installed_app_id,
+            SystemSignalScope::Cell(cell_id) => {
+                match self
+                    .cells
+                    .entry((installed_app_id.clone(), cell_id.clone()))
+                {
+                    Entry::Occupied(entry) => *entry.get(),
+                    Entry::Vacant(entry) => {
+                        *entry.insert(api.is_cell_in_apps(&app(), cell_id).await.unwrap_or(false))
+                    }
+                }
+            }
+            SystemSignalScope::Dna(dna_hash) => {
+                match self
+                    .dnas
+                    .entry((installed_app_id.clone(), dna_hash.clone()))
+                {
+                    Entry::Occupied(entry) => *entry.get(),
+                    Entry::Vacant(entry) => {
+                        *entry.insert(api.is_dna_in_apps(&app(), dna_hash).await.unwrap_or(false))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Handles messages on admin interfaces. Authentication only concerns the
+/// connection it is sent on, so it is handled here rather than by the api.
+///
+/// If the interface has an auth token, every other request is only passed on
+/// to the api once the connection has been authenticated with it. Only the
+/// hash of the token is held, hex encoded.
+async fn handle_incoming_admin_message<A>(
+    ws_msg: WebsocketMessage,
+    api: A,
+    auth_token_hash: Option<Arc<String>>,
+    authenticated: Arc<AtomicBool>,
+) -> InterfaceResult<()>
 where
-    A: InterfaceApi,
+    A: InterfaceApi<ApiRequest = AdminRequest, ApiResponse = AdminResponse>,
 {
     let (bytes, respond) = ws_msg;
-    Ok(respond
-        .respond(api.handle_request(bytes.try_into()).await?.try_into()?)
-        .await?)
+    let request: Result<AdminRequest, _> = bytes.try_into();
+    let response = match request {
+        Ok(AdminRequest::Authenticate(request)) => match &auth_token_hash {
+            Some(hash)
+                if tokens_match(hash.as_bytes(), admin_token_hash(&request.token).as_bytes()) =>
+            {
+                authenticated.store(true, Ordering::SeqCst);
+                AdminResponse::Authenticated
+            }
+            Some(_) => AdminResponse::Error(
+                ConductorApiError::AuthenticationFailed("The token is invalid".to_string()).into(),
+            ),
+            None => AdminResponse::Authenticated,
+        },
+        Ok(_) if !authenticated.load(Ordering::SeqCst) => AdminResponse::Error(
+            ConductorApiError::Unauthorized(
+                "The connection must be authenticated before making requests".to_string(),
+            )
+            .into(),
+        ),
+        request => api.handle_request(request).await?,
+    };
+    Ok(respond.respond(response.try_into()?).await?)
+}
+
+/// The hex encoded blake2b-256 hash of an admin interface auth token.
+pub(crate) fn admin_token_hash(token: &str) -> String {
+    holo_hash::encode::blake2b_256(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Compare tokens in time independent of where they differ, so that a token
+/// can't be guessed byte by byte.
+fn tokens_match(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Handles messages on app interfaces. Authentication, signal subscriptions
+/// and streaming zome calls only concern the connection they are sent on,
+/// so they are handled here rather than by the api.
+///
+/// Every other request is only passed on to the api once the connection has
+/// been authenticated and if it may access the apps the request concerns.
+async fn handle_incoming_app_message<A>(
+    ws_msg: WebsocketMessage,
+    api: A,
+    subscriptions: SignalSubscriptions,
+    auth: ConnectionAuth,
+    streams: ZomeCallStreams,
+) -> InterfaceResult<()>
+where
+    A: InterfaceApi<ApiRequest = AppRequest, ApiResponse = AppResponse> + AppInterfaceApi,
+{
+    let (bytes, respond) = ws_msg;
+    let request: Result<AppRequest, _> = bytes.try_into();
+    let allowed_apps = auth.allowed_apps();
+    let response = match (request, allowed_apps) {
+        (Ok(AppRequest::Authenticate(_)), Some(_)) => AppResponse::Error(
+            ConductorApiError::AuthenticationFailed(
+                "This connection has already been authenticated".to_string(),
+            )
+            .into(),
+        ),
+        (Ok(AppRequest::Authenticate(request)), None) => {
+            match api.authenticate(&request.token).await {
+                Ok(session) => {
+                    let mut installed_app_ids = session.installed_app_ids.clone();
+                    installed_app_ids.sort();
+                    *auth.allowed_apps.write() = Some(installed_app_ids.iter().cloned().collect());
+                    // The receiver is only dropped once the connection is closed.
+                    let _ = auth.sessions.send(session);
+                    AppResponse::Authenticated(installed_app_ids)
+                }
+                Err(e) => AppResponse::Error(e.into()),
+            }
+        }
+        (Err(e), _) => api.handle_request(Err(e)).await?,
+        (Ok(_), None) => AppResponse::Error(
+            ConductorApiError::Unauthorized(
+                "The connection must be authenticated before making requests".to_string(),
+            )
+            .into(),
+        ),
+        (Ok(request), Some(allowed_apps)) => match api.authorize(&allowed_apps, &request).await {
+            Err(e) => AppResponse::Error(e.into()),
+            Ok(()) => match request {
+                AppRequest::SignalSubscription(subscription) => {
+                    subscriptions
+                        .write()
+                        .insert(subscription.installed_app_id, subscription.filters);
+                    AppResponse::SignalSubscriptionUpdated
+                }
+                AppRequest::CallZomeStreaming(payload) => {
+                    match streams.call_zome(&api, *payload).await {
+                        Ok(response) => response,
+                        Err(e) => AppResponse::Error(e.into()),
+                    }
+                }
+                AppRequest::CancelZomeCallStream(stream_id) => {
+                    if streams.cancel(stream_id) {
+                        AppResponse::ZomeCallStreamCancelled
+                    } else {
+                        AppResponse::Error(
+                            ConductorApiError::Other(
+                                format!("There is no zome call stream with id {}", stream_id)
+                                    .into(),
+                            )
+                            .into(),
+                        )
+                    }
+                }
+                request => api.handle_request(Ok(request)).await?,
+            },
+        },
+    };
+    Ok(respond.respond(response.try_into()?).await?)
 }
 
 /// Test items needed by other crates
@@ -241,6 +730,10 @@ pub mod test {
     use crate::test_utils::install_app_in_conductor;
     use ::fixt::prelude::*;
     use futures::future::FutureExt;
+    use holochain_conductor_api::signal_subscription::SignalSubscription;
+    use holochain_conductor_api::AdminAuthenticationRequest;
+    use holochain_conductor_api::AppAuthenticationRequest;
+    use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
     use holochain_p2p::{AgentPubKeyExt, DnaHashExt};
     use holochain_serialized_bytes::prelude::*;
     use holochain_sqlite::prelude::*;
@@ -251,6 +744,7 @@ pub mod test {
     use holochain_types::test_utils::fake_dna_zomes;
     use holochain_wasm_test_utils::TestWasm;
     use holochain_wasm_test_utils::TestZomes;
+    use holochain_websocket::AllowedOrigins;
     use holochain_websocket::Respond;
     use holochain_zome_types::cell::CellId;
     use holochain_zome_types::test_utils::fake_agent_pubkey_2;
@@ -266,6 +760,18 @@ pub mod test {
     use tempfile::TempDir;
     use uuid::Uuid;
 
+    /// Handles a message with the api directly, without the authentication
+    /// of the interface it would have arrived on.
+    async fn handle_incoming_message<A>(ws_msg: WebsocketMessage, api: A) -> InterfaceResult<()>
+    where
+        A: InterfaceApi,
+    {
+        let (bytes, respond) = ws_msg;
+        Ok(respond
+            .respond(api.handle_request(bytes.try_into()).await?.try_into()?)
+            .await?)
+    }
+
     #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
     #[serde(rename_all = "snake_case", tag = "type", content = "data")]
     // NB: intentionally misspelled to test for serialization errors :)
@@ -453,6 +959,87 @@ pub mod test {
         handle.shutdown().await.unwrap().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn websocket_call_zome_streaming() {
+        holochain_trace::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![(TestWasm::EmitSignal.into(), TestWasm::EmitSignal.into())],
+        );
+        let cell_id = CellId::from((dna.dna_hash().clone(), fake_agent_pubkey_1()));
+        let installed_cell = InstalledCell::new(cell_id.clone(), "handle".into());
+
+        let (_tmpdir, app_api, handle) = setup_app_in_new_conductor(
+            "test app".to_string(),
+            vec![dna],
+            vec![(installed_cell, None)],
+        )
+        .await;
+        let subscriptions: SignalSubscriptions = Arc::new(parking_lot::RwLock::new(
+            [("test app".to_string(), SignalFilterSet::block_all())].into(),
+        ));
+        let (auth, _rx_sessions) = ConnectionAuth::new();
+        *auth.allowed_apps.write() = Some(["test app".to_string()].into_iter().collect());
+        let (streams, mut rx_chunks) = ZomeCallStreams::new();
+
+        let mut call: ZomeCall =
+            crate::fixt::ZomeCallInvocationFixturator::new(crate::fixt::NamedInvocation(
+                cell_id.clone(),
+                TestWasm::EmitSignal.into(),
+                "stream".into(),
+                ExternIO::encode(3_u32).unwrap(),
+            ))
+            .next()
+            .unwrap()
+            .into();
+        call.cell_id = cell_id;
+        let call = call
+            .resign_zome_call(&test_keystore(), fixt!(AgentPubKey, Predictable, 0))
+            .await
+            .unwrap();
+
+        let msg = AppRequest::CallZomeStreaming(Box::new(CallZomeStreamingPayload {
+            stream_id: 7,
+            call,
+        }));
+        let msg = msg.try_into().unwrap();
+        let respond = |bytes: SerializedBytes| {
+            let response: AppResponse = bytes.try_into().unwrap();
+            assert_matches!(
+                response,
+                AppResponse::ZomeCalled(output) if output.decode::<u32>().unwrap() == 3
+            );
+            async { Ok(()) }.boxed().into()
+        };
+        let respond = Respond::Request(Box::new(respond));
+        handle_incoming_app_message(
+            (msg, respond),
+            app_api,
+            subscriptions,
+            auth,
+            streams.clone(),
+        )
+        .await
+        .unwrap();
+
+        // Every chunk was passed on ahead of the response, regardless of
+        // the signal subscriptions of the connection.
+        for i in 0..3_u32 {
+            match rx_chunks.try_recv().unwrap() {
+                Signal::ZomeCallStream { stream_id, chunk } => {
+                    assert_eq!(7, stream_id);
+                    assert_eq!(i, chunk.decode::<u32>().unwrap());
+                }
+                other => panic!("unexpected signal {:?}", other),
+            }
+        }
+        // The stream is over, so there is nothing left to cancel
+        assert!(!streams.cancel(7));
+
+        handle.shutdown().await.unwrap().unwrap();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn gossip_info_request() {
         holochain_trace::test_run().ok();
@@ -515,6 +1102,226 @@ pub mod test {
         handle.shutdown().await.unwrap().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn signal_subscription_updates_connection_filters() {
+        holochain_trace::test_run().ok();
+        let (_tmpdir, conductor_handle) = setup_admin().await;
+        let app_api = RealAppInterfaceApi::new(conductor_handle.clone());
+        let subscriptions: SignalSubscriptions = Default::default();
+        let (auth, _rx_sessions) = ConnectionAuth::new();
+        *auth.allowed_apps.write() = Some(["test app".to_string()].into_iter().collect());
+
+        let subscribe = |installed_app_id: &str, check: fn(AppResponse)| {
+            let subscription = SignalSubscription {
+                installed_app_id: installed_app_id.to_string(),
+                filters: SignalFilterSet::block_all(),
+            };
+            let msg = AppRequest::SignalSubscription(Box::new(subscription));
+            let msg = msg.try_into().unwrap();
+            let respond = move |bytes: SerializedBytes| {
+                check(bytes.try_into().unwrap());
+                async { Ok(()) }.boxed().into()
+            };
+            let respond = Respond::Request(Box::new(respond));
+            handle_incoming_app_message(
+                (msg, respond),
+                app_api.clone(),
+                subscriptions.clone(),
+                auth.clone(),
+                ZomeCallStreams::new().0,
+            )
+        };
+
+        // The connection can't set filters for an app it can't access
+        subscribe("other app", |response| {
+            assert_matches!(
+                response,
+                AppResponse::Error(ExternalApiWireError::Unauthorized(_))
+            )
+        })
+        .await
+        .unwrap();
+        assert!(subscriptions.read().is_empty());
+
+        subscribe("test app", |response| {
+            assert_matches!(response, AppResponse::SignalSubscriptionUpdated)
+        })
+        .await
+        .unwrap();
+
+        assert!(!subscriptions.read()["test app"].allows(&test_signal("after")));
+        conductor_handle.shutdown();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn admin_connection_must_authenticate_with_interface_token() {
+        holochain_trace::test_run().ok();
+        let (_tmpdir, conductor_handle) = setup_admin().await;
+        let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
+        let auth_token_hash = Some(Arc::new(admin_token_hash("secret")));
+        let authenticated = Arc::new(AtomicBool::new(false));
+
+        let send = |request: AdminRequest, check: fn(AdminResponse)| {
+            let msg = request.try_into().unwrap();
+            let respond = move |bytes: SerializedBytes| {
+                check(bytes.try_into().unwrap());
+                async { Ok(()) }.boxed().into()
+            };
+            let respond = Respond::Request(Box::new(respond));
+            handle_incoming_admin_message(
+                (msg, respond),
+                admin_api.clone(),
+                auth_token_hash.clone(),
+                authenticated.clone(),
+            )
+        };
+
+        // Requests are refused until the connection is authenticated
+        send(AdminRequest::ListDnas, |response| {
+            assert_matches!(
+                response,
+                AdminResponse::Error(ExternalApiWireError::Unauthorized(_))
+            )
+        })
+        .await
+        .unwrap();
+        send(
+            AdminRequest::Authenticate(AdminAuthenticationRequest {
+                token: "wrong".to_string(),
+            }),
+            |response| {
+                assert_matches!(
+                    response,
+                    AdminResponse::Error(ExternalApiWireError::AuthenticationFailed(_))
+                )
+            },
+        )
+        .await
+        .unwrap();
+
+        send(
+            AdminRequest::Authenticate(AdminAuthenticationRequest {
+                token: "secret".to_string(),
+            }),
+            |response| assert_matches!(response, AdminResponse::Authenticated),
+        )
+        .await
+        .unwrap();
+        send(AdminRequest::ListDnas, |response| {
+            assert_matches!(response, AdminResponse::DnasListed(_))
+        })
+        .await
+        .unwrap();
+        conductor_handle.shutdown();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn app_connection_must_authenticate() {
+        holochain_trace::test_run().ok();
+        let (_tmpdir, conductor_handle) = setup_admin().await;
+        let app_api = RealAppInterfaceApi::new(conductor_handle.clone());
+        let subscriptions: SignalSubscriptions = Default::default();
+        let (auth, mut rx_sessions) = ConnectionAuth::new();
+        let (streams, _rx_chunks) = ZomeCallStreams::new();
+
+        let send = |request: AppRequest, check: fn(AppResponse)| {
+            let msg = request.try_into().unwrap();
+            let respond = move |bytes: SerializedBytes| {
+                check(bytes.try_into().unwrap());
+                async { Ok(()) }.boxed().into()
+            };
+            let respond = Respond::Request(Box::new(respond));
+            handle_incoming_app_message(
+                (msg, respond),
+                app_api.clone(),
+                subscriptions.clone(),
+                auth.clone(),
+                streams.clone(),
+            )
+        };
+
+        // Requests are refused until the connection is authenticated
+        send(
+            AppRequest::AppInfo {
+                installed_app_id: "test app".to_string(),
+            },
+            |response| {
+                assert_matches!(
+                    response,
+                    AppResponse::Error(ExternalApiWireError::Unauthorized(_))
+                )
+            },
+        )
+        .await
+        .unwrap();
+
+        send(
+            AppRequest::Authenticate(AppAuthenticationRequest { token: vec![0; 32] }),
+            |response| {
+                assert_matches!(
+                    response,
+                    AppResponse::Error(ExternalApiWireError::AuthenticationFailed(_))
+                )
+            },
+        )
+        .await
+        .unwrap();
+
+        let issued = conductor_handle
+            .issue_app_authentication_token(IssueAppAuthenticationTokenPayload::for_apps(vec![
+                "test app".to_string(),
+            ]))
+            .unwrap();
+        send(
+            AppRequest::Authenticate(AppAuthenticationRequest {
+                token: issued.token.clone(),
+            }),
+            |response| {
+                assert_matches!(response, AppResponse::Authenticated(apps) if apps == vec!["test app".to_string()])
+            },
+        )
+        .await
+        .unwrap();
+
+        // The connection can only access the apps the token was issued for
+        send(
+            AppRequest::AppInfo {
+                installed_app_id: "test app".to_string(),
+            },
+            |response| assert_matches!(response, AppResponse::AppInfo(None)),
+        )
+        .await
+        .unwrap();
+        send(
+            AppRequest::AppInfo {
+                installed_app_id: "other app".to_string(),
+            },
+            |response| {
+                assert_matches!(
+                    response,
+                    AppResponse::Error(ExternalApiWireError::Unauthorized(_))
+                )
+            },
+        )
+        .await
+        .unwrap();
+
+        // The token was single use
+        assert!(conductor_handle
+            .authenticate_app_token(&issued.token)
+            .is_none());
+
+        // Revoking the token ends the session of the connection
+        let session = rx_sessions.try_recv().unwrap();
+        conductor_handle
+            .revoke_app_authentication_token(&issued.token)
+            .unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(1), session.ended())
+            .await
+            .unwrap();
+        conductor_handle.shutdown();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn storage_info() {
         holochain_trace::test_run().ok();
@@ -806,7 +1613,10 @@ pub mod test {
         holochain_trace::test_run().ok();
         let (_tmpdir, conductor_handle) = setup_admin().await;
         let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
-        let msg = AdminRequest::AttachAppInterface { port: None };
+        let msg = AdminRequest::AttachAppInterface {
+            port: None,
+            allowed_origins: AllowedOrigins::Any,
+        };
         let msg = msg.try_into().unwrap();
         let respond = |bytes: SerializedBytes| {
             let response: AdminResponse = bytes.try_into().unwrap();
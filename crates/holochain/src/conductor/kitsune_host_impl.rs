@@ -21,8 +21,11 @@ use holochain_types::{
 };
 use holochain_zome_types::Timestamp;
 use kitsune_p2p::{
-    agent_store::AgentInfoSigned, dependencies::kitsune_p2p_fetch::OpHashSized,
-    event::GetAgentInfoSignedEvt, KitsuneHost, KitsuneHostResult,
+    agent_store::AgentInfoSigned,
+    dependencies::kitsune_p2p_fetch::{FetchPoolPending, OpHashSized},
+    dht_arc::ArcPin,
+    event::GetAgentInfoSignedEvt,
+    KitsuneHost, KitsuneHostResult,
 };
 use kitsune_p2p_types::{
     config::KitsuneP2pTuningParams, dependencies::lair_keystore_api, KOpData, KOpHash,
@@ -286,6 +289,44 @@ impl KitsuneHost for KitsuneHostImpl {
         .into()
     }
 
+    fn put_fetch_pool_pending(
+        &self,
+        space: Arc<kitsune_p2p::KitsuneSpace>,
+        pending: Vec<FetchPoolPending>,
+    ) -> KitsuneHostResult<()> {
+        async move {
+            let db = self.spaces.p2p_agents_db(&DnaHash::from_kitsune(&space))?;
+            Ok(holochain_sqlite::db::p2p_put_fetch_pool(&db, pending).await?)
+        }
+        .boxed()
+        .into()
+    }
+
+    fn get_fetch_pool_pending(
+        &self,
+        space: Arc<kitsune_p2p::KitsuneSpace>,
+    ) -> KitsuneHostResult<Vec<FetchPoolPending>> {
+        async move {
+            let db = self.spaces.p2p_agents_db(&DnaHash::from_kitsune(&space))?;
+            Ok(holochain_sqlite::db::p2p_get_fetch_pool(&db).await?)
+        }
+        .boxed()
+        .into()
+    }
+
+    fn get_arc_pin(
+        &self,
+        space: Arc<kitsune_p2p::KitsuneSpace>,
+        agent: Arc<kitsune_p2p::KitsuneAgent>,
+    ) -> KitsuneHostResult<Option<ArcPin>> {
+        async move {
+            let db = self.spaces.p2p_agents_db(&DnaHash::from_kitsune(&space))?;
+            Ok(holochain_sqlite::db::p2p_get_arc_pin(&db, agent).await?)
+        }
+        .boxed()
+        .into()
+    }
+
     fn lair_tag(&self) -> Option<Arc<str>> {
         self.lair_tag.clone()
     }
@@ -0,0 +1,146 @@
+//! Metrics recorded by the conductor, and the endpoint which serves them,
+//! along with the metrics of every other crate in the process, in the
+//! Prometheus text format.
+
+use super::manager::ManagedTaskResult;
+use super::manager::TaskManagerClient;
+use futures::Future;
+use holochain_trace::export::prometheus::register_histogram_vec;
+use holochain_trace::export::prometheus::register_int_gauge_vec;
+use holochain_trace::export::prometheus::HistogramVec;
+use holochain_trace::export::prometheus::IntGaugeVec;
+use once_cell::sync::Lazy;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tracing::*;
+
+/// The number of ops waiting to be processed by a workflow when it last ran,
+/// by workflow and DNA.
+pub(crate) static WORKFLOW_QUEUE_DEPTH: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "holochain_workflow_queue_depth",
+        "The number of ops waiting to be processed by a workflow",
+        &["workflow", "dna"]
+    )
+    .expect("Metric names are unique")
+});
+
+/// How long zome calls take, by zome and function.
+pub(crate) static ZOME_CALL_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "holochain_zome_call_duration_seconds",
+        "The time taken to run zome calls",
+        &["zome", "fn"],
+        vec![0.001, 0.005, 0.02, 0.1, 0.5, 2.0, 10.0, 60.0]
+    )
+    .expect("Metric names are unique")
+});
+
+/// The largest HTTP request head the metrics endpoint will read.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Serve all metrics at `/metrics` on the given local port, or a free port
+/// if it is 0. Returns the port.
+pub(crate) async fn spawn_prometheus_endpoint(
+    tm: TaskManagerClient,
+    port: u16,
+) -> std::io::Result<u16> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    let port = listener.local_addr()?.port();
+    info!(port, "Serving Prometheus metrics");
+    tm.add_conductor_task_ignored("prometheus metrics endpoint", move |stop| {
+        serve_prometheus(listener, stop)
+    });
+    Ok(port)
+}
+
+async fn serve_prometheus(
+    listener: TcpListener,
+    stop: impl Future<Output = ()>,
+) -> ManagedTaskResult {
+    futures::pin_mut!(stop);
+    loop {
+        tokio::select! {
+            _ = &mut stop => return Ok(()),
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::task::spawn(async move {
+                        if let Err(e) = respond(stream).await {
+                            debug!(?e, "Failed to respond to a metrics request");
+                        }
+                    });
+                }
+                Err(e) => warn!(?e, "Failed to accept a metrics connection"),
+            },
+        }
+    }
+}
+
+/// Answer a single HTTP request and close the connection.
+async fn respond(mut stream: TcpStream) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    let request_line = String::from_utf8_lossy(&request);
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            holochain_trace::export::PROMETHEUS_CONTENT_TYPE,
+            holochain_trace::export::prometheus_text(),
+        ),
+        _ => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn get(port: u16, path: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        stream
+            .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn metrics_are_served_in_prometheus_format() {
+        WORKFLOW_QUEUE_DEPTH
+            .with_label_values(&["test_workflow", "test_dna"])
+            .set(3);
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_prometheus(listener, futures::future::pending()));
+
+        let response = get(port, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains(
+            "holochain_workflow_queue_depth{dna=\"test_dna\",workflow=\"test_workflow\"} 3"
+        ));
+
+        let response = get(port, "/other").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+    }
+}
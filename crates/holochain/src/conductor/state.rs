@@ -1,12 +1,14 @@
 //! Structs which allow the Conductor's state to be persisted across
 //! startups and shutdowns
 
+use holochain_conductor_api::config::AllowedOrigins;
 use holochain_conductor_api::config::InterfaceDriver;
 use holochain_conductor_api::signal_subscription::SignalSubscription;
 use holochain_types::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use super::error::{ConductorError, ConductorResult};
@@ -155,13 +157,36 @@ impl ConductorState {
             .ok_or_else(|| ConductorError::AppNotInstalled(id.clone()))
     }
 
-    /// Getter for a single app. Returns error if app missing.
+    /// Remove a single app. Returns error if app missing, or if other apps
+    /// depend on any of its cells.
     pub fn remove_app(&mut self, id: &InstalledAppId) -> ConductorResult<InstalledApp> {
+        let dependents = self.dependent_apps(id)?;
+        if !dependents.is_empty() {
+            return Err(ConductorError::AppHasDependents(id.clone(), dependents));
+        }
         self.installed_apps
             .remove(id)
             .ok_or_else(|| ConductorError::AppNotInstalled(id.clone()))
     }
 
+    /// The IDs of all other apps which depend on cells owned by this app.
+    pub fn dependent_apps(&self, id: &InstalledAppId) -> ConductorResult<Vec<InstalledAppId>> {
+        let owned_cells: HashSet<_> = self.get_app(id)?.owned_cells().collect();
+        let mut dependents: Vec<_> = self
+            .installed_apps
+            .iter()
+            .filter(|(other_id, other_app)| {
+                *other_id != id
+                    && other_app
+                        .dependency_cells()
+                        .any(|c| owned_cells.contains(c))
+            })
+            .map(|(other_id, _)| other_id.clone())
+            .collect();
+        dependents.sort();
+        Ok(dependents)
+    }
+
     /// Add an app in the Deactivated state. Returns an error if an app is already
     /// present at the given ID.
     pub fn add_app(&mut self, app: InstalledAppCommon) -> ConductorResult<StoppedApp> {
@@ -199,8 +224,6 @@ impl ConductorState {
 /// GUIs, browser based web UIs, local native UIs, other local applications and scripts.
 /// We currently have:
 /// * websockets
-///
-/// We will also soon develop
 /// * Unix domain sockets
 ///
 /// The cells (referenced by ID) that are to be made available via that interface should be listed.
@@ -215,11 +238,22 @@ pub struct AppInterfaceConfig {
 }
 
 impl AppInterfaceConfig {
+    /// Create config for an interface with the given driver
+    pub fn new(driver: InterfaceDriver) -> Self {
+        Self {
+            signal_subscriptions: HashMap::new(),
+            driver,
+        }
+    }
+
     /// Create config for a websocket interface
-    pub fn websocket(port: u16) -> Self {
+    pub fn websocket(port: u16, allowed_origins: AllowedOrigins) -> Self {
         Self {
             signal_subscriptions: HashMap::new(),
-            driver: InterfaceDriver::Websocket { port },
+            driver: InterfaceDriver::Websocket {
+                port,
+                allowed_origins,
+            },
         }
     }
 }
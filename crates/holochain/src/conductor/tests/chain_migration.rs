@@ -0,0 +1,277 @@
+use crate::{
+    conductor::{api::error::ConductorApiResult, error::ConductorError, CellError},
+    core::workflow::{error::WorkflowError, migrate_agent_workflow::ChainMigration},
+    sweettest::*,
+};
+use holo_hash::ActionHash;
+use holochain_state::source_chain::dump_state;
+use holochain_types::inline_zome::InlineZomeSet;
+use holochain_types::prelude::*;
+use matches::assert_matches;
+
+/// A zome which can create entries, and whose `migrate_agent` callback
+/// returns the given result
+fn migration_zome(result: MigrateAgentCallbackResult) -> InlineZomeSet {
+    SweetInlineZomes::new(vec![EntryDef::from_id("unit")], 0)
+        .function("create_unit", |api, ()| {
+            let entry = Entry::app(().try_into().unwrap()).unwrap();
+            let hash = api.create(CreateInput::new(
+                InlineZomeSet::get_entry_location(&api, EntryDefIndex(0)),
+                EntryVisibility::Public,
+                entry,
+                ChainTopOrdering::default(),
+            ))?;
+            Ok(hash)
+        })
+        .function(
+            "migrate_agent",
+            move |_, _: MigrateAgent| Ok(result.clone()),
+        )
+        .into()
+}
+
+async fn chain_action(cell: &SweetCell, hash: &ActionHash) -> Action {
+    dump_state(
+        cell.authored_db().clone().into(),
+        cell.agent_pubkey().clone(),
+    )
+    .await
+    .unwrap()
+    .records
+    .into_iter()
+    .find(|r| r.action_address == *hash)
+    .unwrap()
+    .action
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_chain_to_new_dna() {
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let (dna_1, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(migration_zome(MigrateAgentCallbackResult::Pass))
+            .await;
+    let (dna_2, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(migration_zome(MigrateAgentCallbackResult::Pass))
+            .await;
+    let (dna_3, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(migration_zome(MigrateAgentCallbackResult::Pass))
+            .await;
+    let (cell_1, cell_2, cell_3) = conductor
+        .setup_app_for_agent("app", alice, [&dna_1, &dna_2, &dna_3])
+        .await
+        .unwrap()
+        .into_tuple();
+    let zome_1 = cell_1.zome(SweetInlineZomes::COORDINATOR);
+    let zome_2 = cell_2.zome(SweetInlineZomes::COORDINATOR);
+
+    let _: ActionHash = conductor.call(&zome_1, "create_unit", ()).await;
+
+    let (close_hash, open_hash) = conductor
+        .raw_handle()
+        .migrate_chain(cell_1.cell_id().clone(), cell_2.cell_id().clone())
+        .await
+        .unwrap();
+
+    assert_matches!(
+        chain_action(&cell_1, &close_hash).await,
+        Action::CloseChain(CloseChain { new_target: MigrationTarget::Dna(h), .. })
+            if h == *dna_2.dna_hash()
+    );
+    assert_matches!(
+        chain_action(&cell_2, &open_hash).await,
+        Action::OpenChain(OpenChain { prev_target: MigrationTarget::Dna(h), close_hash: c, .. })
+            if h == *dna_1.dna_hash() && c == close_hash
+    );
+
+    // Nothing can be written to the closed chain
+    let result: ConductorApiResult<ActionHash> =
+        conductor.call_fallible(&zome_1, "create_unit", ()).await;
+    assert!(result.is_err());
+
+    // Running the same migration again finds it already done
+    let again = conductor
+        .raw_handle()
+        .migrate_chain(cell_1.cell_id().clone(), cell_2.cell_id().clone())
+        .await
+        .unwrap();
+    assert_eq!(again, (close_hash, open_hash));
+
+    // But the chain can't be closed again in favor of another one
+    let result = conductor
+        .raw_handle()
+        .migrate_chain(cell_1.cell_id().clone(), cell_3.cell_id().clone())
+        .await;
+    assert!(result.is_err());
+
+    // The new chain is open for business
+    let _: ActionHash = conductor.call(&zome_2, "create_unit", ()).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_chain_to_new_agent_key() {
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (alice, alice_new) = SweetAgents::two(conductor.keystore()).await;
+    let (dna, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(migration_zome(MigrateAgentCallbackResult::Pass))
+            .await;
+    let apps = conductor
+        .setup_app_for_agents("app", [&alice, &alice_new], [&dna])
+        .await
+        .unwrap();
+    let ((old_cell,), (new_cell,)) = apps.into_tuples();
+
+    let (close_hash, open_hash) = conductor
+        .raw_handle()
+        .migrate_chain(old_cell.cell_id().clone(), new_cell.cell_id().clone())
+        .await
+        .unwrap();
+
+    assert_matches!(
+        chain_action(&old_cell, &close_hash).await,
+        Action::CloseChain(CloseChain { new_target: MigrationTarget::Agent(a), .. })
+            if a == alice_new
+    );
+    assert_matches!(
+        chain_action(&new_cell, &open_hash).await,
+        Action::OpenChain(OpenChain { prev_target: MigrationTarget::Agent(a), .. })
+            if a == alice
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_chain_resumes_after_the_old_chain_was_closed() {
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let (dna_1, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(migration_zome(MigrateAgentCallbackResult::Pass))
+            .await;
+    let (dna_2, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(migration_zome(MigrateAgentCallbackResult::Pass))
+            .await;
+    let (cell_1, cell_2) = conductor
+        .setup_app_for_agent("app", alice, [&dna_1, &dna_2])
+        .await
+        .unwrap()
+        .into_tuple();
+
+    // Only the old chain is closed, as if the migration stopped between
+    // writing its two ends
+    let from_cell = conductor
+        .raw_handle()
+        .cell_by_id(cell_1.cell_id(), false)
+        .await
+        .unwrap();
+    let close = from_cell
+        .prepare_chain_migration(ChainMigration::Close(MigrationTarget::Dna(
+            dna_2.dna_hash().clone(),
+        )))
+        .await
+        .unwrap();
+    let close_hash = from_cell.commit_chain_migration(close).await.unwrap();
+
+    let (resumed_close_hash, open_hash) = conductor
+        .raw_handle()
+        .migrate_chain(cell_1.cell_id().clone(), cell_2.cell_id().clone())
+        .await
+        .unwrap();
+
+    assert_eq!(resumed_close_hash, close_hash);
+    assert_matches!(
+        chain_action(&cell_2, &open_hash).await,
+        Action::OpenChain(OpenChain { close_hash: c, .. }) if c == close_hash
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_chain_rejected_by_callback() {
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let (dna_1, _, _) = SweetDnaFile::unique_from_inline_zomes(migration_zome(
+        MigrateAgentCallbackResult::Fail("not leaving".into()),
+    ))
+    .await;
+    let (dna_2, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(migration_zome(MigrateAgentCallbackResult::Pass))
+            .await;
+    let (cell_1, cell_2) = conductor
+        .setup_app_for_agent("app", alice, [&dna_1, &dna_2])
+        .await
+        .unwrap()
+        .into_tuple();
+
+    let result = conductor
+        .raw_handle()
+        .migrate_chain(cell_1.cell_id().clone(), cell_2.cell_id().clone())
+        .await;
+    assert_matches!(
+        result,
+        Err(ConductorError::InternalCellError(CellError::WorkflowError(e)))
+            if matches!(*e, WorkflowError::MigrateAgentFailed(_, _))
+    );
+
+    // The chain was left open
+    let _: ActionHash = conductor
+        .call(
+            &cell_1.zome(SweetInlineZomes::COORDINATOR),
+            "create_unit",
+            (),
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_chain_rejected_by_new_chain() {
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let (dna_1, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(migration_zome(MigrateAgentCallbackResult::Pass))
+            .await;
+    let (dna_2, _, _) = SweetDnaFile::unique_from_inline_zomes(migration_zome(
+        MigrateAgentCallbackResult::Fail("not arriving".into()),
+    ))
+    .await;
+    let (cell_1, cell_2) = conductor
+        .setup_app_for_agent("app", alice, [&dna_1, &dna_2])
+        .await
+        .unwrap()
+        .into_tuple();
+
+    let result = conductor
+        .raw_handle()
+        .migrate_chain(cell_1.cell_id().clone(), cell_2.cell_id().clone())
+        .await;
+    assert_matches!(
+        result,
+        Err(ConductorError::InternalCellError(CellError::WorkflowError(e)))
+            if matches!(*e, WorkflowError::MigrateAgentFailed(_, _))
+    );
+
+    // The old chain wasn't closed, since the new one couldn't be opened
+    let _: ActionHash = conductor
+        .call(
+            &cell_1.zome(SweetInlineZomes::COORDINATOR),
+            "create_unit",
+            (),
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_chain_requires_one_difference() {
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (dna, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(migration_zome(MigrateAgentCallbackResult::Pass))
+            .await;
+    let (cell,) = conductor
+        .setup_app("app", [&dna])
+        .await
+        .unwrap()
+        .into_tuple();
+
+    let result = conductor
+        .raw_handle()
+        .migrate_chain(cell.cell_id().clone(), cell.cell_id().clone())
+        .await;
+    assert_matches!(result, Err(ConductorError::InvalidChainMigration(_, _)));
+}
@@ -4,9 +4,10 @@ use crate::{conductor::error::ConductorError, sweettest::*};
 use fixt::prelude::strum_macros;
 use holo_hash::{AgentPubKey, DnaHash};
 use holochain_types::prelude::{
-    mapvec, AppBundle, AppBundleError, AppBundleSource, AppManifestCurrentBuilder,
+    mapvec, AppBundle, AppBundleError, AppBundleSource, AppError, AppManifestCurrentBuilder,
     AppManifestError, AppRoleDnaManifest, AppRoleManifest, CellProvisioning,
     CreateCloneCellPayload, DnaBundle, DnaFile, DnaLocation, InstallAppPayload,
+    ProvisionDeferredCellPayload,
 };
 use holochain_wasm_test_utils::TestWasm;
 use holochain_zome_types::{CellId, DnaModifiersOpt, Timestamp};
@@ -116,7 +117,215 @@ async fn clone_only_provisioning_creates_no_cell_and_allows_cloning() {
 
         assert_eq!(app.all_cells().count(), 1);
     }
-    // TODO: test that the cell can't be provisioned later
+    // The base cell of a CloneOnly role can't be provisioned later
+    assert_matches!(
+        conductor
+            .clone()
+            .provision_deferred_cell(ProvisionDeferredCellPayload {
+                app_id: "app_1".into(),
+                role_name: "name".into(),
+                membrane_proof: None,
+            })
+            .await
+            .unwrap_err(),
+        ConductorError::AppError(AppError::RoleNotDeferred(_))
+    );
+}
+
+/// Build an app bundle with a single role using the given provisioning strategy.
+async fn single_role_bundle(dna: &DnaFile, provisioning: CellProvisioning) -> AppBundle {
+    let path = PathBuf::from(format!("{}", dna.dna_hash()));
+    let roles = vec![AppRoleManifest {
+        name: "name".into(),
+        dna: AppRoleDnaManifest {
+            location: Some(DnaLocation::Bundled(path.clone())),
+            modifiers: DnaModifiersOpt::none(),
+            installed_hash: Some(dna.dna_hash().clone().into()),
+            clone_limit: 0,
+        },
+        provisioning: Some(provisioning),
+    }];
+
+    let manifest = AppManifestCurrentBuilder::default()
+        .name("test_app".into())
+        .description(None)
+        .roles(roles)
+        .build()
+        .unwrap();
+    let dna_bundle = DnaBundle::from_dna_file(dna.clone()).await.unwrap();
+    AppBundle::new(
+        manifest.into(),
+        vec![(path, dna_bundle)],
+        PathBuf::from("."),
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn use_existing_provisioning_shares_cell_and_blocks_uninstall() {
+    let conductor = SweetConductor::from_standard_config().await;
+    let agent = SweetAgents::one(conductor.keystore()).await;
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+
+    let payload = |app_id: &str, bundle: AppBundle| InstallAppPayload {
+        agent_key: agent.clone(),
+        source: AppBundleSource::Bundle(bundle),
+        installed_app_id: Some(app_id.into()),
+        network_seed: None,
+        membrane_proofs: HashMap::new(),
+    };
+
+    // There is no existing cell to use yet
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(payload(
+                "app_2",
+                single_role_bundle(&dna, CellProvisioning::UseExisting { deferred: false }).await
+            ))
+            .await
+            .unwrap_err(),
+        ConductorError::AppBundleError(AppBundleError::CellResolutionFailure(_, _))
+    );
+
+    let app_1 = conductor
+        .clone()
+        .install_app_bundle(payload(
+            "app_1",
+            single_role_bundle(&dna, CellProvisioning::Create { deferred: false }).await,
+        ))
+        .await
+        .unwrap();
+
+    // The cell exists, but only a running cell can be used
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(payload(
+                "app_2",
+                single_role_bundle(&dna, CellProvisioning::UseExisting { deferred: false }).await
+            ))
+            .await
+            .unwrap_err(),
+        ConductorError::AppBundleError(AppBundleError::CellResolutionFailure(_, _))
+    );
+
+    conductor.enable_app("app_1".into()).await.unwrap();
+    let app_2 = conductor
+        .clone()
+        .install_app_bundle(payload(
+            "app_2",
+            single_role_bundle(&dna, CellProvisioning::UseExisting { deferred: false }).await,
+        ))
+        .await
+        .unwrap();
+
+    let cell_id = app_1.all_cells().next().unwrap().clone();
+    assert_eq!(app_2.role(&"name".into()).unwrap().cell_id(), &cell_id);
+    assert!(app_2.role(&"name".into()).unwrap().is_dependency());
+    assert_eq!(app_2.owned_cells().count(), 0);
+
+    // app_1 owns the cell used by app_2, so it can't be uninstalled
+    assert_matches!(
+        conductor.clone().uninstall_app(&"app_1".into()).await.unwrap_err(),
+        ConductorError::AppHasDependents(_, dependents) if dependents == vec!["app_2".to_string()]
+    );
+
+    // Uninstalling the dependent app leaves the shared cell in place
+    conductor
+        .clone()
+        .uninstall_app(&"app_2".into())
+        .await
+        .unwrap();
+    let state = conductor.get_state().await.unwrap();
+    assert_eq!(
+        state
+            .get_app(&"app_1".to_string())
+            .unwrap()
+            .all_cells()
+            .next(),
+        Some(&cell_id)
+    );
+    conductor
+        .clone()
+        .uninstall_app(&"app_1".into())
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn deferred_provisioning_creates_cell_on_request() {
+    let conductor = SweetConductor::from_standard_config().await;
+    let agent = SweetAgents::one(conductor.keystore()).await;
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+
+    let app = conductor
+        .clone()
+        .install_app_bundle(InstallAppPayload {
+            agent_key: agent.clone(),
+            source: AppBundleSource::Bundle(
+                single_role_bundle(&dna, CellProvisioning::Create { deferred: true }).await,
+            ),
+            installed_app_id: Some("app_1".into()),
+            network_seed: None,
+            membrane_proofs: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    // No cell is created at installation time
+    assert_eq!(app.all_cells().count(), 0);
+    assert!(app.role(&"name".into()).unwrap().is_deferred());
+    conductor.enable_app("app_1".into()).await.unwrap();
+
+    // Of two calls racing to provision the cell, only one does
+    let provision = || {
+        conductor
+            .clone()
+            .provision_deferred_cell(ProvisionDeferredCellPayload {
+                app_id: "app_1".into(),
+                role_name: "name".into(),
+                membrane_proof: None,
+            })
+    };
+    let (first, second) = futures::join!(provision(), provision());
+    let provisioned = match (first, second) {
+        (Ok(provisioned), Err(err)) | (Err(err), Ok(provisioned)) => {
+            assert_matches!(err, ConductorError::AppError(AppError::RoleNotDeferred(_)));
+            provisioned
+        }
+        other => panic!(
+            "expected exactly one call to provision the cell, got {:?}",
+            other
+        ),
+    };
+    assert_eq!(
+        provisioned.cell_id,
+        CellId::new(dna.dna_hash().clone(), agent.clone())
+    );
+    assert!(conductor
+        .running_cell_ids(None)
+        .contains(&provisioned.cell_id));
+
+    let state = conductor.get_state().await.unwrap();
+    let app = state.get_app(&"app_1".to_string()).unwrap();
+    assert_eq!(app.all_cells().count(), 1);
+    assert!(!app.role(&"name".into()).unwrap().is_deferred());
+
+    // The cell can only be provisioned once
+    assert_matches!(
+        conductor
+            .clone()
+            .provision_deferred_cell(ProvisionDeferredCellPayload {
+                app_id: "app_1".into(),
+                role_name: "name".into(),
+                membrane_proof: None,
+            })
+            .await
+            .unwrap_err(),
+        ConductorError::AppError(AppError::RoleNotDeferred(_))
+    );
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -1,5 +1,6 @@
 mod app_info;
 mod cell_cloning;
+mod chain_migration;
 mod install_app_bundle;
 mod network_info;
 mod request_dna_def;
@@ -176,6 +176,7 @@ pub async fn spawn_queue_consumer_tasks(
             space.clone(),
             conductor.task_manager(),
             network.clone(),
+            keystore.clone(),
             tx_sys.clone(),
         )
     });
@@ -3,14 +3,16 @@
 use super::*;
 use crate::conductor::manager::TaskManagerClient;
 use crate::core::workflow::countersigning_workflow::countersigning_workflow;
+use holochain_keystore::MetaLairClient;
 use tracing::*;
 
 /// Spawn the QueueConsumer for countersigning workflow
-#[instrument(skip(space, tm, dna_network, trigger_sys))]
+#[instrument(skip(space, tm, dna_network, keystore, trigger_sys))]
 pub(crate) fn spawn_countersigning_consumer(
     space: Space,
     tm: TaskManagerClient,
     dna_network: HolochainP2pDna,
+    keystore: MetaLairClient,
     trigger_sys: TriggerSender,
 ) -> TriggerSender {
     let (tx, rx) = TriggerSender::new();
@@ -20,7 +22,14 @@ pub(crate) fn spawn_countersigning_consumer(
         space.dna_hash.clone(),
         tm,
         (tx.clone(), rx),
-        move || countersigning_workflow(space.clone(), dna_network.clone(), trigger_sys.clone()),
+        move || {
+            countersigning_workflow(
+                space.clone(),
+                dna_network.clone(),
+                keystore.clone(),
+                trigger_sys.clone(),
+            )
+        },
     );
 
     tx
@@ -537,6 +537,10 @@ impl From<ZomeCallInvocation> for ZomeCall {
     }
 }
 
+/// Sends the chunks yielded by a streaming zome call towards the client
+/// that made it, or `None` if the zome call is not streaming.
+pub type ZomeCallStream = Option<tokio::sync::mpsc::Sender<ExternIO>>;
+
 #[derive(Clone, Constructor)]
 pub struct ZomeCallHostAccess {
     pub workspace: HostFnWorkspace,
@@ -544,6 +548,11 @@ pub struct ZomeCallHostAccess {
     pub network: HolochainP2pDna,
     pub signal_tx: SignalBroadcaster,
     pub call_zome_handle: CellConductorReadHandle,
+    /// When the zome call expires.
+    /// Host functions that wait, like `sleep`, give up at this time.
+    pub expires_at: Timestamp,
+    /// Where `stream_chunk` sends chunks, if the zome call is streaming.
+    pub stream: ZomeCallStream,
 }
 
 impl std::fmt::Debug for ZomeCallHostAccess {
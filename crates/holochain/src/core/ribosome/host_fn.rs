@@ -80,14 +80,14 @@ host_fn_api_impls! {
 
     fn call (Vec<zt::call::Call>) -> Vec<zt::ZomeCallResponse>;
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List the local capability claims, optionally filtered by grantor and tag.
+    fn capability_claims (zt::capability::CapClaimsInput) -> Vec<zt::capability::CapClaim>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List the capability grants committed to the local source chain.
+    fn capability_grants (zt::capability::CapGrantsInput) -> Vec<zt::capability::CapGrantInfo>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability that authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapabilityInfo;
 
     // The EntryDefId determines how a create is handled on the host side.
     // CapGrant and CapClaim are handled natively.
@@ -184,6 +184,9 @@ host_fn_api_impls! {
     // @todo
     fn sleep (core::time::Duration) -> ();
 
+    // Send a chunk of output to the client of a streaming zome call.
+    fn stream_chunk (zt::zome_io::ExternIO) -> ();
+
     // @todo
     fn version (()) -> zt::version::ZomeApiVersion;
 
@@ -18,48 +18,7 @@ pub fn call_info(
             bindings: Permission::Allow,
             ..
         } => {
-            let (provenance, cap_grant) = {
-                match call_context.auth() {
-                    InvocationAuth::Cap(provenance, cap_secret) => {
-                        let check_function = (
-                            call_context.zome.zome_name().clone(),
-                            call_context.function_name().clone(),
-                        );
-                        let check_agent = provenance.clone();
-                        let call_context = call_context.clone();
-                        let cap_grant = tokio_helper::block_forever_on(async move {
-                            Result::<_, WasmError>::Ok(call_context
-                            .host_context
-                            .workspace()
-                            .source_chain()
-                            .as_ref()
-                            .expect("Must have source chain if bindings access is given")
-                            .valid_cap_grant(
-                                check_function,
-                                check_agent,
-                                cap_secret,
-                            ).await.map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))?
-                            // This is really a problem.
-                            // It means that the host function calling into `call_info`
-                            // is using a cap secret that never had authorization to call in the first place.
-                            // The host must NEVER allow this so `None` is a critical bug.
-                            .expect("The host is using an unauthorized cap_secret, which should never happen"))
-                        })?;
-                        (provenance, cap_grant)
-                    }
-                    InvocationAuth::LocalCallback => {
-                        let author = call_context
-                            .host_context
-                            .workspace()
-                            .source_chain()
-                            .as_ref()
-                            .expect("Must have source chain if bindings access is given")
-                            .agent_pubkey()
-                            .clone();
-                        (author.clone(), CapGrant::ChainAuthor(author))
-                    }
-                }
-            };
+            let (provenance, cap_grant) = call_provenance_and_grant(&call_context)?;
             Ok(CallInfo {
                 function_name: call_context.function_name.clone(),
                 as_at: call_context
@@ -89,6 +48,53 @@ pub fn call_info(
     }
 }
 
+/// The agent who made this call and the capability grant that authorized it.
+/// The caller must have checked that the call has bindings access.
+pub(crate) fn call_provenance_and_grant(
+    call_context: &Arc<CallContext>,
+) -> Result<(AgentPubKey, CapGrant), RuntimeError> {
+    match call_context.auth() {
+        InvocationAuth::Cap(provenance, cap_secret) => {
+            let check_function = (
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+            );
+            let check_agent = provenance.clone();
+            let call_context = call_context.clone();
+            let cap_grant = tokio_helper::block_forever_on(async move {
+                Result::<_, WasmError>::Ok(call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain if bindings access is given")
+                .valid_cap_grant(
+                    check_function,
+                    check_agent,
+                    cap_secret,
+                ).await.map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))?
+                // This is really a problem.
+                // It means that the host function calling into `call_info`
+                // is using a cap secret that never had authorization to call in the first place.
+                // The host must NEVER allow this so `None` is a critical bug.
+                .expect("The host is using an unauthorized cap_secret, which should never happen"))
+            })?;
+            Ok((provenance, cap_grant))
+        }
+        InvocationAuth::LocalCallback => {
+            let author = call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain if bindings access is given")
+                .agent_pubkey()
+                .clone();
+            Ok((author.clone(), CapGrant::ChainAuthor(author)))
+        }
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 pub mod test {
@@ -1,16 +1,46 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::*;
 use std::sync::Arc;
 
 /// list all the grants stored locally in the chain filtered by tag
-/// this is only the current grants as per local CRUD
+/// revoked grants are only included if requested
 pub fn capability_grants(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), RuntimeError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: CapGrantsInput,
+) -> Result<Vec<CapGrantInfo>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let grants = call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain to query the source chain")
+                .cap_grants(input)
+                .await
+                .map_err(|source_chain_error| -> RuntimeError {
+                    wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+                })?;
+            Ok(grants)
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "capability_grants".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +88,127 @@ pub mod wasm_test {
         assert_eq!(entry_secret, secret);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_capability_grants_claims_and_info() {
+        holochain_trace::test_run().ok();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_pubkey,
+            bob,
+            bob_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Capability).await;
+
+        #[derive(serde::Serialize, serde::Deserialize, SerializedBytes, Debug)]
+        pub struct CapFor(CapSecret, AgentPubKey);
+
+        // BOB CAN LIST A NEWLY COMMITTED GRANT
+
+        let secret: CapSecret = conductor.call(&bob, "cap_secret", ()).await;
+        let original_grant_hash: ActionHash =
+            conductor.call(&bob, "transferable_cap_grant", secret).await;
+
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(&bob, "cap_grants", CapGrantsInput::default())
+            .await;
+        let original_grant = grants
+            .iter()
+            .find(|info| info.action_hash == original_grant_hash)
+            .unwrap();
+        assert!(original_grant.is_active());
+        assert_matches!(
+            original_grant.cap_grant.access,
+            CapAccess::Transferable { secret: s } if s == secret
+        );
+
+        // ROLLING THE GRANT REVOKES THE ORIGINAL
+
+        let new_grant_hash: ActionHash = conductor
+            .call(&bob, "roll_cap_grant", original_grant_hash.clone())
+            .await;
+
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(&bob, "cap_grants", CapGrantsInput::default())
+            .await;
+        assert!(grants
+            .iter()
+            .all(|info| info.action_hash != original_grant_hash));
+        let new_grant = grants
+            .iter()
+            .find(|info| info.action_hash == new_grant_hash)
+            .unwrap()
+            .clone();
+
+        let grants: Vec<CapGrantInfo> = conductor
+            .call(
+                &bob,
+                "cap_grants",
+                CapGrantsInput {
+                    tag: None,
+                    include_revoked: true,
+                },
+            )
+            .await;
+        let original_grant = grants
+            .iter()
+            .find(|info| info.action_hash == original_grant_hash)
+            .unwrap();
+        assert!(!original_grant.is_active());
+
+        // ALICE STORES A CLAIM FOR THE NEW GRANT AND FINDS IT AGAIN
+
+        let new_secret = match new_grant.cap_grant.access {
+            CapAccess::Transferable { secret } => secret,
+            _ => unreachable!(),
+        };
+        let claim = CapClaim::new("bob".into(), bob_pubkey.clone(), new_secret);
+        let _: ActionHash = conductor
+            .call(&alice, "accept_cap_claim", claim.clone())
+            .await;
+
+        let claims: Vec<CapClaim> = conductor
+            .call(
+                &alice,
+                "cap_claims",
+                CapClaimsInput {
+                    grantor: Some(bob_pubkey.clone()),
+                    tag: Some("bob".into()),
+                },
+            )
+            .await;
+        assert_eq!(claims, vec![claim]);
+        let claims: Vec<CapClaim> = conductor
+            .call(
+                &alice,
+                "cap_claims",
+                CapClaimsInput {
+                    grantor: Some(alice_pubkey.clone()),
+                    tag: None,
+                },
+            )
+            .await;
+        assert!(claims.is_empty());
+
+        // THE CAPABILITY INFO SHOWS WHICH GRANT AUTHORIZED THE CALL
+
+        let info: CapabilityInfo = conductor.call(&alice, "cap_info", ()).await;
+        assert_eq!(info.provenance, alice_pubkey);
+        assert_eq!(info.cap_grant, CapGrant::ChainAuthor(alice_pubkey.clone()));
+        assert_eq!(info.action_hash, None);
+
+        let response: ZomeCallResponse = conductor
+            .call(&alice, "try_cap_info", CapFor(new_secret, bob_pubkey))
+            .await;
+        let info: CapabilityInfo = match response {
+            ZomeCallResponse::Ok(io) => io.decode().unwrap(),
+            other => panic!("Unexpected response {:?}", other),
+        };
+        assert_eq!(info.provenance, alice_pubkey);
+        assert_eq!(info.cap_grant, CapGrant::RemoteAgent(new_grant.cap_grant));
+        assert_eq!(info.action_hash, Some(new_grant_hash));
+    }
+
     // MAYBE: [ B-03669 ] can move this to an integration test (may need to switch to using a RibosomeStore)
     #[tokio::test(flavor = "multi_thread")]
     async fn ribosome_authorized_call() -> anyhow::Result<()> {
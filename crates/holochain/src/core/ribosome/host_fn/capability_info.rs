@@ -1,14 +1,69 @@
+use super::call_info::call_provenance_and_grant;
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
-use std::sync::Arc;
+use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
 
 /// return the access info used for this call
 /// also return who is originated the call (pubkey)
 pub fn capability_info(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    call_context: Arc<CallContext>,
     _input: (),
-) -> Result<(), RuntimeError> {
-    unimplemented!();
+) -> Result<CapabilityInfo, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            bindings: Permission::Allow,
+            ..
+        } => {
+            let (provenance, cap_grant) = call_provenance_and_grant(&call_context)?;
+            // The implicit author grant isn't committed, so only remote agent
+            // grants have an action to find.
+            let action_hash = match &cap_grant {
+                CapGrant::ChainAuthor(_) => None,
+                CapGrant::RemoteAgent(grant) => {
+                    let input = CapGrantsInput {
+                        tag: Some(grant.tag.clone()),
+                        include_revoked: false,
+                    };
+                    let call_context = call_context.clone();
+                    let grants = tokio_helper::block_forever_on(async move {
+                        call_context
+                            .host_context
+                            .workspace()
+                            .source_chain()
+                            .as_ref()
+                            .expect("Must have source chain if bindings access is given")
+                            .cap_grants(input)
+                            .await
+                    })
+                    .map_err(|source_chain_error| -> RuntimeError {
+                        wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+                    })?;
+                    grants
+                        .into_iter()
+                        .rev()
+                        .find(|info| &info.cap_grant == grant)
+                        .map(|info| info.action_hash)
+                }
+            };
+            Ok(CapabilityInfo {
+                provenance,
+                cap_grant,
+                action_hash,
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "capability_info".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
 }
@@ -27,6 +27,7 @@ pub fn get_link_details<'a>(
                             base_address,
                             link_type,
                             tag_prefix,
+                            ..
                         } = input;
 
                         let key = WireLinkKey {
@@ -30,6 +30,7 @@ pub fn get_links<'a>(
                             base_address,
                             link_type,
                             tag_prefix,
+                            filter,
                         } = input;
 
                         let key = WireLinkKey {
@@ -41,7 +42,13 @@ pub fn get_links<'a>(
                             &call_context.host_context.workspace(),
                             call_context.host_context.network().to_owned(),
                         )
-                        .dht_get_links(key, GetLinksOptions::default())
+                        .dht_get_links(
+                            key,
+                            GetLinksOptions {
+                                filter,
+                                ..Default::default()
+                            },
+                        )
                         .await?)
                     }))
                     // Limit concurrent calls to 10 as each call
@@ -245,6 +252,96 @@ pub mod slow_tests {
         assert_eq!(links[0].create_link_hash, action_hash);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_links_with_filter() {
+        holochain_trace::test_run().ok();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_pubkey,
+            bob_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Link).await;
+
+        let mut hashes = Vec::new();
+        for _ in 0..5 {
+            let action_hash: ActionHash = conductor.call(&alice, "create_link", ()).await;
+            hashes.push(action_hash);
+        }
+        let all: Vec<Link> = conductor
+            .call(&alice, "get_links_filtered", GetLinksFilter::new())
+            .await;
+        assert_eq!(
+            all.iter()
+                .map(|l| l.create_link_hash.clone())
+                .collect::<Vec<_>>(),
+            hashes
+        );
+
+        // Page through the links two at a time.
+        let filter = GetLinksFilter::new().limit(2);
+        let page: Vec<Link> = conductor
+            .call(&alice, "get_links_filtered", filter.clone())
+            .await;
+        assert_eq!(page, all[..2]);
+        let page: Vec<Link> = conductor
+            .call(
+                &alice,
+                "get_links_filtered",
+                filter.clone().cursor(page.last().unwrap().into()),
+            )
+            .await;
+        assert_eq!(page, all[2..4]);
+        let page: Vec<Link> = conductor
+            .call(
+                &alice,
+                "get_links_filtered",
+                filter.cursor(page.last().unwrap().into()),
+            )
+            .await;
+        assert_eq!(page, all[4..]);
+
+        // Newest first.
+        let page: Vec<Link> = conductor
+            .call(
+                &alice,
+                "get_links_filtered",
+                GetLinksFilter::new().descending().limit(1),
+            )
+            .await;
+        assert_eq!(page, all[4..]);
+
+        // Time range, exclusive at both ends.
+        let in_range: Vec<Link> = conductor
+            .call(
+                &alice,
+                "get_links_filtered",
+                GetLinksFilter::new()
+                    .after(all[0].timestamp)
+                    .before(all[4].timestamp),
+            )
+            .await;
+        assert_eq!(in_range, all[1..4]);
+
+        // Author.
+        let by_alice: Vec<Link> = conductor
+            .call(
+                &alice,
+                "get_links_filtered",
+                GetLinksFilter::new().author(alice_pubkey),
+            )
+            .await;
+        assert_eq!(by_alice, all);
+        let by_bob: Vec<Link> = conductor
+            .call(
+                &alice,
+                "get_links_filtered",
+                GetLinksFilter::new().author(bob_pubkey),
+            )
+            .await;
+        assert!(by_bob.is_empty());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn multi_get_links() {
         holochain_trace::test_run().ok();
@@ -1,12 +1,94 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostContext;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
-use std::sync::Arc;
+use crate::core::ribosome::ZomeCallHostAccess;
 use holochain_wasmer_host::prelude::*;
+use holochain_zome_types::Timestamp;
+use std::sync::Arc;
 
+/// Pause the zome call for the given duration.
+///
+/// Host functions are called synchronously from wasm, which can't be
+/// suspended part way through a call, so the zome call keeps its wasm
+/// instance and the blocking thread it runs on while it sleeps. The async
+/// runtime and other zome calls aren't held up. To bound what sleeping calls
+/// hold, only the conductor's configured number of calls may sleep at once,
+/// and others get an error. Sleeps longer than the conductor's configured
+/// maximum are refused, and a sleep is cut short with an error if the zome
+/// call expires first.
 pub fn sleep(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: core::time::Duration,
+    call_context: Arc<CallContext>,
+    input: core::time::Duration,
 ) -> Result<(), RuntimeError> {
-    unimplemented!()
+    match call_context.host_context() {
+        HostContext::ZomeCall(ZomeCallHostAccess {
+            call_zome_handle,
+            expires_at,
+            ..
+        }) => {
+            let max_sleep = call_zome_handle.max_sleep_duration();
+            if input > max_sleep {
+                return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                    "Cannot sleep for {:?} as the maximum is {:?}",
+                    input, max_sleep
+                )))
+                .into());
+            }
+            let until_expired = core::time::Duration::from_micros(
+                (expires_at.as_micros() - Timestamp::now().as_micros()).max(0) as u64,
+            );
+            let _permit = call_zome_handle.try_start_sleep().ok_or_else(|| {
+                wasm_error!(WasmErrorInner::Host(
+                    "Cannot sleep as the most zome calls allowed are already sleeping".to_string()
+                ))
+            })?;
+            // Zome calls run on a blocking thread, so it can simply be put to sleep.
+            std::thread::sleep(input.min(until_expired));
+            if input > until_expired {
+                Err(wasm_error!(WasmErrorInner::Host(
+                    "The zome call expired while sleeping".to_string()
+                ))
+                .into())
+            } else {
+                Ok(())
+            }
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "sleep".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::conductor::api::error::ConductorApiResult;
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn invoke_import_sleep_test() {
+        holochain_trace::test_run().ok();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::SysTime).await;
+
+        let start = std::time::Instant::now();
+        let _: () = conductor.call(&alice, "sleep", 100_u64).await;
+        assert!(start.elapsed() >= std::time::Duration::from_millis(100));
+
+        // Sleeping for longer than the conductor allows is an error.
+        let too_long = conductor.get_config().max_sleep_duration().as_millis() as u64 + 1;
+        let result: ConductorApiResult<()> =
+            conductor.call_fallible(&alice, "sleep", too_long).await;
+        assert!(result.is_err());
+    }
 }
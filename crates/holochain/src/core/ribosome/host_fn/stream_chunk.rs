@@ -0,0 +1,125 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostContext;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use crate::core::ribosome::ZomeCallHostAccess;
+use holochain_types::prelude::*;
+use holochain_util::tokio_helper;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+
+/// Send a chunk of output to the client of a streaming zome call.
+///
+/// Only a few chunks are buffered on their way to the client, so a zome
+/// function which yields chunks faster than the client receives them waits
+/// here until there is room.
+/// The zome call fails if the client cancels the stream or the zome call
+/// expires while waiting.
+pub fn stream_chunk(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: ExternIO,
+) -> Result<(), RuntimeError> {
+    match call_context.host_context() {
+        HostContext::ZomeCall(ZomeCallHostAccess {
+            stream: Some(stream),
+            expires_at,
+            ..
+        }) => {
+            let until_expired = core::time::Duration::from_micros(
+                (expires_at.as_micros() - Timestamp::now().as_micros()).max(0) as u64,
+            );
+            match tokio_helper::block_forever_on(tokio::time::timeout(
+                until_expired,
+                stream.send(input),
+            )) {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(_)) => Err(wasm_error!(WasmErrorInner::Host(
+                    "The zome call stream was cancelled".to_string()
+                ))
+                .into()),
+                Err(_) => Err(wasm_error!(WasmErrorInner::Host(
+                    "The zome call expired while waiting to stream a chunk".to_string()
+                ))
+                .into()),
+            }
+        }
+        HostContext::ZomeCall(_) => Err(wasm_error!(WasmErrorInner::Guest(
+            "Chunks can only be streamed from a streaming zome call".to_string()
+        ))
+        .into()),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "stream_chunk".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use crate::test_utils::new_zome_call;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn invoke_import_stream_chunk_test() {
+        holochain_trace::test_run().ok();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_cell,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::EmitSignal).await;
+
+        let call = new_zome_call(
+            &conductor.keystore(),
+            alice_cell.cell_id(),
+            "stream",
+            5_u32,
+            TestWasm::EmitSignal,
+        )
+        .await
+        .unwrap();
+        // A single slot makes the zome function wait for every chunk to be
+        // received before it can yield the next one.
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        let handle = conductor.raw_handle();
+        let call = tokio::spawn(async move { handle.call_zome_streaming(call, tx).await });
+
+        for i in 0..5_u32 {
+            let chunk: u32 = rx.recv().await.unwrap().decode().unwrap();
+            assert_eq!(i, chunk);
+        }
+        match call.await.unwrap().unwrap().unwrap() {
+            ZomeCallResponse::Ok(output) => assert_eq!(5_u32, output.decode::<u32>().unwrap()),
+            other => panic!("unexpected response {:?}", other),
+        }
+
+        // Dropping the receiver cancels the stream, which fails the zome call.
+        let call = new_zome_call(
+            &conductor.keystore(),
+            alice_cell.cell_id(),
+            "stream",
+            5_u32,
+            TestWasm::EmitSignal,
+        )
+        .await
+        .unwrap();
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        drop(rx);
+        let result = conductor.raw_handle().call_zome_streaming(call, tx).await;
+        assert!(matches!(result, Ok(Err(_))));
+
+        // Outside of a streaming zome call there is nowhere to stream to.
+        let result: crate::conductor::api::error::ConductorApiResult<u32> =
+            conductor.call_fallible(&alice, "stream", 1_u32).await;
+        assert!(result.is_err());
+    }
+}
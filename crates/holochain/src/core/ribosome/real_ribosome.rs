@@ -53,6 +53,7 @@ use crate::core::ribosome::host_fn::schedule::schedule;
 use crate::core::ribosome::host_fn::sign::sign;
 use crate::core::ribosome::host_fn::sign_ephemeral::sign_ephemeral;
 use crate::core::ribosome::host_fn::sleep::sleep;
+use crate::core::ribosome::host_fn::stream_chunk::stream_chunk;
 use crate::core::ribosome::host_fn::sys_time::sys_time;
 use crate::core::ribosome::host_fn::trace::trace;
 use crate::core::ribosome::host_fn::unblock_agent::unblock_agent;
@@ -604,6 +605,7 @@ impl RealRibosome {
             .with_host_function(&mut ns, "__hc__random_bytes_1", random_bytes)
             .with_host_function(&mut ns, "__hc__sys_time_1", sys_time)
             .with_host_function(&mut ns, "__hc__sleep_1", sleep)
+            .with_host_function(&mut ns, "__hc__stream_chunk_1", stream_chunk)
             .with_host_function(&mut ns, "__hc__capability_claims_1", capability_claims)
             .with_host_function(&mut ns, "__hc__capability_grants_1", capability_grants)
             .with_host_function(&mut ns, "__hc__capability_info_1", capability_info)
@@ -1122,6 +1124,7 @@ pub mod wasm_test {
                 "__hc__sign_1",
                 "__hc__sign_ephemeral_1",
                 "__hc__sleep_1",
+                "__hc__stream_chunk_1",
                 "__hc__sys_time_1",
                 "__hc__trace_1",
                 "__hc__unblock_agent_1",
@@ -5,6 +5,8 @@ use super::queue_consumer::TriggerSender;
 use super::ribosome::RibosomeT;
 use super::workflow::incoming_dht_ops_workflow::incoming_dht_ops_workflow;
 use super::workflow::sys_validation_workflow::SysValidationWorkspace;
+use crate::conductor::conductor::DpkiService;
+use crate::conductor::conductor::KeyState;
 use crate::conductor::entry_def_store::get_entry_def;
 use crate::conductor::space::Space;
 use crate::conductor::Conductor;
@@ -40,6 +42,12 @@ pub const MAX_ENTRY_SIZE: usize = ENTRY_SIZE_LIMIT;
 /// fast lookup so they should be small.
 pub const MAX_TAG_SIZE: usize = 1000;
 
+/// How long after an action was authored DPKI may still have no record of
+/// its author's key, while the key's registration spreads through the DPKI
+/// DHT. An action whose author DPKI hasn't heard of by then is rejected.
+pub const DPKI_KEY_REGISTRATION_TIMEOUT: std::time::Duration =
+    std::time::Duration::from_secs(60 * 60);
+
 /// Verify the signature for this action
 pub async fn verify_action_signature(sig: &Signature, action: &Action) -> SysValidationResult<()> {
     if action.author().verify_signature(sig, action).await {
@@ -52,10 +60,34 @@ pub async fn verify_action_signature(sig: &Signature, action: &Action) -> SysVal
 }
 
 /// Verify the author key was valid at the time
-/// of signing with dpki
-/// TODO: This is just a stub until we have dpki.
-pub async fn author_key_is_valid(_author: &AgentPubKey) -> SysValidationResult<()> {
-    Ok(())
+/// of signing with dpki.
+/// Actions in the DPKI DNA itself are exempt, since DPKI
+/// cannot vouch for the keys which maintain it.
+pub async fn author_key_is_valid(
+    action: &Action,
+    dna_hash: &DnaHash,
+    dpki: Option<Arc<dyn DpkiService>>,
+) -> SysValidationResult<()> {
+    let dpki = match dpki {
+        Some(dpki) => dpki,
+        None => return Ok(()),
+    };
+    if dpki.cell_ids().iter().any(|c| c.dna_hash() == dna_hash) {
+        return Ok(());
+    }
+    let author = action.author().clone();
+    let timestamp = action.timestamp();
+    match dpki.key_state(author.clone(), timestamp).await? {
+        KeyState::Valid => Ok(()),
+        KeyState::NotFound
+            if Timestamp::now() > timestamp.saturating_add(&DPKI_KEY_REGISTRATION_TIMEOUT) =>
+        {
+            Err(ValidationOutcome::DpkiAgentUnregistered(author, timestamp).into())
+        }
+        // The key's registration may just not have reached DPKI yet
+        KeyState::NotFound => Err(ValidationOutcome::DpkiAgentMissing(author, timestamp).into()),
+        KeyState::Invalidated => Err(ValidationOutcome::DpkiAgentInvalid(author, timestamp).into()),
+    }
 }
 
 /// Verify the countersigning session contains the specified action.
@@ -97,9 +129,29 @@ pub fn check_countersigning_session_data_contains_action(
 pub async fn check_countersigning_preflight_response_signature(
     preflight_response: &PreflightResponse,
 ) -> SysValidationResult<()> {
-    let signature_is_valid = preflight_response
-        .request()
-        .signing_agents
+    check_preflight_response_signature_for_agents(
+        preflight_response,
+        &preflight_response.request().signing_agents,
+    )
+    .await
+}
+
+/// Verify that the signature on an optional signer's preflight request is valid.
+pub async fn check_countersigning_optional_preflight_response_signature(
+    preflight_response: &PreflightResponse,
+) -> SysValidationResult<()> {
+    check_preflight_response_signature_for_agents(
+        preflight_response,
+        &preflight_response.request().optional_signing_agents,
+    )
+    .await
+}
+
+async fn check_preflight_response_signature_for_agents(
+    preflight_response: &PreflightResponse,
+    agents: &CounterSigningAgents,
+) -> SysValidationResult<()> {
+    let signature_is_valid = agents
         .get(*preflight_response.agent_state().agent_index() as usize)
         .ok_or_else(|| {
             SysValidationError::ValidationOutcome(ValidationOutcome::PreflightResponseSignature(
@@ -151,8 +203,26 @@ pub async fn check_countersigning_session_data(
             check_countersigning_preflight_response_signature(&preflight_response).await
         })
         .collect();
+    let optional_tasks: Vec<_> = session_data
+        .optional_responses()
+        .iter()
+        .map(|(response, signature)| async move {
+            let preflight_response = PreflightResponse::try_new(
+                session_data.preflight_request().clone(),
+                response.clone(),
+                signature.clone(),
+            )?;
+            check_countersigning_optional_preflight_response_signature(&preflight_response).await
+        })
+        .collect();
 
-    let results: Vec<SysValidationResult<()>> = futures::future::join_all(tasks).await;
+    let (results, optional_results) = futures::future::join(
+        futures::future::join_all(tasks),
+        futures::future::join_all(optional_tasks),
+    )
+    .await;
+    let results: Vec<SysValidationResult<()>> =
+        results.into_iter().chain(optional_results).collect();
     let results: SysValidationResult<()> = results.into_iter().collect();
     match results {
         Ok(_) => Ok(()),
@@ -273,6 +343,23 @@ pub fn check_agent_validation_pkg_predecessor(
     }
 }
 
+/// Check that the previous action didn't close the chain, since nothing may
+/// be written to a chain after a [`CloseChain`]
+pub fn check_prev_action_not_close_chain(
+    action: &Action,
+    prev_action: &Action,
+) -> SysValidationResult<()> {
+    if let Action::CloseChain(_) = prev_action {
+        Err(PrevActionError::InvalidSuccessor(
+            "No action may follow a CloseChain".to_string(),
+            Box::new((prev_action.clone(), action.clone())),
+        ))
+        .map_err(|e| ValidationOutcome::from(e).into())
+    } else {
+        Ok(())
+    }
+}
+
 /// Check that the author didn't change between actions
 pub fn check_prev_author(action: &Action, prev_action: &Action) -> SysValidationResult<()> {
     // Agent updates will be valid when DPKI support lands
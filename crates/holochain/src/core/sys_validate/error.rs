@@ -2,6 +2,7 @@ use std::convert::TryFrom;
 
 use super::SourceChainError;
 use crate::conductor::api::error::ConductorApiError;
+use crate::conductor::conductor::DpkiServiceError;
 use crate::conductor::entry_def_store::error::EntryDefStoreError;
 use crate::core::validation::OutcomeOrError;
 use crate::core::workflow::error::WorkflowError;
@@ -51,6 +52,8 @@ pub enum SysValidationError {
     ConductorApiError(#[from] Box<ConductorApiError>),
     #[error("Expected Entry-based Action, but got: {0:?}")]
     NonEntryAction(Action),
+    #[error(transparent)]
+    DpkiServiceError(#[from] DpkiServiceError),
 }
 
 impl From<CounterSigningError> for SysValidationError {
@@ -107,6 +110,14 @@ pub enum ValidationOutcome {
     CounterSigningError(#[from] CounterSigningError),
     #[error("The dependency {0:?} was not found on the DHT")]
     DepMissingFromDht(AnyDhtHash),
+    #[error("DPKI reports that the author key {0:?} was not valid at {1:?}")]
+    DpkiAgentInvalid(AgentPubKey, Timestamp),
+    #[error("DPKI has no record of the author key {0:?} at {1:?} yet")]
+    DpkiAgentMissing(AgentPubKey, Timestamp),
+    #[error("DPKI still has no record of the author key {0:?} at {1:?}, long after it was used")]
+    DpkiAgentUnregistered(AgentPubKey, Timestamp),
+    #[error("DPKI is still starting up, so author keys can't be checked yet")]
+    DpkiNotReady,
     #[error("The app entry def {0:?} entry def id was out of range")]
     EntryDefId(AppEntryDef),
     #[error("The entry has a different hash to the action's entry hash")]
@@ -140,6 +140,38 @@ async fn check_valid_if_dna_test() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn author_key_unknown_to_dpki_is_rejected_once_registration_timed_out() {
+    use crate::conductor::conductor::MockDpkiService;
+    use futures::FutureExt;
+
+    let mut dpki = MockDpkiService::new();
+    dpki.expect_key_state()
+        .returning(|_, _| async move { Ok(KeyState::NotFound) }.boxed());
+    dpki.expect_cell_ids()
+        .return_const(std::collections::HashSet::new());
+    let dpki: Arc<dyn DpkiService> = Arc::new(dpki);
+    let dna_hash = fixt!(DnaHash);
+
+    // A recent action may be from a key whose registration is still on its way
+    let mut action = fixt!(CreateLink);
+    action.timestamp = Timestamp::now();
+    assert_matches!(
+        author_key_is_valid(&action.clone().into(), &dna_hash, Some(dpki.clone())).await,
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::DpkiAgentMissing(_, _)
+        ))
+    );
+
+    action.timestamp = (Timestamp::now() - DPKI_KEY_REGISTRATION_TIMEOUT * 2).unwrap();
+    assert_matches!(
+        author_key_is_valid(&action.into(), &dna_hash, Some(dpki)).await,
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::DpkiAgentUnregistered(_, _)
+        ))
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn check_previous_timestamp() {
     let mut action = fixt!(CreateLink);
@@ -200,6 +232,24 @@ async fn check_previous_seq() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn check_previous_action_not_close_chain() {
+    let action: Action = fixt!(CreateLink).into();
+    let open_chain: Action = fixt!(OpenChain).into();
+    let close_chain: Action = fixt!(CloseChain).into();
+
+    assert_matches!(
+        check_prev_action_not_close_chain(&action, &open_chain),
+        Ok(())
+    );
+    assert_matches!(
+        check_prev_action_not_close_chain(&action, &close_chain),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::PrevActionError(PrevActionError::InvalidSuccessor(_, _)),
+        ))
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn check_entry_type_test() {
     let entry_fixt = EntryFixturator::new(Predictable);
@@ -5,6 +5,8 @@ use std::sync::Arc;
 
 use super::error::WorkflowResult;
 use super::sys_validation_workflow::validation_query;
+use crate::conductor::conductor::CellStatus;
+use crate::conductor::metrics::WORKFLOW_QUEUE_DEPTH;
 use crate::conductor::ConductorHandle;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
@@ -13,6 +15,7 @@ use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomesToInvoke;
+use crate::core::validation::OutcomeOrError;
 use error::AppValidationResult;
 pub use error::*;
 use futures::stream::StreamExt;
@@ -62,14 +65,16 @@ pub async fn app_validation_workflow(
     network: HolochainP2pDna,
     dht_query_cache: DhtDbQueryCache,
 ) -> WorkflowResult<WorkComplete> {
-    let complete = app_validation_workflow_inner(
-        dna_hash,
-        workspace,
-        conductor_handle,
+    let (complete, rejected) = app_validation_workflow_inner(
+        dna_hash.clone(),
+        workspace.clone(),
+        conductor_handle.clone(),
         &network,
         dht_query_cache,
     )
     .await?;
+
+    issue_warrants(&dna_hash, &conductor_handle, &workspace, &network, rejected).await?;
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
     // trigger other workflows
@@ -84,10 +89,13 @@ async fn app_validation_workflow_inner(
     conductor: ConductorHandle,
     network: &HolochainP2pDna,
     dht_query_cache: DhtDbQueryCache,
-) -> WorkflowResult<WorkComplete> {
+) -> WorkflowResult<(WorkComplete, Vec<(SignedAction, OpBasis, String)>)> {
     let db = workspace.dht_db.clone().into();
     let sorted_ops = validation_query::get_ops_to_app_validate(&db).await?;
     let start_len = sorted_ops.len();
+    WORKFLOW_QUEUE_DEPTH
+        .with_label_values(&["app_validation", &dna_hash.to_string()])
+        .set(start_len as i64);
     tracing::debug!("validating {} ops", start_len);
     let start = (start_len >= NUM_CONCURRENT_OPS).then(std::time::Instant::now);
     let saturated = start.is_some();
@@ -107,6 +115,7 @@ async fn app_validation_workflow_inner(
                 let action = op.action();
                 let dependency = get_dependency(op_type, &action);
                 let op_light = op.to_light();
+                let signed_action = SignedAction(action.clone(), op.signature().clone());
 
                 // If this is agent activity, track it for the cache.
                 let activity = matches!(op_type, DhtOpType::RegisterAgentActivity).then(|| {
@@ -125,7 +134,7 @@ async fn app_validation_workflow_inner(
                     }
                     Err(e) => Err(e),
                 };
-                (op_hash, dependency, op_light, r, activity)
+                (op_hash, dependency, op_light, signed_action, r, activity)
             }
         }
     });
@@ -156,6 +165,7 @@ async fn app_validation_workflow_inner(
         tokio_stream::wrappers::ReceiverStream::new(rx).ready_chunks(NUM_CONCURRENT_OPS * 100);
 
     let mut total = 0;
+    let mut rejected_actions = Vec::new();
     let mut round_time = start.is_some().then(std::time::Instant::now);
     // Pull in a chunk of results.
     while let Some(chunk) = iter.next().await {
@@ -163,15 +173,16 @@ async fn app_validation_workflow_inner(
             "Committing {} ops",
             chunk.iter().map(|c| c.len()).sum::<usize>()
         );
-        let (t, a, r, activity) = workspace
+        let (t, a, r, activity, rejected) = workspace
             .dht_db
             .async_commit(move |txn| {
                 let mut total = 0;
                 let mut awaiting = 0;
                 let mut rejected = 0;
                 let mut agent_activity = Vec::new();
+                let mut rejected_actions = Vec::new();
                 for outcome in chunk.into_iter().flatten() {
-                    let (op_hash, dependency, op_light, outcome, activity) = outcome;
+                    let (op_hash, dependency, op_light, signed_action, outcome, activity) = outcome;
                     // Get the outcome or return the error
                     let outcome = outcome.or_else(|outcome_or_err| outcome_or_err.try_into())?;
 
@@ -203,7 +214,7 @@ async fn app_validation_workflow_inner(
                             let status = ValidationLimboStatus::AwaitingAppDeps(deps);
                             put_validation_limbo(txn, &op_hash, status)?;
                         }
-                        Outcome::Rejected(_) => {
+                        Outcome::Rejected(reason) => {
                             rejected += 1;
                             tracing::info!(
                                 "Received invalid op. The op author will be blocked.\nOp: {:?}",
@@ -214,10 +225,15 @@ async fn app_validation_workflow_inner(
                             } else {
                                 put_integration_limbo(txn, &op_hash, ValidationStatus::Rejected)?;
                             }
+                            rejected_actions.push((
+                                signed_action,
+                                op_light.dht_basis().clone(),
+                                reason,
+                            ));
                         }
                     }
                 }
-                WorkflowResult::Ok((total, awaiting, rejected, agent_activity))
+                WorkflowResult::Ok((total, awaiting, rejected, agent_activity, rejected_actions))
             })
             .await?;
 
@@ -237,6 +253,7 @@ async fn app_validation_workflow_inner(
             }
         }
         total += t;
+        rejected_actions.extend(rejected);
         if let (Some(start), Some(round_time)) = (start, &mut round_time) {
             let round_el = round_time.elapsed();
             *round_time = std::time::Instant::now();
@@ -259,11 +276,128 @@ async fn app_validation_workflow_inner(
     }
     jh.await?;
     tracing::debug!("accepted {} ops", total);
-    Ok(if saturated {
+    let complete = if saturated {
         WorkComplete::Incomplete
     } else {
         WorkComplete::Complete
-    })
+    };
+    Ok((complete, rejected_actions))
+}
+
+/// Sign, store and publish a warrant for each rejected action, issued by
+/// the agent of ours which validated it, i.e. whose storage arc holds the
+/// basis of the rejected op. Actions authored by our own agents are not warranted.
+async fn issue_warrants(
+    dna_hash: &DnaHash,
+    conductor: &ConductorHandle,
+    workspace: &AppValidationWorkspace,
+    network: &HolochainP2pDna,
+    rejected: Vec<(SignedAction, OpBasis, String)>,
+) -> WorkflowResult<()> {
+    if rejected.is_empty() {
+        return Ok(());
+    }
+    let mut validators = Vec::new();
+    for cell_id in conductor
+        .running_cell_ids(Some(CellStatus::Joined))
+        .into_iter()
+        .filter(|id| id.dna_hash() == dna_hash)
+    {
+        let arc = conductor
+            .get_agent_infos(Some(cell_id.clone()))
+            .await
+            .ok()
+            .and_then(|infos| infos.first().map(|info| info.storage_arc));
+        validators.push((cell_id.agent_pubkey().clone(), arc));
+    }
+
+    // Several ops of the same action may have been rejected,
+    // but one warrant per action is enough.
+    let mut warranted = HashSet::new();
+    for (action, basis, reason) in rejected {
+        if validators
+            .iter()
+            .any(|(agent, _)| agent == action.action().author())
+        {
+            continue;
+        }
+        let issuer = match validators.iter().find(|(_, arc)| {
+            arc.map(|arc| arc.contains(basis.get_loc()))
+                .unwrap_or(false)
+        }) {
+            Some((issuer, _)) => issuer.clone(),
+            None => continue,
+        };
+        if !warranted.insert(ActionHash::with_data_sync(action.action())) {
+            continue;
+        }
+        let warrant = Warrant::new(action, reason, issuer, Timestamp::now());
+        let warrant = match sign_warrant(warrant, &workspace.keystore).await {
+            Ok(warrant) => warrant,
+            Err(e) => {
+                info!(failed_to_sign_warrant = ?e);
+                continue;
+            }
+        };
+        workspace
+            .dht_db
+            .async_commit({
+                let warrant = warrant.clone();
+                move |txn| insert_warrant(txn, warrant)
+            })
+            .await?;
+        if let Err(e) = network.publish_warrant(warrant).await {
+            // The warrant is still held locally, and other validators
+            // will warrant the action when they reject it.
+            info!(failed_to_publish_warrant = ?e);
+        }
+    }
+    Ok(())
+}
+
+/// Validate the action a warrant is against for ourselves, so that its
+/// author is only ever blocked for an action we agree is invalid, rather
+/// than on the word of the issuer.
+///
+/// Returns `true` if our own app validation rejects any op of the action.
+/// If the action or the dependencies of its ops can't be found, the
+/// warrant can't be confirmed and `false` is returned.
+pub async fn confirm_warrant(
+    dna_hash: Arc<DnaHash>,
+    warrant: &Warrant,
+    conductor: &ConductorHandle,
+    workspace: &AppValidationWorkspace,
+    network: &HolochainP2pDna,
+) -> bool {
+    let action_hash = ActionHash::with_data_sync(warrant.action.action());
+    let cascade = workspace.full_cascade(network.clone());
+    let record = match cascade
+        .retrieve(action_hash.into(), NetworkGetOptions::default())
+        .await
+    {
+        Ok(Some((record, _))) if record.action() == warrant.action.action() => record,
+        Ok(_) => return false,
+        Err(e) => {
+            debug!(?e, "Could not fetch the warranted action");
+            return false;
+        }
+    };
+    for op_type in action_to_op_types(record.action()) {
+        let outcome = match record_to_op(record.clone(), op_type, &cascade).await {
+            Ok((op, _)) => {
+                validate_op_outer(dna_hash.clone(), &op, conductor, workspace, network).await
+            }
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(Outcome::Rejected(_)) | Err(OutcomeOrError::Outcome(Outcome::Rejected(_))) => {
+                return true
+            }
+            Ok(_) | Err(OutcomeOrError::Outcome(_)) => (),
+            Err(OutcomeOrError::Err(e)) => debug!(?e, ?op_type, "Could not check a warranted op"),
+        }
+    }
+    false
 }
 
 pub async fn record_to_op(
@@ -17,6 +17,7 @@ use holochain_state::prelude::fresh_reader_test;
 use holochain_state::prelude::from_blob;
 use holochain_state::prelude::StateQueryResult;
 use holochain_types::inline_zome::InlineZomeSet;
+use holochain_types::prelude::block::BlockTargetId;
 use holochain_types::prelude::*;
 use holochain_wasm_test_utils::TestWasm;
 
@@ -214,6 +215,135 @@ async fn test_private_entries_are_passed_to_validation_only_when_authored_with_f
     )
 }
 
+/// A validator which rejects an action issues a warrant against its author.
+/// Other agents receive the warrant, and only block the author and hold the
+/// warrant if their own validation rejects the action too.
+#[tokio::test(flavor = "multi_thread")]
+async fn rejected_actions_are_warranted() {
+    holochain_trace::test_run().ok();
+
+    let unit_entry_def = EntryDef::from_id("unit");
+    let zomes = |coordinator_uuid: &str| {
+        InlineZomeSet::new_single(
+            "integrity",
+            "coordinator",
+            "a",
+            coordinator_uuid,
+            vec![unit_entry_def.clone()],
+            0,
+        )
+        .function("coordinator", "create", |api, ()| {
+            let entry = Entry::app(().try_into().unwrap()).unwrap();
+            let hash = api.create(CreateInput::new(
+                InlineZomeSet::get_entry_location(&api, EntryDefIndex(0)),
+                EntryVisibility::Public,
+                entry,
+                ChainTopOrdering::default(),
+            ))?;
+            Ok(hash)
+        })
+        .function("coordinator", "activity", |api, agent: AgentPubKey| {
+            Ok(api.get_agent_activity(GetAgentActivityInput::new(
+                agent,
+                ChainQueryFilter::new(),
+                ActivityRequest::Full,
+            ))?)
+        })
+    };
+    // Only bob rejects app entries. Validation callbacks don't contribute to
+    // the DNA hash, so everyone is still in the same network.
+    let zomes_that_reject = zomes("b").function("integrity", "validate", |_api, op: Op| match op {
+        Op::StoreEntry(StoreEntry { action, .. })
+            if action.hashed.content.app_entry_def().is_some() =>
+        {
+            Ok(ValidateResult::Invalid("Entry defs are bad".into()))
+        }
+        _ => Ok(ValidateResult::Valid),
+    });
+
+    let (dna_file, _, _) = SweetDnaFile::from_inline_zomes("seed".into(), zomes("b")).await;
+    let (dna_that_rejects, _, _) =
+        SweetDnaFile::from_inline_zomes("seed".into(), zomes_that_reject).await;
+    assert_eq!(dna_file.dna_hash(), dna_that_rejects.dna_hash());
+
+    let mut conductors = SweetConductorBatch::from_standard_config(4).await;
+    let (alice,) = conductors[0]
+        .setup_app("test_app", &[dna_file.clone()])
+        .await
+        .unwrap()
+        .into_tuple();
+    let (bob,) = conductors[1]
+        .setup_app("test_app", &[dna_that_rejects.clone()])
+        .await
+        .unwrap()
+        .into_tuple();
+    let (carol,) = conductors[2]
+        .setup_app("test_app", &[dna_that_rejects])
+        .await
+        .unwrap()
+        .into_tuple();
+    let (dave,) = conductors[3]
+        .setup_app("test_app", &[dna_file])
+        .await
+        .unwrap()
+        .into_tuple();
+    conductors.exchange_peer_info().await;
+
+    let action_hash: ActionHash = conductors[0]
+        .call(&alice.zome("coordinator"), "create", ())
+        .await;
+
+    let alice_block_target = BlockTargetId::Cell(alice.cell_id().clone());
+    crate::assert_eq_retry_10s!(
+        conductors[2]
+            .spaces
+            .is_blocked(alice_block_target.clone(), Timestamp::now())
+            .await
+            .unwrap(),
+        true
+    );
+
+    // Carol agrees the action is invalid, so she holds bob's warrant.
+    let bob_warrant = || async {
+        let activity: AgentActivity = conductors[2]
+            .call(
+                &carol.zome("coordinator"),
+                "activity",
+                alice.agent_pubkey().clone(),
+            )
+            .await;
+        activity
+            .warrants
+            .into_iter()
+            .map(|w| w.warrant().clone())
+            .find(|w| w.issuer == *bob.agent_pubkey())
+    };
+    crate::assert_eq_retry_10s!(bob_warrant().await.is_some(), true);
+    let warrant = bob_warrant().await.unwrap();
+    assert_eq!(warrant.warrantee(), alice.agent_pubkey());
+    assert_eq!(warrant.reason, "Entry defs are bad");
+    assert_eq!(
+        ActionHash::with_data_sync(warrant.action.action()),
+        action_hash
+    );
+
+    // Dave's own validation accepts the action, so the warrants of bob and
+    // carol aren't enough for him to block alice.
+    consistency_10s([&alice, &bob, &carol, &dave]).await;
+    assert!(!conductors[3]
+        .spaces
+        .is_blocked(alice_block_target.clone(), Timestamp::now())
+        .await
+        .unwrap());
+
+    // Alice never blocks herself, even though she holds the warrant.
+    assert!(!conductors[0]
+        .spaces
+        .is_blocked(alice_block_target, Timestamp::now())
+        .await
+        .unwrap());
+}
+
 const SELECT: &'static str = "SELECT count(hash) FROM DhtOp WHERE";
 
 // These are the expected invalid ops
@@ -5,6 +5,7 @@ use super::error::WorkflowResult;
 use super::sys_validation_workflow::sys_validate_record;
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::api::CellConductorApiT;
+use crate::conductor::conductor::record_committed_bytes;
 use crate::conductor::interface::SignalBroadcaster;
 use crate::conductor::ConductorHandle;
 use crate::core::queue_consumer::TriggerSender;
@@ -13,6 +14,7 @@ use crate::core::ribosome::guest_callback::post_commit::send_post_commit;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomeCallHostAccess;
 use crate::core::ribosome::ZomeCallInvocation;
+use crate::core::ribosome::ZomeCallStream;
 use crate::core::workflow::error::WorkflowError;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::HolochainP2pDna;
@@ -37,6 +39,7 @@ pub struct CallZomeWorkflowArgs<RibosomeT> {
     pub conductor_handle: ConductorHandle,
     pub is_root_zome_call: bool,
     pub cell_id: CellId,
+    pub stream: ZomeCallStream,
 }
 
 #[instrument(skip(
@@ -73,59 +76,104 @@ where
 
     // commit the workspace
     if should_write {
-        let is_empty = workspace.source_chain().is_empty()?;
-        let countersigning_op = workspace.source_chain().countersigning_op()?;
-        match HostFnWorkspace::from(workspace.clone())
-            .flush(&network)
-            .await
+        if let Some(error_response) = flush_zome_call_workspace(
+            workspace,
+            network,
+            keystore,
+            conductor_handle,
+            coordinator_zome.into_iter().collect(),
+            trigger_publish_dht_ops,
+            trigger_integrate_dht_ops,
+        )
+        .await?
         {
-            Ok(flushed_actions) => {
-                // Q: what is the purpose of checking for an empty chain? When would this ever happen? The chain should
-                //    be genesis'd by now, right?
-                if !is_empty {
-                    match countersigning_op {
-                        Some(op) => {
-                            if let Err(error_response) =
-                                super::countersigning_workflow::countersigning_publish(
-                                    &network,
-                                    op,
-                                    (*workspace.author().ok_or_else(|| {
-                                        WorkflowError::Other("author required".into())
-                                    })?)
-                                    .clone(),
-                                )
-                                .await
-                            {
-                                return Ok(Ok(error_response));
-                            }
-                        }
-                        None => {
-                            trigger_publish_dht_ops.trigger(&"trigger_publish_dht_ops");
-                            trigger_integrate_dht_ops.trigger(&"trigger_integrate_dht_ops");
-                        }
-                    }
-                }
+            return Ok(Ok(error_response));
+        }
+    };
+
+    Ok(result)
+}
+
+/// The size of data once it is serialized to be written to the source chain.
+fn encoded_len<T: serde::Serialize + std::fmt::Debug>(data: &T) -> u64 {
+    holochain_serialized_bytes::encode(data).map_or(0, |bytes| bytes.len() as u64)
+}
+
+/// Write everything the root zome call, or batch of zome calls, added to the
+/// workspace to the source chain, then publish it and send `post_commit` to
+/// the given coordinator zomes.
+///
+/// The bytes written are counted towards the zome calls being measured for
+/// the rate limits, if any.
+///
+/// Returns the response to give in place of the zome call's own if the
+/// countersigning session it started couldn't be published.
+pub(crate) async fn flush_zome_call_workspace(
+    workspace: SourceChainWorkspace,
+    network: HolochainP2pDna,
+    keystore: MetaLairClient,
+    conductor_handle: ConductorHandle,
+    coordinator_zomes: Vec<CoordinatorZome>,
+    trigger_publish_dht_ops: TriggerSender,
+    trigger_integrate_dht_ops: TriggerSender,
+) -> WorkflowResult<Option<ZomeCallResponse>> {
+    let is_empty = workspace.source_chain().is_empty()?;
+    let countersigning_op = workspace.source_chain().countersigning_op()?;
+    let committed_bytes = workspace
+        .source_chain()
+        .scratch()
+        .apply(|scratch| {
+            let actions: u64 = scratch.actions().map(encoded_len).sum();
+            let entries: u64 = scratch
+                .entries()
+                .map(|(_, entry)| encoded_len(entry.as_ref()))
+                .sum();
+            actions + entries
+        })
+        .map_err(SourceChainError::from)?;
+    let flushed_actions = HostFnWorkspace::from(workspace.clone())
+        .flush(&network)
+        .await?;
+    record_committed_bytes(committed_bytes);
 
-                // Only send post commit if this is a coordinator zome.
-                if let Some(coordinator_zome) = coordinator_zome {
-                    send_post_commit(
-                        conductor_handle,
-                        workspace,
-                        network,
-                        keystore,
-                        flushed_actions,
-                        vec![coordinator_zome],
-                    )
-                    .await?;
+    // Q: what is the purpose of checking for an empty chain? When would this ever happen? The chain should
+    //    be genesis'd by now, right?
+    if !is_empty {
+        match countersigning_op {
+            Some(op) => {
+                if let Err(error_response) = super::countersigning_workflow::countersigning_publish(
+                    &network,
+                    op,
+                    (*workspace
+                        .author()
+                        .ok_or_else(|| WorkflowError::Other("author required".into()))?)
+                    .clone(),
+                )
+                .await
+                {
+                    return Ok(Some(error_response));
                 }
             }
-            err => {
-                err?;
+            None => {
+                trigger_publish_dht_ops.trigger(&"trigger_publish_dht_ops");
+                trigger_integrate_dht_ops.trigger(&"trigger_integrate_dht_ops");
             }
         }
-    };
+    }
 
-    Ok(result)
+    // Only send post commit to coordinator zomes.
+    if !coordinator_zomes.is_empty() {
+        send_post_commit(
+            conductor_handle,
+            workspace,
+            network,
+            keystore,
+            flushed_actions,
+            coordinator_zomes,
+        )
+        .await?;
+    }
+    Ok(None)
 }
 
 async fn call_zome_workflow_inner<Ribosome>(
@@ -143,6 +191,7 @@ where
         signal_tx,
         conductor_handle,
         cell_id,
+        stream,
         ..
     } = args;
 
@@ -156,6 +205,8 @@ where
         network.clone(),
         signal_tx,
         call_zome_handle,
+        invocation.expires_at,
+        stream,
     );
     let (ribosome, result) =
         call_zome_function_authorized(ribosome, host_access, invocation).await?;
@@ -1,18 +1,27 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use holo_hash::{ActionHash, AgentPubKey, DhtOpHash, EntryHash, OpBasis};
+use holochain_conductor_api::{EnzymaticCountersigningSessionInfo, EnzymaticCountersigningStatus};
 use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::MetaLairClient;
+use holochain_p2p::actor::GetActivityOptions;
 use holochain_p2p::{HolochainP2pDna, HolochainP2pDnaT};
 use holochain_state::integrate::authored_ops_to_dht_db_without_check;
 use holochain_state::mutations;
 use holochain_state::prelude::{
-    current_countersigning_session, SourceChainResult, StateMutationResult, Store,
+    chain_head_db, current_countersigning_session, get_countersigning_sessions, SourceChainResult,
+    StateMutationResult, Store,
 };
+use holochain_types::activity::ChainItems;
 use holochain_types::dht_op::DhtOp;
 use holochain_types::signal::{Signal, SystemSignal};
 use holochain_zome_types::Timestamp;
-use holochain_zome_types::{Entry, SignedAction, ZomeCallResponse};
+use holochain_zome_types::{
+    ChainQueryFilter, ChainQueryFilterRange, CounterSigningSessionData, Entry, SignedAction,
+    ZomeCallResponse,
+};
 use kitsune_p2p_types::tx2::tx2_utils::Share;
 use rusqlite::{named_params, Transaction};
 
@@ -25,9 +34,21 @@ use holochain_p2p::event::CountersigningSessionNegotiationMessage;
 
 use super::{error::WorkflowResult, incoming_dht_ops_workflow::incoming_dht_ops_workflow};
 
+/// How long to wait before checking again on interrupted countersigning
+/// sessions whose outcome couldn't be decided.
+pub(crate) const RESUME_SESSIONS_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long an enzyme keeps reporting the outcome of a session after it expires.
+const FINISHED_SESSION_RETENTION: Duration = Duration::from_secs(60 * 10);
+
 #[derive(Clone)]
 /// A cheaply clonable, thread safe and in-memory store for
 /// active countersigning sessions.
+///
+/// This holds the sessions this conductor is an authority or an enzyme for.
+/// Sessions that local agents are signing are persisted in the
+/// authored database so they can be resumed after a restart,
+/// see [`resume_countersigning_sessions`].
 pub struct CountersigningWorkspace {
     inner: Share<CountersigningWorkspaceInner>,
 }
@@ -36,6 +57,9 @@ pub struct CountersigningWorkspace {
 /// Pending countersigning sessions.
 pub struct CountersigningWorkspaceInner {
     pending: HashMap<EntryHash, Session>,
+    /// The outcome of enzymatic sessions that are no longer pending,
+    /// keyed by entry hash along with the enzyme.
+    finished: HashMap<EntryHash, (AgentPubKey, EnzymaticCountersigningSessionInfo)>,
 }
 
 #[derive(Default)]
@@ -47,6 +71,10 @@ struct Session {
     /// When this session expires.
     /// If this is none the session is empty.
     expires: Option<Timestamp>,
+    /// Every agent that has to sign for this session to be complete.
+    signers: Vec<AgentPubKey>,
+    /// The agent coordinating this session if it is enzymatic.
+    enzyme: Option<AgentPubKey>,
 }
 
 /// New incoming DhtOps for a countersigning session.
@@ -68,6 +96,16 @@ pub(crate) fn incoming_countersigning(
             // Must have a counter sign entry type.
             if let Entry::CounterSign(session_data, _) = entry.as_ref() {
                 let entry_hash = EntryHash::with_data_sync(&**entry);
+                // The session must have all its required responses and
+                // enough optional responses to ever be complete.
+                if let Err(e) = session_data.check_integrity() {
+                    tracing::warn!(
+                        ?entry_hash,
+                        "Ignoring invalid countersigning session: {}",
+                        e
+                    );
+                    continue;
+                }
                 // Get the required actions for this session.
                 let weight = weigh_placeholder();
                 let action_set = session_data.build_action_set(entry_hash, weight)?;
@@ -81,13 +119,29 @@ pub(crate) fn incoming_countersigning(
                     // Hash the required actions.
                     let required_actions: Vec<_> = action_set
                         .into_iter()
-                        .map(|h| ActionHash::with_data_sync(&h))
+                        .map(|h| (h.author().clone(), ActionHash::with_data_sync(&h)))
                         .collect();
+                    let enzyme = op.enzymatic_countersigning_enzyme().cloned();
 
                     // Check if already timed out.
-                    if holochain_zome_types::Timestamp::now() < expires {
+                    let now = holochain_zome_types::Timestamp::now();
+                    if now < expires {
+                        let is_enzymatic = enzyme.is_some();
                         // Put this op in the pending map.
-                        workspace.put(entry_hash, hash, op, required_actions, expires);
+                        let is_new_session =
+                            workspace.put(entry_hash, hash, op, required_actions, enzyme, expires);
+                        // An enzyme has to tell the signers if the session times out,
+                        // so make sure the workflow runs once it has expired.
+                        if is_new_session && is_enzymatic {
+                            let trigger = trigger.clone();
+                            let timeout = Duration::from_micros(
+                                (expires.as_micros() - now.as_micros()).max(0) as u64,
+                            );
+                            tokio::spawn(async move {
+                                tokio::time::sleep(timeout).await;
+                                trigger.trigger(&"enzymatic_countersigning_timeout");
+                            });
+                        }
                         // We have new ops so we should trigger the workflow.
                         should_trigger = true;
                     }
@@ -105,11 +159,49 @@ pub(crate) fn incoming_countersigning(
 
 /// Countersigning workflow that checks for complete sessions and
 /// pushes the complete ops to validation then messages the signers.
+///
+/// Signers of enzymatic sessions that timed out are told to abandon them.
 pub(crate) async fn countersigning_workflow(
     space: Space,
     network: impl HolochainP2pDnaT + Send + Sync,
+    keystore: MetaLairClient,
     sys_validation_trigger: TriggerSender,
 ) -> WorkflowResult<WorkComplete> {
+    // Notify the signers of any enzymatic sessions that timed out.
+    for (entry_hash, enzyme, agents) in space.countersigning_workspace.take_timed_out_sessions() {
+        // The enzyme signs the notice so signers can ignore it from anyone else.
+        let signature = match enzyme
+            .sign_raw(&keystore, entry_hash.get_raw_39().into())
+            .await
+        {
+            Ok(signature) => signature,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to sign countersigning session timeout because of {:?}",
+                    e
+                );
+                continue;
+            }
+        };
+        if let Err(e) = network
+            .countersigning_session_negotiation(
+                agents,
+                CountersigningSessionNegotiationMessage::EnzymeSessionTimeout {
+                    entry_hash,
+                    signature,
+                },
+            )
+            .await
+        {
+            // The signers will also find out the session was abandoned
+            // when they next resume their interrupted sessions.
+            tracing::info!(
+                "Failed to notify agents: countersigning session timed out because of {:?}",
+                e
+            );
+        }
+    }
+
     // Get any complete sessions.
     let complete_sessions = space.countersigning_workspace.get_complete_sessions();
     let mut notify_agents = Vec::with_capacity(complete_sessions.len());
@@ -160,6 +252,7 @@ pub(crate) async fn countersigning_success(
     trigger: QueueTriggers,
     mut signal: SignalBroadcaster,
 ) -> WorkflowResult<()> {
+    let cell_id = CellId::new((*space.dna_hash).clone(), author.clone());
     let authored_db = space.authored_db;
     let dht_db = space.dht_db;
     let dht_db_cache = space.dht_query_cache;
@@ -262,6 +355,7 @@ pub(crate) async fn countersigning_success(
                         }) {
                             // All checks have passed so unlock the chain.
                             mutations::unlock_chain(txn, &author)?;
+                            mutations::delete_countersigning_session(txn, &author, &cs_entry_hash)?;
                             // Update ops to publish.
                             txn.execute("UPDATE DhtOp SET withhold_publish = NULL WHERE action_hash = :action_hash",
                             named_params! {
@@ -306,15 +400,278 @@ pub(crate) async fn countersigning_success(
             }
         }
         // Signal to the UI.
-        signal.send(Signal::System(SystemSignal::SuccessfulCountersigning(
+        signal.send(Signal::System(SystemSignal::SuccessfulCountersigning {
+            cell_id,
             entry_hash,
-        )))?;
+        }))?;
 
         publish_trigger.trigger(&"publish countersigning_success");
     }
     Ok(())
 }
 
+/// What became of a countersigning session that was interrupted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionOutcome {
+    /// Every other signer has published their action for the session.
+    Complete,
+    /// The session has expired and no other signer published their action.
+    Abandoned,
+    /// Not enough is known to decide yet.
+    Unknown,
+}
+
+impl SessionOutcome {
+    /// Decide the outcome from how many of the other signers' actions were found
+    /// at their agent activity authorities.
+    ///
+    /// Sessions are only abandoned once they have expired and every authority
+    /// could be asked, so a signer that is slow to publish or an unreachable
+    /// authority can't cause a completed session to be thrown away.
+    fn decide(expected: usize, found: usize, unreachable: usize, expired: bool) -> Self {
+        if found == expected {
+            SessionOutcome::Complete
+        } else if found == 0 && unreachable == 0 && expired {
+            SessionOutcome::Abandoned
+        } else {
+            SessionOutcome::Unknown
+        }
+    }
+}
+
+/// Find out the outcome of the countersigning sessions this author committed
+/// to their chain but didn't hear back about, e.g. because the conductor
+/// restarted mid-session.
+///
+/// The other signers' agent activity authorities are asked whether each
+/// signer's action for the session has been published. If all of them have,
+/// the session completed and this author's action is published too.
+/// If the session expired without any of them being published it is
+/// abandoned: the countersigned record is removed from the chain.
+/// Either way the chain is unlocked and a system signal is emitted.
+///
+/// Returns true if there are sessions whose outcome can't be decided yet,
+/// in which case this should be called again later.
+pub(crate) async fn resume_countersigning_sessions(
+    space: Space,
+    network: &(dyn HolochainP2pDnaT + Send + Sync),
+    author: AgentPubKey,
+    trigger: QueueTriggers,
+    mut signal: SignalBroadcaster,
+) -> WorkflowResult<bool> {
+    let sessions = space
+        .authored_db
+        .async_reader({
+            let author = author.clone();
+            move |txn| get_countersigning_sessions(&txn, &author)
+        })
+        .await?;
+
+    let mut unresolved = false;
+    for (entry_hash, session_data) in sessions {
+        let weight = weigh_placeholder();
+        let actions = session_data.build_action_set(entry_hash.clone(), weight)?;
+        let this_cells_action_hash = match actions.iter().find(|a| *a.author() == author) {
+            Some(action) => ActionHash::with_data_sync(action),
+            None => {
+                tracing::warn!(
+                    ?entry_hash,
+                    "Dropping countersigning session that this agent is not a signer of"
+                );
+                space
+                    .authored_db
+                    .async_commit({
+                        let author = author.clone();
+                        move |txn| {
+                            mutations::delete_countersigning_session(txn, &author, &entry_hash)
+                        }
+                    })
+                    .await?;
+                continue;
+            }
+        };
+
+        match check_session_outcome(network, &author, &session_data, actions).await {
+            SessionOutcome::Complete => {
+                complete_session(
+                    &space,
+                    author.clone(),
+                    entry_hash,
+                    this_cells_action_hash,
+                    &trigger,
+                    &mut signal,
+                )
+                .await?;
+            }
+            SessionOutcome::Abandoned => {
+                abandon_session(
+                    &space,
+                    author.clone(),
+                    entry_hash,
+                    this_cells_action_hash,
+                    &mut signal,
+                )
+                .await?;
+            }
+            SessionOutcome::Unknown => unresolved = true,
+        }
+    }
+    Ok(unresolved)
+}
+
+/// Ask the agent activity authorities of the other signers whether they
+/// have published their actions for this session.
+async fn check_session_outcome(
+    network: &(dyn HolochainP2pDnaT + Send + Sync),
+    author: &AgentPubKey,
+    session_data: &CounterSigningSessionData,
+    actions: Vec<holochain_zome_types::Action>,
+) -> SessionOutcome {
+    let mut expected = 0;
+    let mut found = 0;
+    let mut unreachable = 0;
+    for action in actions {
+        if action.author() == author {
+            continue;
+        }
+        expected += 1;
+        let action_hash = ActionHash::with_data_sync(&action);
+        let seq = action.action_seq();
+        let query =
+            ChainQueryFilter::new().sequence_range(ChainQueryFilterRange::ActionSeqRange(seq, seq));
+        let options = GetActivityOptions {
+            include_valid_activity: true,
+            ..Default::default()
+        };
+        match network
+            .get_agent_activity(action.author().clone(), query, options)
+            .await
+        {
+            // No responses means no authority could be reached.
+            Ok(responses) if responses.is_empty() => unreachable += 1,
+            Ok(responses) => {
+                let published = responses.iter().any(|response| {
+                    matches!(
+                        &response.valid_activity,
+                        ChainItems::Hashes(hashes) if hashes.iter().any(|(_, h)| *h == action_hash)
+                    )
+                });
+                if published {
+                    found += 1;
+                }
+            }
+            Err(e) => {
+                tracing::info!(
+                    "Failed to get agent activity for countersigning session because of {:?}",
+                    e
+                );
+                unreachable += 1;
+            }
+        }
+    }
+    let expired = Timestamp::now() > *session_data.preflight_request().session_times.end();
+    SessionOutcome::decide(expected, found, unreachable, expired)
+}
+
+/// Finish committing a session that completed while this conductor wasn't
+/// listening for the outcome.
+async fn complete_session(
+    space: &Space,
+    author: AgentPubKey,
+    entry_hash: EntryHash,
+    this_cells_action_hash: ActionHash,
+    trigger: &QueueTriggers,
+    signal: &mut SignalBroadcaster,
+) -> WorkflowResult<()> {
+    let cell_id = CellId::new((*space.dna_hash).clone(), author.clone());
+    let op_hashes = space
+        .authored_db
+        .async_commit({
+            let entry_hash = entry_hash.clone();
+            move |txn| {
+                mutations::unlock_chain(txn, &author)?;
+                mutations::delete_countersigning_session(txn, &author, &entry_hash)?;
+                txn.execute(
+                    "UPDATE DhtOp SET withhold_publish = NULL WHERE action_hash = :action_hash",
+                    named_params! {
+                        ":action_hash": this_cells_action_hash,
+                    },
+                )?;
+                let op_hashes: Vec<DhtOpHash> = txn
+                    .prepare("SELECT hash FROM DhtOp WHERE action_hash = :action_hash")?
+                    .query_map(
+                        named_params! {
+                            ":action_hash": this_cells_action_hash
+                        },
+                        |row| row.get("hash"),
+                    )?
+                    .collect::<Result<_, _>>()?;
+                StateMutationResult::Ok(op_hashes)
+            }
+        })
+        .await?;
+
+    authored_ops_to_dht_db_without_check(
+        op_hashes,
+        &(space.authored_db.clone().into()),
+        &space.dht_db,
+        &space.dht_query_cache,
+    )
+    .await?;
+    trigger
+        .integrate_dht_ops
+        .trigger(&"resume_countersigning_sessions");
+    signal.send(Signal::System(SystemSignal::SuccessfulCountersigning {
+        cell_id,
+        entry_hash,
+    }))?;
+    trigger
+        .publish_dht_ops
+        .trigger(&"publish resume_countersigning_sessions");
+    Ok(())
+}
+
+/// Remove the record of a session that didn't complete and unlock the chain.
+async fn abandon_session(
+    space: &Space,
+    author: AgentPubKey,
+    entry_hash: EntryHash,
+    this_cells_action_hash: ActionHash,
+    signal: &mut SignalBroadcaster,
+) -> WorkflowResult<()> {
+    let cell_id = CellId::new((*space.dna_hash).clone(), author.clone());
+    space
+        .authored_db
+        .async_commit({
+            let entry_hash = entry_hash.clone();
+            move |txn| {
+                let head = chain_head_db(txn, Arc::new(author.clone()))?;
+                // The record can only be removed while nothing has been built on it.
+                if head.map(|h| h.action) == Some(this_cells_action_hash.clone()) {
+                    mutations::remove_countersigned_record(
+                        txn,
+                        &this_cells_action_hash,
+                        &entry_hash,
+                    )?;
+                } else {
+                    tracing::warn!(
+                        ?entry_hash,
+                        "Abandoned countersigning session is no longer at the chain head so it was left in place"
+                    );
+                }
+                mutations::unlock_chain(txn, &author)?;
+                mutations::delete_countersigning_session(txn, &author, &entry_hash)?;
+                SourceChainResult::Ok(())
+            }
+        })
+        .await?;
+    signal.send(Signal::System(SystemSignal::AbandonedCountersigning {
+        cell_id,
+        entry_hash,
+    }))?;
+    Ok(())
+}
+
 /// Publish to entry authorities so they can gather all the signed
 /// actions for this session and respond with a session complete.
 pub async fn countersigning_publish(
@@ -362,20 +719,25 @@ impl CountersigningWorkspace {
     }
 
     /// Put a single signers store entry op in the workspace.
+    ///
+    /// Returns true if this is the first op for the session.
     fn put(
         &self,
         entry_hash: EntryHash,
         op_hash: DhtOpHash,
         op: DhtOp,
-        required_actions: Vec<ActionHash>,
+        required_actions: Vec<(AgentPubKey, ActionHash)>,
+        enzyme: Option<AgentPubKey>,
         expires: Timestamp,
-    ) {
+    ) -> bool {
         // hash the action of this ops.
         let action_hash = ActionHash::with_data_sync(&op.action());
+        let (signers, required_actions): (Vec<_>, Vec<_>) = required_actions.into_iter().unzip();
         self.inner
             .share_mut(|i, _| {
                 // Get the session at this entry or create an empty one.
                 let session = i.pending.entry(entry_hash).or_default();
+                let is_new_session = session.expires.is_none();
 
                 // Insert the op into the session.
                 session
@@ -384,10 +746,79 @@ impl CountersigningWorkspace {
 
                 // Set the expires time.
                 session.expires = Some(expires);
-                Ok(())
+                session.signers = signers;
+                session.enzyme = enzyme;
+                Ok(is_new_session)
             })
             // We don't close this share so we can ignore this error.
-            .ok();
+            .unwrap_or(false)
+    }
+
+    /// Remove the enzymatic sessions that expired before they were complete,
+    /// returning their enzyme and the agents that were meant to sign each of them.
+    fn take_timed_out_sessions(&self) -> Vec<(EntryHash, AgentPubKey, AgentsToNotify)> {
+        let now = holochain_zome_types::Timestamp::now();
+        self.inner
+            .share_mut(|i, _| {
+                // Forget the outcome of sessions that finished a while ago.
+                i.finished.retain(|_, (_, info)| {
+                    (info.expires + FINISHED_SESSION_RETENTION)
+                        .map(|until| now < until)
+                        .unwrap_or(true)
+                });
+
+                let timed_out: Vec<_> = i
+                    .pending
+                    .iter()
+                    .filter(|(_, session)| {
+                        session.enzyme.is_some()
+                            && session.expires.as_ref().map(|e| now >= *e).unwrap_or(false)
+                    })
+                    .map(|(entry_hash, _)| entry_hash.clone())
+                    .collect();
+
+                let mut ret = Vec::with_capacity(timed_out.len());
+                for entry_hash in timed_out {
+                    if let Some(session) = i.pending.remove(&entry_hash) {
+                        if let Some((enzyme, info)) = session.enzymatic_info(
+                            entry_hash.clone(),
+                            EnzymaticCountersigningStatus::TimedOut,
+                        ) {
+                            i.finished
+                                .insert(entry_hash.clone(), (enzyme.clone(), info));
+                            ret.push((entry_hash, enzyme, session.signers));
+                        }
+                    }
+                }
+                Ok(ret)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get the sessions that the enzyme is coordinating or recently finished.
+    pub fn enzymatic_sessions(
+        &self,
+        enzyme: &AgentPubKey,
+    ) -> Vec<EnzymaticCountersigningSessionInfo> {
+        self.inner
+            .share_ref(|i| {
+                let pending = i.pending.iter().filter_map(|(entry_hash, session)| {
+                    session
+                        .enzymatic_info(
+                            entry_hash.clone(),
+                            EnzymaticCountersigningStatus::Collecting,
+                        )
+                        .filter(|(e, _)| e == enzyme)
+                        .map(|(_, info)| info)
+                });
+                let finished = i
+                    .finished
+                    .values()
+                    .filter(|(e, _)| e == enzyme)
+                    .map(|(_, info)| info.clone());
+                Ok(pending.chain(finished).collect())
+            })
+            .unwrap_or_default()
     }
 
     fn get_complete_sessions(&self) -> Vec<(AgentsToNotify, Ops, SignedActions)> {
@@ -425,6 +856,11 @@ impl CountersigningWorkspace {
                 // and the ops to validate.
                 for hash in complete {
                     if let Some(session) = i.pending.remove(&hash) {
+                        if let Some((enzyme, info)) = session
+                            .enzymatic_info(hash.clone(), EnzymaticCountersigningStatus::Complete)
+                        {
+                            i.finished.insert(hash, (enzyme, info));
+                        }
                         let map = session.map;
                         let r = map.into_iter().fold(
                             (Vec::new(), Vec::new(), Vec::new()),
@@ -449,6 +885,39 @@ impl CountersigningWorkspace {
     }
 }
 
+impl Session {
+    /// Describe this session if it is enzymatic, along with its enzyme.
+    fn enzymatic_info(
+        &self,
+        entry_hash: EntryHash,
+        status: EnzymaticCountersigningStatus,
+    ) -> Option<(AgentPubKey, EnzymaticCountersigningSessionInfo)> {
+        let enzyme = self.enzyme.clone()?;
+        let expires = self.expires?;
+        let signed: Vec<AgentPubKey> = self
+            .map
+            .values()
+            .map(|(_, op, _)| op.action().author().clone())
+            .collect();
+        let outstanding = self
+            .signers
+            .iter()
+            .filter(|agent| !signed.contains(agent))
+            .cloned()
+            .collect();
+        Some((
+            enzyme,
+            EnzymaticCountersigningSessionInfo {
+                entry_hash,
+                status,
+                signed,
+                outstanding,
+                expires,
+            },
+        ))
+    }
+}
+
 impl Default for CountersigningWorkspace {
     fn default() -> Self {
         Self::new()
@@ -485,7 +954,7 @@ mod tests {
             let action_hash = ActionHash::with_data_sync(&action);
             op_hashes.push(op_hash);
             ops.push(op);
-            required_actions.push(action_hash);
+            required_actions.push((action.author().clone(), action_hash));
         }
 
         // - Put the ops in the workspace with expiry set to one hour from now.
@@ -496,6 +965,7 @@ mod tests {
                 op_h,
                 op,
                 required_actions.clone(),
+                None,
                 expires,
             );
         }
@@ -515,6 +985,24 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    /// Test that interrupted sessions are only abandoned once expired
+    /// and no signer has published.
+    fn decides_session_outcome() {
+        use SessionOutcome::*;
+        // Every other signer published.
+        assert_eq!(SessionOutcome::decide(2, 2, 0, false), Complete);
+        assert_eq!(SessionOutcome::decide(2, 2, 0, true), Complete);
+        // Still running so others may yet publish.
+        assert_eq!(SessionOutcome::decide(2, 0, 0, false), Unknown);
+        // Expired and nobody published.
+        assert_eq!(SessionOutcome::decide(2, 0, 0, true), Abandoned);
+        // Someone published so the rest might still.
+        assert_eq!(SessionOutcome::decide(2, 1, 0, true), Unknown);
+        // An authority couldn't be asked.
+        assert_eq!(SessionOutcome::decide(2, 0, 1, true), Unknown);
+    }
+
     #[test]
     /// Test that expired sessions are removed.
     fn expired_sessions_removed() {
@@ -530,7 +1018,14 @@ mod tests {
         let expires = (Timestamp::now() - std::time::Duration::from_secs(60 * 60)).unwrap();
 
         // - Add it to the workspace.
-        workspace.put(entry_hash, op_hash, op, vec![action_hash], expires);
+        workspace.put(
+            entry_hash,
+            op_hash,
+            op,
+            vec![(action.author().clone(), action_hash)],
+            None,
+            expires,
+        );
         let r = workspace.get_complete_sessions();
 
         // - Expect we have no complete sessions.
@@ -544,4 +1039,76 @@ mod tests {
             })
             .unwrap();
     }
+
+    #[test]
+    /// Test that an enzymatic session reports its progress and
+    /// that the signers are returned once it times out.
+    fn enzymatic_session_times_out() {
+        let mut u = arbitrary::Unstructured::new(&holochain_zome_types::NOISE);
+        let workspace = CountersigningWorkspace::new();
+
+        // - Create a session with two signers where only one has pushed their op.
+        let op_hash = DhtOpHash::arbitrary(&mut u).unwrap();
+        let op = DhtOp::arbitrary(&mut u).unwrap();
+        let action = op.action();
+        let signer = action.author().clone();
+        let other_signer = AgentPubKey::arbitrary(&mut u).unwrap();
+        let enzyme = signer.clone();
+        let entry_hash = EntryHash::arbitrary(&mut u).unwrap();
+        let required_actions = vec![
+            (signer.clone(), ActionHash::with_data_sync(&action)),
+            (other_signer.clone(), ActionHash::arbitrary(&mut u).unwrap()),
+        ];
+        let expires = (Timestamp::now() + std::time::Duration::from_secs(60 * 60)).unwrap();
+        assert!(workspace.put(
+            entry_hash.clone(),
+            op_hash,
+            op,
+            required_actions,
+            Some(enzyme.clone()),
+            expires,
+        ));
+
+        // - The enzyme sees the session collecting signatures.
+        let sessions = workspace.enzymatic_sessions(&enzyme);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(
+            sessions[0].status,
+            EnzymaticCountersigningStatus::Collecting
+        );
+        assert_eq!(sessions[0].signed, vec![signer.clone()]);
+        assert_eq!(sessions[0].outstanding, vec![other_signer.clone()]);
+        assert!(workspace.enzymatic_sessions(&other_signer).is_empty());
+
+        // - Nothing has timed out or completed yet.
+        assert!(workspace.take_timed_out_sessions().is_empty());
+        assert!(workspace.get_complete_sessions().is_empty());
+
+        // - Expire the session.
+        workspace
+            .inner
+            .share_mut(|i, _| {
+                i.pending.get_mut(&entry_hash).unwrap().expires =
+                    Some((Timestamp::now() - std::time::Duration::from_secs(1)).unwrap());
+                Ok(())
+            })
+            .unwrap();
+
+        // - Both signers are told the session timed out.
+        let timed_out = workspace.take_timed_out_sessions();
+        assert_eq!(
+            timed_out,
+            vec![(
+                entry_hash.clone(),
+                enzyme.clone(),
+                vec![signer, other_signer.clone()]
+            )]
+        );
+
+        // - The enzyme can still see the outcome.
+        let sessions = workspace.enzymatic_sessions(&enzyme);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].status, EnzymaticCountersigningStatus::TimedOut);
+        assert_eq!(sessions[0].outstanding, vec![other_signer]);
+    }
 }
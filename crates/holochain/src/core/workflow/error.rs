@@ -55,6 +55,9 @@ pub enum WorkflowError {
     #[error("Capability token missing")]
     CapabilityMissing,
 
+    #[error("The migrate_agent callback in zome {0} failed: {1}")]
+    MigrateAgentFailed(ZomeName, String),
+
     #[error(transparent)]
     SerializedBytesError(#[from] SerializedBytesError),
 
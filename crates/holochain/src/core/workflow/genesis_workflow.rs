@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use super::error::WorkflowError;
 use super::error::WorkflowResult;
+use crate::conductor::conductor::DpkiServiceExt;
 use crate::core::ribosome::guest_callback::genesis_self_check::{
     GenesisSelfCheckHostAccess, GenesisSelfCheckInvocation, GenesisSelfCheckResult,
 };
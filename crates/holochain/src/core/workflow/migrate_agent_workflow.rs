@@ -0,0 +1,188 @@
+//! Closes or opens a source chain as one end of a chain migration,
+//! once the `migrate_agent` callbacks have agreed to it.
+//!
+//! The action is validated in the scratch space and only flushed later,
+//! so that both ends of a migration can be checked before either is written.
+//! The two ends are on different chains and can't be flushed together, so
+//! an end which is already on its chain is found and used instead, letting
+//! a migration which failed between the two flushes be run again.
+
+use super::error::WorkflowError;
+use super::error::WorkflowResult;
+use crate::conductor::ConductorHandle;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentHostAccess;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
+use crate::core::ribosome::RibosomeT;
+use derive_more::Constructor;
+use holochain_p2p::HolochainP2pDna;
+use holochain_state::host_fn_workspace::HostFnWorkspace;
+use holochain_state::host_fn_workspace::SourceChainWorkspace;
+use holochain_types::prelude::*;
+use holochain_zome_types::action::builder;
+
+/// Which end of a migration a source chain is on
+#[derive(Clone, Debug)]
+pub enum ChainMigration {
+    /// Close the chain in favor of the given successor
+    Close(MigrationTarget),
+    /// Open the chain as the successor of a chain closed by the given action
+    Open {
+        /// The DNA or agent key of the closed chain
+        prev_target: MigrationTarget,
+        /// The hash of the [`CloseChain`] action on the closed chain
+        close_hash: ActionHash,
+    },
+}
+
+impl ChainMigration {
+    fn migrate_agent(&self) -> MigrateAgent {
+        match self {
+            Self::Close(_) => MigrateAgent::Close,
+            Self::Open { .. } => MigrateAgent::Open,
+        }
+    }
+}
+
+#[derive(Constructor)]
+pub struct MigrateAgentWorkflowArgs<Ribosome>
+where
+    Ribosome: RibosomeT + 'static,
+{
+    pub ribosome: Ribosome,
+    pub conductor_handle: ConductorHandle,
+    pub migration: ChainMigration,
+}
+
+/// A [`CloseChain`] or [`OpenChain`] action which has been validated in
+/// the scratch space, but not yet flushed to the source chain, or which
+/// was already on the source chain.
+pub struct PendingChainMigration {
+    /// The hash of the action
+    pub action_hash: ActionHash,
+    unflushed: Option<(SourceChainWorkspace, HolochainP2pDna)>,
+}
+
+impl PendingChainMigration {
+    /// Flush the action to the source chain, returning its hash.
+    pub async fn flush(self) -> WorkflowResult<ActionHash> {
+        if let Some((workspace, network)) = self.unflushed {
+            HostFnWorkspace::from(workspace).flush(&network).await?;
+        }
+        Ok(self.action_hash)
+    }
+}
+
+/// Find the action for this end of the migration, if it is already on the
+/// source chain.
+async fn find_chain_migration(
+    workspace: &SourceChainWorkspace,
+    migration: &ChainMigration,
+) -> WorkflowResult<Option<ActionHash>> {
+    let action_type = match migration {
+        ChainMigration::Close(_) => ActionType::CloseChain,
+        ChainMigration::Open { .. } => ActionType::OpenChain,
+    };
+    let records = workspace
+        .source_chain()
+        .query(ChainQueryFilter::new().action_type(action_type))
+        .await?;
+    Ok(records.into_iter().find_map(|record| {
+        let found = match (record.action(), migration) {
+            (Action::CloseChain(close), ChainMigration::Close(new_target)) => {
+                close.new_target == *new_target
+            }
+            (
+                Action::OpenChain(open),
+                ChainMigration::Open {
+                    prev_target,
+                    close_hash,
+                },
+            ) => open.prev_target == *prev_target && open.close_hash == *close_hash,
+            _ => false,
+        };
+        found.then(|| record.action_address().clone())
+    }))
+}
+
+/// Run the `migrate_agent` callbacks, and if they all pass, write the
+/// [`CloseChain`] or [`OpenChain`] action to the scratch space and validate it.
+///
+/// Nothing is written to the source chain until the returned
+/// [`PendingChainMigration`] is flushed. If the action is already on the
+/// source chain, the callbacks aren't run again and flushing does nothing.
+pub async fn migrate_agent_workflow<Ribosome>(
+    workspace: SourceChainWorkspace,
+    network: HolochainP2pDna,
+    args: MigrateAgentWorkflowArgs<Ribosome>,
+) -> WorkflowResult<PendingChainMigration>
+where
+    Ribosome: RibosomeT + 'static,
+{
+    let MigrateAgentWorkflowArgs {
+        ribosome,
+        conductor_handle,
+        migration,
+    } = args;
+
+    if let Some(action_hash) = find_chain_migration(&workspace, &migration).await? {
+        return Ok(PendingChainMigration {
+            action_hash,
+            unflushed: None,
+        });
+    }
+
+    let result = {
+        let host_access = MigrateAgentHostAccess::new(workspace.clone().into());
+        let invocation = MigrateAgentInvocation::new(
+            ribosome.dna_def().as_content().clone(),
+            migration.migrate_agent(),
+        );
+        ribosome.run_migrate_agent(host_access, invocation)?
+    };
+    if let MigrateAgentResult::Fail(zome_name, reason) = result {
+        return Err(WorkflowError::MigrateAgentFailed(zome_name, reason));
+    }
+
+    let source_chain = workspace.source_chain();
+    let action_hash = match migration {
+        ChainMigration::Close(new_target) => {
+            source_chain
+                .put(
+                    builder::CloseChain { new_target },
+                    None,
+                    ChainTopOrdering::Strict,
+                )
+                .await?
+        }
+        ChainMigration::Open {
+            prev_target,
+            close_hash,
+        } => {
+            source_chain
+                .put(
+                    builder::OpenChain {
+                        prev_target,
+                        close_hash,
+                    },
+                    None,
+                    ChainTopOrdering::Strict,
+                )
+                .await?
+        }
+    };
+
+    // This is where a write to an already closed chain is caught
+    super::inline_validation(
+        workspace.clone(),
+        network.clone(),
+        conductor_handle,
+        ribosome,
+    )
+    .await?;
+
+    Ok(PendingChainMigration {
+        action_hash,
+        unflushed: Some((workspace, network)),
+    })
+}
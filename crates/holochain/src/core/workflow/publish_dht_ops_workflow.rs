@@ -11,6 +11,7 @@
 //!
 
 use super::error::WorkflowResult;
+use crate::conductor::metrics::WORKFLOW_QUEUE_DEPTH;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
 use holo_hash::*;
@@ -41,6 +42,9 @@ pub async fn publish_dht_ops_workflow(
 ) -> WorkflowResult<WorkComplete> {
     let mut complete = WorkComplete::Complete;
     let to_publish = publish_dht_ops_workflow_inner(db.clone().into(), agent.clone()).await?;
+    WORKFLOW_QUEUE_DEPTH
+        .with_label_values(&["publish_dht_ops", &network.dna_hash().to_string()])
+        .set(to_publish.values().map(Vec::len).sum::<usize>() as i64);
 
     // Commit to the network
     tracing::info!("publishing to {} nodes", to_publish.len());
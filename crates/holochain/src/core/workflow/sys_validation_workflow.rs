@@ -1,6 +1,7 @@
 //! The workflow and queue consumer for sys validation
 
 use super::*;
+use crate::conductor::metrics::WORKFLOW_QUEUE_DEPTH;
 use crate::conductor::space::Space;
 use crate::conductor::Conductor;
 use crate::conductor::ConductorHandle;
@@ -85,6 +86,9 @@ async fn sys_validation_workflow_inner(
     let db = workspace.dht_db.clone();
     let sorted_ops = validation_query::get_ops_to_sys_validate(&db).await?;
     let start_len = sorted_ops.len();
+    WORKFLOW_QUEUE_DEPTH
+        .with_label_values(&["sys_validation", &workspace.dna_hash().to_string()])
+        .set(start_len as i64);
     tracing::debug!("Validating {} ops", start_len);
     let start = (start_len >= NUM_CONCURRENT_OPS).then(std::time::Instant::now);
     let saturated = start.is_some();
@@ -282,6 +286,10 @@ fn handle_failed(error: ValidationOutcome) -> Outcome {
         }
         ValidationOutcome::ActionNotInCounterSigningSession(_, _) => Rejected,
         ValidationOutcome::DepMissingFromDht(_) => MissingDhtDep,
+        ValidationOutcome::DpkiAgentInvalid(_, _) => Rejected,
+        ValidationOutcome::DpkiAgentMissing(_, _) => MissingDhtDep,
+        ValidationOutcome::DpkiAgentUnregistered(_, _) => Rejected,
+        ValidationOutcome::DpkiNotReady => MissingDhtDep,
         ValidationOutcome::EntryDefId(_) => Rejected,
         ValidationOutcome::EntryHash => Rejected,
         ValidationOutcome::EntryTooLarge(_, _) => Rejected,
@@ -312,6 +320,13 @@ async fn validate_op_inner(
     conductor_handle: &Conductor,
     incoming_dht_ops_sender: Option<IncomingDhtOpSender>,
 ) -> SysValidationResult<()> {
+    // Until a configured DPKI service is running, only a permissive stand-in
+    // is available, so ops wait rather than skip the check.
+    if !conductor_handle.is_dpki_ready() {
+        return Err(ValidationOutcome::DpkiNotReady.into());
+    }
+    author_key_is_valid(&op.action(), dna_def.as_hash(), conductor_handle.dpki()).await?;
+
     match op {
         DhtOp::StoreRecord(_, action, entry) => {
             store_record(action, cascade).await?;
@@ -519,11 +534,10 @@ async fn sys_validate_record_inner(
     }
 }
 
-/// Check if the op has valid signature and author.
+/// Check if the op has a valid signature.
 /// Ops that fail this check should be dropped.
 pub async fn counterfeit_check(signature: &Signature, action: &Action) -> SysValidationResult<()> {
     verify_action_signature(signature, action).await?;
-    author_key_is_valid(action.author()).await?;
     Ok(())
 }
 
@@ -544,7 +558,7 @@ async fn register_agent_activity(
             prev_action_hash,
             cascade,
             incoming_dht_ops_sender,
-            |_| Ok(()),
+            |prev_record| check_prev_action_not_close_chain(action, prev_record.action()),
         )
         .await?;
     }
@@ -568,6 +582,7 @@ async fn store_record(action: &Action, cascade: &Cascade) -> SysValidationResult
         check_prev_timestamp(action, prev_action.action())?;
         check_prev_seq(action, prev_action.action())?;
         check_agent_validation_pkg_predecessor(action, prev_action.action())?;
+        check_prev_action_not_close_chain(action, prev_action.action())?;
     }
     Ok(())
 }
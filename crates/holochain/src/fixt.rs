@@ -19,6 +19,7 @@ use crate::core::ribosome::HostContext;
 use crate::core::ribosome::InvocationAuth;
 use crate::core::ribosome::ZomeCallHostAccess;
 use crate::core::ribosome::ZomeCallInvocation;
+use crate::core::ribosome::ZomeCallStream;
 use crate::core::ribosome::ZomesToInvoke;
 use crate::sweettest::SweetDnaFile;
 use crate::test_utils::fake_genesis;
@@ -353,9 +354,18 @@ fixturator!(
     vanilla fn make_call_zome_handle();
 );
 
+fn make_zome_call_stream() -> ZomeCallStream {
+    None
+}
+
+fixturator!(
+    ZomeCallStream;
+    vanilla fn make_zome_call_stream();
+);
+
 fixturator!(
     ZomeCallHostAccess;
-    constructor fn new(HostFnWorkspace, MetaLairClient, HolochainP2pDna, SignalBroadcaster, CellConductorReadHandle);
+    constructor fn new(HostFnWorkspace, MetaLairClient, HolochainP2pDna, SignalBroadcaster, CellConductorReadHandle, Timestamp, ZomeCallStream);
 );
 
 fixturator!(
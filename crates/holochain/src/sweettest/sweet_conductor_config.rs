@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use crate::sweettest::SweetRendezvous;
-use holochain_conductor_api::{conductor::ConductorConfig, AdminInterfaceConfig, InterfaceDriver};
+use holochain_conductor_api::{
+    conductor::ConductorConfig, AdminInterfaceAuth, AdminInterfaceConfig,
+};
 use kitsune_p2p::KitsuneP2pConfig;
 
 /// Wrapper around ConductorConfig with some helpful builder methods
@@ -20,9 +22,9 @@ impl From<KitsuneP2pConfig> for SweetConductorConfig {
     fn from(network: KitsuneP2pConfig) -> Self {
         ConductorConfig {
             network: Some(network),
-            admin_interfaces: Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port: 0 },
-            }]),
+            admin_interfaces: Some(vec![
+                AdminInterfaceConfig::websocket(0).with_auth(AdminInterfaceAuth::None)
+            ]),
             ..Default::default()
         }
         .into()
@@ -1,9 +1,9 @@
 //! Utils for Holochain tests
 use crate::conductor::api::RealAppInterfaceApi;
 use crate::conductor::conductor::CellStatus;
+use crate::conductor::config::AdminInterfaceAuth;
 use crate::conductor::config::AdminInterfaceConfig;
 use crate::conductor::config::ConductorConfig;
-use crate::conductor::config::InterfaceDriver;
 use crate::conductor::p2p_agent_store;
 use crate::conductor::ConductorBuilder;
 use crate::conductor::ConductorHandle;
@@ -421,9 +421,9 @@ pub async fn setup_app_inner(
 ) -> (RealAppInterfaceApi, ConductorHandle) {
     let conductor_handle = ConductorBuilder::new()
         .config(ConductorConfig {
-            admin_interfaces: Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port: 0 },
-            }]),
+            admin_interfaces: Some(vec![
+                AdminInterfaceConfig::websocket(0).with_auth(AdminInterfaceAuth::None)
+            ]),
             network,
             ..Default::default()
         })
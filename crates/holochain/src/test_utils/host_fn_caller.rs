@@ -195,6 +195,8 @@ impl HostFnCaller {
             network,
             signal_tx,
             call_zome_handle,
+            Timestamp::MAX,
+            None,
         );
         let ribosome = Arc::new(ribosome);
         let zome = ribosome.dna_def().get_zome(&zome_name).unwrap();
@@ -327,7 +327,10 @@ async fn get_links_from_another_agent() {
     )
     .await;
 
-    let link_options = GetLinksOptions { timeout_ms: None };
+    let link_options = GetLinksOptions {
+        timeout_ms: None,
+        ..Default::default()
+    };
 
     // Bob store links
     let base = Post("Bananas are good for you".into());
@@ -1,8 +1,8 @@
 use assert_cmd::prelude::*;
 use holochain_conductor_api::config::conductor::ConductorConfig;
 use holochain_conductor_api::config::conductor::KeystoreConfig;
+use holochain_conductor_api::AdminInterfaceAuth;
 use holochain_conductor_api::AdminInterfaceConfig;
-use holochain_conductor_api::InterfaceDriver;
 use kitsune_p2p_types::dependencies::lair_keystore_api;
 use lair_keystore_api::dependencies::*;
 use lair_keystore_api::ipc_keystore::*;
@@ -39,9 +39,9 @@ async fn test_new_lair_conductor_integration() {
 
     // set up conductor config to use the started keystore
     let mut conductor_config = ConductorConfig::default();
-    conductor_config.admin_interfaces = Some(vec![AdminInterfaceConfig {
-        driver: InterfaceDriver::Websocket { port: ADMIN_PORT },
-    }]);
+    conductor_config.admin_interfaces = Some(vec![
+        AdminInterfaceConfig::websocket(ADMIN_PORT).with_auth(AdminInterfaceAuth::None)
+    ]);
     conductor_config.environment_path = tmp.path().to_owned().into();
     conductor_config.keystore = KeystoreConfig::LairServer {
         connection_url: keystore_config.connection_url.clone().into(),
@@ -489,6 +489,9 @@ async fn mock_network_sharded_gossip() {
                         holochain_p2p::WireMessage::PublishCountersign { .. } => {
                             debug!("PublishCountersign")
                         }
+                        holochain_p2p::WireMessage::PublishWarrant { .. } => {
+                            debug!("PublishWarrant")
+                        }
                         /* (david.b) TODO - this has been replaced by
                          *                  combined `receive_ops`
                         holochain_p2p::WireMessage::Publish { ops, .. } => {
@@ -1058,6 +1061,9 @@ async fn mock_network_sharding() {
                         holochain_p2p::WireMessage::PublishCountersign { .. } => {
                             debug!("publish_countersign")
                         }
+                        holochain_p2p::WireMessage::PublishWarrant { .. } => {
+                            debug!("publish_warrant")
+                        }
                     },
                     HolochainP2pMockMsg::CallResp(_) => debug!("CallResp"),
                     HolochainP2pMockMsg::MetricExchange(_) => debug!("MetricExchange"),
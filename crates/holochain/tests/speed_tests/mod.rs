@@ -178,7 +178,10 @@ async fn speed_test(n: Option<usize>) -> Arc<TempDir> {
 
     // Setup websocket handle and app interface
     let (mut client, _) = websocket_client(&handle).await.unwrap();
-    let request = AdminRequest::AttachAppInterface { port: None };
+    let request = AdminRequest::AttachAppInterface {
+        port: None,
+        allowed_origins: Default::default(),
+    };
     let response = client.request(request);
     let response = response.await.unwrap();
     let app_port = match response {
@@ -186,6 +189,7 @@ async fn speed_test(n: Option<usize>) -> Arc<TempDir> {
         _ => panic!("Attach app interface failed: {:?}", response),
     };
     let (mut app_interface, _) = websocket_client_by_port(app_port).await.unwrap();
+    authenticate_app_ws_client(&mut client, &mut app_interface, "test app".to_string()).await;
 
     // /////////////
     // END CONDUCTOR
@@ -27,8 +27,8 @@ use assert_cmd::prelude::*;
 use futures::Future;
 use holochain_conductor_api::conductor::ConductorConfig;
 use holochain_conductor_api::conductor::KeystoreConfig;
+use holochain_conductor_api::AdminInterfaceAuth;
 use holochain_conductor_api::AdminInterfaceConfig;
-use holochain_conductor_api::InterfaceDriver;
 use matches::assert_matches;
 use serde::Serialize;
 use std::time::Duration;
@@ -44,7 +44,9 @@ use holochain::{
     conductor::api::ZomeCall,
     conductor::api::{AdminRequest, AdminResponse, AppRequest},
 };
+use holochain_conductor_api::AppAuthenticationRequest;
 use holochain_conductor_api::AppResponse;
+use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
 use holochain_types::prelude::*;
 use holochain_util::tokio_helper;
 use holochain_websocket::*;
@@ -159,7 +161,10 @@ pub async fn call_zome_fn<S>(
 }
 
 pub async fn attach_app_interface(client: &mut WebsocketSender, port: Option<u16>) -> u16 {
-    let request = AdminRequest::AttachAppInterface { port };
+    let request = AdminRequest::AttachAppInterface {
+        port,
+        allowed_origins: Default::default(),
+    };
     let response = client.request(request);
     let response = check_timeout(response, 3000).await;
     match response {
@@ -168,6 +173,29 @@ pub async fn attach_app_interface(client: &mut WebsocketSender, port: Option<u16
     }
 }
 
+/// Issue a token for the app and use it to authenticate an app interface connection.
+pub async fn authenticate_app_ws_client(
+    admin_tx: &mut WebsocketSender,
+    app_tx: &mut WebsocketSender,
+    installed_app_id: InstalledAppId,
+) {
+    let request = AdminRequest::IssueAppAuthenticationToken(
+        // The connection is closed once the token expires
+        IssueAppAuthenticationTokenPayload::for_apps(vec![installed_app_id]).expiry_seconds(0),
+    );
+    let response = admin_tx.request(request);
+    let response = check_timeout(response, 3000).await;
+    let token = match response {
+        AdminResponse::AppAuthenticationTokenIssued(issued) => issued.token,
+        _ => panic!("Issue app authentication token failed: {:?}", response),
+    };
+
+    let request = AppRequest::Authenticate(AppAuthenticationRequest { token });
+    let response = app_tx.request(request);
+    let response = check_timeout(response, 3000).await;
+    assert_matches!(response, AppResponse::Authenticated(_));
+}
+
 pub async fn retry_admin_interface(
     port: u16,
     mut attempts: usize,
@@ -335,9 +363,9 @@ pub async fn check_started(holochain: &mut Child) {
 
 pub fn create_config(port: u16, environment_path: PathBuf) -> ConductorConfig {
     ConductorConfig {
-        admin_interfaces: Some(vec![AdminInterfaceConfig {
-            driver: InterfaceDriver::Websocket { port },
-        }]),
+        admin_interfaces: Some(vec![
+            AdminInterfaceConfig::websocket(port).with_auth(AdminInterfaceAuth::None)
+        ]),
         environment_path: environment_path.into(),
         keystore: KeystoreConfig::DangerTestKeystore,
         ..Default::default()
@@ -9,11 +9,15 @@ use holochain::sweettest::SweetDnaFile;
 use holochain::{
     conductor::{
         api::{AdminRequest, AdminResponse},
+        config::{
+            AdminInterfaceAuth, AdminInterfaceConfig, ConductorConfig, ADMIN_AUTH_TOKEN_FILE,
+        },
         error::ConductorError,
         Conductor,
     },
     fixt::*,
 };
+use holochain_conductor_api::AdminAuthenticationRequest;
 use holochain_trace;
 use holochain_types::{
     prelude::*,
@@ -182,6 +186,7 @@ async fn call_zome() {
     let app_port = attach_app_interface(&mut admin_tx, None).await;
 
     let (mut app_tx, _) = websocket_client_by_port(app_port).await.unwrap();
+    authenticate_app_ws_client(&mut admin_tx, &mut app_tx, "test".to_string()).await;
 
     // Call Zome
     tracing::info!("Calling zome");
@@ -223,11 +228,12 @@ async fn call_zome() {
     let response = admin_tx.request(request);
     let response = check_timeout(response, 3000).await;
     let app_port = match response {
-        AdminResponse::AppInterfacesListed(ports) => *ports.first().unwrap(),
+        AdminResponse::AppInterfacesListed(drivers) => drivers.first().unwrap().port().unwrap(),
         _ => panic!("Unexpected response"),
     };
 
     let (mut app_tx, _) = websocket_client_by_port(app_port).await.unwrap();
+    authenticate_app_ws_client(&mut admin_tx, &mut app_tx, "test".to_string()).await;
 
     // Call Zome again on the existing app interface port
     tracing::info!("Calling zome again");
@@ -386,7 +392,9 @@ async fn emit_signals() {
     // Emit signals (the real test!)
 
     let (mut app_tx_1, app_rx_1) = websocket_client_by_port(app_port).await.unwrap();
-    let (_, app_rx_2) = websocket_client_by_port(app_port).await.unwrap();
+    let (mut app_tx_2, app_rx_2) = websocket_client_by_port(app_port).await.unwrap();
+    authenticate_app_ws_client(&mut admin_tx, &mut app_tx_1, "test".to_string()).await;
+    authenticate_app_ws_client(&mut admin_tx, &mut app_tx_2, "test".to_string()).await;
 
     call_zome_fn(
         &mut app_tx_1,
@@ -453,6 +461,85 @@ async fn conductor_admin_interface_runs_from_config() -> Result<()> {
     Ok(())
 }
 
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn conductor_admin_interface_runs_on_unix_socket() -> Result<()> {
+    holochain_trace::test_run().ok();
+    let tmp_dir = TempDir::new().unwrap();
+    let environment_path = tmp_dir.path().to_path_buf();
+    let admin_path = tmp_dir.path().join("admin.sock");
+    let config = ConductorConfig {
+        admin_interfaces: Some(vec![AdminInterfaceConfig::unix_socket(admin_path.clone())
+            .with_auth(AdminInterfaceAuth::None)]),
+        ..create_config(0, environment_path)
+    };
+    let conductor_handle = Conductor::builder().config(config).build().await?;
+    let (mut client, _) =
+        holochain_websocket::connect_unix(admin_path, Arc::new(WebsocketConfig::default())).await?;
+
+    let app_path = tmp_dir.path().join("app.sock");
+    let request = AdminRequest::AttachAppUnixSocketInterface {
+        path: app_path.clone(),
+    };
+    let response = client.request(request).await?;
+    assert_matches!(response, AdminResponse::AppUnixSocketInterfaceAttached { path } if path == app_path);
+
+    // Unix socket app interfaces have no port to list
+    let response = client.request(AdminRequest::ListAppInterfaces).await?;
+    assert_matches!(response, AdminResponse::AppInterfacesListed(ports) if ports.is_empty());
+
+    let _ =
+        holochain_websocket::connect_unix(app_path, Arc::new(WebsocketConfig::default())).await?;
+
+    conductor_handle.shutdown();
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn admin_interface_authenticates_with_generated_token_by_default() -> Result<()> {
+    holochain_trace::test_run().ok();
+    let tmp_dir = TempDir::new().unwrap();
+    let environment_path = tmp_dir.path().to_path_buf();
+    let config = ConductorConfig {
+        admin_interfaces: Some(vec![AdminInterfaceConfig::websocket(0)]),
+        ..create_config(0, environment_path.clone())
+    };
+    let conductor_handle = Conductor::builder().config(config).build().await?;
+    let (mut client, _) = websocket_client(&conductor_handle).await?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = std::fs::metadata(environment_path.join(ADMIN_AUTH_TOKEN_FILE))?;
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    }
+    let token = std::fs::read_to_string(environment_path.join(ADMIN_AUTH_TOKEN_FILE))?;
+
+    let response = client.request(AdminRequest::ListDnas).await?;
+    assert_matches!(response, AdminResponse::Error(_));
+
+    let response = client
+        .request(AdminRequest::Authenticate(AdminAuthenticationRequest {
+            token: "wrong".to_string(),
+        }))
+        .await?;
+    assert_matches!(response, AdminResponse::Error(_));
+
+    let response = client
+        .request(AdminRequest::Authenticate(AdminAuthenticationRequest {
+            token,
+        }))
+        .await?;
+    assert_matches!(response, AdminResponse::Authenticated);
+    let response = client.request(AdminRequest::ListDnas).await?;
+    assert_matches!(response, AdminResponse::DnasListed(_));
+
+    conductor_handle.shutdown();
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn list_app_interfaces_succeeds() -> Result<()> {
     holochain_trace::test_run().ok();
@@ -683,6 +770,9 @@ async fn network_stats() {
             let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
             let backend = parsed.as_object().unwrap().get("backend").unwrap();
             assert_eq!(EXPECT, backend);
+
+            let bandwidth = parsed.as_object().unwrap().get("bandwidth").unwrap();
+            assert!(bandwidth["total"]["outbound_bytes"].as_u64().unwrap() > 0);
         }
         _ => panic!("unexpected"),
     }
@@ -13,7 +13,9 @@ use super::error::CascadeResult;
 use holo_hash::ActionHash;
 use holo_hash::AgentPubKey;
 use holochain_state::query::Query;
+use holochain_state::query::StateQueryResult;
 use holochain_state::query::Txn;
+use holochain_state::warrants::get_warrants_for_agent;
 use holochain_types::prelude::*;
 use holochain_zome_types::agent_activity::DeterministicGetAgentActivityFilter;
 use tracing::*;
@@ -62,9 +64,13 @@ pub async fn handle_get_agent_activity(
     query: ChainQueryFilter,
     options: holochain_p2p::event::GetActivityOptions,
 ) -> CascadeResult<AgentActivityResponse<ActionHash>> {
-    let query = GetAgentActivityQuery::new(agent, query, options);
+    let query = GetAgentActivityQuery::new(agent.clone(), query, options);
     let results = env
-        .async_reader(move |txn| query.run(Txn::from(&txn)))
+        .async_reader(move |txn| {
+            let mut response = query.run(Txn::from(&txn))?;
+            response.warrants = get_warrants_for_agent(&txn, &agent)?;
+            StateQueryResult::Ok(response)
+        })
         .await?;
     Ok(results)
 }
@@ -95,13 +101,13 @@ pub async fn handle_get_agent_activity_deterministic(
 }
 
 /// Handler for get_links query to a Record/Entry authority
-#[instrument(skip(env, _options))]
+#[instrument(skip(env, options))]
 pub async fn handle_get_links(
     env: DbRead<DbKindDht>,
     link_key: WireLinkKey,
-    _options: holochain_p2p::event::GetLinksOptions,
+    options: holochain_p2p::event::GetLinksOptions,
 ) -> CascadeResult<WireLinkOps> {
-    let query = GetLinksOpsQuery::new(link_key);
+    let query = GetLinksOpsQuery::new(link_key, options.filter);
     let results = env
         .async_reader(move |txn| query.run(Txn::from(&txn)))
         .await?;
@@ -168,6 +168,7 @@ impl Query for GetAgentActivityQuery {
             rejected_activity,
             status,
             highest_observed,
+            warrants: Vec::with_capacity(0),
         })
     }
 }
@@ -1,5 +1,7 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use holo_hash::ActionHash;
 use holo_hash::AnyLinkableHash;
 use holochain_sqlite::rusqlite::named_params;
 use holochain_sqlite::rusqlite::Row;
@@ -11,6 +13,7 @@ use holochain_types::link::WireDeleteLink;
 use holochain_types::link::WireLinkOps;
 use holochain_types::sql::ToSqlStatement;
 use holochain_zome_types::Action;
+use holochain_zome_types::GetLinksFilter;
 use holochain_zome_types::HasValidationStatus;
 use holochain_zome_types::Judged;
 use holochain_zome_types::LinkTag;
@@ -24,14 +27,16 @@ pub struct GetLinksOpsQuery {
     base: Arc<AnyLinkableHash>,
     type_query: LinkTypeFilter,
     tag: Option<Arc<LinkTag>>,
+    filter: GetLinksFilter,
 }
 
 impl GetLinksOpsQuery {
-    pub fn new(key: WireLinkKey) -> Self {
+    pub fn new(key: WireLinkKey, filter: GetLinksFilter) -> Self {
         Self {
             base: Arc::new(key.base),
             type_query: key.type_query,
             tag: key.tag.map(Arc::new),
+            filter,
         }
     }
     pub fn tag_to_hex(tag: &LinkTag) -> String {
@@ -46,18 +51,25 @@ impl GetLinksOpsQuery {
 
 pub struct Item {
     action: SignedAction,
+    hash: ActionHash,
     op_type: DhtOpType,
 }
 
+/// The link ops found, with the hashes of the creates kept for pagination.
+pub struct State {
+    creates: Vec<(ActionHash, WireCreateLink)>,
+    deletes: Vec<WireDeleteLink>,
+}
+
 impl Query for GetLinksOpsQuery {
     type Item = Judged<Item>;
-    type State = WireLinkOps;
-    type Output = Self::State;
+    type State = State;
+    type Output = WireLinkOps;
 
     fn query(&self) -> String {
         let create = "
-            SELECT Action.blob AS action_blob, DhtOp.type AS dht_type,
-            DhtOp.validation_status AS status
+            SELECT Action.blob AS action_blob, Action.hash AS action_hash,
+            DhtOp.type AS dht_type, DhtOp.validation_status AS status
             FROM DhtOp
         ";
         let sub_create = "
@@ -70,6 +82,9 @@ impl Query for GetLinksOpsQuery {
             Action.base_hash = :base_hash
             AND
             DhtOp.when_integrated IS NOT NULL
+            AND (:after IS NULL OR DhtOp.authored_timestamp > :after)
+            AND (:before IS NULL OR DhtOp.authored_timestamp < :before)
+            AND (:author IS NULL OR Action.author = :author)
         "
         .to_string();
 
@@ -96,8 +111,8 @@ impl Query for GetLinksOpsQuery {
         let sub_create_query = format!("{}{}", sub_create, common_query);
         let delete_query = format!(
             "
-            SELECT Action.blob AS action_blob, DhtOp.type AS dht_type,
-            DhtOp.validation_status AS status
+            SELECT Action.blob AS action_blob, Action.hash AS action_hash,
+            DhtOp.type AS dht_type, DhtOp.validation_status AS status
             FROM DhtOp
             JOIN Action On DhtOp.action_hash = Action.hash
             WHERE DhtOp.type = :delete
@@ -116,6 +131,9 @@ impl Query for GetLinksOpsQuery {
             ":create": DhtOpType::RegisterAddLink,
             ":delete": DhtOpType::RegisterRemoveLink,
             ":base_hash": self.base,
+            ":after": self.filter.after,
+            ":before": self.filter.before,
+            ":author": self.filter.author,
         }
         .to_vec()
     }
@@ -124,31 +142,41 @@ impl Query for GetLinksOpsQuery {
         let f = |row: &Row| {
             let action =
                 from_blob::<SignedAction>(row.get(row.as_ref().column_index("action_blob")?)?)?;
+            let hash = row.get(row.as_ref().column_index("action_hash")?)?;
             let op_type = row.get(row.as_ref().column_index("dht_type")?)?;
             let validation_status = row.get(row.as_ref().column_index("status")?)?;
-            Ok(Judged::raw(Item { action, op_type }, validation_status))
+            Ok(Judged::raw(
+                Item {
+                    action,
+                    hash,
+                    op_type,
+                },
+                validation_status,
+            ))
         };
         Arc::new(f)
     }
 
     fn init_fold(&self) -> StateQueryResult<Self::State> {
-        Ok(WireLinkOps::new())
+        Ok(State {
+            creates: Vec::new(),
+            deletes: Vec::new(),
+        })
     }
 
     fn fold(&self, mut state: Self::State, dht_op: Self::Item) -> StateQueryResult<Self::State> {
         match &dht_op.data.op_type {
             DhtOpType::RegisterAddLink => {
                 let validation_status = dht_op.validation_status();
-                let item = dht_op.data.action;
+                let Item { action, hash, .. } = dht_op.data;
                 if let (
                     SignedAction(Action::CreateLink(action), signature),
                     Some(validation_status),
-                ) = (item, validation_status)
+                ) = (action, validation_status)
                 {
-                    state.creates.push(WireCreateLink::condense(
-                        action,
-                        signature,
-                        validation_status,
+                    state.creates.push((
+                        hash,
+                        WireCreateLink::condense(action, signature, validation_status),
                     ));
                 }
             }
@@ -176,6 +204,24 @@ impl Query for GetLinksOpsQuery {
     where
         S: Store,
     {
-        Ok(state)
+        let State { creates, deletes } = state;
+        let creates = if self.filter.is_paginated() {
+            // Only live links count towards the page. The deletes are
+            // still returned so the requester can remove stale links.
+            let deleted: HashSet<_> = deletes.iter().map(|d| &d.link_add_address).collect();
+            self.filter
+                .paginate(
+                    creates
+                        .into_iter()
+                        .filter(|(hash, _)| !deleted.contains(hash)),
+                    |(hash, create)| (create.timestamp, hash.clone()),
+                )
+                .into_iter()
+                .map(|(_, create)| create)
+                .collect()
+        } else {
+            creates.into_iter().map(|(_, create)| create).collect()
+        };
+        Ok(WireLinkOps { creates, deletes })
     }
 }
@@ -853,11 +853,12 @@ where
         key: WireLinkKey,
         options: GetLinksOptions,
     ) -> CascadeResult<Vec<Link>> {
+        let filter = options.filter.clone();
         let authority = self.am_i_an_authority(key.base.clone()).await?;
         if !authority {
             self.fetch_links(key.clone(), options).await?;
         }
-        let query = GetLinksQuery::new(key.base, key.type_query, key.tag);
+        let query = GetLinksQuery::with_filter(key.base, key.type_query, key.tag, filter);
         let results = self.cascading(query).await?;
         Ok(results)
     }
@@ -1005,6 +1006,7 @@ where
             rejected_activity,
             status,
             highest_observed,
+            warrants,
         } = merged_response;
         let valid_activity = match valid_activity {
             ChainItems::Hashes(hashes) => {
@@ -1059,6 +1061,7 @@ where
             rejected_activity,
             status,
             highest_observed,
+            warrants,
         };
         Ok(r)
     }
@@ -47,6 +47,7 @@ use holochain_types::metadata::MetadataSet;
 use holochain_types::prelude::WireEntryOps;
 use holochain_types::record::WireRecordOps;
 use holochain_types::test_utils::chain::*;
+use holochain_zome_types::warrant::SignedWarrant;
 use holochain_zome_types::zome_io::Nonce256Bits;
 use holochain_zome_types::ActionRefMut;
 use holochain_zome_types::QueryFilter;
@@ -242,6 +243,10 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         todo!()
     }
 
+    async fn publish_warrant(&self, _warrant: SignedWarrant) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
     async fn send_validation_receipt(
         &self,
         _to_agent: AgentPubKey,
@@ -445,6 +450,10 @@ impl HolochainP2pDnaT for MockNetwork {
         todo!()
     }
 
+    async fn publish_warrant(&self, _warrant: SignedWarrant) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
     async fn send_validation_receipt(
         &self,
         _to_agent: AgentPubKey,
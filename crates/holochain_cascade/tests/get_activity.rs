@@ -63,6 +63,7 @@ async fn get_activity() {
         rejected_activity: ChainItems::NotRequested,
         status: ChainStatus::Valid(td.chain_head.clone()),
         highest_observed: Some(td.highest_observed.clone()),
+        warrants: vec![],
     };
     assert_eq!(r, expected);
 }
@@ -1,9 +1,15 @@
 use holo_hash::*;
+use holochain_state::schedule::ScheduledFnInfo;
 use holochain_types::prelude::*;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::dht_arc::ArcPin;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
-use crate::{AppInfo, FullStateDump, StorageInfo};
+use crate::{
+    AllowedOrigins, AppInfo, FullStateDump, InterfaceDriver, ProvisionedCell, StorageInfo,
+};
 
 /// Represents the available conductor functions to call over an admin interface.
 ///
@@ -19,6 +25,19 @@ use crate::{AppInfo, FullStateDump, StorageInfo};
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 #[serde(rename_all = "snake_case", tag = "type", content = "data")]
 pub enum AdminRequest {
+    /// Authenticate the connection this request is sent on with the token
+    /// set by the `auth` of the admin interface's [`AdminInterfaceConfig`].
+    ///
+    /// Unless the interface's `auth` is `none`, every connection has to be
+    /// authenticated before any other request is handled.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::Authenticated`]
+    ///
+    /// [`AdminInterfaceConfig`]: crate::config::AdminInterfaceConfig
+    Authenticate(AdminAuthenticationRequest),
+
     /// Set up and register one or more new admin interfaces
     /// as specified by a list of configurations.
     ///
@@ -79,6 +98,10 @@ pub enum AdminRequest {
     /// any persisted data.
     /// Cells which are still referenced by other installed apps will not be removed.
     ///
+    /// An app whose cells other apps depend on, through the `use_existing` or
+    /// `create_if_not_exists` provisioning strategies, can't be uninstalled
+    /// until those apps have been uninstalled.
+    ///
     /// # Returns
     ///
     /// [`AdminResponse::AppUninstalled`]
@@ -150,6 +173,8 @@ pub enum AdminRequest {
     /// Open up a new websocket for processing [`AppRequest`]s.
     ///
     /// Any active app will be callable via the attached app interface.
+    /// Clients must authenticate each connection with a token from
+    /// [`AdminRequest::IssueAppAuthenticationToken`] before making requests.
     ///
     /// # Returns
     ///
@@ -165,14 +190,34 @@ pub enum AdminRequest {
     AttachAppInterface {
         /// Optional port number
         port: Option<u16>,
+        /// The origins that may connect to the interface.
+        /// Any origin may connect if this is not set.
+        #[serde(default)]
+        allowed_origins: AllowedOrigins,
+    },
+
+    /// Open up a new unix domain socket for processing [`AppRequest`]s,
+    /// using the same websocket framing as [`AdminRequest::AttachAppInterface`].
+    ///
+    /// Access to the interface is controlled by the permissions of the socket
+    /// file, and clients must still authenticate each connection.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppUnixSocketInterfaceAttached`]
+    ///
+    /// [`AppRequest`]: super::AppRequest
+    AttachAppUnixSocketInterface {
+        /// The path at which to create the socket
+        path: PathBuf,
     },
 
     /// List all the app interfaces currently attached with [`AttachAppInterface`].
     ///
     /// # Returns
     ///
-    /// [`AdminResponse::AppInterfacesListed`], a list of websocket ports that can
-    /// process [`AppRequest`]s.
+    /// [`AdminResponse::AppInterfacesListed`], a list of the drivers of the
+    /// interfaces that can process [`AppRequest`]s, giving their port or socket path.
     ///
     /// [`AttachAppInterface`]: AdminRequest::AttachAppInterface
     /// [`AppRequest`]: super::AppRequest
@@ -222,9 +267,22 @@ pub enum AdminRequest {
         dna_hash: Option<DnaHash>,
     },
 
-    /// Dump raw json network statistics from the backend networking lib.
+    /// Dump raw json network statistics from the backend networking lib,
+    /// along with the bytes exchanged with each peer in each space under
+    /// the `bandwidth` key.
     DumpNetworkStats,
 
+    /// Dump a summary of the ops waiting to be fetched for a DNA, including
+    /// those restored from before the conductor was last restarted.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::FetchPoolDumped`]
+    DumpFetchPool {
+        /// The DNA hash of the space whose fetch pool to dump.
+        dna_hash: DnaHash,
+    },
+
     /// Add a list of agents to this conductor's peer store.
     ///
     /// This is a way of shortcutting peer discovery and is useful for testing.
@@ -259,6 +317,26 @@ pub enum AdminRequest {
         cell_id: Option<CellId>,
     },
 
+    /// Pin the storage arc of a cell's agent at a fixed size, so that it's
+    /// no longer resized according to the density of its peers, or unpin it.
+    ///
+    /// Pin the arc to full on always-on nodes which should hold all the data,
+    /// or to empty on nodes with too few resources to hold data for others,
+    /// which still publish the data they author.
+    ///
+    /// The agent info of the cell is signed and published with the new arc
+    /// straight away. The pin is persisted, so it's kept across restarts.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ArcPinSet`]
+    SetArcPin {
+        /// The cell whose agent's arc to pin.
+        cell_id: CellId,
+        /// The pin to hold the arc with, or `None` to resize it dynamically again.
+        pin: Option<ArcPin>,
+    },
+
     /// "Graft" [`Record`]s onto the source chain of the specified [`CellId`].
     ///
     /// The records must form a valid chain segment (ascending sequence numbers,
@@ -324,8 +402,126 @@ pub enum AdminRequest {
     /// [`AdminResponse::CloneCellDeleted`]
     DeleteCloneCell(Box<DeleteCloneCellPayload>),
 
+    /// Provision the cell of a role whose provisioning was deferred when the
+    /// app was installed, i.e. a role with `deferred: true` in the app manifest.
+    ///
+    /// Depending on the role's provisioning strategy, the role is either bound
+    /// to an existing cell of another app, or a new cell is created with the
+    /// given membrane proof. If the app is enabled, the new cell is started.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::DeferredCellProvisioned`]
+    ProvisionDeferredCell(Box<ProvisionDeferredCellPayload>),
+
     /// Info about storage used by apps
     StorageInfo,
+
+    /// Migrate an agent's source chain from one cell to another.
+    ///
+    /// The two cells must differ either in their DNA hash, to migrate to a new
+    /// version of a DNA, or in their agent key, to migrate to a new key.
+    /// Both cells must be running on this conductor.
+    ///
+    /// The `migrate_agent` callbacks are run in both cells, and if all pass,
+    /// the source chain of `from` is closed with a `CloseChain` action pointing
+    /// at `to`, and the source chain of `to` is opened with an `OpenChain`
+    /// action referencing the closed chain.
+    /// No further actions may be written to the closed chain.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ChainMigrated`]
+    MigrateChain {
+        /// The cell whose chain is to be closed
+        from: CellId,
+        /// The cell whose chain succeeds it
+        to: CellId,
+    },
+
+    /// Issue a token that a client can use to authenticate a connection
+    /// to an app interface, see [`AppRequest::Authenticate`].
+    ///
+    /// The connection will only be able to access the apps the token is
+    /// issued for, and is closed once the token expires or is revoked.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppAuthenticationTokenIssued`]
+    ///
+    /// [`AppRequest::Authenticate`]: super::AppRequest::Authenticate
+    IssueAppAuthenticationToken(IssueAppAuthenticationTokenPayload),
+
+    /// Revoke a token so that it can no longer be used to authenticate.
+    /// Connections that were already authenticated with it are closed.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppAuthenticationTokenRevoked`]
+    RevokeAppAuthenticationToken(AppAuthenticationToken),
+
+    /// List the functions scheduled for a cell, with when each will next be
+    /// dispatched and its most recent executions.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ScheduledFunctionsListed`]
+    ListScheduledFunctions {
+        /// The cell to list the scheduled functions of
+        cell_id: CellId,
+    },
+
+    /// Stop dispatching a scheduled function until it is resumed.
+    /// The function keeps its schedule while paused.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ScheduledFunctionPaused`]
+    PauseScheduledFunction {
+        /// The cell the function is scheduled for
+        cell_id: CellId,
+        /// The zome the function is in
+        zome_name: ZomeName,
+        /// The scheduled function
+        fn_name: FunctionName,
+    },
+
+    /// Resume dispatching a paused scheduled function.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ScheduledFunctionResumed`]
+    ResumeScheduledFunction {
+        /// The cell the function is scheduled for
+        cell_id: CellId,
+        /// The zome the function is in
+        zome_name: ZomeName,
+        /// The scheduled function
+        fn_name: FunctionName,
+    },
+
+    /// Unschedule a function and forget its execution history.
+    /// The zome may schedule the function again.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ScheduledFunctionCancelled`]
+    CancelScheduledFunction {
+        /// The cell the function is scheduled for
+        cell_id: CellId,
+        /// The zome the function is in
+        zome_name: ZomeName,
+        /// The scheduled function
+        fn_name: FunctionName,
+    },
+
+    /// Dump the number of zome calls that have been rejected by the
+    /// conductor's zome call rate limits since it started.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ZomeCallRateLimitMetricsDumped`]
+    DumpZomeCallRateLimitMetrics,
 }
 
 /// Represents the possible responses to an [`AdminRequest`]
@@ -400,8 +596,14 @@ pub enum AdminResponse {
         port: u16,
     },
 
+    /// The successful response to an [`AdminRequest::AttachAppUnixSocketInterface`].
+    AppUnixSocketInterfaceAttached {
+        /// Path of the socket of the new `AppInterfaceApi`
+        path: PathBuf,
+    },
+
     /// The list of attached app interfaces.
-    AppInterfacesListed(Vec<u16>),
+    AppInterfacesListed(Vec<InterfaceDriver>),
 
     /// The successful response to an [`AdminRequest::EnableApp`].
     ///
@@ -440,9 +642,14 @@ pub enum AdminResponse {
     /// The successful result of a call to [`AdminRequest::DumpNetworkStats`].
     ///
     /// The string is a raw JSON blob returned directly from the backend
-    /// networking library.
+    /// networking library, with the bandwidth usage added.
     NetworkStatsDumped(String),
 
+    /// The successful result of a call to [`AdminRequest::DumpFetchPool`].
+    ///
+    /// The string is a human readable table of the items in the fetch pool.
+    FetchPoolDumped(String),
+
     /// The successful response to an [`AdminRequest::AddAgentInfo`].
     ///
     /// This means the agent info was successfully added to the peer store.
@@ -453,6 +660,9 @@ pub enum AdminResponse {
     /// This is all the agent info that was found for the request.
     AgentInfo(Vec<AgentInfoSigned>),
 
+    /// The successful response to an [`AdminRequest::SetArcPin`].
+    ArcPinSet,
+
     /// The successful response to an [`AdminRequest::GraftRecords`].
     RecordsGrafted,
 
@@ -462,8 +672,42 @@ pub enum AdminResponse {
     /// The successful response to an [`AdminRequest::DeleteCloneCell`].
     CloneCellDeleted,
 
+    /// The successful response to an [`AdminRequest::ProvisionDeferredCell`].
+    ///
+    /// Contains the cell which the role is now bound to.
+    DeferredCellProvisioned(ProvisionedCell),
+
     /// The successful response to an [`AdminRequest::StorageInfo`].
     StorageInfo(StorageInfo),
+
+    /// The successful response to an [`AdminRequest::MigrateChain`].
+    ///
+    /// Contains the hashes of the `CloseChain` and `OpenChain` actions, in that order.
+    ChainMigrated(ActionHash, ActionHash),
+
+    /// The successful response to an [`AdminRequest::Authenticate`].
+    Authenticated,
+
+    /// The successful response to an [`AdminRequest::IssueAppAuthenticationToken`].
+    AppAuthenticationTokenIssued(AppAuthenticationTokenIssued),
+
+    /// The successful response to an [`AdminRequest::RevokeAppAuthenticationToken`].
+    AppAuthenticationTokenRevoked,
+
+    /// The successful response to an [`AdminRequest::ListScheduledFunctions`].
+    ScheduledFunctionsListed(Vec<ScheduledFnInfo>),
+
+    /// The successful response to an [`AdminRequest::PauseScheduledFunction`].
+    ScheduledFunctionPaused,
+
+    /// The successful response to an [`AdminRequest::ResumeScheduledFunction`].
+    ScheduledFunctionResumed,
+
+    /// The successful response to an [`AdminRequest::CancelScheduledFunction`].
+    ScheduledFunctionCancelled,
+
+    /// The successful response to an [`AdminRequest::DumpZomeCallRateLimitMetrics`].
+    ZomeCallRateLimitMetricsDumped(ZomeCallRateLimitMetrics),
 }
 
 /// Error type that goes over the websocket wire.
@@ -489,6 +733,24 @@ pub enum ExternalApiWireError {
     ZomeCallUnauthorized(String),
     /// A countersigning session has failed.
     CountersigningSessionError(String),
+    /// An app interface connection could not be authenticated.
+    AuthenticationFailed(String),
+    /// The request is not allowed on this app interface connection.
+    Unauthorized(String),
+    /// The zome call was rejected because it is over a rate limit.
+    /// It may succeed if retried once the limit has recovered.
+    RateLimited(String),
+    /// The zome calls were rejected because they could never fit within a
+    /// rate limit, being more calls than the limit allows per second.
+    /// Retrying them won't help.
+    RateLimitExceeded(String),
+    /// A zome call of a batch failed, so none of the batch was committed.
+    ZomeCallBatchFailed {
+        /// The position of the zome call in the batch
+        index: usize,
+        /// Why the zome call failed
+        error: Box<ExternalApiWireError>,
+    },
 }
 
 impl ExternalApiWireError {
@@ -500,6 +762,91 @@ impl ExternalApiWireError {
     }
 }
 
+/// The data provided over an admin interface to authenticate the connection.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct AdminAuthenticationRequest {
+    /// The token set by the `auth` of the admin interface's
+    /// [`AdminInterfaceConfig`].
+    ///
+    /// [`AdminInterfaceConfig`]: crate::config::AdminInterfaceConfig
+    pub token: String,
+}
+
+/// A token that lets a client authenticate a connection to an app interface.
+pub type AppAuthenticationToken = Vec<u8>;
+
+/// The payload for [`AdminRequest::IssueAppAuthenticationToken`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct IssueAppAuthenticationTokenPayload {
+    /// The apps that connections authenticated with the token can access.
+    pub installed_app_ids: Vec<InstalledAppId>,
+    /// How many seconds the token can be used for, `0` for no expiry.
+    /// Connections authenticated with the token are closed when it expires.
+    /// [default = 30]
+    #[serde(default = "default_token_expiry_seconds")]
+    pub expiry_seconds: u64,
+    /// Whether the token is revoked once it has been used. [default = true]
+    #[serde(default = "default_token_single_use")]
+    pub single_use: bool,
+}
+
+fn default_token_expiry_seconds() -> u64 {
+    30
+}
+
+fn default_token_single_use() -> bool {
+    true
+}
+
+impl IssueAppAuthenticationTokenPayload {
+    /// A single use token for these apps which expires after 30 seconds.
+    pub fn for_apps(installed_app_ids: Vec<InstalledAppId>) -> Self {
+        Self {
+            installed_app_ids,
+            expiry_seconds: default_token_expiry_seconds(),
+            single_use: default_token_single_use(),
+        }
+    }
+
+    /// Builder-style setter.
+    pub fn expiry_seconds(mut self, expiry_seconds: u64) -> Self {
+        self.expiry_seconds = expiry_seconds;
+        self
+    }
+
+    /// Builder-style setter.
+    pub fn single_use(mut self, single_use: bool) -> Self {
+        self.single_use = single_use;
+        self
+    }
+}
+
+/// The response to [`AdminRequest::IssueAppAuthenticationToken`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AppAuthenticationTokenIssued {
+    /// The token to pass to [`AppRequest::Authenticate`].
+    ///
+    /// [`AppRequest::Authenticate`]: super::AppRequest::Authenticate
+    pub token: AppAuthenticationToken,
+    /// When the token expires, if it does.
+    pub expires_at: Option<Timestamp>,
+}
+
+/// The response to [`AdminRequest::DumpZomeCallRateLimitMetrics`].
+///
+/// Cap secrets are not included, so rejections by the per cap secret limit
+/// are only counted in total.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ZomeCallRateLimitMetrics {
+    /// The number of zome calls rejected by the per app limit, by app.
+    pub rejected_by_app: HashMap<InstalledAppId, u64>,
+    /// The number of zome calls rejected by the per cap secret limit.
+    pub rejected_by_cap_secret: u64,
+    /// The number of zome calls rejected by the per provenance limit,
+    /// by provenance.
+    pub rejected_by_provenance: HashMap<AgentPubKey, u64>,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone)]
 /// Filter for [`AdminRequest::ListApps`].
 pub enum AppStatusFilter {
@@ -1,3 +1,5 @@
+use crate::signal_subscription::SignalSubscription;
+use crate::AppAuthenticationToken;
 use crate::ExternalApiWireError;
 use holo_hash::AgentPubKey;
 use holochain_keystore::LairResult;
@@ -10,12 +12,26 @@ use std::collections::HashMap;
 /// and will result in a corresponding [`AppResponse`] message being sent back over the
 /// interface connection.
 ///
+/// Every connection has to be authenticated with [`AppRequest::Authenticate`]
+/// before any other request is handled, and can then only access the apps
+/// its token was issued for.
+///
 /// # Errors
 ///
 /// Returns an [`AppResponse::Error`] with a reason why the request failed.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 #[serde(rename_all = "snake_case", tag = "type", content = "data")]
 pub enum AppRequest {
+    /// Authenticate the connection this request is sent on with a token from
+    /// [`AdminRequest::IssueAppAuthenticationToken`].
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::Authenticated`]
+    ///
+    /// [`AdminRequest::IssueAppAuthenticationToken`]: crate::AdminRequest::IssueAppAuthenticationToken
+    Authenticate(AppAuthenticationRequest),
+
     /// Get info about the app identified by the given `installed_app_id` argument,
     /// including info about each cell installed by this app.
     ///
@@ -38,6 +54,43 @@ pub enum AppRequest {
     /// [`AppResponse::ZomeCalled`]
     CallZome(Box<ZomeCall>),
 
+    /// Call several zome functions of the same cell, committing what they
+    /// all write to the source chain together.
+    ///
+    /// The calls are made in order. If one of them fails, or what it writes
+    /// is invalid, the rest are not made and nothing any of them wrote is
+    /// committed.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeBatchCalled`], or an [`AppResponse::Error`] with an
+    /// [`ExternalApiWireError::ZomeCallBatchFailed`] for the call that failed.
+    /// An error which happens after the batch was committed is returned in
+    /// the [`AppResponse::ZomeBatchCalled`].
+    CallZomeBatch(Vec<ZomeCall>),
+
+    /// Call a zome function which streams its output with the `stream_chunk`
+    /// host function.
+    ///
+    /// Each chunk is sent on this connection as a [`Signal::ZomeCallStream`]
+    /// with the given `stream_id`, ahead of the response to this request.
+    /// The zome function is held up while the client falls behind in
+    /// receiving chunks, and fails if the stream is cancelled with
+    /// [`AppRequest::CancelZomeCallStream`].
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeCalled`] with the return value of the zome function,
+    /// once every chunk has been sent.
+    CallZomeStreaming(Box<CallZomeStreamingPayload>),
+
+    /// Cancel a streaming zome call made on this connection.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeCallStreamCancelled`]
+    CancelZomeCallStream(ZomeCallStreamId),
+
     /// Clone a DNA (in the biological sense), thus creating a new `Cell`.
     ///
     /// Using the provided, already-registered DNA, create a new DNA with a unique
@@ -77,6 +130,26 @@ pub enum AppRequest {
     ///
     /// [`AppResponse::ListWasmHostFunctions`]
     ListWasmHostFunctions,
+
+    /// List the countersigning sessions that the given cell is coordinating
+    /// as the enzyme, along with recently finished ones.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::EnzymaticCountersigningSessions`]
+    EnzymaticCountersigningSessions(Box<CellId>),
+
+    /// Replace the signal filters for an app on the connection this request
+    /// is sent on. The connection must have access to the app.
+    ///
+    /// Subscriptions are kept per connection and app, so other clients of
+    /// the same app interface are unaffected. Until a subscription is set
+    /// for an app, every signal about that app is sent.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::SignalSubscriptionUpdated`]
+    SignalSubscription(Box<SignalSubscription>),
 }
 
 /// Represents the possible responses to an [`AppRequest`].
@@ -93,7 +166,8 @@ pub enum AppResponse {
     /// Option will be `None` if there is no installed app with the given `installed_app_id`.
     AppInfo(Option<AppInfo>),
 
-    /// The successful response to an [`AppRequest::CallZome`].
+    /// The successful response to an [`AppRequest::CallZome`] or an
+    /// [`AppRequest::CallZomeStreaming`].
     ///
     /// Note that [`ExternIO`] is simply a structure of [`struct@SerializedBytes`], so the client will have
     /// to decode this response back into the data provided by the zome using a [msgpack] library to utilize it.
@@ -101,6 +175,17 @@ pub enum AppResponse {
     /// [msgpack]: https://msgpack.org/
     ZomeCalled(Box<ExternIO>),
 
+    /// The successful response to an [`AppRequest::CallZomeBatch`].
+    ///
+    /// Contains the output of each zome call, in the order they were made.
+    ZomeBatchCalled {
+        /// The output of each zome call
+        outputs: Vec<ExternIO>,
+        /// Set if the batch was committed, but a countersigning session one
+        /// of the calls started couldn't be published afterwards.
+        post_commit_error: Option<ExternalApiWireError>,
+    },
+
     /// The successful response to an [`AppRequest::CreateCloneCell`].
     ///
     /// The response contains the created clone [`ClonedCell`].
@@ -122,6 +207,34 @@ pub enum AppResponse {
 
     /// All the wasm host functions supported by this conductor.
     ListWasmHostFunctions(Vec<String>),
+
+    /// The successful response to an [`AppRequest::EnzymaticCountersigningSessions`].
+    EnzymaticCountersigningSessions(Vec<EnzymaticCountersigningSessionInfo>),
+
+    /// The successful response to an [`AppRequest::CancelZomeCallStream`].
+    ///
+    /// No more chunks will be sent for the stream, and the zome call it
+    /// belongs to fails.
+    ZomeCallStreamCancelled,
+
+    /// The successful response to an [`AppRequest::SignalSubscription`].
+    ///
+    /// The filters apply to all signals sent on this connection from now on.
+    SignalSubscriptionUpdated,
+
+    /// The successful response to an [`AppRequest::Authenticate`].
+    ///
+    /// Contains the apps this connection can access.
+    Authenticated(Vec<InstalledAppId>),
+}
+
+/// The data provided over an app interface to authenticate the connection.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct AppAuthenticationRequest {
+    /// The token issued by [`AdminRequest::IssueAppAuthenticationToken`].
+    ///
+    /// [`AdminRequest::IssueAppAuthenticationToken`]: crate::AdminRequest::IssueAppAuthenticationToken
+    pub token: AppAuthenticationToken,
 }
 
 /// The data provided over an app interface in order to make a zome call
@@ -149,6 +262,17 @@ pub struct ZomeCall {
     pub expires_at: Timestamp,
 }
 
+/// The data provided over an app interface in order to make a streaming
+/// zome call
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct CallZomeStreamingPayload {
+    /// Identifies the stream among the others on this connection.
+    /// Every chunk sent for this call carries it.
+    pub stream_id: ZomeCallStreamId,
+    /// The zome call to make
+    pub call: ZomeCall,
+}
+
 impl From<ZomeCall> for ZomeCallUnsigned {
     fn from(zome_call: ZomeCall) -> Self {
         Self {
@@ -221,7 +345,6 @@ pub enum CellInfo {
     Cloned(ClonedCell),
 
     /// Potential cells with deferred installation as defined in the bundle.
-    /// Not yet implemented.
     Stem(StemCell),
 }
 
@@ -254,7 +377,6 @@ impl CellInfo {
 }
 
 /// Cell whose instantiation has been deferred.
-/// Not yet implemented.
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct StemCell {
     /// The hash of the DNA that this cell would be instantiated from
@@ -350,10 +472,17 @@ impl AppInfo {
                 } else {
                     tracing::error!("no DNA definition found for cell id {}", provisioned_cell);
                 }
-            } else {
-                // no provisioned cell, thus there must be a deferred cell
-                // this is not implemented as of now
-                unimplemented!()
+            } else if role_assignment.is_deferred() {
+                // no provisioned cell yet, the cell is deferred.
+                // If the cell will use an existing cell of another app, its DNA may
+                // not be installed yet, in which case there's nothing to report.
+                if let Some(dna_def) = dna_definitions.get(role_assignment.cell_id()) {
+                    cell_info_for_role.push(CellInfo::Stem(StemCell {
+                        original_dna_hash: dna_def.hash.clone(),
+                        dna_modifiers: dna_def.modifiers.to_owned(),
+                        name: None,
+                    }));
+                }
             };
 
             // push enabled clone cells to the vector of cell infos
@@ -436,6 +565,35 @@ impl From<AppInfoStatus> for AppStatus {
     }
 }
 
+/// Info about a countersigning session that a cell is coordinating as the
+/// enzyme, returned as part of [`AppResponse::EnzymaticCountersigningSessions`]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct EnzymaticCountersigningSessionInfo {
+    /// The hash of the countersigned entry
+    pub entry_hash: EntryHash,
+    /// Where the session is up to
+    pub status: EnzymaticCountersigningStatus,
+    /// The signers whose signed actions the enzyme has received
+    pub signed: Vec<AgentPubKey>,
+    /// The signers the enzyme is still waiting on
+    pub outstanding: Vec<AgentPubKey>,
+    /// When the session times out if it isn't complete
+    pub expires: Timestamp,
+}
+
+/// The status of a countersigning session coordinated by an enzyme
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnzymaticCountersigningStatus {
+    /// Signed actions are still being collected from the signers.
+    Collecting,
+    /// Every signer has signed and the signed actions were sent to all of them.
+    Complete,
+    /// The session expired before every signer had signed, so the signers
+    /// were told to abandon it.
+    TimedOut,
+}
+
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
 pub struct NetworkInfo {
     pub fetch_pool_info: FetchPoolInfo,
@@ -7,23 +7,35 @@ use serde::Deserialize;
 use serde::Serialize;
 
 mod admin_interface_config;
+mod app_store_config;
 mod dpki_config;
 #[allow(missing_docs)]
 mod error;
 mod keystore_config;
+mod metrics_config;
 pub mod paths;
+mod rate_limit_config;
 //mod logger_config;
 //mod signal_config;
 pub use paths::DatabaseRootPath;
 
 pub use super::*;
+pub use app_store_config::AppStoreConfig;
 pub use dpki_config::DpkiConfig;
 //pub use logger_config::LoggerConfig;
 pub use error::*;
 pub use keystore_config::KeystoreConfig;
+pub use metrics_config::MetricsConfig;
+pub use rate_limit_config::*;
 //pub use signal_config::SignalConfig;
 use std::path::Path;
 
+/// The default for [`ConductorConfig::max_sleep_ms`].
+pub const DEFAULT_MAX_SLEEP_MS: u64 = 60 * 1000;
+
+/// The default for [`ConductorConfig::max_sleeping_zome_calls`].
+pub const DEFAULT_MAX_SLEEPING_ZOME_CALLS: usize = 64;
+
 // TODO change types from "stringly typed" to Url2
 /// All the config information for the conductor
 #[derive(Clone, Deserialize, Serialize, Default, Debug, PartialEq)]
@@ -44,15 +56,22 @@ pub struct ConductorConfig {
     /// keys for new instances.
     pub dpki: Option<DpkiConfig>,
 
+    /// Optional app store configuration, if the conductor should be able to install
+    /// apps by their hash from an app store DNA.
+    #[serde(default)]
+    pub app_store: Option<AppStoreConfig>,
+
     /// Setup admin interfaces to control this conductor through a websocket connection.
     pub admin_interfaces: Option<Vec<AdminInterfaceConfig>>,
 
     /// Optional config for the network module.
     pub network: Option<holochain_p2p::kitsune_p2p::KitsuneP2pConfig>,
 
-    /// **PLACEHOLDER**: Optional specification of the Cloudflare namespace to use in Chain Head Coordination
-    /// service URLs. This is a placeholder for future work and may even go away.
-    /// Setting this to anything other than `None` will surely lead to no good.
+    /// Optional base URL of a remote Chain Head Coordination service.
+    ///
+    /// Each cell's CHC endpoints live under `{chc_namespace}/{dna_hash}/{agent_pubkey}`.
+    /// The special value `"#LOCAL#"` uses an in-process CHC, for testing only.
+    /// Requires the `chc` feature.
     #[serde(default)]
     pub chc_namespace: Option<String>,
 
@@ -66,6 +85,29 @@ pub struct ConductorConfig {
     /// [sqlite documentation]: https://www.sqlite.org/pragma.html#pragma_synchronous
     #[serde(default)]
     pub db_sync_strategy: DbSyncStrategy,
+
+    /// The longest a zome call can sleep for with a single call to the
+    /// `sleep` host function, in milliseconds.
+    /// Defaults to [`DEFAULT_MAX_SLEEP_MS`].
+    #[serde(default)]
+    pub max_sleep_ms: Option<u64>,
+
+    /// The most zome calls which can be sleeping at once. A sleeping zome call
+    /// keeps its wasm instance and a blocking thread, so this bounds what they
+    /// can hold. Further calls to `sleep` fail until one wakes.
+    /// Defaults to [`DEFAULT_MAX_SLEEPING_ZOME_CALLS`].
+    #[serde(default)]
+    pub max_sleeping_zome_calls: Option<usize>,
+
+    /// Optional limits on how fast clients of the app interfaces can call
+    /// zome functions. There are no limits if omitted.
+    #[serde(default)]
+    pub zome_call_rate_limits: Option<ZomeCallRateLimitConfig>,
+
+    /// Optional config for exporting metrics and traces for monitoring.
+    /// Nothing is exported if omitted.
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
     //
     //
     // Which signals to emit
@@ -90,7 +132,24 @@ impl ConductorConfig {
             }
             _ => err.into(),
         })?;
-        config_from_yaml(&config_yaml)
+        let config: ConductorConfig = config_from_yaml(&config_yaml)?;
+        if let Some(network) = &config.network {
+            network
+                .validate_gossip_strategies()
+                .map_err(|e| ConductorConfigError::InvalidNetworkConfig(e.to_string()))?;
+        }
+        Ok(config)
+    }
+
+    /// The longest a zome call can sleep for at a time.
+    pub fn max_sleep_duration(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.max_sleep_ms.unwrap_or(DEFAULT_MAX_SLEEP_MS))
+    }
+
+    /// The most zome calls which can be sleeping at once.
+    pub fn max_sleeping_zome_calls(&self) -> usize {
+        self.max_sleeping_zome_calls
+            .unwrap_or(DEFAULT_MAX_SLEEPING_ZOME_CALLS)
     }
 }
 
@@ -135,10 +194,15 @@ pub mod tests {
                 environment_path: PathBuf::from("/path/to/env").into(),
                 network: None,
                 dpki: None,
+                app_store: None,
                 keystore: KeystoreConfig::DangerTestKeystore,
                 admin_interfaces: None,
                 db_sync_strategy: DbSyncStrategy::default(),
                 chc_namespace: None,
+                max_sleep_ms: None,
+                max_sleeping_zome_calls: None,
+                zome_call_rate_limits: None,
+                metrics: None,
             }
         );
     }
@@ -182,6 +246,13 @@ pub mod tests {
       network_type: quic_bootstrap
 
     db_sync_strategy: Fast
+
+    zome_call_rate_limits:
+      per_app:
+        calls_per_second: 10
+      per_provenance:
+        calls_per_second: 5
+        bytes_per_minute: 1000000
     "#;
         let result: ConductorConfigResult<ConductorConfig> = config_from_yaml(yaml);
         use holochain_p2p::kitsune_p2p::*;
@@ -208,15 +279,36 @@ pub mod tests {
                 environment_path: PathBuf::from("/path/to/env").into(),
                 dpki: Some(DpkiConfig {
                     instance_id: "some_id".into(),
-                    init_params: "some_params".into()
+                    init_params: "some_params".into(),
+                    dna_path: None,
                 }),
+                app_store: None,
                 keystore: KeystoreConfig::LairServerInProc { lair_root: None },
                 admin_interfaces: Some(vec![AdminInterfaceConfig {
-                    driver: InterfaceDriver::Websocket { port: 1234 }
+                    driver: InterfaceDriver::Websocket {
+                        port: 1234,
+                        allowed_origins: AllowedOrigins::Any,
+                    },
+                    session_duration_seconds: None,
+                    auth: AdminInterfaceAuth::GeneratedToken,
                 }]),
                 network: Some(network_config),
                 db_sync_strategy: DbSyncStrategy::Fast,
                 chc_namespace: None,
+                max_sleep_ms: None,
+                max_sleeping_zome_calls: None,
+                zome_call_rate_limits: Some(ZomeCallRateLimitConfig {
+                    per_app: Some(RateLimit {
+                        calls_per_second: Some(10),
+                        bytes_per_minute: None,
+                    }),
+                    per_cap_secret: None,
+                    per_provenance: Some(RateLimit {
+                        calls_per_second: Some(5),
+                        bytes_per_minute: Some(1000000),
+                    }),
+                }),
+                metrics: None,
             }
         );
     }
@@ -239,12 +331,17 @@ pub mod tests {
                 environment_path: PathBuf::from("/path/to/env").into(),
                 network: None,
                 dpki: None,
+                app_store: None,
                 keystore: KeystoreConfig::LairServer {
                     connection_url: url2::url2!("unix:///var/run/lair-keystore/socket?k=EcRDnP3xDIZ9Rk_1E-egPE0mGZi5CcszeRxVkb2QXXQ").into(),
                 },
                 admin_interfaces: None,
                 db_sync_strategy: DbSyncStrategy::Fast,
                 chc_namespace: None,
+                max_sleep_ms: None,
+                max_sleeping_zome_calls: None,
+                zome_call_rate_limits: None,
+                metrics: None,
             }
         );
     }
@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Configure which app instance ID to treat as the app store, from which
+/// app and DNA bundles can be fetched by hash.
+///
+/// At startup, if no app is installed under `instance_id`, the DNA at `dna_path`
+/// is installed under that id with a freshly generated agent key, and enabled.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct AppStoreConfig {
+    /// The InstalledAppId of the app store app
+    pub instance_id: String,
+
+    /// Path to the app store DNA bundle. Only required if the app store app is not
+    /// already installed.
+    #[serde(default)]
+    pub dna_path: Option<PathBuf>,
+}
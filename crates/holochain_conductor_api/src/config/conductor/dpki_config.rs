@@ -1,13 +1,27 @@
-// Legacy config that will probably change
-#![allow(missing_docs)]
+use std::path::PathBuf;
 
 use serde::Deserialize;
 use serde::Serialize;
 
 /// Configure which app instance ID to treat as the DPKI application handler
 /// as well as what parameters to pass it on its initialization.
+///
+/// At startup, if no app is installed under `instance_id`, the DNA at `dna_path`
+/// is installed under that id with a freshly generated agent key, and enabled.
+/// From then on, the conductor consults this DNA to register new agent keys
+/// and to check the validity of authors' keys during sys validation.
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct DpkiConfig {
+    /// The InstalledAppId of the DPKI app
     pub instance_id: String,
+
+    /// DNA properties, as a YAML string, to install the DPKI DNA with.
+    /// Leave empty to use the properties in the DNA bundle.
+    #[serde(default)]
     pub init_params: String,
+
+    /// Path to the DPKI DNA bundle. Only required if the DPKI app is not
+    /// already installed.
+    #[serde(default)]
+    pub dna_path: Option<PathBuf>,
 }
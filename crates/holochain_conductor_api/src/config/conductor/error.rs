@@ -11,6 +11,9 @@ pub enum ConductorConfigError {
     #[error("Config deserialization error: {0}")]
     SerializationError(#[from] serde_yaml::Error),
 
+    #[error("Invalid network config: {0}")]
+    InvalidNetworkConfig(String),
+
     #[error("Error while performing IO for the Conductor: {0}")]
     IoError(#[from] std::io::Error),
 }
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Configure how the conductor exports metrics and traces for monitoring.
+#[derive(Clone, Deserialize, Serialize, Default, Debug, PartialEq, Eq)]
+pub struct MetricsConfig {
+    /// Serve metrics in the Prometheus text format at `/metrics` on this
+    /// port, on the local interface only.
+    #[serde(default)]
+    pub prometheus_port: Option<u16>,
+
+    /// Send traces to the OpenTelemetry collector listening for OTLP over
+    /// gRPC at this endpoint, e.g. `http://localhost:4317`.
+    ///
+    /// Requires the conductor to be built with the `otlp` feature.
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+}
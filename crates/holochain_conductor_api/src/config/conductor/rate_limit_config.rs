@@ -0,0 +1,43 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Limits on how fast clients of the app interfaces can call zome functions.
+///
+/// Each kind of limit is tracked separately for every app, cap secret and
+/// provenance that calls are made with, and a call must be within all of the
+/// limits that apply to it. Calls that are over a limit are rejected without
+/// being run, and calls whose signature or cap grant can't be verified don't
+/// count against the limits.
+#[derive(Clone, Deserialize, Serialize, Default, Debug, PartialEq, Eq)]
+pub struct ZomeCallRateLimitConfig {
+    /// The limit for calls to the cells of each app.
+    #[serde(default)]
+    pub per_app: Option<RateLimit>,
+
+    /// The limit for calls made with each cap secret.
+    #[serde(default)]
+    pub per_cap_secret: Option<RateLimit>,
+
+    /// The limit for calls made by each provenance.
+    #[serde(default)]
+    pub per_provenance: Option<RateLimit>,
+}
+
+/// A rate limit on zome calls. Limits which are not set are unlimited.
+///
+/// Short bursts up to the full limit are allowed, after which calls are only
+/// allowed as fast as the limit recovers.
+#[derive(Clone, Deserialize, Serialize, Default, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    /// The number of zome calls which can be made per second.
+    #[serde(default)]
+    pub calls_per_second: Option<u32>,
+
+    /// The number of bytes zome calls can commit to the source chain per minute.
+    ///
+    /// What a call commits is only known once it has been made, so calls are
+    /// allowed until this is used up, and a call which commits more than is
+    /// left delays the calls after it until the limit has recovered.
+    #[serde(default)]
+    pub bytes_per_minute: Option<u64>,
+}
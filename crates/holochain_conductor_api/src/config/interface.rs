@@ -1,15 +1,88 @@
 use serde::Deserialize;
 use serde::Serialize;
+use std::path::PathBuf;
+
+pub use holochain_websocket::AllowedOrigins;
 
 /// Information neeeded to spawn an admin interface
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct AdminInterfaceConfig {
     /// By what means the interface will be exposed.
-    /// Currently the only option is a local websocket running on a configurable port.
+    /// Either a local websocket running on a configurable port
+    /// or a unix domain socket at a configurable path.
     pub driver: InterfaceDriver,
-    // How long will this interface be accessible between authentications?
-    // TODO: implement once we have authentication
-    // _session_duration_seconds: Option<u32>,
+
+    /// How long, in seconds, a connection to this interface stays open.
+    /// Once it is closed the client has to connect again, passing the
+    /// origin check and authenticating again. Connections stay open if this
+    /// is `None`.
+    #[serde(default)]
+    pub session_duration_seconds: Option<u32>,
+
+    /// How connections to this interface authenticate, using
+    /// [`AdminRequest::Authenticate`], before any other request is handled.
+    /// By default they use a token generated by the conductor.
+    ///
+    /// [`AdminRequest::Authenticate`]: crate::AdminRequest::Authenticate
+    #[serde(default)]
+    pub auth: AdminInterfaceAuth,
+}
+
+/// The name of the file in the conductor's environment path that the
+/// token for [`AdminInterfaceAuth::GeneratedToken`] is written to.
+pub const ADMIN_AUTH_TOKEN_FILE: &str = "admin_auth_token";
+
+/// What connections to an admin interface authenticate with.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AdminInterfaceAuth {
+    /// A random token which the conductor generates each time it starts,
+    /// and writes to [`ADMIN_AUTH_TOKEN_FILE`] in its environment path,
+    /// readable only by the user the conductor runs as.
+    GeneratedToken,
+    /// A token whose blake2b-256 hash is given here, hex encoded, so that
+    /// the token itself isn't kept in the config.
+    TokenHash {
+        /// The hex encoded hash of the token
+        hash: String,
+    },
+    /// No authentication. Anyone who can connect to the interface has full
+    /// control of the conductor.
+    None,
+}
+
+impl Default for AdminInterfaceAuth {
+    fn default() -> Self {
+        Self::GeneratedToken
+    }
+}
+
+impl AdminInterfaceConfig {
+    /// Config for a websocket admin interface on this port which accepts
+    /// connections from any origin.
+    pub fn websocket(port: u16) -> Self {
+        Self {
+            driver: InterfaceDriver::websocket(port),
+            session_duration_seconds: None,
+            auth: AdminInterfaceAuth::default(),
+        }
+    }
+
+    /// Config for an admin interface on a unix domain socket at this path.
+    pub fn unix_socket(path: impl Into<PathBuf>) -> Self {
+        Self {
+            driver: InterfaceDriver::unix_socket(path),
+            session_duration_seconds: None,
+            auth: AdminInterfaceAuth::default(),
+        }
+    }
+
+    /// Use this way of authenticating connections instead of a generated
+    /// token.
+    pub fn with_auth(mut self, auth: AdminInterfaceAuth) -> Self {
+        self.auth = auth;
+        self
+    }
 }
 
 /// Configuration for interfaces, specifying the means by which an interface
@@ -28,14 +101,52 @@ pub enum InterfaceDriver {
     Websocket {
         /// The port on which to establish the WebsocketListener
         port: u16,
+        /// The origins that may connect to this interface.
+        /// Any origin may connect if this is not set.
+        #[serde(default)]
+        allowed_origins: AllowedOrigins,
+    },
+    /// An interface implemented via websockets over a unix domain socket.
+    ///
+    /// Only local processes can connect, and the permissions of the socket
+    /// file control which users they may run as.
+    UnixSocket {
+        /// The path at which to create the socket
+        path: PathBuf,
     },
 }
 
 impl InterfaceDriver {
-    /// Get the port for this driver.
-    pub fn port(&self) -> u16 {
+    /// A websocket driver on this port which accepts connections from any origin.
+    pub fn websocket(port: u16) -> Self {
+        InterfaceDriver::Websocket {
+            port,
+            allowed_origins: AllowedOrigins::Any,
+        }
+    }
+
+    /// A unix domain socket driver at this path.
+    pub fn unix_socket(path: impl Into<PathBuf>) -> Self {
+        InterfaceDriver::UnixSocket { path: path.into() }
+    }
+
+    /// Get the port for this driver, if it listens on one.
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            InterfaceDriver::Websocket { port, .. } => Some(*port),
+            InterfaceDriver::UnixSocket { .. } => None,
+        }
+    }
+
+    /// Get the origins that may connect to this driver.
+    /// Browsers can't connect to unix domain sockets, so any origin
+    /// is allowed for those.
+    pub fn allowed_origins(&self) -> AllowedOrigins {
         match self {
-            InterfaceDriver::Websocket { port } => *port,
+            InterfaceDriver::Websocket {
+                allowed_origins, ..
+            } => allowed_origins.clone(),
+            InterfaceDriver::UnixSocket { .. } => AllowedOrigins::Any,
         }
     }
 }
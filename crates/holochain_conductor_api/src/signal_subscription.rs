@@ -1,16 +1,23 @@
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::app::InstalledAppId;
+use holochain_types::signal::Signal;
+use holochain_types::signal::SystemSignalKind;
+use holochain_types::signal::SystemSignalScope;
 use holochain_zome_types::cell::CellId;
+use holochain_zome_types::zome::ZomeName;
+use holochain_zome_types::ExternIO;
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 /// Declares updated Signal subscription settings for an App.
 /// This message is part of the AppInterfaceApi
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
 pub struct SignalSubscription {
-    /// The app for which to manage subscription
-    installed_app_id: InstalledAppId,
+    /// The app for which to manage subscription. The filters only apply to
+    /// signals about this app.
+    pub installed_app_id: InstalledAppId,
     /// Fine-grained per-cell filters
-    filters: SignalFilterSet,
+    pub filters: SignalFilterSet,
 }
 
 /// Associate a SignalFilter with each Cell in an App.
@@ -19,6 +26,9 @@ pub struct SignalSubscription {
 ///
 /// An empty Exclude filter means "allow all signals" (subscribe to all).
 /// An empty Include filter means "block all signals" (unsubscribe from all).
+///
+/// System signals which concern a single Cell are checked against that Cell's
+/// filter. Other system signals are checked against every filter in the set.
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
 pub enum SignalFilterSet {
     /// Only allow signals from the specified Cells with the specified filters,
@@ -46,22 +56,256 @@ impl SignalFilterSet {
     pub fn block_all() -> Self {
         SignalFilterSet::Include(HashMap::new())
     }
+
+    /// Whether this filter set lets the signal through.
+    /// The chunks of a streaming zome call were asked for by the connection
+    /// itself, so they are never filtered out.
+    pub fn allows(&self, signal: &Signal) -> bool {
+        if let Signal::ZomeCallStream { .. } = signal {
+            return true;
+        }
+        match self {
+            SignalFilterSet::Include(filters) => Self::any_match(filters, signal),
+            SignalFilterSet::Exclude(filters) => !Self::any_match(filters, signal),
+        }
+    }
+
+    fn any_match(filters: &HashMap<CellId, SignalFilter>, signal: &Signal) -> bool {
+        match signal {
+            Signal::App { cell_id, .. } => filters
+                .get(cell_id)
+                .map(|filter| filter.matches(signal))
+                .unwrap_or(false),
+            Signal::System(system_signal) => match system_signal.scope() {
+                SystemSignalScope::Cell(cell_id) => filters
+                    .get(cell_id)
+                    .map(|filter| filter.matches(signal))
+                    .unwrap_or(false),
+                _ => filters.values().any(|filter| filter.matches(signal)),
+            },
+            Signal::ZomeCallStream { .. } => true,
+        }
+    }
 }
 
-/// Specifies fine-grained filter controls for the signals
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-pub struct SignalFilter;
+/// The kind of a [`Signal`], for filtering purposes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SignalType {
+    /// Signals emitted by a zome via `emit_signal`
+    App,
+    /// Signals emitted by the Holochain system
+    System,
+}
 
-impl Default for SignalFilter {
-    fn default() -> Self {
-        // The default is no filter
-        Self::empty()
+/// Matches app signals whose payload is a map containing `value` at `field`.
+///
+/// `field` is a dot-separated path into nested maps, e.g. `"post.author"`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PayloadFieldFilter {
+    /// Path of the field within the payload
+    pub field: String,
+    /// The value the field must be equal to
+    pub value: serde_json::Value,
+}
+
+impl PayloadFieldFilter {
+    /// Whether the decoded payload has the expected value at this field.
+    /// Payloads which can't be decoded as a map never match.
+    pub fn matches(&self, payload: &ExternIO) -> bool {
+        let payload: serde_json::Value = match payload.decode() {
+            Ok(payload) => payload,
+            Err(_) => return false,
+        };
+        self.field
+            .split('.')
+            .try_fold(&payload, |value, key| value.get(key))
+            .map(|value| *value == self.value)
+            .unwrap_or(false)
     }
 }
 
+/// Specifies fine-grained filter controls for the signals.
+///
+/// Every criterion which is set must match for the filter to match,
+/// so the empty filter matches every signal of its Cell.
+/// A filter only matches system signals if its `signal_type` is
+/// [`SignalType::System`], and then only those of its `system_signal_kinds`.
+#[derive(
+    Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+pub struct SignalFilter {
+    /// Only match app signals emitted by one of these zomes.
+    /// An empty set matches signals from any zome.
+    #[serde(default)]
+    pub zome_names: HashSet<ZomeName>,
+    /// Only match signals of this type. `None` matches app signals only.
+    #[serde(default)]
+    pub signal_type: Option<SignalType>,
+    /// Only match app signals with this payload field.
+    #[serde(default)]
+    pub payload_field: Option<PayloadFieldFilter>,
+    /// Only match system signals of one of these kinds.
+    /// An empty set matches system signals of any kind.
+    #[serde(default)]
+    pub system_signal_kinds: HashSet<SystemSignalKind>,
+}
+
 impl SignalFilter {
     /// A passthrough filter which filters nothing
     pub fn empty() -> Self {
-        SignalFilter
+        Self::default()
+    }
+
+    /// Whether the signal matches every criterion of this filter
+    pub fn matches(&self, signal: &Signal) -> bool {
+        match signal {
+            Signal::App {
+                zome_name, signal, ..
+            } => {
+                self.signal_type != Some(SignalType::System)
+                    && (self.zome_names.is_empty() || self.zome_names.contains(zome_name))
+                    && self
+                        .payload_field
+                        .as_ref()
+                        .map(|filter| filter.matches(&signal.clone().into_inner()))
+                        .unwrap_or(true)
+            }
+            Signal::System(system_signal) => {
+                self.signal_type == Some(SignalType::System)
+                    && (self.system_signal_kinds.is_empty()
+                        || self.system_signal_kinds.contains(&system_signal.kind()))
+            }
+            Signal::ZomeCallStream { .. } => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holo_hash::AgentPubKey;
+    use holo_hash::DnaHash;
+    use holochain_types::signal::test_signal;
+    use holochain_types::signal::SystemSignal;
+    use holochain_zome_types::prelude::*;
+
+    fn cell_id(n: u8) -> CellId {
+        CellId::new(
+            DnaHash::from_raw_36(vec![n; 36]),
+            AgentPubKey::from_raw_36(vec![0; 36]),
+        )
+    }
+
+    fn app_signal(cell_id: &CellId, zome_name: &str, payload: serde_json::Value) -> Signal {
+        Signal::App {
+            cell_id: cell_id.clone(),
+            zome_name: zome_name.into(),
+            signal: AppSignal::new(ExternIO::encode(payload).unwrap()),
+        }
+    }
+
+    #[test]
+    fn filter_matches_zome_type_and_payload() {
+        let cell_id = cell_id(1);
+        let signal = app_signal(
+            &cell_id,
+            "posts",
+            serde_json::json!({ "post": { "author": "alice" } }),
+        );
+
+        assert!(SignalFilter::empty().matches(&signal));
+        assert!(!SignalFilter::empty().matches(&test_signal("system")));
+
+        let by_zome = SignalFilter {
+            zome_names: [ZomeName::from("comments")].into_iter().collect(),
+            ..Default::default()
+        };
+        assert!(!by_zome.matches(&signal));
+
+        let system_only = SignalFilter {
+            signal_type: Some(SignalType::System),
+            ..Default::default()
+        };
+        assert!(!system_only.matches(&signal));
+        assert!(system_only.matches(&test_signal("system")));
+
+        let peers_only = SignalFilter {
+            signal_type: Some(SignalType::System),
+            system_signal_kinds: [SystemSignalKind::Peer].into_iter().collect(),
+            ..Default::default()
+        };
+        assert!(!peers_only.matches(&test_signal("system")));
+        assert!(peers_only.matches(&Signal::System(SystemSignal::PeerLeft {
+            dna_hash: cell_id.dna_hash().clone(),
+            agent: cell_id.agent_pubkey().clone(),
+        })));
+
+        let by_payload = |value: &str| SignalFilter {
+            payload_field: Some(PayloadFieldFilter {
+                field: "post.author".into(),
+                value: value.into(),
+            }),
+            ..Default::default()
+        };
+        assert!(by_payload("alice").matches(&signal));
+        assert!(!by_payload("bob").matches(&signal));
+    }
+
+    #[test]
+    fn filter_set_include_and_exclude() {
+        let cell_1 = cell_id(1);
+        let cell_2 = cell_id(2);
+        let signal_1 = app_signal(&cell_1, "zome", serde_json::Value::Null);
+        let signal_2 = app_signal(&cell_2, "zome", serde_json::Value::Null);
+        let system = test_signal("system");
+
+        for signal in [&signal_1, &signal_2, &system] {
+            assert!(SignalFilterSet::allow_all().allows(signal));
+            assert!(!SignalFilterSet::block_all().allows(signal));
+        }
+
+        let filters: HashMap<_, _> = [(cell_1.clone(), SignalFilter::empty())].into();
+        let include = SignalFilterSet::Include(filters.clone());
+        assert!(include.allows(&signal_1));
+        assert!(!include.allows(&signal_2));
+        assert!(!include.allows(&system));
+
+        let exclude = SignalFilterSet::Exclude(filters);
+        assert!(!exclude.allows(&signal_1));
+        assert!(exclude.allows(&signal_2));
+        assert!(exclude.allows(&system));
+    }
+
+    #[test]
+    fn zome_call_stream_chunks_are_never_filtered() {
+        let chunk = Signal::ZomeCallStream {
+            stream_id: 1,
+            chunk: ExternIO::encode(()).unwrap(),
+        };
+        assert!(SignalFilterSet::block_all().allows(&chunk));
+        assert!(
+            SignalFilterSet::Exclude([(cell_id(1), SignalFilter::empty())].into()).allows(&chunk)
+        );
+    }
+
+    #[test]
+    fn cell_scoped_system_signals_use_their_cells_filter() {
+        let cell_1 = cell_id(1);
+        let cell_2 = cell_id(2);
+        let system = |cell_id: &CellId| {
+            Signal::System(SystemSignal::SuccessfulCountersigning {
+                cell_id: cell_id.clone(),
+                entry_hash: EntryHash::from_raw_36(vec![0; 36]),
+            })
+        };
+        let system_only = SignalFilter {
+            signal_type: Some(SignalType::System),
+            ..Default::default()
+        };
+
+        let include = SignalFilterSet::Include([(cell_1.clone(), system_only)].into());
+        assert!(include.allows(&system(&cell_1)));
+        assert!(!include.allows(&system(&cell_2)));
+        assert!(include.allows(&test_signal("system")));
     }
 }
@@ -469,8 +469,8 @@ pub struct DeleteLink {
     pub link_add_address: ActionHash,
 }
 
-/// When migrating to a new version of a DNA, this action is committed to the
-/// new chain to declare the migration path taken. **Currently unused**
+/// When migrating to a new version of a DNA or to a new agent key, this action
+/// is committed to the new chain to declare the migration path taken.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, SerializedBytes, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct OpenChain {
@@ -479,11 +479,16 @@ pub struct OpenChain {
     pub action_seq: u32,
     pub prev_action: ActionHash,
 
-    pub prev_dna_hash: DnaHash,
+    /// The chain which was closed in favor of this one
+    pub prev_target: MigrationTarget,
+    /// The hash of the [`CloseChain`] action on the previous chain
+    pub close_hash: ActionHash,
 }
 
-/// When migrating to a new version of a DNA, this action is committed to the
-/// old chain to declare the migration path taken. **Currently unused**
+/// When migrating to a new version of a DNA or to a new agent key, this action
+/// is committed to the old chain to declare the migration path taken.
+///
+/// No further actions may be committed to a chain after it is closed.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, SerializedBytes, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CloseChain {
@@ -492,7 +497,22 @@ pub struct CloseChain {
     pub action_seq: u32,
     pub prev_action: ActionHash,
 
-    pub new_dna_hash: DnaHash,
+    /// The successor of this chain
+    pub new_target: MigrationTarget,
+}
+
+/// The other end of a chain migration, as recorded in [`OpenChain`] and
+/// [`CloseChain`] actions.
+///
+/// Only the part of the cell ID which changes is recorded:
+/// the DNA hash for a DNA migration, or the agent key for a key migration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, SerializedBytes, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum MigrationTarget {
+    /// Migration to or from a different DNA, by the same agent
+    Dna(DnaHash),
+    /// Migration to or from a different agent key, in the same DNA
+    Agent(AgentPubKey),
 }
 
 /// A action which "speaks" Entry content into being. The same content can be
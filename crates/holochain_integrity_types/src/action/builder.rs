@@ -10,6 +10,7 @@ use crate::MembraneProof;
 use crate::RateWeight;
 use crate::ZomeIndex;
 use action::Dna;
+use action::MigrationTarget;
 use holo_hash::ActionHash;
 use holo_hash::AgentPubKey;
 use holo_hash::AnyLinkableHash;
@@ -246,11 +247,12 @@ builder_variant!(DeleteLink {
 });
 
 builder_variant!(OpenChain {
-    prev_dna_hash: DnaHash,
+    prev_target: MigrationTarget,
+    close_hash: ActionHash,
 });
 
 builder_variant!(CloseChain {
-    new_dna_hash: DnaHash,
+    new_target: MigrationTarget,
 });
 
 builder_variant!(Create<EntryRateWeight> {
@@ -513,6 +513,8 @@ impl CounterSigningSessionData {
     }
 
     /// Get the agent state for a specific agent.
+    /// Required signers are looked up first, then any optional signer that
+    /// responded to the session.
     pub fn agent_state_for_agent(
         &self,
         agent: &AgentPubKey,
@@ -527,33 +529,58 @@ impl CounterSigningSessionData {
                 Some((agent_state, _)) => Ok(agent_state),
                 None => Err(CounterSigningError::AgentIndexOutOfBounds),
             },
-            None => Err(CounterSigningError::AgentIndexOutOfBounds),
+            None => match self
+                .preflight_request
+                .optional_signing_agents
+                .iter()
+                .position(|(pubkey, _)| pubkey == agent)
+            {
+                Some(agent_index) => self
+                    .optional_responses
+                    .iter()
+                    .find(|(agent_state, _)| *agent_state.agent_index() as usize == agent_index)
+                    .map(|(agent_state, _)| agent_state)
+                    .ok_or(CounterSigningError::AgentIndexOutOfBounds),
+                None => Err(CounterSigningError::AgentIndexOutOfBounds),
+            },
         }
     }
 
+    /// Get all the optional signers that responded to this session, excluding
+    /// the enzyme which is also a required signer.
+    pub fn optional_signers_responded(&self) -> impl Iterator<Item = &AgentPubKey> {
+        self.optional_responses
+            .iter()
+            .filter_map(|(agent_state, _)| {
+                self.preflight_request
+                    .optional_signing_agents
+                    .get(*agent_state.agent_index() as usize)
+                    .map(|(agent, _)| agent)
+                    .filter(|agent| !self.signing_agents().any(|a| a == *agent))
+            })
+    }
+
     /// Attempt to map countersigning session data to a set of actions.
     /// A given countersigning session always maps to the same ordered set of actions or an error.
+    /// The set has an action for every required signer followed by every
+    /// optional signer that responded to the session.
     /// Note the actions are not signed as the intent is to build actions for other agents without their private keys.
     pub fn build_action_set(
         &self,
         entry_hash: EntryHash,
         weight: EntryRateWeight,
     ) -> Result<Vec<Action>, CounterSigningError> {
-        let mut actions = vec![];
-        let mut build_actions = |countersigning_agents: &CounterSigningAgents| -> Result<(), _> {
-            for (agent, _role) in countersigning_agents.iter() {
-                actions.push(Action::from_countersigning_data(
+        self.signing_agents()
+            .chain(self.optional_signers_responded())
+            .map(|agent| {
+                Action::from_countersigning_data(
                     entry_hash.clone(),
                     self,
                     agent.clone(),
                     weight.clone(),
-                )?);
-            }
-            Ok(())
-        };
-        build_actions(&self.preflight_request.signing_agents)?;
-        build_actions(&self.preflight_request.optional_signing_agents)?;
-        Ok(actions)
+                )
+            })
+            .collect()
     }
 
     /// Fallible constructor.
@@ -573,7 +600,33 @@ impl CounterSigningSessionData {
 
     /// Combines all integrity checks.
     pub fn check_integrity(&self) -> Result<(), CounterSigningError> {
-        self.check_responses_indexes()
+        self.check_responses_indexes()?;
+        self.check_optional_responses()
+    }
+
+    /// Check that every optional response is for a distinct optional signer
+    /// and that at least the minimum number of optional signers responded.
+    pub fn check_optional_responses(&self) -> Result<(), CounterSigningError> {
+        let optional_signing_agents = &self.preflight_request().optional_signing_agents;
+        let mut seen = std::collections::HashSet::new();
+        for (response, _response_signature) in self.optional_responses().iter() {
+            let agent_index = *response.agent_index();
+            if agent_index as usize >= optional_signing_agents.len() || !seen.insert(agent_index) {
+                return Err(
+                    CounterSigningError::CounterSigningSessionOptionalResponsesIndex(agent_index),
+                );
+            }
+        }
+        let minimum = self.preflight_request().minimum_optional_signing_agents;
+        if !optional_signing_agents.is_empty() && seen.len() < minimum as usize {
+            return Err(
+                CounterSigningError::CounterSigningSessionOptionalResponsesLength(
+                    seen.len(),
+                    minimum,
+                ),
+            );
+        }
+        Ok(())
     }
 
     /// Check that the countersigning session data responses all have the
@@ -630,6 +683,17 @@ impl CounterSigningSessionData {
     pub fn responses_mut(&mut self) -> &mut Vec<(CounterSigningAgentState, Signature)> {
         &mut self.responses
     }
+
+    /// Accessor to optional responses.
+    pub fn optional_responses(&self) -> &Vec<(CounterSigningAgentState, Signature)> {
+        &self.optional_responses
+    }
+
+    /// Mutable optional responses accessor for testing.
+    #[cfg(feature = "test_utils")]
+    pub fn optional_responses_mut(&mut self) -> &mut Vec<(CounterSigningAgentState, Signature)> {
+        &mut self.optional_responses
+    }
 }
 
 #[cfg(test)]
@@ -905,4 +969,67 @@ pub mod test {
         (*session_data.responses_mut()).push((bob_state, bob_signature));
         assert_eq!(session_data.check_responses_indexes().unwrap(), (),);
     }
+
+    #[test]
+    pub fn test_check_countersigning_session_data_optional_responses() {
+        let mut u = arbitrary::Unstructured::new(&[0; 1000]);
+        let mut session_data = CounterSigningSessionData::arbitrary(&mut u).unwrap();
+
+        let data: Vec<_> = (0u8..255).cycle().take(100000).collect();
+        let mut uk = arbitrary::Unstructured::new(&data);
+        let alice = AgentPubKey::arbitrary(&mut uk).unwrap();
+        let bob = AgentPubKey::arbitrary(&mut uk).unwrap();
+        let carol = AgentPubKey::arbitrary(&mut uk).unwrap();
+
+        // No optional signers is a pass.
+        assert_eq!(session_data.check_optional_responses().unwrap(), ());
+
+        // 2 of 3 optional signers are required.
+        let request = session_data.preflight_request_mut();
+        request.signing_agents = vec![(alice.clone(), vec![]), (bob.clone(), vec![])];
+        request.optional_signing_agents = vec![
+            (alice.clone(), vec![]),
+            (bob.clone(), vec![]),
+            (carol.clone(), vec![]),
+        ];
+        request.minimum_optional_signing_agents = 2;
+        let mut state = |index: u8| {
+            let mut state = CounterSigningAgentState::arbitrary(&mut u).unwrap();
+            *state.agent_index_mut() = index;
+            (state, Signature::arbitrary(&mut u).unwrap())
+        };
+        *session_data.responses_mut() = vec![state(0), state(1)];
+
+        // Too few optional responses is a fail.
+        *session_data.optional_responses_mut() = vec![state(0)];
+        assert!(matches!(
+            session_data.check_optional_responses(),
+            Err(CounterSigningError::CounterSigningSessionOptionalResponsesLength(1, 2))
+        ));
+
+        // The same optional signer responding twice is a fail.
+        session_data.optional_responses_mut().push(state(0));
+        assert!(matches!(
+            session_data.check_optional_responses(),
+            Err(CounterSigningError::CounterSigningSessionOptionalResponsesIndex(0))
+        ));
+
+        // An optional response for a signer that isn't in the request is a fail.
+        session_data.optional_responses_mut().pop();
+        session_data.optional_responses_mut().push(state(3));
+        assert!(matches!(
+            session_data.check_optional_responses(),
+            Err(CounterSigningError::CounterSigningSessionOptionalResponsesIndex(3))
+        ));
+
+        // Enough distinct optional signers is a pass.
+        session_data.optional_responses_mut().pop();
+        session_data.optional_responses_mut().push(state(2));
+        assert_eq!(session_data.check_optional_responses().unwrap(), ());
+
+        // Optional signers that are also required signers are only counted once.
+        let signers: Vec<_> = session_data.optional_signers_responded().cloned().collect();
+        assert_eq!(signers, vec![carol.clone()]);
+        assert!(session_data.agent_state_for_agent(&carol).is_ok());
+    }
 }
@@ -11,6 +11,10 @@ pub enum CounterSigningError {
     CounterSigningSessionResponsesLength(usize, usize),
     /// Session response agents all need to be in the correct positions.
     CounterSigningSessionResponsesOrder(u8, usize),
+    /// Optional session responses must each be for a different optional signer.
+    CounterSigningSessionOptionalResponsesIndex(u8),
+    /// Optional session responses must meet the minimum optional signers.
+    CounterSigningSessionOptionalResponsesLength(usize, u8),
     /// Enzyme must match for required and optional signers if set.
     EnzymeMismatch(
         Option<(holo_hash::AgentPubKey, Vec<Role>)>,
@@ -53,6 +57,14 @@ impl core::fmt::Display for CounterSigningError {
                     "The countersigning session response with agent index {} was found in index position {}",
                     index, pos
             ),
+            CounterSigningError::CounterSigningSessionOptionalResponsesIndex(index) => write!(f,
+                    "The countersigning session optional response with agent index {} is out of bounds or repeated",
+                    index
+            ),
+            CounterSigningError::CounterSigningSessionOptionalResponsesLength(resp, min) => write!(f,
+                    "The countersigning session has {} optional responses which is less than the minimum {}",
+                    resp, min
+            ),
             CounterSigningError::EnzymeMismatch(required_signer, optional_signer) => write!(f,
                 "The enzyme is mismatche for required signer {:?} and optional signer {:?}",
                 required_signer, optional_signer
@@ -96,6 +96,10 @@ pub trait HolochainP2pDnaT: Send + Sync {
         op: DhtOp,
     ) -> actor::HolochainP2pResult<()>;
 
+    /// Publish a warrant to the agent activity authorities of the
+    /// agent it is against.
+    async fn publish_warrant(&self, warrant: SignedWarrant) -> actor::HolochainP2pResult<()>;
+
     /// Get an entry from the DHT.
     async fn get(
         &self,
@@ -296,6 +300,15 @@ impl HolochainP2pDnaT for HolochainP2pDna {
             .await
     }
 
+    /// Publish a warrant to the agent activity authorities of the
+    /// agent it is against.
+    async fn publish_warrant(&self, warrant: SignedWarrant) -> actor::HolochainP2pResult<()> {
+        let basis_hash = warrant.warrant.warrantee().clone().into();
+        self.sender
+            .publish_warrant((*self.dna_hash).clone(), basis_hash, warrant)
+            .await
+    }
+
     /// Get [`DhtOp::StoreRecord`] or [`DhtOp::StoreEntry`] from the DHT.
     async fn get(
         &self,
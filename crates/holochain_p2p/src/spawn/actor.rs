@@ -160,6 +160,17 @@ impl WrapEvtSender {
         }, %op_count, "(hp2p:handle) publish")
     }
 
+    fn warrant_received(
+        &self,
+        dna_hash: DnaHash,
+        warrant: SignedWarrant,
+    ) -> impl Future<Output = HolochainP2pResult<()>> + 'static + Send {
+        timing_trace!(
+            { self.0.warrant_received(dna_hash, warrant) },
+            "(hp2p:handle) warrant_received"
+        )
+    }
+
     fn get(
         &self,
         dna_hash: DnaHash,
@@ -548,6 +559,21 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving an incoming warrant from a remote node
+    fn handle_incoming_warrant(
+        &mut self,
+        dna_hash: DnaHash,
+        warrant: SignedWarrant,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<()> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            evt_sender.warrant_received(dna_hash, warrant).await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_incoming_countersigning_session_negotiation(
         &mut self,
         dna_hash: DnaHash,
@@ -743,6 +769,12 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
                 )
                 .into())
             }
+            crate::wire::WireMessage::PublishWarrant { .. } => {
+                Err(HolochainP2pError::invalid_p2p_message(
+                    "invalid: warrants are broadcast, not requests".to_string(),
+                )
+                .into())
+            }
         }
     }
 
@@ -830,6 +862,9 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             crate::wire::WireMessage::PublishCountersign { flag, op } => {
                 self.handle_incoming_publish(space, false, flag, vec![op])
             }
+            crate::wire::WireMessage::PublishWarrant { warrant } => {
+                self.handle_incoming_warrant(space, warrant)
+            }
         }
     }
 
@@ -977,6 +1012,23 @@ impl HolochainP2pHandler for HolochainP2pActor {
             .into())
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_refresh_agent_info(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let agent = agent_pub_key.into_kitsune();
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(
+            async move { Ok(kitsune_p2p.refresh_agent_info(space, agent).await?) }
+                .boxed()
+                .into(),
+        )
+    }
+
     /// Dispatch an outgoing remote call.
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_call_remote(
@@ -1154,6 +1206,30 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .into())
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_publish_warrant(
+        &mut self,
+        dna_hash: DnaHash,
+        basis_hash: holo_hash::OpBasis,
+        warrant: SignedWarrant,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let basis = basis_hash.to_kitsune();
+        let timeout = self.tuning_params.implicit_timeout();
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            let payload = crate::wire::WireMessage::publish_warrant(warrant).encode()?;
+
+            kitsune_p2p
+                .broadcast(space, basis, timeout, BroadcastData::User(payload))
+                .await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     #[tracing::instrument(skip(self, dna_hash, dht_hash, options), level = "trace")]
     fn handle_get(
         &mut self,
@@ -31,6 +31,14 @@ impl HolochainP2pHandler for StubNetwork {
         Err("stub".into())
     }
 
+    fn handle_refresh_agent_info(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
+
     fn handle_call_remote(
         &mut self,
         dna_hash: DnaHash,
@@ -86,6 +94,15 @@ impl HolochainP2pHandler for StubNetwork {
         Err("stub".into())
     }
 
+    fn handle_publish_warrant(
+        &mut self,
+        dna_hash: DnaHash,
+        basis_hash: holo_hash::OpBasis,
+        warrant: SignedWarrant,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
+
     fn handle_get(
         &mut self,
         dna_hash: DnaHash,
@@ -197,6 +197,11 @@ pub struct GetLinksOptions {
     /// Note - if all requests time-out you will receive an empty result,
     /// not a timeout error.
     pub timeout_ms: Option<u64>,
+
+    /// ```[Remote]```
+    /// Time range, author, ordering and pagination of the links,
+    /// applied by the authority so only matching links are returned.
+    pub filter: GetLinksFilter,
 }
 
 #[derive(Debug, Clone)]
@@ -252,6 +257,10 @@ ghost_actor::ghost_chan! {
         /// If a cell is disabled, we'll need to \"leave\" the network module as well.
         fn leave(dna_hash: DnaHash, agent_pub_key: AgentPubKey) -> ();
 
+        /// Re-sign and publish the agent info of a joined dna/agent pair,
+        /// e.g. after its arc pin was changed.
+        fn refresh_agent_info(dna_hash: DnaHash, agent_pub_key: AgentPubKey) -> ();
+
         /// Invoke a zome function on a remote node (if you have been granted the capability).
         fn call_remote(
             dna_hash: DnaHash,
@@ -302,6 +311,14 @@ ghost_actor::ghost_chan! {
             op: DhtOp,
         ) -> ();
 
+        /// Publish a warrant to the agent activity authorities of the
+        /// agent it is against.
+        fn publish_warrant(
+            dna_hash: DnaHash,
+            basis_hash: holo_hash::OpBasis,
+            warrant: SignedWarrant,
+        ) -> ();
+
         /// Get an entry from the DHT.
         fn get(
             dna_hash: DnaHash,
@@ -59,12 +59,18 @@ impl From<&actor::GetMetaOptions> for GetMetaOptions {
 }
 
 /// GetLinks options help control how the get is processed at various levels.
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct GetLinksOptions {}
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct GetLinksOptions {
+    /// Time range, author, ordering and pagination of the links.
+    #[serde(default)]
+    pub filter: GetLinksFilter,
+}
 
 impl From<&actor::GetLinksOptions> for GetLinksOptions {
-    fn from(_a: &actor::GetLinksOptions) -> Self {
-        Self {}
+    fn from(a: &actor::GetLinksOptions) -> Self {
+        Self {
+            filter: a.filter.clone(),
+        }
     }
 }
 
@@ -109,6 +115,14 @@ pub enum CountersigningSessionNegotiationMessage {
     /// Counterparties are sending their signed action to an enzyme instead of
     /// authorities as part of an enzymatic session.
     EnzymePush(Box<DhtOp>),
+    /// An enzyme is telling the counterparties that the session for this entry
+    /// expired before every signer had signed, so it will never complete.
+    EnzymeSessionTimeout {
+        /// The hash of the countersigned entry of the session.
+        entry_hash: holo_hash::EntryHash,
+        /// The enzyme's signature of the entry hash.
+        signature: Signature,
+    },
 }
 
 /// Multiple ways to fetch op data
@@ -195,6 +209,13 @@ ghost_actor::ghost_chan! {
             ops: Vec<holochain_types::dht_op::DhtOp>,
         ) -> ();
 
+        /// A remote node is publishing a warrant against an agent whose
+        /// activity we claim to be holding.
+        fn warrant_received(
+            dna_hash: DnaHash,
+            warrant: SignedWarrant,
+        ) -> ();
+
         /// A remote node is requesting entry data from us.
         fn get(
             dna_hash: DnaHash,
@@ -303,6 +324,7 @@ impl HolochainP2pEvent {
     pub fn dna_hash(&self) -> &DnaHash {
         match_p2p_evt!(self => |dna_hash| { dna_hash }, {
             HolochainP2pEvent::Publish { dna_hash, .. } => { dna_hash }
+            HolochainP2pEvent::WarrantReceived { dna_hash, .. } => { dna_hash }
             HolochainP2pEvent::FetchOpData { dna_hash, .. } => { dna_hash }
             HolochainP2pEvent::QueryOpHashes { dna_hash, .. } => { dna_hash }
             HolochainP2pEvent::QueryAgentInfoSigned { dna_hash, .. } => { dna_hash }
@@ -317,6 +339,7 @@ impl HolochainP2pEvent {
     pub fn target_agents(&self) -> &AgentPubKey {
         match_p2p_evt!(self => |to_agent| { to_agent }, {
             HolochainP2pEvent::Publish { .. } => { unimplemented!("There is no single agent target for Publish") }
+            HolochainP2pEvent::WarrantReceived { .. } => { unimplemented!("There is no single agent target for WarrantReceived") }
             HolochainP2pEvent::FetchOpData { .. } => { unimplemented!("There is no single agent target for FetchOpData") }
             HolochainP2pEvent::QueryOpHashes { .. } => { unimplemented!("There is no single agent target for QueryOpHashes") }
             HolochainP2pEvent::QueryAgentInfoSigned { .. } => { unimplemented!("There is no single agent target for QueryAgentInfoSigned") },
@@ -138,6 +138,8 @@ pub enum HolochainP2pMockMsg {
 pub enum GossipProtocol {
     /// Sharded gossip wire protocol.
     Sharded(kitsune_p2p::gossip::sharded_gossip::ShardedGossipWire),
+    /// Full sync gossip wire protocol.
+    FullSync(kitsune_p2p::gossip::full_sync_gossip::FullSyncGossipWire),
 }
 
 /// This type allows a response to be sent to
@@ -350,9 +352,8 @@ impl HolochainP2pMockMsg {
                 | crate::wire::WireMessage::PublishCountersign { .. }
                 | crate::wire::WireMessage::MustGetAgentActivity { .. } => next_msg_id().as_req(),
 
-                crate::wire::WireMessage::CountersigningSessionNegotiation { .. } => {
-                    MsgId::new_notify()
-                }
+                crate::wire::WireMessage::CountersigningSessionNegotiation { .. }
+                | crate::wire::WireMessage::PublishWarrant { .. } => MsgId::new_notify(),
             },
             HolochainP2pMockMsg::PeerGet(_) | HolochainP2pMockMsg::PeerQuery(_) => {
                 next_msg_id().as_req()
@@ -378,6 +379,7 @@ impl HolochainP2pMockMsg {
                     | crate::wire::WireMessage::GetAgentActivity { .. }
                     | crate::wire::WireMessage::MustGetAgentActivity { .. } => true,
                     crate::wire::WireMessage::PublishCountersign { .. }
+                    | crate::wire::WireMessage::PublishWarrant { .. }
                     | crate::wire::WireMessage::CountersigningSessionNegotiation { .. } => false,
                 };
                 let to_agent = to_agent.to_kitsune();
@@ -412,6 +414,7 @@ impl HolochainP2pMockMsg {
                 let space = dna.to_kitsune();
                 let data = match gossip {
                     GossipProtocol::Sharded(gossip) => gossip.encode_vec().unwrap().into(),
+                    GossipProtocol::FullSync(gossip) => gossip.encode_vec().unwrap().into(),
                 };
                 kwire::Wire::Gossip(kwire::Gossip {
                     space,
@@ -491,6 +494,13 @@ impl HolochainP2pMockMsg {
                             ShardedGossipWire::decode_ref(data.as_ref()).unwrap().1,
                         )
                     }
+                    GossipModuleType::FullSync => GossipProtocol::FullSync(
+                        kitsune_p2p::gossip::full_sync_gossip::FullSyncGossipWire::decode_ref(
+                            data.as_ref(),
+                        )
+                        .unwrap()
+                        .1,
+                    ),
                 };
                 let dna = holo_hash::DnaHash::from_kitsune(&space);
                 HolochainP2pMockMsg::Gossip {
@@ -80,6 +80,9 @@ pub enum WireMessage {
         flag: bool,
         op: DhtOp,
     },
+    PublishWarrant {
+        warrant: SignedWarrant,
+    },
 }
 
 #[allow(missing_docs)]
@@ -96,6 +99,10 @@ impl WireMessage {
         Self::PublishCountersign { flag, op }
     }
 
+    pub fn publish_warrant(warrant: SignedWarrant) -> WireMessage {
+        Self::PublishWarrant { warrant }
+    }
+
     /// For an outgoing remote call.
     #[allow(clippy::too_many_arguments)]
     pub fn call_remote(
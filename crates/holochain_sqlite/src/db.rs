@@ -22,6 +22,12 @@ use tokio::{
 mod p2p_agent_store;
 pub use p2p_agent_store::*;
 
+mod p2p_fetch_pool;
+pub use p2p_fetch_pool::*;
+
+mod p2p_arc_pin;
+pub use p2p_arc_pin::*;
+
 mod p2p_metrics;
 pub use p2p_metrics::*;
 
@@ -156,6 +162,7 @@ impl<Kind: DbKindT> DbRead<Kind> {
         let now = std::time::Instant::now();
         let r = Ok(PConn::new(self.connection_pool.get()?));
         let el = now.elapsed();
+        crate::metrics::observe_pool_wait(&self.kind.kind(), "connection", el);
         if el.as_millis() > 20 {
             tracing::error!("Connection pool took {:?} to be free'd", el);
         }
@@ -191,11 +198,15 @@ impl<Kind: DbKindT> DbRead<Kind> {
     }
 
     async fn acquire_reader_permit(&self) -> OwnedSemaphorePermit {
-        self.read_semaphore
+        let now = std::time::Instant::now();
+        let permit = self
+            .read_semaphore
             .clone()
             .acquire_owned()
             .await
-            .expect("We don't ever close these semaphores")
+            .expect("We don't ever close these semaphores");
+        crate::metrics::observe_pool_wait(&self.kind.kind(), "read", now.elapsed());
+        permit
     }
 }
 
@@ -367,12 +378,16 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
     }
 
     async fn acquire_writer_permit(&self) -> OwnedSemaphorePermit {
-        self.0
+        let now = std::time::Instant::now();
+        let permit = self
+            .0
             .write_semaphore
             .clone()
             .acquire_owned()
             .await
-            .expect("We don't ever close these semaphores")
+            .expect("We don't ever close these semaphores");
+        crate::metrics::observe_pool_wait(&self.0.kind.kind(), "write", now.elapsed());
+        permit
     }
 }
 
@@ -0,0 +1,61 @@
+//! p2p_arc_pin sql logic
+
+use crate::prelude::*;
+use crate::sql::*;
+use kitsune_p2p::dht_arc::ArcPin;
+use kitsune_p2p::KitsuneAgent;
+use rusqlite::*;
+use std::sync::Arc;
+
+#[cfg(test)]
+mod p2p_arc_pin_test;
+
+/// Pin the arc of a local agent at a fixed size, or unpin it with `None`.
+pub async fn p2p_put_arc_pin(
+    db: &DbWrite<DbKindP2pAgents>,
+    agent: Arc<KitsuneAgent>,
+    pin: Option<ArcPin>,
+) -> DatabaseResult<()> {
+    let encoded = pin.map(|pin| rmp_serde::to_vec_named(&pin)).transpose()?;
+    db.async_commit(move |txn| {
+        match encoded {
+            Some(encoded) => txn.execute(
+                sql_p2p_agent_store::ARC_PIN_INSERT,
+                named_params! {
+                    ":agent": &agent.0,
+                    ":pin": &encoded,
+                },
+            )?,
+            None => txn.execute(
+                sql_p2p_agent_store::ARC_PIN_DELETE,
+                named_params! {
+                    ":agent": &agent.0,
+                },
+            )?,
+        };
+        DatabaseResult::Ok(())
+    })
+    .await
+}
+
+/// Get the pin of the arc of a local agent, if it is pinned.
+pub async fn p2p_get_arc_pin(
+    db: &DbWrite<DbKindP2pAgents>,
+    agent: Arc<KitsuneAgent>,
+) -> DatabaseResult<Option<ArcPin>> {
+    db.async_reader(move |txn| {
+        let encoded = txn
+            .query_row(
+                sql_p2p_agent_store::ARC_PIN_SELECT,
+                named_params! { ":agent": &agent.0 },
+                |row| row.get::<_, Vec<u8>>(0),
+            )
+            .optional()?;
+        DatabaseResult::Ok(
+            encoded
+                .map(|encoded| rmp_serde::from_slice(&encoded))
+                .transpose()?,
+        )
+    })
+    .await
+}
@@ -0,0 +1,54 @@
+use crate::prelude::*;
+use kitsune_p2p::dht_arc::ArcPin;
+use kitsune_p2p::{KitsuneAgent, KitsuneSpace};
+use std::sync::Arc;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_p2p_arc_pin_roundtrip() {
+    let tmp_dir = tempfile::Builder::new()
+        .prefix("p2p_arc_pin_roundtrip")
+        .tempdir()
+        .unwrap();
+
+    let space = Arc::new(KitsuneSpace(vec![0; 36]));
+    let db = DbWrite::test(tmp_dir.path(), DbKindP2pAgents(space)).unwrap();
+    let alice = Arc::new(KitsuneAgent(vec![1; 36]));
+    let bob = Arc::new(KitsuneAgent(vec![2; 36]));
+
+    assert_eq!(p2p_get_arc_pin(&db, alice.clone()).await.unwrap(), None);
+
+    p2p_put_arc_pin(&db, alice.clone(), Some(ArcPin::Full))
+        .await
+        .unwrap();
+    p2p_put_arc_pin(&db, bob.clone(), Some(ArcPin::HalfLength(1000)))
+        .await
+        .unwrap();
+    assert_eq!(
+        p2p_get_arc_pin(&db, alice.clone()).await.unwrap(),
+        Some(ArcPin::Full)
+    );
+    assert_eq!(
+        p2p_get_arc_pin(&db, bob.clone()).await.unwrap(),
+        Some(ArcPin::HalfLength(1000))
+    );
+
+    // A new pin replaces the old one
+    p2p_put_arc_pin(&db, alice.clone(), Some(ArcPin::Empty))
+        .await
+        .unwrap();
+    assert_eq!(
+        p2p_get_arc_pin(&db, alice.clone()).await.unwrap(),
+        Some(ArcPin::Empty)
+    );
+
+    // Unpinning removes only that agent's pin
+    p2p_put_arc_pin(&db, alice.clone(), None).await.unwrap();
+    assert_eq!(p2p_get_arc_pin(&db, alice).await.unwrap(), None);
+    assert_eq!(
+        p2p_get_arc_pin(&db, bob).await.unwrap(),
+        Some(ArcPin::HalfLength(1000))
+    );
+
+    // clean up temp dir
+    tmp_dir.close().unwrap();
+}
@@ -0,0 +1,53 @@
+//! p2p_fetch_pool sql logic
+
+use crate::prelude::*;
+use crate::sql::*;
+use kitsune_p2p::dependencies::kitsune_p2p_fetch::FetchPoolPending;
+use rusqlite::*;
+
+#[cfg(test)]
+mod p2p_fetch_pool_test;
+
+/// Replace the items persisted as pending in the fetch pool of a space
+/// with a new list.
+pub async fn p2p_put_fetch_pool(
+    db: &DbWrite<DbKindP2pAgents>,
+    pending: Vec<FetchPoolPending>,
+) -> DatabaseResult<()> {
+    let mut encoded = Vec::with_capacity(pending.len());
+    for item in pending {
+        encoded.push(rmp_serde::to_vec_named(&item)?);
+    }
+    db.async_commit(move |txn| {
+        txn.execute(sql_p2p_agent_store::FETCH_POOL_DELETE_ALL, [])?;
+        for encoded in encoded {
+            txn.execute(
+                sql_p2p_agent_store::FETCH_POOL_INSERT,
+                named_params! {
+                    ":encoded": &encoded,
+                },
+            )?;
+        }
+        DatabaseResult::Ok(())
+    })
+    .await
+}
+
+/// Get the items persisted as pending in the fetch pool of a space,
+/// in the order they were persisted.
+pub async fn p2p_get_fetch_pool(
+    db: &DbWrite<DbKindP2pAgents>,
+) -> DatabaseResult<Vec<FetchPoolPending>> {
+    db.async_reader(|txn| {
+        let mut stmt = txn.prepare(sql_p2p_agent_store::FETCH_POOL_SELECT_ALL)?;
+        let encoded = stmt
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut pending = Vec::with_capacity(encoded.len());
+        for encoded in encoded {
+            pending.push(rmp_serde::from_slice(&encoded)?);
+        }
+        DatabaseResult::Ok(pending)
+    })
+    .await
+}
@@ -0,0 +1,44 @@
+use crate::prelude::*;
+use kitsune_p2p::dependencies::kitsune_p2p_fetch::{FetchKey, FetchPoolPending, FetchSource};
+use kitsune_p2p::{KitsuneAgent, KitsuneOpHash, KitsuneSpace};
+use std::sync::Arc;
+
+fn pending(i: u8) -> FetchPoolPending {
+    FetchPoolPending {
+        key: FetchKey::Op(Arc::new(KitsuneOpHash(vec![i; 36]))),
+        sources: vec![
+            FetchSource::Agent(Arc::new(KitsuneAgent(vec![i; 36]))),
+            FetchSource::Agent(Arc::new(KitsuneAgent(vec![i + 1; 36]))),
+        ],
+        size: Some(100.into()),
+        context: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_p2p_fetch_pool_roundtrip() {
+    let tmp_dir = tempfile::Builder::new()
+        .prefix("p2p_fetch_pool_roundtrip")
+        .tempdir()
+        .unwrap();
+
+    let space = Arc::new(KitsuneSpace(vec![0; 36]));
+    let db = DbWrite::test(tmp_dir.path(), DbKindP2pAgents(space)).unwrap();
+
+    assert!(p2p_get_fetch_pool(&db).await.unwrap().is_empty());
+
+    p2p_put_fetch_pool(&db, vec![pending(1), pending(2)])
+        .await
+        .unwrap();
+    assert_eq!(
+        p2p_get_fetch_pool(&db).await.unwrap(),
+        vec![pending(1), pending(2)]
+    );
+
+    // A new list replaces the old one
+    p2p_put_fetch_pool(&db, vec![pending(3)]).await.unwrap();
+    assert_eq!(p2p_get_fetch_pool(&db).await.unwrap(), vec![pending(3)]);
+
+    // clean up temp dir
+    tmp_dir.close().unwrap();
+}
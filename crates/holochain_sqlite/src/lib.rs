@@ -10,6 +10,7 @@ pub mod error;
 pub mod exports;
 pub mod fatal;
 pub mod functions;
+mod metrics;
 pub mod nonce;
 pub mod prelude;
 pub mod schema;
@@ -0,0 +1,37 @@
+//! Metrics about database usage, exported along with the rest of the
+//! process's metrics.
+
+use crate::db::DbKind;
+use holochain_trace::export::prometheus::register_histogram_vec;
+use holochain_trace::export::prometheus::HistogramVec;
+use once_cell::sync::Lazy;
+use std::time::Duration;
+
+/// How long it took to get a database connection, by the kind of database
+/// and what was waited for: a `read` or `write` permit, or a `connection`
+/// from the pool.
+static DB_POOL_WAIT: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "holochain_db_pool_wait_seconds",
+        "Time spent waiting to get a database connection",
+        &["kind", "wait"],
+        vec![0.0001, 0.001, 0.005, 0.02, 0.1, 0.5, 2.0, 10.0]
+    )
+    .expect("Metric names are unique")
+});
+
+/// Record how long it took to get a permit or connection for a database.
+pub(crate) fn observe_pool_wait(kind: &DbKind, wait: &'static str, elapsed: Duration) {
+    let kind = match kind {
+        DbKind::Authored(_) => "authored",
+        DbKind::Dht(_) => "dht",
+        DbKind::Cache(_) => "cache",
+        DbKind::Conductor => "conductor",
+        DbKind::Wasm => "wasm",
+        DbKind::P2pAgentStore(_) => "p2p_agent_store",
+        DbKind::P2pMetrics(_) => "p2p_metrics",
+    };
+    DB_POOL_WAIT
+        .with_label_values(&[kind, wait])
+        .observe(elapsed.as_secs_f64());
+}
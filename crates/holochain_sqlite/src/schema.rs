@@ -25,6 +25,18 @@ pub static SCHEMA_CELL: Lazy<Schema> = Lazy::new(|| Schema {
             forward: include_str!("sql/cell/schema/1-up.sql").into(),
             _schema: include_str!("sql/cell/schema/1.sql").into(),
         },
+        M {
+            forward: include_str!("sql/cell/schema/2-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/2.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/3-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/3.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/4-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/4.sql").into(),
+        },
     ],
 });
 
@@ -43,7 +55,17 @@ pub static SCHEMA_WASM: Lazy<Schema> = Lazy::new(|| Schema {
 });
 
 pub static SCHEMA_P2P_STATE: Lazy<Schema> = Lazy::new(|| Schema {
-    migrations: vec![M::initial(include_str!("sql/p2p_agent_store/schema/0.sql"))],
+    migrations: vec![
+        M::initial(include_str!("sql/p2p_agent_store/schema/0.sql")),
+        M {
+            forward: include_str!("sql/p2p_agent_store/schema/1-up.sql").into(),
+            _schema: include_str!("sql/p2p_agent_store/schema/1.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/p2p_agent_store/schema/2-up.sql").into(),
+            _schema: include_str!("sql/p2p_agent_store/schema/2.sql").into(),
+        },
+    ],
 });
 
 pub static SCHEMA_P2P_METRICS: Lazy<Schema> = Lazy::new(|| Schema {
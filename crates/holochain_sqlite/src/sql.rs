@@ -45,6 +45,11 @@ pub mod sql_cell {
             include_str!("sql/cell/schedule/delete_all_ephemeral.sql");
         pub const DELETE_LIVE_EPHEMERAL: &str =
             include_str!("sql/cell/schedule/delete_live_ephemeral.sql");
+        pub const LIST: &str = include_str!("sql/cell/schedule/list.sql");
+        pub const SET_PAUSED: &str = include_str!("sql/cell/schedule/set_paused.sql");
+        pub const HISTORY: &str = include_str!("sql/cell/schedule/history.sql");
+        pub const PRUNE_HISTORY: &str = include_str!("sql/cell/schedule/prune_history.sql");
+        pub const DELETE_HISTORY: &str = include_str!("sql/cell/schedule/delete_history.sql");
     }
     pub mod state_dump {
         pub const DHT_OPS_IN_INTEGRATION_LIMBO: &str =
@@ -67,6 +72,9 @@ pub mod sql_conductor {
         include_str!("sql/conductor/select_valid_cap_grant_for_cap_secret.sql");
     pub const SELECT_VALID_UNRESTRICTED_CAP_GRANT: &str =
         include_str!("sql/conductor/select_valid_unrestricted_cap_grant.sql");
+    pub const SELECT_CAP_GRANTS: &str = include_str!("sql/conductor/select_cap_grants.sql");
+    pub const SELECT_VALID_CAP_CLAIMS: &str =
+        include_str!("sql/conductor/select_valid_cap_claims.sql");
 }
 
 pub(crate) mod sql_p2p_agent_store {
@@ -81,6 +89,15 @@ pub(crate) mod sql_p2p_agent_store {
     pub(crate) const EXTRAPOLATED_COVERAGE: &str =
         include_str!("sql/p2p_agent_store/extrapolated_coverage.sql");
     pub(crate) const PRUNE: &str = include_str!("sql/p2p_agent_store/prune.sql");
+    pub(crate) const FETCH_POOL_INSERT: &str =
+        include_str!("sql/p2p_agent_store/fetch_pool_insert.sql");
+    pub(crate) const FETCH_POOL_DELETE_ALL: &str =
+        include_str!("sql/p2p_agent_store/fetch_pool_delete_all.sql");
+    pub(crate) const FETCH_POOL_SELECT_ALL: &str =
+        include_str!("sql/p2p_agent_store/fetch_pool_select_all.sql");
+    pub(crate) const ARC_PIN_INSERT: &str = include_str!("sql/p2p_agent_store/arc_pin_insert.sql");
+    pub(crate) const ARC_PIN_DELETE: &str = include_str!("sql/p2p_agent_store/arc_pin_delete.sql");
+    pub(crate) const ARC_PIN_SELECT: &str = include_str!("sql/p2p_agent_store/arc_pin_select.sql");
 }
 
 pub(crate) mod sql_p2p_metrics {
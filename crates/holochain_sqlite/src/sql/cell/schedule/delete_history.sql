@@ -0,0 +1,6 @@
+DELETE FROM
+  ScheduledFunctionHistory
+WHERE
+  author = :author
+  AND zome_name = :zome_name
+  AND scheduled_fn = :scheduled_fn
@@ -4,3 +4,4 @@ WHERE
   ephemeral = TRUE
   AND START <= :now
   AND author = :author
+  AND NOT paused
@@ -0,0 +1,13 @@
+SELECT
+  fired_at,
+  outcome
+FROM
+  ScheduledFunctionHistory
+WHERE
+  author = :author
+  AND zome_name = :zome_name
+  AND scheduled_fn = :scheduled_fn
+ORDER BY
+  fired_at DESC
+LIMIT
+  :limit
@@ -0,0 +1,12 @@
+SELECT
+  zome_name,
+  scheduled_fn,
+  maybe_schedule,
+  START,
+  paused
+FROM
+  ScheduledFunctions
+WHERE
+  author = :author
+ORDER BY
+  START ASC
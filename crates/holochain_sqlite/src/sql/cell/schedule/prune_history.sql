@@ -0,0 +1,7 @@
+DELETE FROM
+  ScheduledFunctionHistory
+WHERE
+  author = :author
+  AND zome_name = :zome_name
+  AND scheduled_fn = :scheduled_fn
+  AND fired_at < :fired_at
@@ -0,0 +1,8 @@
+UPDATE
+  ScheduledFunctions
+SET
+  paused = :paused
+WHERE
+  zome_name = :zome_name
+  AND scheduled_fn = :scheduled_fn
+  AND author = :author
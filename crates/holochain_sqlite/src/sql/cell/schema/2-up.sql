@@ -0,0 +1,6 @@
+CREATE TABLE Warrant (
+  hash BLOB PRIMARY KEY ON CONFLICT IGNORE,
+  warrantee BLOB NOT NULL,
+  blob BLOB NOT NULL
+);
+CREATE INDEX Warrant_warrantee_idx ON Warrant (warrantee);
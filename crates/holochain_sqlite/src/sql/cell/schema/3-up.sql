@@ -0,0 +1,6 @@
+CREATE TABLE CountersigningSession (
+  author BLOB NOT NULL,
+  entry_hash BLOB NOT NULL,
+  blob BLOB NOT NULL,
+  PRIMARY KEY (author, entry_hash) ON CONFLICT IGNORE
+);
@@ -0,0 +1,12 @@
+ALTER TABLE
+  ScheduledFunctions
+ADD
+  COLUMN paused BOOLEAN NOT NULL DEFAULT FALSE;
+CREATE TABLE ScheduledFunctionHistory (
+  author BLOB NOT NULL,
+  zome_name TEXT NOT NULL,
+  scheduled_fn TEXT NOT NULL,
+  fired_at INTEGER NOT NULL,
+  outcome BLOB NOT NULL
+);
+CREATE INDEX ScheduledFunctionHistory_fn_idx ON ScheduledFunctionHistory (author, zome_name, scheduled_fn);
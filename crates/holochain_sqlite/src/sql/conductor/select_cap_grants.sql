@@ -0,0 +1,34 @@
+SELECT
+  Action.hash,
+  Action.blob AS action_blob,
+  Entry.hash AS entry_hash,
+  Entry.blob AS entry_blob,
+  (
+    SELECT
+      Revoke.blob
+    FROM
+      Action AS Revoke
+    WHERE
+      Revoke.author = :author
+      AND (
+        Revoke.original_entry_hash = Entry.hash
+        OR Revoke.deletes_entry_hash = Entry.hash
+      )
+    ORDER BY
+      Revoke.seq ASC
+    LIMIT
+      1
+  ) AS revoke_blob
+FROM
+  Entry
+  JOIN Action ON Action.entry_hash = Entry.hash
+WHERE
+  Action.author = :author
+  AND Action.type IN (:create, :update)
+  AND Entry.access_type IS NOT NULL
+  AND (
+    :tag IS NULL
+    OR Entry.tag = :tag
+  )
+ORDER BY
+  Action.seq ASC
@@ -0,0 +1,33 @@
+SELECT
+  Entry.hash,
+  Entry.blob
+FROM
+  Entry
+  JOIN Action ON Action.entry_hash = Entry.hash
+WHERE
+  Action.author = :author
+  AND Entry.grantor IS NOT NULL
+  AND (
+    :grantor IS NULL
+    OR Entry.grantor = :grantor
+  )
+  AND (
+    :tag IS NULL
+    OR Entry.tag = :tag
+  )
+  AND (
+    SELECT
+      COUNT(Revoke.hash)
+    FROM
+      Action AS Revoke
+    WHERE
+      Revoke.author = :author
+      AND (
+        Revoke.original_entry_hash = Entry.hash
+        OR Revoke.deletes_entry_hash = Entry.hash
+      )
+  ) = 0
+GROUP BY
+  Entry.hash
+ORDER BY
+  MIN(Action.seq) ASC
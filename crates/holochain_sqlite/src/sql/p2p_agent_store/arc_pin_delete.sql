@@ -0,0 +1,4 @@
+DELETE FROM
+  p2p_arc_pin
+WHERE
+  agent = :agent;
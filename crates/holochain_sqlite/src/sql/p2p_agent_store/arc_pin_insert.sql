@@ -0,0 +1,4 @@
+INSERT INTO
+  p2p_arc_pin (agent, pin)
+VALUES
+  (:agent, :pin);
@@ -0,0 +1,6 @@
+SELECT
+  pin
+FROM
+  p2p_arc_pin
+WHERE
+  agent = :agent;
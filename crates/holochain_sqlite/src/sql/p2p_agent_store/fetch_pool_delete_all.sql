@@ -0,0 +1,2 @@
+DELETE FROM
+  p2p_fetch_pool;
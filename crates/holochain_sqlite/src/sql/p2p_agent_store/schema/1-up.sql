@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS p2p_fetch_pool (
+  rowid INTEGER PRIMARY KEY UNIQUE NOT NULL,
+  encoded BLOB NOT NULL
+);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS p2p_arc_pin (
+  agent BLOB PRIMARY KEY ON CONFLICT REPLACE,
+  pin BLOB NOT NULL
+);
@@ -0,0 +1,102 @@
+//! Module for items related to the countersigning sessions of local agents
+
+use holo_hash::AgentPubKey;
+use holo_hash::EntryHash;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_zome_types::CounterSigningSessionData;
+
+use crate::prelude::from_blob;
+use crate::prelude::StateQueryResult;
+
+/// Get the countersigning sessions that the author has committed to their
+/// chain but doesn't yet know the outcome of.
+///
+/// Sessions are recorded when the countersigned entry is written and removed
+/// once the session has either completed or been abandoned, so any sessions
+/// returned here were interrupted if the conductor has just started.
+pub fn get_countersigning_sessions(
+    txn: &Transaction,
+    author: &AgentPubKey,
+) -> StateQueryResult<Vec<(EntryHash, CounterSigningSessionData)>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT entry_hash, blob FROM CountersigningSession WHERE author = :author
+        ",
+    )?;
+    let iter = stmt.query_and_then(
+        named_params! {
+            ":author": author
+        },
+        |row| {
+            let entry_hash: EntryHash = row.get("entry_hash")?;
+            let session_data = from_blob::<CounterSigningSessionData>(row.get("blob")?)?;
+            StateQueryResult::Ok((entry_hash, session_data))
+        },
+    )?;
+    iter.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations;
+    use ::fixt::prelude::*;
+    use arbitrary::Arbitrary;
+    use holo_hash::fixt::*;
+    use holochain_sqlite::prelude::*;
+
+    #[test]
+    fn sessions_roundtrip_through_db() {
+        let mut u = arbitrary::Unstructured::new(&holochain_zome_types::NOISE);
+        let test_db = crate::test_utils::test_authored_db();
+        let env = test_db.to_db();
+
+        let author = fixt!(AgentPubKey);
+        let other_author = fixt!(AgentPubKey);
+        let entry_hash = fixt!(EntryHash);
+        let session_data = CounterSigningSessionData::arbitrary(&mut u).unwrap();
+
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| {
+                // Inserting the same session twice is a no-op
+                mutations::insert_countersigning_session(txn, &author, &entry_hash, &session_data)?;
+                mutations::insert_countersigning_session(txn, &author, &entry_hash, &session_data)?;
+                mutations::insert_countersigning_session(
+                    txn,
+                    &other_author,
+                    &entry_hash,
+                    &session_data,
+                )
+            })
+            .unwrap();
+
+        let mut g = env.conn().unwrap();
+        g.with_reader_test(|reader| {
+            let sessions = get_countersigning_sessions(&reader, &author).unwrap();
+            assert_eq!(sessions, vec![(entry_hash.clone(), session_data.clone())]);
+        });
+
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| {
+                mutations::delete_countersigning_session(txn, &author, &entry_hash)
+            })
+            .unwrap();
+
+        let mut g = env.conn().unwrap();
+        g.with_reader_test(|reader| {
+            assert!(get_countersigning_sessions(&reader, &author)
+                .unwrap()
+                .is_empty());
+            // Other authors' sessions for the same entry are untouched
+            assert_eq!(
+                get_countersigning_sessions(&reader, &other_author)
+                    .unwrap()
+                    .len(),
+                1
+            );
+        });
+    }
+}
@@ -3,6 +3,9 @@ use crate::prelude::SignedValidationReceipt;
 use crate::query::from_blob;
 use crate::query::to_blob;
 use crate::schedule::fn_is_scheduled;
+use crate::schedule::scheduled_fn_history;
+use crate::schedule::ScheduledFnExecution;
+use crate::schedule::SCHEDULE_HISTORY_LIMIT;
 use crate::scratch::Scratch;
 use crate::validation_db::ValidationLimboStatus;
 use holo_hash::encode::blake2b_256;
@@ -216,6 +219,20 @@ pub fn insert_validation_receipt(
     Ok(())
 }
 
+/// Insert a [`SignedWarrant`] into the database, ignoring duplicates.
+pub fn insert_warrant(txn: &mut Transaction, warrant: SignedWarrant) -> StateMutationResult<()> {
+    let warrantee = warrant.warrant.warrantee().clone();
+    let bytes: UnsafeBytes = SerializedBytes::try_from(warrant)?.into();
+    let bytes: Vec<u8> = bytes.into();
+    let hash = blake2b_256(&bytes);
+    sql_insert!(txn, Warrant, {
+        "hash": hash,
+        "warrantee": warrantee,
+        "blob": bytes,
+    })?;
+    Ok(())
+}
+
 /// Insert a [`DnaWasm`](holochain_types::prelude::DnaWasm) into the database.
 pub fn insert_wasm(txn: &mut Transaction, wasm: DnaWasmHashed) -> StateMutationResult<()> {
     let (wasm, hash) = wasm.into_inner();
@@ -727,6 +744,58 @@ pub fn unlock_chain(txn: &mut Transaction, author: &AgentPubKey) -> StateMutatio
     Ok(())
 }
 
+/// Record that the author has committed a countersigned entry to their chain
+/// and is waiting to learn the outcome of the session.
+pub fn insert_countersigning_session(
+    txn: &mut Transaction,
+    author: &AgentPubKey,
+    entry_hash: &EntryHash,
+    session_data: &CounterSigningSessionData,
+) -> StateMutationResult<()> {
+    sql_insert!(txn, CountersigningSession, {
+        "author": author,
+        "entry_hash": entry_hash,
+        "blob": to_blob(session_data)?,
+    })?;
+    Ok(())
+}
+
+/// Forget a countersigning session once its outcome is known.
+pub fn delete_countersigning_session(
+    txn: &mut Transaction,
+    author: &AgentPubKey,
+    entry_hash: &EntryHash,
+) -> StateMutationResult<()> {
+    txn.execute(
+        "DELETE FROM CountersigningSession WHERE author = :author AND entry_hash = :entry_hash",
+        named_params! {
+            ":author": author,
+            ":entry_hash": entry_hash,
+        },
+    )?;
+    Ok(())
+}
+
+/// Remove the record of an abandoned countersigning session from the author's
+/// chain, along with its ops.
+/// This must only be called when the countersigned action is the chain head.
+pub fn remove_countersigned_record(
+    txn: &mut Transaction,
+    action_hash: &ActionHash,
+    entry_hash: &EntryHash,
+) -> StateMutationResult<()> {
+    // Ops are removed by the cascade on the Action table.
+    txn.execute(
+        "DELETE FROM Action WHERE hash = :hash",
+        named_params! { ":hash": action_hash },
+    )?;
+    txn.execute(
+        "DELETE FROM Entry WHERE hash = :hash",
+        named_params! { ":hash": entry_hash },
+    )?;
+    Ok(())
+}
+
 pub fn delete_all_ephemeral_scheduled_fns(txn: &mut Transaction) -> StateMutationResult<()> {
     txn.execute(
         holochain_sqlite::sql::sql_cell::schedule::DELETE_ALL_EPHEMERAL,
@@ -859,3 +928,81 @@ pub fn schedule_fn(
     }
     Ok(())
 }
+
+/// Pause or resume a scheduled function.
+/// Returns false if the function is not scheduled.
+pub fn set_scheduled_fn_paused(
+    txn: &mut Transaction,
+    author: &AgentPubKey,
+    scheduled_fn: &ScheduledFn,
+    paused: bool,
+) -> StateMutationResult<bool> {
+    let updated = txn.execute(
+        holochain_sqlite::sql::sql_cell::schedule::SET_PAUSED,
+        named_params! {
+            ":zome_name": scheduled_fn.zome_name().to_string(),
+            ":scheduled_fn": scheduled_fn.fn_name().to_string(),
+            ":author" : author,
+            ":paused": paused,
+        },
+    )?;
+    Ok(updated > 0)
+}
+
+/// Unschedule a function and forget its execution history.
+/// Returns false if the function is not scheduled.
+pub fn cancel_scheduled_fn(
+    txn: &mut Transaction,
+    author: &AgentPubKey,
+    scheduled_fn: &ScheduledFn,
+) -> StateMutationResult<bool> {
+    let zome_name = scheduled_fn.zome_name().to_string();
+    let fn_name = scheduled_fn.fn_name().to_string();
+    let deleted = txn.execute(
+        holochain_sqlite::sql::sql_cell::schedule::DELETE,
+        named_params! {
+            ":zome_name": zome_name,
+            ":scheduled_fn": fn_name,
+            ":author" : author,
+        },
+    )?;
+    txn.execute(
+        holochain_sqlite::sql::sql_cell::schedule::DELETE_HISTORY,
+        named_params! {
+            ":zome_name": zome_name,
+            ":scheduled_fn": fn_name,
+            ":author" : author,
+        },
+    )?;
+    Ok(deleted > 0)
+}
+
+/// Record an execution of a scheduled function, dropping the oldest
+/// executions beyond [`SCHEDULE_HISTORY_LIMIT`].
+pub fn record_scheduled_fn_execution(
+    txn: &mut Transaction,
+    author: &AgentPubKey,
+    scheduled_fn: &ScheduledFn,
+    execution: ScheduledFnExecution,
+) -> StateMutationResult<()> {
+    sql_insert!(txn, ScheduledFunctionHistory, {
+        "author": author,
+        "zome_name": scheduled_fn.zome_name().to_string(),
+        "scheduled_fn": scheduled_fn.fn_name().to_string(),
+        "fired_at": execution.fired_at,
+        "outcome": to_blob(&execution.outcome)?,
+    })?;
+    let history = scheduled_fn_history(txn, scheduled_fn, author)?;
+    if let (SCHEDULE_HISTORY_LIMIT, Some(oldest)) = (history.len(), history.last()) {
+        txn.execute(
+            holochain_sqlite::sql::sql_cell::schedule::PRUNE_HISTORY,
+            named_params! {
+                ":zome_name": scheduled_fn.zome_name().to_string(),
+                ":scheduled_fn": scheduled_fn.fn_name().to_string(),
+                ":author" : author,
+                ":fired_at": oldest.fired_at,
+            },
+        )?;
+    }
+    Ok(())
+}
@@ -1,8 +1,10 @@
+pub use crate::countersigning::*;
 pub use crate::mutations::*;
 pub use crate::query::prelude::*;
 pub use crate::source_chain::*;
 pub use crate::validation_db::*;
 pub use crate::validation_receipts::*;
+pub use crate::warrants::*;
 pub use crate::wasm::*;
 pub use crate::workspace::*;
 pub use crate::*;
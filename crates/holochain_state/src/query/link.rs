@@ -17,11 +17,21 @@ pub struct LinksQuery {
     pub base: Arc<AnyLinkableHash>,
     pub type_query: LinkTypeFilter,
     pub tag: Option<String>,
+    pub filter: GetLinksFilter,
     query: String,
 }
 
 impl LinksQuery {
     pub fn new(base: AnyLinkableHash, type_query: LinkTypeFilter, tag: Option<LinkTag>) -> Self {
+        Self::with_filter(base, type_query, tag, GetLinksFilter::default())
+    }
+
+    pub fn with_filter(
+        base: AnyLinkableHash,
+        type_query: LinkTypeFilter,
+        tag: Option<LinkTag>,
+        filter: GetLinksFilter,
+    ) -> Self {
         let tag = tag.map(|tag| Self::tag_to_hex(&tag));
         let create_string = Self::create_query_string(&type_query, tag.clone());
         let delete_string = Self::delete_query_string(&type_query, tag.clone());
@@ -29,6 +39,7 @@ impl LinksQuery {
             base: Arc::new(base),
             type_query,
             tag,
+            filter,
             query: Self::create_query(create_string, delete_string),
         }
     }
@@ -63,6 +74,9 @@ impl LinksQuery {
             AND
             DhtOp.validation_status = :status
             AND DhtOp.when_integrated IS NOT NULL
+            AND (:after IS NULL OR DhtOp.authored_timestamp > :after)
+            AND (:before IS NULL OR DhtOp.authored_timestamp < :before)
+            AND (:author IS NULL OR Action.author = :author)
         "
     }
     fn create_query_string(type_query: &LinkTypeFilter, tag: Option<String>) -> String {
@@ -126,6 +140,9 @@ impl LinksQuery {
                 ":delete": DhtOpType::RegisterRemoveLink,
                 ":status": ValidationStatus::Valid,
                 ":base_hash": self.base,
+                ":after": self.filter.after,
+                ":before": self.filter.before,
+                ":author": self.filter.author,
             }
         }
         .to_vec()
@@ -139,6 +156,17 @@ impl GetLinksQuery {
         }
     }
 
+    pub fn with_filter(
+        base: AnyLinkableHash,
+        type_query: LinkTypeFilter,
+        tag: Option<LinkTag>,
+        filter: GetLinksFilter,
+    ) -> Self {
+        Self {
+            query: LinksQuery::with_filter(base, type_query, tag, filter),
+        }
+    }
+
     pub fn base(base: AnyLinkableHash, dependencies: Vec<ZomeIndex>) -> Self {
         Self {
             query: LinksQuery::base(base, dependencies),
@@ -173,12 +201,15 @@ impl Query for GetLinksQuery {
         let base_filter = query.base.clone();
         let type_query_filter = query.type_query.clone();
         let tag_filter = query.tag.clone();
+        let links_filter = query.filter.clone();
         let f = move |action: &QueryData<Self>| match action.action() {
             Action::CreateLink(CreateLink {
                 base_address,
                 tag,
                 zome_index,
                 link_type,
+                author,
+                timestamp,
                 ..
             }) => {
                 *base_address == *base_filter
@@ -186,6 +217,7 @@ impl Query for GetLinksQuery {
                     && tag_filter
                         .as_ref()
                         .map_or(true, |t| LinksQuery::tag_to_hex(tag).starts_with(&(**t)))
+                    && links_filter.matches(author, *timestamp)
             }
             Action::DeleteLink(DeleteLink { base_address, .. }) => *base_address == *base_filter,
             _ => false,
@@ -218,9 +250,11 @@ impl Query for GetLinksQuery {
     where
         S: Store,
     {
-        let mut links: Self::Output = state.creates.into_values().collect();
-        links.sort_by_key(|l| l.timestamp);
-        Ok(links)
+        let links = state.creates.into_values();
+        Ok(self
+            .query
+            .filter
+            .paginate(links, |l| (l.timestamp, l.create_link_hash.clone())))
     }
 }
 
@@ -3,12 +3,52 @@ use crate::query::from_blob;
 use holo_hash::AgentPubKey;
 use holochain_sqlite::rusqlite::OptionalExtension;
 use holochain_sqlite::rusqlite::{named_params, Transaction};
+use holochain_sqlite::sql::sql_cell::schedule;
 use holochain_zome_types::FunctionName;
 use holochain_zome_types::Schedule;
 use holochain_zome_types::ScheduledFn;
 use holochain_zome_types::Timestamp;
 use holochain_zome_types::ZomeName;
 
+/// How many executions of each scheduled function are kept in its history.
+pub const SCHEDULE_HISTORY_LIMIT: usize = 10;
+
+/// The outcome of a single execution of a scheduled function.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum ScheduledFnOutcome {
+    /// The function ran and returned its next schedule, if any.
+    Ok(Option<Schedule>),
+    /// The function could not be called or returned an error.
+    Error(String),
+}
+
+/// A single execution of a scheduled function.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ScheduledFnExecution {
+    /// When the function was dispatched.
+    pub fired_at: Timestamp,
+    /// What came of it.
+    pub outcome: ScheduledFnOutcome,
+}
+
+/// A function that is scheduled for a cell, as seen by the conductor admin.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ScheduledFnInfo {
+    /// The zome the function is in.
+    pub zome_name: ZomeName,
+    /// The scheduled function.
+    pub fn_name: FunctionName,
+    /// The schedule the function was last given.
+    pub schedule: Option<Schedule>,
+    /// When the function will next be dispatched, unless it is paused.
+    pub next_fire: Timestamp,
+    /// Paused functions stay scheduled but are not dispatched.
+    pub paused: bool,
+    /// The most recent executions, newest first.
+    pub history: Vec<ScheduledFnExecution>,
+}
+
 pub fn fn_is_scheduled(
     txn: &Transaction,
     scheduled_fn: ScheduledFn,
@@ -52,6 +92,7 @@ pub fn live_scheduled_fns(
         start <= :now
         AND :now <= end
         AND author = :author
+        AND NOT paused
         ORDER BY start ASC",
     )?;
     let rows = stmt.query_map(
@@ -76,3 +117,179 @@ pub fn live_scheduled_fns(
     }
     Ok(ret)
 }
+
+/// All the functions scheduled for an author, soonest first.
+pub fn list_scheduled_fns(
+    txn: &Transaction,
+    author: &AgentPubKey,
+) -> StateMutationResult<Vec<ScheduledFnInfo>> {
+    let rows = {
+        let mut stmt = txn.prepare(schedule::LIST)?;
+        let rows = stmt.query_map(
+            named_params! {
+                ":author": author,
+            },
+            |row| {
+                Ok((
+                    ScheduledFn::new(
+                        ZomeName(row.get::<_, String>(0)?.into()),
+                        FunctionName(row.get(1)?),
+                    ),
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            },
+        )?;
+        let mut ret = vec![];
+        for row in rows {
+            ret.push(row?);
+        }
+        ret
+    };
+    let mut ret = vec![];
+    for (scheduled_fn, maybe_schedule, next_fire, paused) in rows {
+        let history = scheduled_fn_history(txn, &scheduled_fn, author)?;
+        let (zome_name, fn_name) = (
+            scheduled_fn.zome_name().clone(),
+            scheduled_fn.fn_name().clone(),
+        );
+        ret.push(ScheduledFnInfo {
+            zome_name,
+            fn_name,
+            schedule: from_blob(maybe_schedule)?,
+            next_fire,
+            paused,
+            history,
+        });
+    }
+    Ok(ret)
+}
+
+/// The most recent executions of a scheduled function, newest first.
+pub fn scheduled_fn_history(
+    txn: &Transaction,
+    scheduled_fn: &ScheduledFn,
+    author: &AgentPubKey,
+) -> StateMutationResult<Vec<ScheduledFnExecution>> {
+    let mut stmt = txn.prepare(schedule::HISTORY)?;
+    let rows = stmt.query_map(
+        named_params! {
+            ":zome_name": scheduled_fn.zome_name().to_string(),
+            ":scheduled_fn": scheduled_fn.fn_name().to_string(),
+            ":author": author,
+            ":limit": SCHEDULE_HISTORY_LIMIT,
+        },
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let mut ret = vec![];
+    for row in rows {
+        let (fired_at, outcome) = row?;
+        ret.push(ScheduledFnExecution {
+            fired_at,
+            outcome: from_blob(outcome)?,
+        });
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::*;
+    use holochain_sqlite::prelude::*;
+
+    #[test]
+    fn pause_record_and_cancel_scheduled_fn() {
+        let test_db = crate::test_utils::test_authored_db();
+        let env = test_db.to_db();
+
+        let author = fixt!(AgentPubKey);
+        let scheduled_fn = ScheduledFn::new("foo".into(), "bar".into());
+        let schedule = Schedule::Persisted("* * * * * * *".to_string());
+        let now = Timestamp::now();
+
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| {
+                mutations::schedule_fn(
+                    txn,
+                    &author,
+                    scheduled_fn.clone(),
+                    Some(schedule.clone()),
+                    now,
+                )?;
+                assert!(mutations::set_scheduled_fn_paused(
+                    txn,
+                    &author,
+                    &scheduled_fn,
+                    true
+                )?);
+                // Rescheduling keeps the function paused.
+                mutations::schedule_fn(
+                    txn,
+                    &author,
+                    scheduled_fn.clone(),
+                    Some(schedule.clone()),
+                    now,
+                )?;
+                for i in 0..SCHEDULE_HISTORY_LIMIT as i64 + 2 {
+                    mutations::record_scheduled_fn_execution(
+                        txn,
+                        &author,
+                        &scheduled_fn,
+                        ScheduledFnExecution {
+                            fired_at: Timestamp::from_micros(i),
+                            outcome: ScheduledFnOutcome::Ok(Some(schedule.clone())),
+                        },
+                    )?;
+                }
+                StateMutationResult::Ok(())
+            })
+            .unwrap();
+
+        let mut g = env.conn().unwrap();
+        g.with_reader_test(|reader| {
+            let listed = list_scheduled_fns(&reader, &author).unwrap();
+            assert_eq!(listed.len(), 1);
+            assert!(listed[0].paused);
+            assert_eq!(listed[0].schedule, Some(schedule.clone()));
+            assert!(listed[0].next_fire > now);
+            // Only the most recent executions are kept, newest first.
+            let fired: Vec<_> = listed[0]
+                .history
+                .iter()
+                .map(|e| e.fired_at.as_micros())
+                .collect();
+            let expected: Vec<_> = (2..SCHEDULE_HISTORY_LIMIT as i64 + 2).rev().collect();
+            assert_eq!(fired, expected);
+            // Paused functions are never live.
+            assert!(live_scheduled_fns(&reader, Timestamp::max(), &author)
+                .unwrap()
+                .is_empty());
+        });
+
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| {
+                assert!(mutations::cancel_scheduled_fn(txn, &author, &scheduled_fn)?);
+                assert!(!mutations::cancel_scheduled_fn(
+                    txn,
+                    &author,
+                    &scheduled_fn
+                )?);
+                StateMutationResult::Ok(())
+            })
+            .unwrap();
+
+        let mut g = env.conn().unwrap();
+        g.with_reader_test(|reader| {
+            assert!(list_scheduled_fns(&reader, &author).unwrap().is_empty());
+            assert!(scheduled_fn_history(&reader, &scheduled_fn, &author)
+                .unwrap()
+                .is_empty());
+        });
+    }
+}
@@ -18,6 +18,8 @@ use holochain_p2p::HolochainP2pDnaT;
 use holochain_sqlite::rusqlite::params;
 use holochain_sqlite::rusqlite::OptionalExtension;
 use holochain_sqlite::rusqlite::Transaction;
+use holochain_sqlite::sql::sql_conductor::SELECT_CAP_GRANTS;
+use holochain_sqlite::sql::sql_conductor::SELECT_VALID_CAP_CLAIMS;
 use holochain_sqlite::sql::sql_conductor::SELECT_VALID_CAP_GRANT_FOR_CAP_SECRET;
 use holochain_sqlite::sql::sql_conductor::SELECT_VALID_UNRESTRICTED_CAP_GRANT;
 use holochain_types::chc::ChcError;
@@ -43,7 +45,11 @@ use holochain_zome_types::ActionHashed;
 use holochain_zome_types::ActionType;
 use holochain_zome_types::ActionUnweighed;
 use holochain_zome_types::CapAccess;
+use holochain_zome_types::CapClaim;
+use holochain_zome_types::CapClaimsInput;
 use holochain_zome_types::CapGrant;
+use holochain_zome_types::CapGrantInfo;
+use holochain_zome_types::CapGrantsInput;
 use holochain_zome_types::CapSecret;
 use holochain_zome_types::CellId;
 use holochain_zome_types::ChainQueryFilter;
@@ -62,6 +68,7 @@ use holochain_zome_types::Signature;
 use holochain_zome_types::SignedAction;
 use holochain_zome_types::SignedActionHashed;
 use holochain_zome_types::Timestamp;
+use holochain_zome_types::ZomeCallCapGrant;
 
 use crate::chain_lock::is_chain_locked;
 use crate::chain_lock::is_lock_expired;
@@ -336,6 +343,15 @@ impl SourceChain {
         // If the lock isn't empty this is a countersigning session.
         let is_countersigning_session = !lock.is_empty();
 
+        // The session is recorded so its outcome can be found out
+        // even if the conductor restarts before it completes.
+        let countersigning_session = entries.iter().find_map(|entry| match entry.as_content() {
+            Entry::CounterSign(session_data, _) => {
+                Some((entry.as_hash().clone(), (**session_data).clone()))
+            }
+            _ => None,
+        });
+
         let ops_to_integrate = ops
             .iter()
             .map(|op| (op.1.clone(), op.0.dht_basis().clone()))
@@ -397,6 +413,9 @@ impl SourceChain {
                         set_withhold_publish(txn, op_hash)?;
                     }
                 }
+                if let Some((entry_hash, session_data)) = &countersigning_session {
+                    insert_countersigning_session(txn, &author, entry_hash, session_data)?;
+                }
                 SourceChainResult::Ok(actions)
             })
             .await
@@ -716,6 +735,158 @@ where
         Ok(valid_cap_grant)
     }
 
+    /// List the capability grants committed by the chain author,
+    /// in the order they were committed, including those in the scratch.
+    pub async fn cap_grants(&self, input: CapGrantsInput) -> SourceChainResult<Vec<CapGrantInfo>> {
+        let author = self.author.clone();
+        let tag = input.tag.clone();
+        let mut grants = self
+            .vault
+            .async_reader(move |txn| {
+                let mut stmt = txn.prepare(SELECT_CAP_GRANTS)?;
+                let grants = stmt
+                    .query_and_then(
+                        named_params! {
+                            ":author": author,
+                            ":create": ActionType::Create.as_sql(),
+                            ":update": ActionType::Update.as_sql(),
+                            ":tag": tag,
+                        },
+                        |row| {
+                            let SignedAction(action, _) = from_blob(row.get("action_blob")?)?;
+                            let revoked_by: Option<SignedAction> = row
+                                .get::<_, Option<Vec<u8>>>("revoke_blob")?
+                                .map(from_blob)
+                                .transpose()?;
+                            let action_hash: ActionHash = row.get("hash")?;
+                            let entry_hash: EntryHash = row.get("entry_hash")?;
+                            let cap_grant =
+                                remote_cap_grant(&from_blob::<Entry>(row.get("entry_blob")?)?);
+                            StateQueryResult::Ok(cap_grant.map(|cap_grant| {
+                                (
+                                    entry_hash,
+                                    CapGrantInfo {
+                                        cap_grant,
+                                        action_hash,
+                                        created_at: action.timestamp(),
+                                        revoked_at: revoked_by.map(|revoke| revoke.0.timestamp()),
+                                    },
+                                )
+                            }))
+                        },
+                    )?
+                    .filter_map(Result::transpose)
+                    .collect::<StateQueryResult<Vec<_>>>();
+                grants
+            })
+            .await?;
+
+        // Grants committed in the scratch come after the persisted ones, and
+        // updates and deletes in the scratch may revoke grants of either.
+        self.scratch.apply(|scratch| {
+            for record in scratch.records() {
+                if let Some(revoked) = revoked_entry_hash(record.action()) {
+                    for (entry_hash, info) in grants.iter_mut() {
+                        if entry_hash == revoked && info.revoked_at.is_none() {
+                            info.revoked_at = Some(record.action().timestamp());
+                        }
+                    }
+                }
+                if !matches!(record.action(), Action::Create(_) | Action::Update(_)) {
+                    continue;
+                }
+                let cap_grant = record
+                    .entry()
+                    .as_option()
+                    .and_then(remote_cap_grant)
+                    .filter(|grant| input.tag.as_ref().map_or(true, |tag| *tag == grant.tag));
+                if let (Some(cap_grant), Some(entry_hash)) =
+                    (cap_grant, record.action().entry_hash())
+                {
+                    grants.push((
+                        entry_hash.clone(),
+                        CapGrantInfo {
+                            cap_grant,
+                            action_hash: record.action_address().clone(),
+                            created_at: record.action().timestamp(),
+                            revoked_at: None,
+                        },
+                    ));
+                }
+            }
+        })?;
+
+        Ok(grants
+            .into_iter()
+            .map(|(_, info)| info)
+            .filter(|info| input.include_revoked || info.is_active())
+            .collect())
+    }
+
+    /// List the capability claims committed by the chain author which have not
+    /// been updated or deleted, in the order they were committed, including
+    /// those in the scratch.
+    pub async fn cap_claims(&self, input: CapClaimsInput) -> SourceChainResult<Vec<CapClaim>> {
+        let author = self.author.clone();
+        let grantor = input.grantor.clone();
+        let tag = input.tag.clone();
+        let mut claims = self
+            .vault
+            .async_reader(move |txn| {
+                let mut stmt = txn.prepare(SELECT_VALID_CAP_CLAIMS)?;
+                let claims = stmt
+                    .query_and_then(
+                        named_params! {
+                            ":author": author,
+                            ":grantor": grantor,
+                            ":tag": tag,
+                        },
+                        |row| {
+                            let entry_hash: EntryHash = row.get("hash")?;
+                            StateQueryResult::Ok(match from_blob(row.get("blob")?)? {
+                                Entry::CapClaim(claim) => Some((entry_hash, claim)),
+                                _ => None,
+                            })
+                        },
+                    )?
+                    .filter_map(Result::transpose)
+                    .collect::<StateQueryResult<Vec<_>>>();
+                claims
+            })
+            .await?;
+
+        // Claims committed in the scratch come after the persisted ones, and
+        // updates and deletes in the scratch may revoke claims of either.
+        self.scratch.apply(|scratch| {
+            for record in scratch.records() {
+                if let Some(revoked) = revoked_entry_hash(record.action()) {
+                    claims.retain(|(entry_hash, _)| entry_hash != revoked);
+                }
+                if !matches!(record.action(), Action::Create(_) | Action::Update(_)) {
+                    continue;
+                }
+                let claim = record
+                    .entry()
+                    .as_option()
+                    .and_then(Entry::as_cap_claim)
+                    .filter(|claim| {
+                        input
+                            .grantor
+                            .as_ref()
+                            .map_or(true, |grantor| grantor == claim.grantor())
+                            && input.tag.as_ref().map_or(true, |tag| tag == claim.tag())
+                    });
+                if let (Some(claim), Some(entry_hash)) = (claim, record.action().entry_hash()) {
+                    if claims.iter().all(|(hash, _)| hash != entry_hash) {
+                        claims.push((entry_hash.clone(), claim.clone()));
+                    }
+                }
+            }
+        })?;
+
+        Ok(claims.into_iter().map(|(_, claim)| claim).collect())
+    }
+
     /// Query Actions in the source chain.
     /// This returns a Vec rather than an iterator because it is intended to be
     /// used by the `query` host function, which crosses the wasm boundary
@@ -1036,6 +1207,23 @@ async fn rebase_actions_on(
     Ok(actions)
 }
 
+/// The remote agent grant held by an entry, if it is a capability grant.
+fn remote_cap_grant(entry: &Entry) -> Option<ZomeCallCapGrant> {
+    match entry.as_cap_grant() {
+        Some(CapGrant::RemoteAgent(grant)) => Some(grant),
+        _ => None,
+    }
+}
+
+/// The entry an update or delete action revokes, as a grant or claim.
+fn revoked_entry_hash(action: &Action) -> Option<&EntryHash> {
+    match action {
+        Action::Update(update) => Some(&update.original_entry_address),
+        Action::Delete(delete) => Some(&delete.deletes_entry_address),
+        _ => None,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn genesis(
     authored: DbWrite<DbKindAuthored>,
@@ -1423,7 +1611,7 @@ pub mod tests {
         .await?;
 
         let action_builder = builder::CloseChain {
-            new_dna_hash: fixt!(DnaHash),
+            new_target: MigrationTarget::Dna(fixt!(DnaHash)),
         };
         chain_1
             .put(action_builder.clone(), None, ChainTopOrdering::Strict)
@@ -1917,6 +2105,109 @@ pub mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn cap_grants_and_claims_include_scratch() -> SourceChainResult<()> {
+        let test_db = test_authored_db();
+        let dht_db = test_dht_db();
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.to_db().into());
+        let keystore = test_keystore();
+        let db = test_db.to_db();
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+        mock.expect_chc().return_const(None);
+
+        let mut agents = AgentPubKeyFixturator::new(Predictable);
+        let alice = agents.next().unwrap();
+        let bob = agents.next().unwrap();
+        let secret = CapSecretFixturator::new(Unpredictable).next().unwrap();
+        let grant =
+            ZomeCallCapGrant::new("tag".into(), CapAccess::from(secret), GrantedFunctions::All);
+        let claim = CapClaim::new("tag".into(), bob, secret);
+        source_chain::genesis(
+            db.clone(),
+            dht_db.to_db(),
+            &dht_db_cache,
+            keystore.clone(),
+            fake_dna_hash(1),
+            alice.clone(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let chain = SourceChain::new(
+            db.clone(),
+            dht_db.to_db(),
+            dht_db_cache.clone(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+
+        let create = |entry: Entry, entry_type: EntryType| {
+            let chain = &chain;
+            async move {
+                let (entry, entry_hash) = EntryHashed::from_content_sync(entry).into_inner();
+                let action_builder = builder::Create {
+                    entry_type,
+                    entry_hash: entry_hash.clone(),
+                };
+                let action_hash = chain
+                    .put_weightless(action_builder, Some(entry), ChainTopOrdering::default())
+                    .await?;
+                SourceChainResult::Ok((action_hash, entry_hash))
+            }
+        };
+
+        // Grants and claims are listed before they have been flushed
+        let (grant_action_hash, grant_entry_hash) =
+            create(Entry::CapGrant(grant.clone()), EntryType::CapGrant).await?;
+        let (claim_action_hash, claim_entry_hash) =
+            create(Entry::CapClaim(claim.clone()), EntryType::CapClaim).await?;
+        let grants = chain.cap_grants(CapGrantsInput::default()).await?;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].cap_grant, grant);
+        assert_eq!(grants[0].action_hash, grant_action_hash);
+        assert!(grants[0].is_active());
+        assert_eq!(
+            chain.cap_claims(CapClaimsInput::default()).await?,
+            vec![claim.clone()]
+        );
+        chain.flush(&mock).await.unwrap();
+
+        // Deletes in the scratch revoke grants and claims which were flushed
+        for (deletes_address, deletes_entry_address) in [
+            (grant_action_hash, grant_entry_hash),
+            (claim_action_hash, claim_entry_hash),
+        ] {
+            let action_builder = builder::Delete {
+                deletes_address,
+                deletes_entry_address,
+            };
+            chain
+                .put_weightless(action_builder, None, ChainTopOrdering::default())
+                .await?;
+        }
+        assert!(chain
+            .cap_grants(CapGrantsInput::default())
+            .await?
+            .is_empty());
+        let grants = chain
+            .cap_grants(CapGrantsInput {
+                tag: None,
+                include_revoked: true,
+            })
+            .await?;
+        assert_eq!(grants.len(), 1);
+        assert!(!grants[0].is_active());
+        assert!(chain
+            .cap_claims(CapClaimsInput::default())
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
     // @todo bring all this back when we want to administer cap claims better
     // #[tokio::test(flavor = "multi_thread")]
     // async fn test_get_cap_claim() -> SourceChainResult<()> {
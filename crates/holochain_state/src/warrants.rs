@@ -0,0 +1,140 @@
+//! Module for items related to issuing and storing warrants
+
+use holo_hash::AgentPubKey;
+use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::MetaLairClient;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_zome_types::warrant::SignedWarrant;
+use holochain_zome_types::warrant::Warrant;
+
+use crate::prelude::from_blob;
+use crate::prelude::StateQueryResult;
+
+/// Sign a warrant as its issuer.
+pub async fn sign_warrant(
+    warrant: Warrant,
+    keystore: &MetaLairClient,
+) -> holochain_keystore::LairResult<SignedWarrant> {
+    let signature = warrant.issuer.sign(keystore, warrant.clone()).await?;
+    Ok(SignedWarrant::new(warrant, signature))
+}
+
+/// Check that a warrant was signed by its issuer, and that the action it
+/// refers to was really signed by the agent it is against.
+///
+/// This does not re-run validation of the action, so a verified warrant only
+/// proves that the issuer claims the action is invalid.
+pub async fn verify_warrant(signed_warrant: &SignedWarrant) -> bool {
+    let SignedWarrant { warrant, signature } = signed_warrant;
+    let action = warrant.action.action().clone();
+    warrant
+        .issuer
+        .verify_signature(signature, warrant.clone())
+        .await
+        && warrant
+            .warrantee()
+            .verify_signature(warrant.action.signature(), action)
+            .await
+}
+
+/// Get all the warrants held against an agent.
+pub fn get_warrants_for_agent(
+    txn: &Transaction,
+    warrantee: &AgentPubKey,
+) -> StateQueryResult<Vec<SignedWarrant>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT blob FROM Warrant WHERE warrantee = :warrantee
+        ",
+    )?;
+    let iter = stmt.query_and_then(
+        named_params! {
+            ":warrantee": warrantee
+        },
+        |row| from_blob::<SignedWarrant>(row.get("blob")?),
+    )?;
+    iter.collect()
+}
+
+/// Check whether any warrant is held against this action.
+pub fn contains_warrant_for_action(txn: &Transaction, warrant: &Warrant) -> StateQueryResult<bool> {
+    Ok(get_warrants_for_agent(txn, warrant.warrantee())?
+        .iter()
+        .any(|held| held.warrant.action == warrant.action))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations;
+    use ::fixt::prelude::*;
+    use holochain_sqlite::prelude::*;
+    use holochain_zome_types::fixt::*;
+    use holochain_zome_types::prelude::*;
+
+    async fn fake_warrant(keystore: &MetaLairClient) -> SignedWarrant {
+        let author = keystore.new_sign_keypair_random().await.unwrap();
+        let issuer = keystore.new_sign_keypair_random().await.unwrap();
+        let mut action = fixt!(Create);
+        action.author = author.clone();
+        let action = Action::Create(action);
+        let signature = author.sign(keystore, action.clone()).await.unwrap();
+        let warrant = Warrant::new(
+            SignedAction(action, signature),
+            "invalid".into(),
+            issuer,
+            Timestamp::now(),
+        );
+        sign_warrant(warrant, keystore).await.unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn warrants_verify_and_roundtrip_through_db() {
+        holochain_trace::test_run().ok();
+
+        let test_db = crate::test_utils::test_dht_db();
+        let env = test_db.to_db();
+        let keystore = crate::test_utils::test_keystore();
+
+        let warrant = fake_warrant(&keystore).await;
+        assert!(verify_warrant(&warrant).await);
+
+        // Tampering with the reason invalidates the issuer's signature
+        let mut tampered = warrant.clone();
+        tampered.warrant.reason = "something else".into();
+        assert!(!verify_warrant(&tampered).await);
+
+        // A warrant against an action not signed by its author doesn't verify
+        let mut forged = warrant.warrant.clone();
+        forged.action.1 = fixt!(Signature);
+        let forged = sign_warrant(forged, &keystore).await.unwrap();
+        assert!(!verify_warrant(&forged).await);
+
+        let other = fake_warrant(&keystore).await;
+        let warrantee = warrant.warrant.warrantee().clone();
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| {
+                // Inserting the same warrant twice is a no-op
+                mutations::insert_warrant(txn, warrant.clone())?;
+                mutations::insert_warrant(txn, warrant.clone())?;
+                mutations::insert_warrant(txn, other)
+            })
+            .unwrap();
+
+        // A warrant from another issuer against the same action is already
+        // covered, but one against a different action isn't
+        let mut same_action = warrant.warrant.clone();
+        same_action.issuer = keystore.new_sign_keypair_random().await.unwrap();
+        let unrelated = fake_warrant(&keystore).await;
+
+        let mut g = env.conn().unwrap();
+        g.with_reader_test(|reader| {
+            let warrants = get_warrants_for_agent(&reader, &warrantee).unwrap();
+            assert_eq!(warrants, vec![warrant]);
+            assert!(contains_warrant_for_action(&reader, &same_action).unwrap());
+            assert!(!contains_warrant_for_action(&reader, &unrelated.warrant).unwrap());
+        });
+    }
+}
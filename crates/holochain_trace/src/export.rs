@@ -0,0 +1,40 @@
+//! # Export
+//! Exporting metrics and traces to external monitoring systems.
+//!
+//! Crates register the metrics they record with the [`prometheus`] default
+//! registry, usually as lazily initialized statics next to where they're
+//! recorded, for example:
+//! ```
+//! use holochain_trace::export::prometheus::{register_int_counter, IntCounter};
+//! use once_cell::sync::Lazy;
+//!
+//! static THINGS_DONE: Lazy<IntCounter> = Lazy::new(|| {
+//!     register_int_counter!("my_crate_things_done_total", "The number of things done")
+//!         .expect("Metric names are unique")
+//! });
+//!
+//! THINGS_DONE.inc();
+//! assert!(holochain_trace::export::prometheus_text().contains("my_crate_things_done_total 1"));
+//! ```
+//! All registered metrics can then be rendered for scraping with
+//! [`prometheus_text`].
+//!
+//! With the `opentelemetry-on` feature, spans can also be sent to an
+//! OpenTelemetry collector, see [`init_fmt_with_otlp`](crate::init_fmt_with_otlp).
+
+pub use prometheus;
+
+use prometheus::Encoder;
+
+/// Render every metric in the default registry in the Prometheus text
+/// exposition format.
+pub fn prometheus_text() -> String {
+    let mut buffer = Vec::new();
+    if let Err(e) = prometheus::TextEncoder::new().encode(&prometheus::gather(), &mut buffer) {
+        tracing::error!(?e, "Failed to encode metrics");
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+/// The content type of [`prometheus_text`] for an HTTP response.
+pub const PROMETHEUS_CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
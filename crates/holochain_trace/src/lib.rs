@@ -86,6 +86,7 @@ use std::{str::FromStr, sync::Once};
 use flames::{toml_path, FlameTimed};
 use fmt::*;
 
+pub mod export;
 mod flames;
 mod fmt;
 pub mod metrics;
@@ -127,6 +128,12 @@ pub type ParseError = String;
 
 static INIT: Once = Once::new();
 
+/// The tracer for sending spans to an OTLP collector, if one was set up by
+/// [init_fmt_with_otlp].
+#[cfg(feature = "opentelemetry-on")]
+static OTLP_TRACER: once_cell::sync::OnceCell<opentelemetry::sdk::trace::Tracer> =
+    once_cell::sync::OnceCell::new();
+
 impl FromStr for Output {
     type Err = ParseError;
     fn from_str(day: &str) -> Result<Self, Self::Err> {
@@ -312,9 +319,58 @@ pub fn init_fmt(output: Output) -> Result<(), errors::TracingError> {
     }
 }
 
+/// The same as [init_fmt], but also sends spans to the OpenTelemetry
+/// collector listening for OTLP over gRPC at `endpoint`,
+/// e.g. `http://localhost:4317`.
+///
+/// Spans are exported in batches from a tokio task, so this must be called
+/// from within a tokio runtime. Call [shutdown_otlp] before exiting to send
+/// any spans that haven't been exported yet.
+#[cfg(feature = "opentelemetry-on")]
+pub fn init_fmt_with_otlp(output: Output, endpoint: &str) -> Result<(), errors::TracingError> {
+    use opentelemetry_otlp::WithExportConfig;
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(opentelemetry::sdk::trace::config().with_resource(
+            opentelemetry::sdk::Resource::new(vec![opentelemetry::KeyValue::new(
+                "service.name",
+                "holochain",
+            )]),
+        ))
+        .install_batch(opentelemetry::runtime::Tokio)?;
+    let _ = OTLP_TRACER.set(tracer);
+    init_fmt(output)
+}
+
+/// Export any spans which are waiting to be sent to the OTLP collector, and
+/// stop sending them.
+#[cfg(feature = "opentelemetry-on")]
+pub fn shutdown_otlp() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
 fn finish<S>(subscriber: S) -> Result<(), errors::TracingError>
 where
     S: Subscriber + Send + Sync + for<'span> LookupSpan<'span>,
+{
+    #[cfg(feature = "opentelemetry-on")]
+    if let Some(tracer) = OTLP_TRACER.get() {
+        use tracing_subscriber::layer::SubscriberExt;
+        return set_global_default(
+            subscriber.with(tracing_opentelemetry::layer().with_tracer(tracer.clone())),
+        );
+    }
+    set_global_default(subscriber)
+}
+
+fn set_global_default<S>(subscriber: S) -> Result<(), errors::TracingError>
+where
+    S: Subscriber + Send + Sync,
 {
     let mut result = Ok(());
     INIT.call_once(|| {
@@ -338,5 +394,8 @@ pub mod errors {
         TracingFlame,
         #[error(transparent)]
         BadDirective(#[from] tracing_subscriber::filter::ParseError),
+        #[cfg(feature = "opentelemetry-on")]
+        #[error(transparent)]
+        Otlp(#[from] opentelemetry::trace::TraceError),
     }
 }
@@ -126,6 +126,19 @@ pub type EnableCloneCellPayload = DisableCloneCellPayload;
 /// Arguments to delete a disabled clone cell of an app.
 pub type DeleteCloneCellPayload = DisableCloneCellPayload;
 
+/// Arguments to provision the cell of a role whose provisioning was deferred
+/// when the app was installed.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ProvisionDeferredCellPayload {
+    /// The app id that the role belongs to
+    pub app_id: InstalledAppId,
+    /// The name of the role to provision
+    pub role_name: RoleName,
+    /// Proof-of-membrane-membership, if required by the DNA.
+    /// Not used if the role is bound to an existing cell.
+    pub membrane_proof: Option<MembraneProof>,
+}
+
 /// An [AppBundle] along with an [AgentPubKey] and optional [InstalledAppId]
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct InstallAppPayload {
@@ -158,16 +171,22 @@ pub enum AppBundleSource {
     Bundle(AppBundle),
     /// A local file path
     Path(PathBuf),
+    /// The hash of a bundle published in the conductor's app store
+    AppStore(AppHash),
     // /// A URL
     // Url(String),
 }
 
 impl AppBundleSource {
     /// Get the bundle from the source. Consumes the source.
+    ///
+    /// An [`AppBundleSource::AppStore`] source cannot be resolved here, since
+    /// it requires access to the conductor's app store service.
     pub async fn resolve(self) -> Result<AppBundle, AppBundleError> {
         Ok(match self {
             Self::Bundle(bundle) => bundle,
             Self::Path(path) => AppBundle::decode(&ffs::read(&path).await?)?,
+            Self::AppStore(hash) => return Err(AppBundleError::AppStoreSourceUnresolved(hash)),
             // Self::Url(url) => todo!("reqwest::get"),
         })
     }
@@ -712,6 +731,43 @@ impl InstalledAppCommon {
             })
     }
 
+    /// Provision the base cell of a role whose provisioning was deferred.
+    ///
+    /// If `is_dependency` is true, the cell is owned by another app.
+    pub fn provision_deferred_cell(
+        &mut self,
+        role_name: &RoleName,
+        cell_id: CellId,
+        is_dependency: bool,
+    ) -> AppResult<()> {
+        let app_role_assignment = self.role_mut(role_name)?;
+        if !app_role_assignment.is_deferred {
+            return Err(AppError::RoleNotDeferred(role_name.clone()));
+        }
+        app_role_assignment.base_cell_id = cell_id;
+        app_role_assignment.is_provisioned = true;
+        app_role_assignment.is_deferred = false;
+        app_role_assignment.is_dependency = is_dependency;
+        Ok(())
+    }
+
+    /// Iterator of the provisioned cells which this app owns, i.e. excluding
+    /// cells which are owned by other apps that this app depends on.
+    pub fn owned_cells(&self) -> impl Iterator<Item = &CellId> {
+        self.role_assignments
+            .values()
+            .filter(|role| !role.is_dependency)
+            .filter_map(|role| role.provisioned_cell())
+    }
+
+    /// Iterator of the cells owned by other apps, which this app depends on.
+    pub fn dependency_cells(&self) -> impl Iterator<Item = &CellId> {
+        self.role_assignments
+            .values()
+            .filter(|role| role.is_dependency)
+            .filter_map(|role| role.provisioned_cell())
+    }
+
     /// Accessor
     pub fn agent_key(&self) -> &AgentPubKey {
         &self.agent_key
@@ -766,6 +822,8 @@ impl InstalledAppCommon {
                 let role = AppRoleAssignment {
                     base_cell_id: cell_id,
                     is_provisioned: true,
+                    is_deferred: false,
+                    is_dependency: false,
                     clones: HashMap::new(),
                     clone_limit: 256,
                     next_clone_index: 0,
@@ -1006,6 +1064,16 @@ pub struct AppRoleAssignment {
     /// If false, then `base_cell_id` is just recording what that cell will be
     /// called in the future.
     is_provisioned: bool,
+    /// Records whether provisioning of the base cell was deferred at
+    /// installation. A deferred cell is provisioned later via the admin API,
+    /// at which point this becomes false.
+    #[serde(default)]
+    is_deferred: bool,
+    /// Records whether the base cell is owned by another app, which this app
+    /// depends on. The owning app can't be uninstalled while this app is
+    /// installed.
+    #[serde(default)]
+    is_dependency: bool,
     /// The number of allowed clone cells.
     clone_limit: u32,
     /// The index of the next clone cell to be created.
@@ -1025,6 +1093,8 @@ impl AppRoleAssignment {
         Self {
             base_cell_id,
             is_provisioned,
+            is_deferred: false,
+            is_dependency: false,
             clone_limit,
             clones: HashMap::new(),
             next_clone_index: 0,
@@ -1032,6 +1102,22 @@ impl AppRoleAssignment {
         }
     }
 
+    /// Constructor for a role whose base cell will be provisioned later.
+    pub fn new_deferred(base_cell_id: CellId, clone_limit: u32) -> Self {
+        Self {
+            is_deferred: true,
+            ..Self::new(base_cell_id, false, clone_limit)
+        }
+    }
+
+    /// Constructor for a role whose base cell is owned by another app.
+    pub fn new_dependency(cell_id: CellId, clone_limit: u32) -> Self {
+        Self {
+            is_dependency: true,
+            ..Self::new(cell_id, true, clone_limit)
+        }
+    }
+
     /// Accessor
     pub fn cell_id(&self) -> &CellId {
         &self.base_cell_id
@@ -1056,6 +1142,16 @@ impl AppRoleAssignment {
         }
     }
 
+    /// Accessor
+    pub fn is_deferred(&self) -> bool {
+        self.is_deferred
+    }
+
+    /// Accessor
+    pub fn is_dependency(&self) -> bool {
+        self.is_dependency
+    }
+
     /// Accessor
     pub fn clone_ids(&self) -> impl Iterator<Item = &CloneId> {
         self.clones.keys()
@@ -17,6 +17,30 @@ mod tests;
 #[derive(Debug, Serialize, Deserialize, derive_more::From, shrinkwraprs::Shrinkwrap)]
 pub struct AppBundle(mr_bundle::Bundle<AppManifest>);
 
+/// The content hash of an AppBundle: the Blake2b-256 hash of its encoded bytes.
+///
+/// This is how an app is referred to in an app store.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AppHash(#[serde(with = "serde_bytes")] Vec<u8>);
+
+impl AppHash {
+    /// Compute the hash of an encoded AppBundle
+    pub fn from_bundle_bytes(bytes: &[u8]) -> Self {
+        Self(holo_hash::encode::blake2b_256(bytes))
+    }
+
+    /// The raw hash bytes
+    pub fn get_raw_32(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for AppHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", holo_hash::encode::holo_hash_encode(&self.0))
+    }
+}
+
 impl AppBundle {
     /// Create an AppBundle from a manifest and DNA files
     pub async fn new<R: IntoIterator<Item = (PathBuf, DnaBundle)>>(
@@ -40,6 +64,11 @@ impl AppBundle {
             .map_err(Into::into)
     }
 
+    /// Compute the content hash of this bundle
+    pub fn app_hash(&self) -> AppBundleResult<AppHash> {
+        Ok(AppHash::from_bundle_bytes(&self.encode()?))
+    }
+
     /// Convert to the inner Bundle
     pub fn into_inner(self) -> mr_bundle::Bundle<AppManifest> {
         self.0
@@ -57,12 +86,16 @@ impl AppBundle {
     }
 
     /// Given a partial list of already available DnaFiles, fetch the missing others via
-    /// mr_bundle::Location resolution
+    /// mr_bundle::Location resolution.
+    ///
+    /// `existing_cells` are the running cells of other installed apps, which
+    /// roles may be bound to rather than creating new cells.
     pub async fn resolve_cells(
         self,
         dna_store: &impl DnaStore,
         agent: AgentPubKey,
         membrane_proofs: HashMap<RoleName, MembraneProof>,
+        existing_cells: &[CellId],
     ) -> AppBundleResult<AppRoleResolution> {
         let AppManifestValidated { name: _, roles } = self.manifest().clone().validate()?;
         let bundle = Arc::new(self);
@@ -70,7 +103,9 @@ impl AppBundle {
             let bundle = bundle.clone();
             Ok((
                 role_name.clone(),
-                bundle.resolve_cell(dna_store, role_name, role).await?,
+                bundle
+                    .resolve_cell(dna_store, &agent, existing_cells, role_name, role)
+                    .await?,
             ))
         });
         let resolution = futures::future::join_all(tasks)
@@ -95,9 +130,21 @@ impl AppBundle {
                             }
 
                             CellProvisioningOp::Existing(cell_id, clone_limit) => {
-                                let role = AppRoleAssignment::new(cell_id, true, clone_limit);
+                                let role = AppRoleAssignment::new_dependency(cell_id, clone_limit);
                                 resolution.role_assignments.push((role_name, role));
                             }
+                            CellProvisioningOp::Deferred(dna_hash, dna, clone_limit) => {
+                                let agent = resolution.agent.clone();
+                                let cell_id = CellId::new(dna_hash, agent);
+                                // The membrane proof is provided when the cell is provisioned
+                                if let Some(dna) = dna {
+                                    resolution.dnas_to_register.push((dna, None));
+                                }
+                                resolution.role_assignments.push((
+                                    role_name,
+                                    AppRoleAssignment::new_deferred(cell_id, clone_limit),
+                                ));
+                            }
                             CellProvisioningOp::ProvisionOnly(dna, clone_limit) => {
                                 let agent = resolution.agent.clone();
                                 let dna_hash = dna.dna_hash().clone();
@@ -134,6 +181,8 @@ impl AppBundle {
     async fn resolve_cell(
         &self,
         dna_store: &impl DnaStore,
+        agent: &AgentPubKey,
+        existing_cells: &[CellId],
         role_name: RoleName,
         role: AppRoleManifestValidated,
     ) -> AppBundleResult<CellProvisioningOp> {
@@ -143,7 +192,7 @@ impl AppBundle {
                 installed_hash,
                 clone_limit,
                 modifiers,
-                deferred: _,
+                deferred,
             } => {
                 let dna = self
                     .resolve_dna(
@@ -154,44 +203,72 @@ impl AppBundle {
                         modifiers,
                     )
                     .await?;
-                CellProvisioningOp::CreateFromDnaFile(dna, clone_limit)
+                if deferred {
+                    CellProvisioningOp::Deferred(dna.dna_hash().clone(), Some(dna), clone_limit)
+                } else {
+                    CellProvisioningOp::CreateFromDnaFile(dna, clone_limit)
+                }
             }
 
             AppRoleManifestValidated::UseExisting {
                 installed_hash,
                 clone_limit,
-                deferred: _,
-            } => self.resolve_cell_existing(&installed_hash, clone_limit),
+                deferred,
+            } => {
+                let dna_hash: DnaHash = installed_hash.into();
+                if deferred {
+                    CellProvisioningOp::Deferred(dna_hash, None, clone_limit)
+                } else {
+                    match find_existing_cell(&dna_hash, agent, existing_cells) {
+                        Some(cell_id) => CellProvisioningOp::Existing(cell_id, clone_limit),
+                        None => {
+                            return Err(AppBundleError::CellResolutionFailure(
+                                role_name,
+                                format!(
+                                    "No existing cell of agent {} with DNA hash {}",
+                                    agent, dna_hash
+                                ),
+                            ))
+                        }
+                    }
+                }
+            }
             AppRoleManifestValidated::CreateIfNotExists {
                 location,
                 installed_hash,
                 clone_limit,
                 modifiers,
-                deferred: _,
-            } => match self.resolve_cell_existing(&installed_hash, clone_limit) {
-                op @ CellProvisioningOp::Existing(_, _) => op,
-                CellProvisioningOp::HashMismatch(_, _) => {
-                    let dna = self
-                        .resolve_dna(
-                            role_name,
-                            dna_store,
-                            &location,
-                            Some(&installed_hash),
-                            modifiers,
-                        )
-                        .await?;
-                    CellProvisioningOp::CreateFromDnaFile(dna, clone_limit)
-                }
-                CellProvisioningOp::Conflict(_) => {
-                    unimplemented!("conflicts are not handled, or even possible yet")
-                }
-                CellProvisioningOp::CreateFromDnaFile(_, _) => {
-                    unreachable!("resolve_cell_existing will never return a Create op")
-                }
-                CellProvisioningOp::ProvisionOnly(_, _) => {
-                    unreachable!("resolve_cell_existing will never return a ProvisionOnly")
+                deferred,
+            } => {
+                let existing = if deferred {
+                    None
+                } else {
+                    find_existing_cell(&installed_hash.clone().into(), agent, existing_cells)
+                };
+                match existing {
+                    Some(cell_id) => CellProvisioningOp::Existing(cell_id, clone_limit),
+                    None => {
+                        let dna = self
+                            .resolve_dna(
+                                role_name,
+                                dna_store,
+                                &location,
+                                Some(&installed_hash),
+                                modifiers,
+                            )
+                            .await?;
+                        if deferred {
+                            CellProvisioningOp::Deferred(
+                                dna.dna_hash().clone(),
+                                Some(dna),
+                                clone_limit,
+                            )
+                        } else {
+                            CellProvisioningOp::CreateFromDnaFile(dna, clone_limit)
+                        }
+                    }
                 }
-            },
+            }
             AppRoleManifestValidated::CloneOnly {
                 clone_limit,
                 location,
@@ -243,14 +320,6 @@ impl AppBundle {
         Ok(dna_file)
     }
 
-    fn resolve_cell_existing(
-        &self,
-        _version: &DnaHashB64,
-        _clone_limit: u32,
-    ) -> CellProvisioningOp {
-        unimplemented!("Reusing existing cells is not yet implemented")
-    }
-
     async fn resolve_location(
         &self,
         location: &mr_bundle::Location,
@@ -263,10 +332,18 @@ impl AppBundle {
     }
 }
 
-/// This function is called in places where it will be necessary to rework that
-/// area after use_existing has been implemented
-pub fn we_must_remember_to_rework_cell_panic_handling_after_implementing_use_existing_cell_resolution(
-) {
+/// Find an existing cell of `agent` with the given DNA hash, for a role to be
+/// bound to. Cells of other agents are never chosen, since that would bind
+/// the app to someone else's identity.
+pub fn find_existing_cell(
+    dna_hash: &DnaHash,
+    agent: &AgentPubKey,
+    existing_cells: &[CellId],
+) -> Option<CellId> {
+    existing_cells
+        .iter()
+        .find(|cell_id| cell_id.dna_hash() == dna_hash && cell_id.agent_pubkey() == agent)
+        .cloned()
 }
 
 /// The answer to the question:
@@ -292,7 +369,8 @@ impl AppRoleResolution {
     }
 
     /// Return the IDs of new cells to be created as part of the resolution.
-    /// Does not return existing cells to be reused.
+    /// Does not return existing cells to be reused, or cells whose
+    /// provisioning is deferred.
     pub fn cells_to_create(&self) -> Vec<(CellId, Option<MembraneProof>)> {
         self.dnas_to_register
             .iter()
@@ -302,6 +380,12 @@ impl AppRoleResolution {
                     proof.clone(),
                 )
             })
+            .filter(|(cell_id, _)| {
+                !self
+                    .role_assignments
+                    .iter()
+                    .any(|(_, role)| role.is_deferred() && role.cell_id() == cell_id)
+            })
             .collect()
     }
 }
@@ -317,6 +401,10 @@ pub enum CellProvisioningOp {
     /// No creation needed, but there might be a clone_limit, and so we need
     /// to know which DNA to use for making clones
     ProvisionOnly(DnaFile, u32),
+    /// Defer provisioning of the Cell with this DNA hash until it is requested
+    /// via the admin API. The DNA file is registered now, if the Cell may
+    /// need to be created from it.
+    Deferred(DnaHash, Option<DnaFile>, u32),
     /// The specified installed_hash does not match the actual hash of the DNA selected for provisioning. Expected: {0}, Actual: {1}
     HashMismatch(DnaHash, DnaHash),
     /// Ambiguous result, needs manual resolution; can't provision (should this be an Err?)
@@ -6,7 +6,10 @@ use app_manifest_v1::tests::{app_manifest_fixture, app_manifest_properties_fixtu
 
 use super::AppBundle;
 
-async fn app_bundle_fixture(modifiers: DnaModifiersOpt<YamlProperties>) -> (AppBundle, DnaFile) {
+async fn app_bundle_fixture(
+    modifiers: DnaModifiersOpt<YamlProperties>,
+    provisioning: CellProvisioning,
+) -> (AppBundle, DnaFile) {
     let dna_wasm = DnaWasmHashed::from_content(DnaWasm::new_invalid()).await;
     let fake_wasms = vec![dna_wasm.clone().into_content()];
     let fake_zomes = vec![IntegrityZome::new(
@@ -19,12 +22,13 @@ async fn app_bundle_fixture(modifiers: DnaModifiersOpt<YamlProperties>) -> (AppB
 
     let path1 = PathBuf::from(format!("{}", dna1.dna_hash()));
 
-    let manifest = app_manifest_fixture(
+    let mut manifest = app_manifest_fixture(
         Some(DnaLocation::Bundled(path1.clone())),
         DnaHash::with_data_sync(dna1.dna_def()),
         modifiers,
     )
     .await;
+    manifest.roles[0].provisioning = Some(provisioning);
 
     let resources = vec![(path1, DnaBundle::from_dna_file(dna1.clone()).await.unwrap())];
 
@@ -45,7 +49,7 @@ async fn provisioning_1_create() {
         origin_time: None,
         quantum_time: None,
     };
-    let (bundle, dna) = app_bundle_fixture(modifiers).await;
+    let (bundle, dna) = app_bundle_fixture(modifiers, CellProvisioning::default()).await;
 
     // Apply the modifier overrides specified in the manifest fixture
     let dna = dna
@@ -61,6 +65,7 @@ async fn provisioning_1_create() {
             &std::collections::HashMap::new(),
             agent.clone(),
             Default::default(),
+            &[],
         )
         .await
         .unwrap();
@@ -76,3 +81,100 @@ async fn provisioning_1_create() {
     };
     assert_eq!(resolution, expected);
 }
+
+/// Test that a deferred cell is not created, but its DNA is registered
+#[tokio::test]
+async fn provisioning_1_create_deferred() {
+    holochain_trace::test_run().ok();
+    let agent = fixt!(AgentPubKey);
+    let (bundle, dna) = app_bundle_fixture(
+        DnaModifiersOpt::none(),
+        CellProvisioning::Create { deferred: true },
+    )
+    .await;
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+
+    let resolution = bundle
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            agent.clone(),
+            Default::default(),
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let role = AppRoleAssignment::new_deferred(cell_id, 50);
+    let expected = AppRoleResolution {
+        agent,
+        dnas_to_register: vec![(dna, None)],
+        role_assignments: vec![("role_name".into(), role)],
+    };
+    assert_eq!(resolution, expected);
+    assert!(resolution.cells_to_create().is_empty());
+}
+
+/// Test that a UseExisting role is bound to an existing cell of the
+/// installing agent, and fails if there is none
+#[tokio::test]
+async fn provisioning_1_use_existing() {
+    holochain_trace::test_run().ok();
+    let agent = fixt!(AgentPubKey);
+    let other_agent = fixt!(AgentPubKey);
+    let (bundle, dna) = app_bundle_fixture(
+        DnaModifiersOpt::none(),
+        CellProvisioning::UseExisting { deferred: false },
+    )
+    .await;
+    let dna_hash = dna.dna_hash().to_owned();
+    let own_cell_id = CellId::new(dna_hash.clone(), agent.clone());
+    let other_cell_id = CellId::new(dna_hash, other_agent);
+    let unrelated_cell_id = fixt!(CellId);
+    let bundle_bytes = bundle.encode().unwrap();
+    let decode = || AppBundle::decode(&bundle_bytes).unwrap();
+
+    let resolution = decode()
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            agent.clone(),
+            Default::default(),
+            &[
+                other_cell_id.clone(),
+                own_cell_id.clone(),
+                unrelated_cell_id.clone(),
+            ],
+        )
+        .await
+        .unwrap();
+    let expected = AppRoleResolution {
+        agent: agent.clone(),
+        dnas_to_register: vec![],
+        role_assignments: vec![(
+            "role_name".into(),
+            AppRoleAssignment::new_dependency(own_cell_id, 50),
+        )],
+    };
+    assert_eq!(resolution, expected);
+    assert!(resolution.cells_to_create().is_empty());
+
+    // Another agent's cell is never used, even if it's the only candidate
+    let result = decode()
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            agent.clone(),
+            Default::default(),
+            &[other_cell_id.clone(), unrelated_cell_id.clone()],
+        )
+        .await;
+    matches::assert_matches!(result, Err(AppBundleError::CellResolutionFailure(..)));
+
+    let result = decode()
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            agent,
+            Default::default(),
+            &[unrelated_cell_id],
+        )
+        .await;
+    matches::assert_matches!(result, Err(AppBundleError::CellResolutionFailure(..)));
+}
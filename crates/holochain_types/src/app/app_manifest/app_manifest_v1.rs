@@ -121,18 +121,15 @@ pub enum CellProvisioning {
     /// Always create a new Cell when installing this App
     Create { deferred: bool },
 
-    /*
-        TODO: implement
+    /// Require that a Cell is already installed by another App for the same
+    /// agent, whose DNA hash matches the `installed_hash` of this role. The Cell is shared
+    /// with that App, which can't be uninstalled while this App depends on it.
+    /// If no such Cell exists, *app installation fails*.
+    UseExisting { deferred: bool },
 
-        /// Require that a Cell is already installed which matches the DNA installed_hash
-        /// spec, and which has an Agent that's associated with this App's agent
-        /// via DPKI. If no such Cell exists, *app installation fails*.
-        UseExisting { deferred: bool },
+    /// Try `UseExisting`, and if that fails, fallback to `Create`
+    CreateIfNotExists { deferred: bool },
 
-        /// Try `UseExisting`, and if that fails, fallback to `Create`
-        CreateIfNotExists { deferred: bool },
-
-    */
     /// Install or locate the DNA, but never create a Cell for this DNA.
     /// Only allow clones to be created from the DNA specified.
     /// This case requires `clone_limit > 0`, otherwise no Cells will ever be created.
@@ -160,6 +157,8 @@ impl AppManifestV1 {
                 CellProvisioning::Create { .. } | CellProvisioning::CloneOnly => {
                     role.dna.modifiers.network_seed = Some(network_seed.clone());
                 }
+                CellProvisioning::UseExisting { .. }
+                | CellProvisioning::CreateIfNotExists { .. } => {}
             }
         }
     }
@@ -196,28 +195,28 @@ impl AppManifestV1 {
                             modifiers,
                             installed_hash,
                         },
-                        // CellProvisioning::UseExisting { deferred } => {
-                        //     AppRoleManifestValidated::UseExisting {
-                        //         deferred,
-                        //         clone_limit,
-                        //         installed_hash: Self::require(
-                        //             installed_hash,
-                        //             "roles.dna.installed_hash",
-                        //         )?,
-                        //     }
-                        // }
-                        // CellProvisioning::CreateIfNotExists { deferred } => {
-                        //     AppRoleManifestValidated::CreateIfNotExists {
-                        //         deferred,
-                        //         clone_limit,
-                        //         location: Self::require(location, "roles.dna.(path|url)")?,
-                        //         installed_hash: Self::require(
-                        //             installed_hash,
-                        //             "roles.dna.installed_hash",
-                        //         )?,
-                        //         modifiers,
-                        //     }
-                        // }
+                        CellProvisioning::UseExisting { deferred } => {
+                            AppRoleManifestValidated::UseExisting {
+                                deferred,
+                                clone_limit,
+                                installed_hash: Self::require(
+                                    installed_hash,
+                                    "roles.dna.installed_hash",
+                                )?,
+                            }
+                        }
+                        CellProvisioning::CreateIfNotExists { deferred } => {
+                            AppRoleManifestValidated::CreateIfNotExists {
+                                deferred,
+                                clone_limit,
+                                location: Self::require(location, "roles.dna.(path|url)")?,
+                                installed_hash: Self::require(
+                                    installed_hash,
+                                    "roles.dna.installed_hash",
+                                )?,
+                                modifiers,
+                            }
+                        }
                         CellProvisioning::CloneOnly => AppRoleManifestValidated::CloneOnly {
                             clone_limit,
                             location: Self::require(location, "roles.dna.(path|url)")?,
@@ -353,14 +352,14 @@ roles:
         manifest.roles = vec![
             AppRoleManifest::arbitrary(&mut u).unwrap(),
             AppRoleManifest::arbitrary(&mut u).unwrap(),
-            // AppRoleManifest::arbitrary(&mut u).unwrap(),
-            // AppRoleManifest::arbitrary(&mut u).unwrap(),
+            AppRoleManifest::arbitrary(&mut u).unwrap(),
+            AppRoleManifest::arbitrary(&mut u).unwrap(),
         ];
         manifest.roles[0].provisioning = Some(CellProvisioning::Create { deferred: false });
         manifest.roles[1].provisioning = Some(CellProvisioning::Create { deferred: false });
-        // manifest.roles[2].provisioning = Some(CellProvisioning::UseExisting { deferred: false });
-        // manifest.roles[3].provisioning =
-        //     Some(CellProvisioning::CreateIfNotExists { deferred: false });
+        manifest.roles[2].provisioning = Some(CellProvisioning::UseExisting { deferred: false });
+        manifest.roles[3].provisioning =
+            Some(CellProvisioning::CreateIfNotExists { deferred: false });
 
         let network_seed = NetworkSeed::from("blabla");
         manifest.set_network_seed(network_seed.clone());
@@ -374,15 +373,15 @@ roles:
             manifest.roles[1].dna.modifiers.network_seed.as_ref(),
             Some(&network_seed)
         );
-        // assert_eq!(
-        //     manifest.roles[3].dna.modifiers.network_seed.as_ref(),
-        //     Some(&network_seed)
-        // );
-
-        // // - The others do not.
-        // assert_ne!(
-        //     manifest.roles[2].dna.modifiers.network_seed.as_ref(),
-        //     Some(&network_seed)
-        // );
+
+        // - The others do not.
+        assert_ne!(
+            manifest.roles[2].dna.modifiers.network_seed.as_ref(),
+            Some(&network_seed)
+        );
+        assert_ne!(
+            manifest.roles[3].dna.modifiers.network_seed.as_ref(),
+            Some(&network_seed)
+        );
     }
 }
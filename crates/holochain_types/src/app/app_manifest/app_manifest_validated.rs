@@ -57,9 +57,8 @@ pub enum AppRoleManifestValidated {
         modifiers: DnaModifiersOpt,
         installed_hash: Option<DnaHashB64>,
     },
-    /// Require that a Cell is already installed with a specified DNA hash,
-    /// and which has an Agent that's associated with this App's agent
-    /// via DPKI. If no such Cell exists, *app installation fails*.
+    /// Require that a Cell is already installed by another App with a
+    /// specified DNA hash. If no such Cell exists, *app installation fails*.
     UseExisting {
         clone_limit: u32,
         deferred: bool,
@@ -26,6 +26,9 @@ pub enum AppError {
     #[error("Tried to access missing role name: '{0}'")]
     RoleNameMissing(RoleName),
 
+    #[error("Tried to provision the cell for role '{0}', whose provisioning was not deferred")]
+    RoleNotDeferred(RoleName),
+
     #[error("Tried to install app '{0}' which contains duplicate role names. The following role names have duplicates: {1:?}")]
     DuplicateRoleNames(InstalledAppId, Vec<RoleName>),
 }
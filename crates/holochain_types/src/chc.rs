@@ -86,6 +86,12 @@ pub enum ChcError {
 
     #[error("The CHC service is unreachable: {0}")]
     ServiceUnreachable(String),
+
+    #[error("The CHC service returned an error (status {0}): {1}")]
+    ServiceError(u16, String),
+
+    #[error("Invalid CHC URL: {0}")]
+    InvalidUrl(String),
 }
 
 #[allow(missing_docs)]
@@ -2,7 +2,12 @@
 //! There are two main kinds of Signal: system-defined, and app-defined:
 //! - App-defined signals are produced via the `emit_signal` host function.
 //! - System-defined signals are produced in various places in the system
+//!
+//! The chunks of a streaming zome call are also sent as signals, but only to
+//! the connection that made the call.
 
+use crate::app::AppStatus;
+use crate::app::InstalledAppId;
 use crate::impl_from;
 use holochain_serialized_bytes::prelude::*;
 use holochain_zome_types::prelude::*;
@@ -22,20 +27,191 @@ pub enum Signal {
     },
     /// System-defined signals
     System(SystemSignal),
+    /// A chunk of output from a streaming zome call, produced via the
+    /// `stream_chunk` host function.
+    ///
+    /// These are only sent to the connection that made the call and are
+    /// never broadcast.
+    ZomeCallStream {
+        /// The id the client gave the streaming zome call
+        stream_id: ZomeCallStreamId,
+        /// The chunk the zome function yielded
+        chunk: ExternIO,
+    },
 }
 
+/// Identifies a streaming zome call among the others made on the same
+/// connection. It is chosen by the client.
+pub type ZomeCallStreamId = u64;
+
 /// A Signal which originates from within the Holochain system, as opposed to
 /// from within a Cell
-///
-/// TODO, decide what these will be. For instance, maybe there is a
-/// DataAvailable signal for doing async network requests
 #[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
 pub enum SystemSignal {
     /// Since we have no real system signals, we use a test signal for testing
     /// TODO: replace instances of this with something real
     Test(String),
     /// A countersigning session has successfully completed.
-    SuccessfulCountersigning(holo_hash::EntryHash),
+    SuccessfulCountersigning {
+        /// The cell that took part in the session
+        cell_id: CellId,
+        /// The countersigned entry
+        entry_hash: holo_hash::EntryHash,
+    },
+    /// A countersigning session that was interrupted did not complete,
+    /// so its entry was removed from the chain and the chain unlocked.
+    AbandonedCountersigning {
+        /// The cell that took part in the session
+        cell_id: CellId,
+        /// The countersigned entry
+        entry_hash: holo_hash::EntryHash,
+    },
+    /// An app was enabled, disabled, paused or started.
+    AppStatusChanged {
+        /// The app whose status changed
+        installed_app_id: InstalledAppId,
+        /// The status the app is now in
+        status: AppStatus,
+    },
+    /// A validator rejected an op that a cell published.
+    PublishRejected {
+        /// The cell that authored the op
+        cell_id: CellId,
+        /// The rejected op
+        dht_op_hash: holo_hash::DhtOpHash,
+        /// The action the op was produced from
+        action_hash: holo_hash::ActionHash,
+        /// The validators that signed the rejection
+        validators: Vec<AgentPubKey>,
+    },
+    /// A peer came online in a network space.
+    PeerJoined {
+        /// The DNA of the space
+        dna_hash: DnaHash,
+        /// The peer
+        agent: AgentPubKey,
+    },
+    /// A peer announced it is leaving a network space.
+    PeerLeft {
+        /// The DNA of the space
+        dna_hash: DnaHash,
+        /// The peer
+        agent: AgentPubKey,
+    },
+    /// A clone cell was created in an app.
+    CloneCellCreated {
+        /// The app the clone cell belongs to
+        installed_app_id: InstalledAppId,
+        /// The clone cell
+        clone_id: CloneId,
+        /// The cell id of the clone cell
+        cell_id: CellId,
+    },
+    /// A disabled clone cell was enabled.
+    CloneCellEnabled {
+        /// The app the clone cell belongs to
+        installed_app_id: InstalledAppId,
+        /// The clone cell
+        clone_id: CloneId,
+        /// The cell id of the clone cell
+        cell_id: CellId,
+    },
+    /// A clone cell was disabled.
+    CloneCellDisabled {
+        /// The app the clone cell belongs to
+        installed_app_id: InstalledAppId,
+        /// The clone cell
+        clone_id: CloneId,
+        /// The cell id of the clone cell
+        cell_id: CellId,
+    },
+    /// A disabled clone cell was deleted.
+    CloneCellDeleted {
+        /// The app the clone cell belonged to
+        installed_app_id: InstalledAppId,
+        /// The clone cell
+        clone_id: CloneId,
+        /// The cell id of the clone cell
+        cell_id: CellId,
+    },
+}
+
+/// The kind of a [`SystemSignal`], for filtering purposes
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemSignalKind {
+    /// [`SystemSignal::Test`]
+    Test,
+    /// The outcome of a countersigning session
+    Countersigning,
+    /// [`SystemSignal::AppStatusChanged`]
+    AppStatus,
+    /// [`SystemSignal::PublishRejected`]
+    PublishRejected,
+    /// Peers joining or leaving a space
+    Peer,
+    /// The lifecycle of clone cells
+    CloneCell,
+}
+
+/// What a [`SystemSignal`] concerns, which determines who may receive it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SystemSignalScope<'a> {
+    /// The whole conductor
+    Conductor,
+    /// A single cell
+    Cell(&'a CellId),
+    /// A single app
+    App(&'a InstalledAppId),
+    /// Every cell of a DNA
+    Dna(&'a DnaHash),
+}
+
+impl SystemSignal {
+    /// The kind of this signal
+    pub fn kind(&self) -> SystemSignalKind {
+        match self {
+            Self::Test(_) => SystemSignalKind::Test,
+            Self::SuccessfulCountersigning { .. } | Self::AbandonedCountersigning { .. } => {
+                SystemSignalKind::Countersigning
+            }
+            Self::AppStatusChanged { .. } => SystemSignalKind::AppStatus,
+            Self::PublishRejected { .. } => SystemSignalKind::PublishRejected,
+            Self::PeerJoined { .. } | Self::PeerLeft { .. } => SystemSignalKind::Peer,
+            Self::CloneCellCreated { .. }
+            | Self::CloneCellEnabled { .. }
+            | Self::CloneCellDisabled { .. }
+            | Self::CloneCellDeleted { .. } => SystemSignalKind::CloneCell,
+        }
+    }
+
+    /// What this signal concerns
+    pub fn scope(&self) -> SystemSignalScope<'_> {
+        match self {
+            Self::Test(_) => SystemSignalScope::Conductor,
+            Self::SuccessfulCountersigning { cell_id, .. }
+            | Self::AbandonedCountersigning { cell_id, .. }
+            | Self::PublishRejected { cell_id, .. } => SystemSignalScope::Cell(cell_id),
+            Self::AppStatusChanged {
+                installed_app_id, ..
+            }
+            | Self::CloneCellCreated {
+                installed_app_id, ..
+            }
+            | Self::CloneCellEnabled {
+                installed_app_id, ..
+            }
+            | Self::CloneCellDisabled {
+                installed_app_id, ..
+            }
+            | Self::CloneCellDeleted {
+                installed_app_id, ..
+            } => SystemSignalScope::App(installed_app_id),
+            Self::PeerJoined { dna_hash, .. } | Self::PeerLeft { dna_hash, .. } => {
+                SystemSignalScope::Dna(dna_hash)
+            }
+        }
+    }
 }
 
 /// Create a test signal
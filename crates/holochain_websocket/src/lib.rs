@@ -85,6 +85,7 @@ use stream_cancel::Valve;
 use tracing::instrument;
 use url2::Url2;
 use util::url_to_addr;
+use util::Socket;
 use websocket::Websocket;
 
 mod websocket_config;
@@ -119,13 +120,31 @@ pub async fn connect(
     // socket.set_keepalive(Some(std::time::Duration::from_secs(
     //     config.tcp_keepalive_s as u64,
     // )))?;
-    let (socket, _) = tokio_tungstenite::client_async_with_config(
-        url.as_str(),
-        socket,
-        Some(config.to_tungstenite()),
-    )
-    .await
-    .map_err(|e| Error::new(ErrorKind::Other, e))?;
+    client_handshake(url.as_str(), Socket::Tcp(socket), config).await
+}
+
+#[cfg(unix)]
+#[instrument(skip(config, path))]
+/// Create a new websocket connection over the unix domain socket at this path.
+pub async fn connect_unix(
+    path: impl AsRef<std::path::Path>,
+    config: Arc<WebsocketConfig>,
+) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
+    let socket = tokio::net::UnixStream::connect(path).await?;
+    // The handshake needs a url, but there is no host to connect to.
+    let url = format!("{}://localhost/", config.scheme);
+    client_handshake(&url, Socket::Unix(socket), config).await
+}
+
+async fn client_handshake(
+    url: &str,
+    socket: Socket,
+    config: Arc<WebsocketConfig>,
+) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
+    let (socket, _) =
+        tokio_tungstenite::client_async_with_config(url, socket, Some(config.to_tungstenite()))
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, e))?;
     tracing::debug!("Client connected");
 
     // Noop valve because we don't have a listener to shutdown the
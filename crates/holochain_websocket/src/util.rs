@@ -1,12 +1,87 @@
 //! internal websocket utility types and code
 
 use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 use url2::{url2, Url2};
 
 use std::io::{Error, ErrorKind, Result};
 
-pub(crate) type ToFromSocket = tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>;
+pub(crate) type ToFromSocket = tokio_tungstenite::WebSocketStream<Socket>;
+
+/// The raw stream that a websocket connection runs over.
+#[derive(Debug)]
+pub(crate) enum Socket {
+    /// A tcp connection.
+    Tcp(tokio::net::TcpStream),
+    /// A unix domain socket connection.
+    #[cfg(unix)]
+    Unix(tokio::net::UnixStream),
+}
+
+impl Socket {
+    /// The url of the other end of this connection.
+    pub(crate) fn peer_url(&self, scheme: &str) -> Result<Url2> {
+        match self {
+            Socket::Tcp(socket) => Ok(addr_to_url(socket.peer_addr()?, scheme)),
+            // The connecting end of a unix socket is usually unnamed,
+            // so fall back to the path of the listening socket.
+            #[cfg(unix)]
+            Socket::Unix(socket) => match socket.peer_addr()?.as_pathname() {
+                Some(path) => Ok(path_to_url(path)),
+                None => match socket.local_addr()?.as_pathname() {
+                    Some(path) => Ok(path_to_url(path)),
+                    None => Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "unix socket has no path",
+                    )),
+                },
+            },
+        }
+    }
+}
+
+impl AsyncRead for Socket {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Socket::Tcp(socket) => Pin::new(socket).poll_read(cx, buf),
+            #[cfg(unix)]
+            Socket::Unix(socket) => Pin::new(socket).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Socket {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        match self.get_mut() {
+            Socket::Tcp(socket) => Pin::new(socket).poll_write(cx, buf),
+            #[cfg(unix)]
+            Socket::Unix(socket) => Pin::new(socket).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Socket::Tcp(socket) => Pin::new(socket).poll_flush(cx),
+            #[cfg(unix)]
+            Socket::Unix(socket) => Pin::new(socket).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Socket::Tcp(socket) => Pin::new(socket).poll_shutdown(cx),
+            #[cfg(unix)]
+            Socket::Unix(socket) => Pin::new(socket).poll_shutdown(cx),
+        }
+    }
+}
 
 /// Amount of time to spend waiting for channels to empty before forcing them to close.
 pub(crate) const CLOSE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
@@ -16,6 +91,12 @@ pub(crate) fn addr_to_url(a: SocketAddr, scheme: &str) -> Url2 {
     url2!("{}://{}", scheme, a)
 }
 
+/// internal helper to convert unix socket paths to urls
+#[cfg(unix)]
+pub(crate) fn path_to_url(path: &std::path::Path) -> Url2 {
+    url2!("unix://{}", path.display())
+}
+
 /// internal helper convert urls to socket addrs for binding / connection
 pub(crate) async fn url_to_addr(url: &Url2, scheme: &str) -> Result<SocketAddr> {
     if url.scheme() != scheme || url.host_str().is_none() || url.port().is_none() {
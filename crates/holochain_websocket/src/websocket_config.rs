@@ -1,5 +1,7 @@
 //! defines a builder-style config struct for setting up websockets
 
+use std::collections::HashSet;
+
 /// A builder-style config struct for setting up websockets.
 #[derive(Debug)]
 pub struct WebsocketConfig {
@@ -26,6 +28,13 @@ pub struct WebsocketConfig {
 
     /// Maximum number of pending new incoming connections. [default = 255]
     pub max_pending_connections: usize,
+
+    /// Origins that a listener accepts connections from. [default = Any]
+    pub allowed_origins: AllowedOrigins,
+
+    /// File permissions of a unix domain socket that a listener binds to,
+    /// which control who can connect to it. [default = 0o600]
+    pub unix_socket_permissions: u32,
 }
 
 impl Default for WebsocketConfig {
@@ -38,6 +47,8 @@ impl Default for WebsocketConfig {
             max_message_size: 64 << 20,
             max_frame_size: 16 << 20,
             max_pending_connections: 255,
+            allowed_origins: AllowedOrigins::Any,
+            unix_socket_permissions: 0o600,
         }
     }
 }
@@ -78,6 +89,81 @@ impl WebsocketConfig {
         self.max_frame_size = max;
         self
     }
+
+    /// Builder-style setter.
+    pub fn allowed_origins(mut self, allowed_origins: AllowedOrigins) -> Self {
+        self.allowed_origins = allowed_origins;
+        self
+    }
+
+    /// Builder-style setter.
+    pub fn unix_socket_permissions(mut self, mode: u32) -> Self {
+        self.unix_socket_permissions = mode;
+        self
+    }
+}
+
+/// The origins a listener accepts connections from, checked against the
+/// `Origin` header of the opening handshake.
+///
+/// Unless any origin is allowed, handshakes without an `Origin` header, or
+/// with one that isn't valid UTF-8, are rejected too. Clients that are not
+/// browsers must send the header to connect to such a listener.
+///
+/// Serializes as `*` for any origin or as a comma separated list of origins.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum AllowedOrigins {
+    /// Accept connections from any origin.
+    Any,
+    /// Only accept connections from these origins.
+    Origins(HashSet<String>),
+}
+
+impl Default for AllowedOrigins {
+    fn default() -> Self {
+        AllowedOrigins::Any
+    }
+}
+
+impl AllowedOrigins {
+    /// Is a handshake with this `Origin` header allowed?
+    pub fn is_allowed(&self, origin: Option<&str>) -> bool {
+        match (self, origin) {
+            (AllowedOrigins::Any, _) => true,
+            (AllowedOrigins::Origins(origins), Some(origin)) => origins.contains(origin),
+            (AllowedOrigins::Origins(_), None) => false,
+        }
+    }
+}
+
+impl From<String> for AllowedOrigins {
+    fn from(s: String) -> Self {
+        if s.trim() == "*" {
+            AllowedOrigins::Any
+        } else {
+            AllowedOrigins::Origins(
+                s.split(',')
+                    .map(str::trim)
+                    .filter(|o| !o.is_empty())
+                    .map(String::from)
+                    .collect(),
+            )
+        }
+    }
+}
+
+impl From<AllowedOrigins> for String {
+    fn from(allowed_origins: AllowedOrigins) -> Self {
+        match allowed_origins {
+            AllowedOrigins::Any => "*".to_string(),
+            AllowedOrigins::Origins(origins) => {
+                let mut origins: Vec<_> = origins.into_iter().collect();
+                origins.sort();
+                origins.join(",")
+            }
+        }
+    }
 }
 
 /// internal helper to convert our configs into tungstenite configs
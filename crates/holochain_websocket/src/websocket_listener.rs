@@ -8,11 +8,15 @@ use std::sync::Arc;
 use stream_cancel::Trigger;
 use stream_cancel::Valve;
 use tracing::instrument;
+use tungstenite::handshake::server::ErrorResponse;
+use tungstenite::handshake::server::Request;
+use tungstenite::handshake::server::Response;
 
 use url2::Url2;
 
 use crate::util::addr_to_url;
 use crate::util::url_to_addr;
+use crate::util::Socket;
 use crate::websocket::Websocket;
 use crate::WebsocketConfig;
 use crate::WebsocketError;
@@ -106,6 +110,38 @@ impl WebsocketListener {
     )> {
         websocket_bind(addr, config).await
     }
+
+    /// Bind to a unix domain socket at this path to accept incoming connections.
+    ///
+    /// Access to the listener is controlled by the permissions of the socket
+    /// file, which are set from [`WebsocketConfig::unix_socket_permissions`].
+    /// A stale socket file left at the path is replaced, but binding fails if
+    /// something is still listening on it.
+    #[cfg(unix)]
+    pub async fn bind_unix(
+        path: impl AsRef<std::path::Path>,
+        config: Arc<WebsocketConfig>,
+    ) -> WebsocketResult<Self> {
+        let (handle, stream) = Self::bind_unix_with_handle(path, config).await?;
+        Ok(Self {
+            handle,
+            stream: stream.boxed(),
+        })
+    }
+
+    /// Same as [`WebsocketListener::bind_unix`] but gives you a [`ListenerHandle`] to shutdown
+    /// the listener and any open connections.
+    #[cfg(unix)]
+    pub async fn bind_unix_with_handle(
+        path: impl AsRef<std::path::Path>,
+        config: Arc<WebsocketConfig>,
+    ) -> WebsocketResult<(
+        ListenerHandle,
+        impl futures::stream::Stream<Item = ListenerItem>,
+    )> {
+        unix_socket_bind(path.as_ref(), config).await
+    }
+
     /// Shutdown the listener stream.
     pub fn close(self) {
         self.handle.close()
@@ -202,8 +238,85 @@ async fn websocket_bind(
     socket.set_nonblocking(true)?;
     let local_addr = addr_to_url(socket.local_addr()?, config.scheme);
     let listener = tokio::net::TcpListener::from_std(socket)?;
-    let listener_stream = tokio_stream::wrappers::TcpListenerStream::new(listener);
+    let listener_stream =
+        tokio_stream::wrappers::TcpListenerStream::new(listener).map_ok(Socket::Tcp);
+
+    Ok(listen(listener_stream, local_addr, config))
+}
 
+#[cfg(unix)]
+async fn unix_socket_bind(
+    path: &std::path::Path,
+    config: Arc<WebsocketConfig>,
+) -> WebsocketResult<(
+    ListenerHandle,
+    impl futures::stream::Stream<Item = ListenerItem>,
+)> {
+    use std::os::unix::fs::DirBuilderExt;
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = std::env::current_dir()?.join(path);
+    if let Ok(metadata) = std::fs::symlink_metadata(&path) {
+        if !metadata.file_type().is_socket() {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            )
+            .into());
+        }
+        // Only replace the socket if nothing is listening on it anymore.
+        if tokio::net::UnixStream::connect(&path).await.is_ok() {
+            return Err(Error::new(
+                ErrorKind::AddrInUse,
+                format!("{} is already in use", path.display()),
+            )
+            .into());
+        }
+        std::fs::remove_file(&path)?;
+    }
+    // Bind inside a directory only we can access and move the socket into
+    // place once its permissions are set, so there is no window in which
+    // other users could connect through the process umask's permissions.
+    let parent = path.parent().ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("{} has no parent directory", path.display()),
+        )
+    })?;
+    let private_dir = parent.join(format!(".{}.tmp", nanoid::nanoid!()));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&private_dir)?;
+    let bind_path = private_dir.join("socket");
+    let bound = (|| {
+        let listener = tokio::net::UnixListener::bind(&bind_path)?;
+        std::fs::set_permissions(
+            &bind_path,
+            std::fs::Permissions::from_mode(config.unix_socket_permissions),
+        )?;
+        std::fs::rename(&bind_path, &path)?;
+        Ok::<_, Error>(listener)
+    })();
+    let _ = std::fs::remove_file(&bind_path);
+    std::fs::remove_dir(&private_dir)?;
+    let listener = bound?;
+    let local_addr = crate::util::path_to_url(&path);
+    let listener_stream =
+        tokio_stream::wrappers::UnixListenerStream::new(listener).map_ok(Socket::Unix);
+
+    Ok(listen(listener_stream, local_addr, config))
+}
+
+/// Accept websocket connections on a stream of new raw connections.
+fn listen(
+    listener_stream: impl futures::stream::Stream<Item = std::io::Result<Socket>> + Send + 'static,
+    local_addr: Url2,
+    config: Arc<WebsocketConfig>,
+) -> (
+    ListenerHandle,
+    impl futures::stream::Stream<Item = ListenerItem>,
+) {
     // Setup proper shutdown
     let (shutdown, valve) = Valve::new();
 
@@ -224,13 +337,13 @@ async fn websocket_bind(
         config,
         local_addr,
     };
-    Ok((listener_handle, stream))
+    (listener_handle, stream)
 }
 
 #[instrument(skip(config, socket, valve))]
 async fn connect(
     config: Arc<WebsocketConfig>,
-    socket: tokio::net::TcpStream,
+    socket: Socket,
     valve: Valve,
 ) -> WebsocketResult<Pair> {
     // TODO: find alternative to set the keepalive
@@ -239,10 +352,28 @@ async fn connect(
     // )))?;
     tracing::debug!(
         message = "accepted incoming raw socket",
-        remote_addr = %socket.peer_addr()?,
+        remote_addr = %socket.peer_url(config.scheme)?,
     );
-    let socket = tokio_tungstenite::accept_async_with_config(
+    let allowed_origins = config.allowed_origins.clone();
+    // Reject the handshake if it comes from an origin we don't allow.
+    // A missing or non UTF-8 header counts as no origin.
+    let check_origin = move |request: &Request, response: Response| {
+        let origin = request
+            .headers()
+            .get(tungstenite::http::header::ORIGIN)
+            .and_then(|origin| origin.to_str().ok());
+        if allowed_origins.is_allowed(origin) {
+            Ok(response)
+        } else {
+            tracing::warn!(?origin, "rejected websocket connection from origin");
+            let mut response = ErrorResponse::new(Some("Origin not allowed".to_string()));
+            *response.status_mut() = tungstenite::http::StatusCode::FORBIDDEN;
+            Err(response)
+        }
+    };
+    let socket = tokio_tungstenite::accept_hdr_async_with_config(
         socket,
+        check_origin,
         Some(tungstenite::protocol::WebSocketConfig {
             max_send_queue: Some(config.max_send_queue),
             max_message_size: Some(config.max_message_size),
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use futures::StreamExt;
 use holochain_serialized_bytes::prelude::*;
 use holochain_websocket::connect;
+use holochain_websocket::AllowedOrigins;
 use holochain_websocket::ListenerHandle;
 use holochain_websocket::ListenerItem;
 use holochain_websocket::WebsocketConfig;
@@ -452,3 +453,100 @@ async fn cancel_response() {
     c_jh.await.unwrap();
     s_jh.await.unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn allowed_origins() {
+    holochain_trace::test_run().ok();
+    let config = WebsocketConfig::default().allowed_origins(AllowedOrigins::Origins(
+        ["http://localhost:8888".to_string()].into_iter().collect(),
+    ));
+    let (handle, mut listener) =
+        WebsocketListener::bind_with_handle(url2!("ws://127.0.0.1:0"), Arc::new(config))
+            .await
+            .unwrap();
+    let binding = handle.local_addr().clone();
+    tokio::task::spawn(async move { while listener.next().await.is_some() {} });
+
+    let connect_from = |origin: Option<&'static str>| {
+        let binding = binding.clone();
+        async move {
+            let mut request = tungstenite::http::Request::builder().uri(binding.as_str());
+            if let Some(origin) = origin {
+                request = request.header(tungstenite::http::header::ORIGIN, origin);
+            }
+            let socket = tokio::net::TcpStream::connect(format!(
+                "{}:{}",
+                binding.host_str().unwrap(),
+                binding.port().unwrap()
+            ))
+            .await
+            .unwrap();
+            tokio_tungstenite::client_async(request.body(()).unwrap(), socket).await
+        }
+    };
+
+    assert!(connect_from(Some("http://localhost:8888")).await.is_ok());
+    assert!(connect_from(None).await.is_err());
+    assert!(connect_from(Some("http://evil.example")).await.is_err());
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn unix_socket() {
+    use std::os::unix::fs::PermissionsExt;
+
+    holochain_trace::test_run().ok();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("test.sock");
+    let (_handle, mut listener) =
+        WebsocketListener::bind_unix_with_handle(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    // The private directory used for binding is cleaned up.
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    let jh = tokio::task::spawn(async move {
+        let (_, mut receiver) = listener.next().await.unwrap().unwrap();
+        let (msg, resp) = receiver.next().await.unwrap();
+        let msg: TestString = msg.try_into().unwrap();
+        assert_eq!(msg.0, "Hey from client");
+        resp.respond(TestString("Bye from server".into()).try_into().unwrap())
+            .await
+            .unwrap();
+    });
+
+    let (mut sender, _receiver) =
+        holochain_websocket::connect_unix(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+
+    // The socket is still being listened on so it can't be taken over.
+    assert!(
+        WebsocketListener::bind_unix(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .is_err()
+    );
+
+    let msg: TestString = sender
+        .request(TestString("Hey from client".into()))
+        .await
+        .unwrap();
+    assert_eq!(msg.0, "Bye from server");
+    jh.await.unwrap();
+}
+
+#[test]
+fn allowed_origins_from_string() {
+    assert_eq!(AllowedOrigins::from("*".to_string()), AllowedOrigins::Any);
+    let origins = AllowedOrigins::from("http://localhost:8888, http://localhost:8889".to_string());
+    assert!(origins.is_allowed(Some("http://localhost:8889")));
+    assert!(!origins.is_allowed(Some("http://localhost:8890")));
+    assert!(!origins.is_allowed(None));
+    assert!(AllowedOrigins::Any.is_allowed(None));
+    assert_eq!(
+        String::from(origins),
+        "http://localhost:8888,http://localhost:8889"
+    );
+}
@@ -1,4 +1,5 @@
 use crate::CellId;
+use holo_hash::ActionHash;
 use holo_hash::AgentPubKey;
 use holo_hash::DhtOpHash;
 use holo_hash::DnaHash;
@@ -29,6 +30,8 @@ pub enum CellBlockReason {
     App(Vec<u8>),
     /// Invalid validation result.
     InvalidOp(DhtOpHash),
+    /// A verified warrant was received for this invalid action.
+    Warrant(ActionHash),
     /// Some bad cryptography.
     BadCrypto,
 }
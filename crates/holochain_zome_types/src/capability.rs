@@ -28,9 +28,12 @@ mod grant;
 pub use grant::*;
 
 pub use holochain_integrity_types::capability::*;
+use holo_hash::ActionHash;
+use holo_hash::AgentPubKey;
 use serde::{Deserialize, Serialize};
 
 use crate::CellId;
+use crate::Timestamp;
 
 /// Parameters for granting a zome call capability.
 #[derive(Debug, Deserialize, Serialize)]
@@ -41,3 +44,55 @@ pub struct GrantZomeCallCapabilityPayload {
     /// signing for as well as access level, secret and assignees.
     pub cap_grant: ZomeCallCapGrant,
 }
+
+/// Input to the `capability_grants` host function.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CapGrantsInput {
+    /// Only return grants with this tag.
+    pub tag: Option<String>,
+    /// Also return grants that have been updated or deleted.
+    pub include_revoked: bool,
+}
+
+/// A capability grant committed to the local source chain, as returned by
+/// the `capability_grants` host function.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CapGrantInfo {
+    /// The grant itself.
+    pub cap_grant: ZomeCallCapGrant,
+    /// Hash of the create or update action that committed the grant.
+    pub action_hash: ActionHash,
+    /// When the grant was committed.
+    pub created_at: Timestamp,
+    /// When the grant was revoked by an update or delete, if it has been.
+    pub revoked_at: Option<Timestamp>,
+}
+
+impl CapGrantInfo {
+    /// A grant is active until it has been updated or deleted.
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+}
+
+/// Input to the `capability_claims` host function.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CapClaimsInput {
+    /// Only return claims on grants made by this agent.
+    pub grantor: Option<AgentPubKey>,
+    /// Only return claims with this tag.
+    pub tag: Option<String>,
+}
+
+/// The capability that authorized the current zome call, as returned by
+/// the `capability_info` host function.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CapabilityInfo {
+    /// The agent who made the call.
+    pub provenance: AgentPubKey,
+    /// The grant that authorized the call.
+    pub cap_grant: CapGrant,
+    /// Hash of the action that committed the grant.
+    /// `None` for the implicit [`CapGrant::ChainAuthor`] grant.
+    pub action_hash: Option<ActionHash>,
+}
@@ -567,14 +567,22 @@ fixturator!(
     constructor fn from_builder(ActionBuilderCommon);
 );
 
+fixturator!(
+    MigrationTarget;
+    variants [
+        Dna(DnaHash)
+        Agent(AgentPubKey)
+    ];
+);
+
 fixturator!(
     OpenChain;
-    constructor fn from_builder(ActionBuilderCommon, DnaHash);
+    constructor fn from_builder(ActionBuilderCommon, MigrationTarget, ActionHash);
 );
 
 fixturator!(
     CloseChain;
-    constructor fn from_builder(ActionBuilderCommon, DnaHash);
+    constructor fn from_builder(ActionBuilderCommon, MigrationTarget);
 );
 
 fixturator!(
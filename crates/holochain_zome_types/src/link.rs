@@ -89,6 +89,9 @@ pub struct GetLinksInput {
     /// The link types to include in this get.
     pub link_type: LinkTypeFilter,
     pub tag_prefix: Option<crate::link::LinkTag>,
+    /// Time range, author, ordering and pagination of the links.
+    #[serde(default)]
+    pub filter: GetLinksFilter,
 }
 
 impl GetLinksInput {
@@ -101,8 +104,157 @@ impl GetLinksInput {
             base_address,
             link_type,
             tag_prefix,
+            filter: GetLinksFilter::default(),
         }
     }
+
+    /// Set the time range, author, ordering and pagination of the links.
+    pub fn filter(mut self, filter: GetLinksFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+}
+
+/// A position in a list of links from which to continue getting links.
+///
+/// The cursor is opaque, create it from the last link of the previous
+/// page to get the next page.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LinkCursor {
+    timestamp: crate::Timestamp,
+    create_link_hash: ActionHash,
+}
+
+impl From<&Link> for LinkCursor {
+    fn from(link: &Link) -> Self {
+        Self {
+            timestamp: link.timestamp,
+            create_link_hash: link.create_link_hash.clone(),
+        }
+    }
+}
+
+/// Which links to return from a get links query, and in which order.
+///
+/// Links are ordered by the time they were created, ties are broken by the
+/// hash of their create link action.
+/// An empty filter returns all links, oldest first.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GetLinksFilter {
+    /// Only links created after this time.
+    pub after: Option<crate::Timestamp>,
+    /// Only links created before this time.
+    pub before: Option<crate::Timestamp>,
+    /// Only links created by this agent.
+    pub author: Option<holo_hash::AgentPubKey>,
+    /// Return at most this many links.
+    pub limit: Option<u32>,
+    /// Only links that come after this cursor in the order of the links.
+    pub cursor: Option<LinkCursor>,
+    /// Return the newest links first.
+    pub order_descending: bool,
+}
+
+impl GetLinksFilter {
+    /// Create a filter which returns all links.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Filter for links created after this time.
+    pub fn after(mut self, after: crate::Timestamp) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    /// Filter for links created before this time.
+    pub fn before(mut self, before: crate::Timestamp) -> Self {
+        self.before = Some(before);
+        self
+    }
+
+    /// Filter for links created by this agent.
+    pub fn author(mut self, author: holo_hash::AgentPubKey) -> Self {
+        self.author = Some(author);
+        self
+    }
+
+    /// Return at most this many links.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Continue after this cursor.
+    pub fn cursor(mut self, cursor: LinkCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// Return the oldest links first.
+    pub fn ascending(mut self) -> Self {
+        self.order_descending = false;
+        self
+    }
+
+    /// Return the newest links first.
+    pub fn descending(mut self) -> Self {
+        self.order_descending = true;
+        self
+    }
+
+    /// Does a link by this author created at this time pass the time range
+    /// and author filters.
+    pub fn matches(&self, author: &holo_hash::AgentPubKey, timestamp: crate::Timestamp) -> bool {
+        self.after.map_or(true, |after| timestamp > after)
+            && self.before.map_or(true, |before| timestamp < before)
+            && self.author.as_ref().map_or(true, |a| a == author)
+    }
+
+    /// Does this filter return only a page of the links.
+    pub fn is_paginated(&self) -> bool {
+        self.limit.is_some() || self.cursor.is_some()
+    }
+
+    /// Order the items, then take the page after the cursor, up to the limit.
+    /// The key of each item is the timestamp and hash of its create link action.
+    pub fn paginate<T>(
+        &self,
+        items: impl IntoIterator<Item = T>,
+        key: impl Fn(&T) -> (crate::Timestamp, ActionHash),
+    ) -> Vec<T> {
+        let mut items: Vec<_> = items.into_iter().map(|item| (key(&item), item)).collect();
+        items.sort_by(|(a, _), (b, _)| a.cmp(b));
+        if self.order_descending {
+            items.reverse();
+        }
+        let start = match &self.cursor {
+            Some(LinkCursor {
+                timestamp,
+                create_link_hash,
+            }) => {
+                let cursor = (*timestamp, create_link_hash.clone());
+                items
+                    .iter()
+                    .position(|(key, _)| {
+                        if self.order_descending {
+                            *key < cursor
+                        } else {
+                            *key > cursor
+                        }
+                    })
+                    .unwrap_or(items.len())
+            }
+            None => 0,
+        };
+        let limit = self.limit.map_or(usize::MAX, |limit| limit as usize);
+        items
+            .into_iter()
+            .skip(start)
+            .take(limit)
+            .map(|(_, item)| item)
+            .collect()
+    }
 }
 
 type CreateLinkWithDeleteLinks = Vec<(SignedActionHashed, Vec<SignedActionHashed>)>;
@@ -128,3 +280,75 @@ impl LinkDetails {
         self.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Timestamp;
+    use holo_hash::AgentPubKey;
+    use holo_hash::EntryHash;
+
+    fn link(micros: i64, hash: u8) -> Link {
+        Link {
+            author: AgentPubKey::from_raw_36(vec![0; 36]),
+            target: EntryHash::from_raw_36(vec![0; 36]).into(),
+            timestamp: Timestamp::from_micros(micros),
+            zome_index: 0.into(),
+            link_type: 0.into(),
+            tag: LinkTag::new(vec![]),
+            create_link_hash: ActionHash::from_raw_36(vec![hash; 36]),
+        }
+    }
+
+    fn paginate(filter: &GetLinksFilter, links: &[Link]) -> Vec<u8> {
+        filter
+            .paginate(links.to_vec(), |l| {
+                (l.timestamp, l.create_link_hash.clone())
+            })
+            .into_iter()
+            .map(|l| l.create_link_hash.get_raw_36()[0])
+            .collect()
+    }
+
+    #[test]
+    fn paginate_links_with_cursor() {
+        let links = vec![link(3, 4), link(1, 1), link(2, 3), link(2, 2)];
+
+        let filter = GetLinksFilter::new();
+        assert_eq!(paginate(&filter, &links), vec![1, 2, 3, 4]);
+        assert_eq!(
+            paginate(&filter.clone().descending(), &links),
+            vec![4, 3, 2, 1]
+        );
+
+        let first_page = GetLinksFilter::new().limit(2);
+        assert_eq!(paginate(&first_page, &links), vec![1, 2]);
+        let next_page = first_page.cursor((&link(2, 2)).into());
+        assert_eq!(paginate(&next_page, &links), vec![3, 4]);
+
+        let next_page = GetLinksFilter::new()
+            .descending()
+            .limit(2)
+            .cursor((&link(2, 3)).into());
+        assert_eq!(paginate(&next_page, &links), vec![2, 1]);
+
+        // A cursor past the end gives an empty page
+        let past_end = GetLinksFilter::new().cursor((&link(3, 4)).into());
+        assert!(paginate(&past_end, &links).is_empty());
+    }
+
+    #[test]
+    fn filter_links_by_time_and_author() {
+        let alice = AgentPubKey::from_raw_36(vec![0; 36]);
+        let bob = AgentPubKey::from_raw_36(vec![1; 36]);
+        let filter = GetLinksFilter::new()
+            .after(Timestamp::from_micros(1))
+            .before(Timestamp::from_micros(3))
+            .author(alice.clone());
+
+        assert!(filter.matches(&alice, Timestamp::from_micros(2)));
+        assert!(!filter.matches(&alice, Timestamp::from_micros(1)));
+        assert!(!filter.matches(&alice, Timestamp::from_micros(3)));
+        assert!(!filter.matches(&bob, Timestamp::from_micros(2)));
+    }
+}
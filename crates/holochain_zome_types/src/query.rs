@@ -5,7 +5,7 @@ use std::collections::HashSet;
 
 use crate::action::ActionType;
 use crate::action::EntryType;
-use crate::warrant::Warrant;
+use crate::warrant::SignedWarrant;
 use crate::ActionHashed;
 use crate::Record;
 use holo_hash::ActionHash;
@@ -98,9 +98,9 @@ pub struct AgentActivity {
     /// The highest chain action that has
     /// been observed by this authority.
     pub highest_observed: Option<HighestObserved>,
-    /// Warrants about this AgentActivity.
-    /// Placeholder for future.
-    pub warrants: Vec<Warrant>,
+    /// Warrants issued against this agent by validators
+    /// which have found their data to be invalid.
+    pub warrants: Vec<SignedWarrant>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -1,6 +1,74 @@
 //! Types for warrants
+//!
+//! A warrant is a validator's signed claim that some agent authored data which
+//! failed validation. Warrants are published to the agent-activity authorities
+//! of the offending agent, who hand them out along with the agent's activity so
+//! that anyone can learn about the misbehavior and act on it.
+
+use crate::record::SignedAction;
+use crate::signature::Signature;
+use holo_hash::AgentPubKey;
+use holochain_integrity_types::Timestamp;
 pub use holochain_serialized_bytes::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-/// Placeholder for warrant type
-pub struct Warrant;
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+/// A claim that an action failed validation, to be signed by the validator
+/// which rejected it.
+pub struct Warrant {
+    /// The invalid action, along with its author's signature
+    pub action: SignedAction,
+    /// The reason the action was rejected, as given by the validation callback
+    pub reason: String,
+    /// The validator which is issuing this warrant
+    pub issuer: AgentPubKey,
+    /// When the warrant was issued
+    pub timestamp: Timestamp,
+}
+
+impl Warrant {
+    /// Constructor
+    pub fn new(
+        action: SignedAction,
+        reason: String,
+        issuer: AgentPubKey,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            action,
+            reason,
+            issuer,
+            timestamp,
+        }
+    }
+
+    /// The agent this warrant is against, i.e. the author of the invalid action
+    pub fn warrantee(&self) -> &AgentPubKey {
+        self.action.action().author()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+/// A [`Warrant`] along with the issuer's signature over it
+pub struct SignedWarrant {
+    /// The content of the warrant
+    pub warrant: Warrant,
+    /// The signature of the issuer
+    pub signature: Signature,
+}
+
+impl SignedWarrant {
+    /// Constructor
+    pub fn new(warrant: Warrant, signature: Signature) -> Self {
+        Self { warrant, signature }
+    }
+
+    /// Accessor for the warrant
+    pub fn warrant(&self) -> &Warrant {
+        &self.warrant
+    }
+
+    /// Accessor for the issuer's signature
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+}
@@ -32,9 +32,16 @@ fn bootstrap(bench: &mut Criterion) {
 
     let mut url = url2!("http://127.0.0.1:0");
     let (driver, addr, _shutdown) = runtime.block_on(async {
-        kitsune_p2p_bootstrap::run(([127, 0, 0, 1], 0), vec![])
-            .await
-            .unwrap()
+        // The agent infos put below have fake signatures
+        kitsune_p2p_bootstrap::run_with_config(
+            ([127, 0, 0, 1], 0),
+            kitsune_p2p_bootstrap::BootstrapConfig {
+                verify_signatures: false,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
     });
     runtime.spawn(async move {
         driver.await;
@@ -13,7 +13,7 @@ pub(crate) fn clear(
 }
 
 async fn clear_info(store: Store) -> Result<impl warp::Reply, warp::Rejection> {
-    store.clear();
+    store.clear().map_err(|_| warp::reject())?;
     Ok(warp::reply())
 }
 
@@ -44,7 +44,7 @@ mod tests {
             )
             .await
             .unwrap();
-            store.put(info);
+            store.put(info).unwrap();
         }
 
         let res = warp::test::request()
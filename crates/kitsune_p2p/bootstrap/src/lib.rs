@@ -2,22 +2,29 @@
 #![allow(opaque_hidden_inferred_bound)]
 
 use std::net::SocketAddr;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
 
 use kitsune_p2p_types::codec::rmp_decode;
 use kitsune_p2p_types::codec::rmp_encode;
+use rate_limit::PutRateLimits;
 use store::Store;
 use warp::{hyper::body::Bytes, Filter};
 
 static NOW: AtomicUsize = AtomicUsize::new(0);
 static RANDOM: AtomicUsize = AtomicUsize::new(0);
 static PUT: AtomicUsize = AtomicUsize::new(0);
+static INVALID: AtomicUsize = AtomicUsize::new(0);
+static RATE_LIMITED: AtomicUsize = AtomicUsize::new(0);
 
 mod clear;
+mod metrics;
 mod now;
 mod proxy_list;
 mod put;
 mod random;
+mod rate_limit;
 mod store;
 
 /// No reason to accept a peer data bigger then 1KB.
@@ -31,6 +38,38 @@ pub type BootstrapDriver = futures::future::BoxFuture<'static, ()>;
 
 pub type BootstrapShutdown = Box<dyn FnOnce() + 'static + Send + Sync>;
 
+/// Configuration of a bootstrap server.
+#[derive(Clone, Debug)]
+pub struct BootstrapConfig {
+    /// Proxy server addresses returned by the `proxy_list` op.
+    pub proxy_list: Vec<String>,
+    /// How often to prune the expired entries.
+    pub prune_frequency: std::time::Duration,
+    /// Persist agent infos in the SQLite database at this path instead of
+    /// keeping them in memory. Requires the `sqlite` or `sqlite-encrypted` feature.
+    pub database_path: Option<PathBuf>,
+    /// Maximum number of puts per minute from a single IP address.
+    pub put_limit_per_ip: Option<NonZeroU32>,
+    /// Maximum number of puts per minute to a single space.
+    pub put_limit_per_space: Option<NonZeroU32>,
+    /// Reject agent infos which aren't signed by the ed25519 key of their agent.
+    /// Test networks using fake signatures must turn this off.
+    pub verify_signatures: bool,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            proxy_list: Vec::new(),
+            prune_frequency: PRUNE_EXPIRED_FREQ,
+            database_path: None,
+            put_limit_per_ip: None,
+            put_limit_per_space: None,
+            verify_signatures: true,
+        }
+    }
+}
+
 /// Run a bootstrap with the default prune frequency [`PRUNE_EXPIRED_FREQ`].
 pub async fn run(
     addr: impl Into<SocketAddr> + 'static,
@@ -45,22 +84,55 @@ pub async fn run_with_prune_freq(
     proxy_list: Vec<String>,
     prune_frequency: std::time::Duration,
 ) -> Result<(BootstrapDriver, SocketAddr, BootstrapShutdown), String> {
-    let store = Store::new(proxy_list);
+    run_with_config(
+        addr,
+        BootstrapConfig {
+            proxy_list,
+            prune_frequency,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Run a bootstrap server with the given configuration.
+pub async fn run_with_config(
+    addr: impl Into<SocketAddr> + 'static,
+    config: BootstrapConfig,
+) -> Result<(BootstrapDriver, SocketAddr, BootstrapShutdown), String> {
+    let store = match &config.database_path {
+        None => Store::new(config.proxy_list),
+        #[cfg(feature = "rusqlite")]
+        Some(path) => Store::open_sqlite(path, config.proxy_list)?,
+        #[cfg(not(feature = "rusqlite"))]
+        Some(_) => return Err("This bootstrap server was built without SQLite support".to_string()),
+    };
+    let rate_limits = PutRateLimits::new(config.put_limit_per_ip, config.put_limit_per_space);
+    let prune_frequency = config.prune_frequency;
 
     {
         let store = store.clone();
+        let rate_limits = rate_limits.clone();
         tokio::task::spawn(async move {
             loop {
                 tokio::time::sleep(prune_frequency).await;
-                store.prune();
+                if let Err(e) = store.prune() {
+                    tracing::error!(%e, "Failed to prune expired agent infos");
+                }
+                rate_limits.prune();
             }
         });
     }
 
     let boot = now::now()
-        .or(put::put(store.clone()))
+        .or(put::put(
+            store.clone(),
+            rate_limits,
+            config.verify_signatures,
+        ))
         .or(random::random(store.clone()))
         .or(proxy_list::proxy_list(store.clone()))
+        .or(metrics::metrics(store.clone()))
         .or(clear::clear(store));
 
     let (s, r) = tokio::sync::oneshot::channel();
@@ -84,3 +156,46 @@ fn with_store(
 ) -> impl Filter<Extract = (Store,), Error = std::convert::Infallible> + Clone {
     warp::any().map(move || store.clone())
 }
+
+#[cfg(test)]
+mod test_util {
+    use std::sync::Arc;
+
+    use kitsune_p2p_types::agent_info::AgentInfoSigned;
+    use kitsune_p2p_types::bin_types::{
+        KitsuneAgent, KitsuneBinType, KitsuneSignature, KitsuneSpace,
+    };
+
+    pub fn now_ms() -> u64 {
+        std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64
+    }
+
+    /// Agent info for a new agent, validly signed with its ed25519 key.
+    pub async fn signed_agent_info(
+        space: Arc<KitsuneSpace>,
+        expires_at_ms: u64,
+    ) -> AgentInfoSigned {
+        use ed25519_dalek::Signer;
+
+        let secret: [u8; 32] = rand::random();
+        let secret = ed25519_dalek::SecretKey::from_bytes(&secret).unwrap();
+        let public = ed25519_dalek::PublicKey::from(&secret);
+        let keypair = ed25519_dalek::Keypair { secret, public };
+        let agent = Arc::new(KitsuneAgent::new(public.to_bytes().to_vec()));
+
+        AgentInfoSigned::sign(
+            space,
+            agent,
+            u32::MAX / 4,
+            vec!["fake:".into()],
+            expires_at_ms - 60_000_000,
+            expires_at_ms,
+            |data| {
+                let signature = keypair.sign(data).to_bytes().to_vec();
+                async move { Ok(Arc::new(KitsuneSignature(signature))) }
+            },
+        )
+        .await
+        .unwrap()
+    }
+}
@@ -12,6 +12,26 @@ struct Args {
     /// multiple times
     #[clap(short, long, verbatim_doc_comment)]
     proxy: Vec<String>,
+
+    /// persist agent infos in the SQLite database
+    /// at this path instead of in memory
+    #[clap(long, verbatim_doc_comment)]
+    database: Option<std::path::PathBuf>,
+
+    /// maximum number of puts per minute
+    /// from a single IP address
+    #[clap(long, verbatim_doc_comment)]
+    put_limit_per_ip: Option<std::num::NonZeroU32>,
+
+    /// maximum number of puts per minute
+    /// to a single space
+    #[clap(long, verbatim_doc_comment)]
+    put_limit_per_space: Option<std::num::NonZeroU32>,
+
+    /// accept agent infos without checking
+    /// their signatures
+    #[clap(long, verbatim_doc_comment)]
+    no_verify_signatures: bool,
 }
 
 #[tokio::main(flavor = "multi_thread")]
@@ -27,7 +47,16 @@ async fn main() {
         .next()
         .unwrap();
 
-    match kitsune_p2p_bootstrap::run(addr, args.proxy).await {
+    let config = kitsune_p2p_bootstrap::BootstrapConfig {
+        proxy_list: args.proxy,
+        database_path: args.database,
+        put_limit_per_ip: args.put_limit_per_ip,
+        put_limit_per_space: args.put_limit_per_space,
+        verify_signatures: !args.no_verify_signatures,
+        ..Default::default()
+    };
+
+    match kitsune_p2p_bootstrap::run_with_config(addr, config).await {
         Ok((driver, addr, _shutdown)) => {
             println!("http://{}", addr);
             driver.await;
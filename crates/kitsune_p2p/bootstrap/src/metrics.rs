@@ -0,0 +1,68 @@
+use crate::store::Store;
+
+use super::*;
+use std::sync::atomic::Ordering;
+use warp::Filter;
+
+/// Serve the request counters and the number of agents per space as json
+/// on `GET /metrics`.
+pub(crate) fn metrics(
+    store: Store,
+) -> impl Filter<Extract = impl warp::Reply + Sized, Error = warp::Rejection> + Clone {
+    warp::get()
+        .and(warp::path("metrics"))
+        .and(warp::path::end())
+        .and(with_store(store))
+        .and_then(get_metrics)
+}
+
+async fn get_metrics(store: Store) -> Result<impl warp::Reply, warp::Rejection> {
+    let agents_per_space = store.agents_per_space().map_err(|_| warp::reject())?;
+    let total_agents: usize = agents_per_space.iter().map(|(_, count)| count).sum();
+    let agents_per_space: serde_json::Map<String, serde_json::Value> = agents_per_space
+        .into_iter()
+        .map(|(space, count)| (space.to_string(), count.into()))
+        .collect();
+    Ok(warp::reply::json(&serde_json::json!({
+        "total_agents": total_agents,
+        "agents_per_space": agents_per_space,
+        "requests": {
+            "now": NOW.load(Ordering::Relaxed),
+            "random": RANDOM.load(Ordering::Relaxed),
+            "put": PUT.load(Ordering::Relaxed),
+            "put_invalid": INVALID.load(Ordering::Relaxed),
+            "put_rate_limited": RATE_LIMITED.load(Ordering::Relaxed),
+        },
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::test_util::{now_ms, signed_agent_info};
+    use kitsune_p2p::KitsuneSpace;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_metrics() {
+        let store = Store::new(vec![]);
+        let filter = metrics(store.clone());
+        let space = Arc::new(KitsuneSpace(vec![1; 36]));
+        for _ in 0..3 {
+            store
+                .put(signed_agent_info(space.clone(), now_ms() + 60_000_000).await)
+                .unwrap();
+        }
+
+        let res = warp::test::request()
+            .method("GET")
+            .path("/metrics")
+            .reply(&filter)
+            .await;
+        assert_eq!(res.status(), 200);
+        let metrics: serde_json::Value = serde_json::from_slice(res.body()).unwrap();
+        assert_eq!(metrics["total_agents"], 3);
+        assert_eq!(metrics["agents_per_space"][space.to_string()], 3);
+    }
+}
@@ -1,41 +1,92 @@
+use crate::rate_limit::PutRateLimits;
 use crate::store::Store;
 
 use super::*;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use kitsune_p2p_types::bin_types::KitsuneBinType;
+use warp::http::StatusCode;
 use warp::Filter;
 
+/// Agent infos signed further in the future than this are rejected.
+const MAX_CLOCK_SKEW_MS: u64 = 5 * 60 * 1000;
+
 pub(crate) fn put(
     store: Store,
+    rate_limits: PutRateLimits,
+    verify_signatures: bool,
 ) -> impl Filter<Extract = impl warp::Reply + Sized, Error = warp::Rejection> + Clone {
     warp::post()
         .and(warp::header::exact("X-Op", "put"))
         .and(warp::body::content_length_limit(SIZE_LIMIT))
+        .and(warp::addr::remote())
         .and(warp::body::bytes())
         .and(with_store(store))
+        .and(warp::any().map(move || rate_limits.clone()))
+        .and(warp::any().map(move || verify_signatures))
         .and_then(put_info)
 }
 
-async fn put_info(peer: Bytes, store: Store) -> Result<impl warp::Reply, warp::Rejection> {
+async fn put_info(
+    remote: Option<SocketAddr>,
+    peer: Bytes,
+    store: Store,
+    rate_limits: PutRateLimits,
+    verify_signatures: bool,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if !rate_limits.check_ip(remote.map(|addr| addr.ip())) {
+        RATE_LIMITED.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        return Ok(warp::reply::with_status(
+            Vec::new(),
+            StatusCode::TOO_MANY_REQUESTS,
+        ));
+    }
     let peer: AgentInfoSigned =
         rmp_decode(&mut AsRef::<[u8]>::as_ref(&peer)).map_err(|_| warp::reject())?;
-    // TODO: Return rejection if agent info was invalid?
-    if valid(&peer) {
-        store.put(peer);
+    if !valid(&peer, verify_signatures) {
+        INVALID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        return Ok(warp::reply::with_status(
+            Vec::new(),
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+    if !rate_limits.check_space(&peer.space) {
+        RATE_LIMITED.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        return Ok(warp::reply::with_status(
+            Vec::new(),
+            StatusCode::TOO_MANY_REQUESTS,
+        ));
     }
+    store.put(peer).map_err(|_| warp::reject())?;
     PUT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
     let mut buf = Vec::with_capacity(1);
     rmp_encode(&mut buf, ()).map_err(|_| warp::reject())?;
-    Ok(buf)
+    Ok(warp::reply::with_status(buf, StatusCode::OK))
 }
 
-fn valid(peer: &AgentInfoSigned) -> bool {
-    // TODO: verify signature
+fn valid(peer: &AgentInfoSigned, verify_signatures: bool) -> bool {
+    let now = std::time::UNIX_EPOCH
+        .elapsed()
+        .expect("Bootstrap system clock is set before the epoch")
+        .as_millis() as u64;
     // Verify time
-    peer.expires_at_ms as u128
-        > std::time::UNIX_EPOCH
-            .elapsed()
-            .expect("Bootstrap system clock is set before the epoch")
-            .as_millis()
+    if peer.expires_at_ms <= now || peer.signed_at_ms > now + MAX_CLOCK_SKEW_MS {
+        return false;
+    }
+    !verify_signatures || valid_signature(peer)
+}
+
+/// The agent's key is an ed25519 public key, which must have signed
+/// the encoded agent info.
+fn valid_signature(peer: &AgentInfoSigned) -> bool {
+    let key = match ed25519_dalek::PublicKey::from_bytes(peer.agent.get_bytes()) {
+        Ok(key) => key,
+        Err(_) => return false,
+    };
+    let signature = match ed25519_dalek::Signature::from_bytes(&peer.signature.0) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    key.verify_strict(&peer.encoded_bytes, &signature).is_ok()
 }
 
 #[cfg(test)]
@@ -43,36 +94,41 @@ mod tests {
     use std::sync::Arc;
 
     use super::*;
+    use crate::test_util::{now_ms, signed_agent_info};
     use fixt::prelude::*;
     use kitsune_p2p::fixt::*;
 
-    #[tokio::test(flavor = "multi_thread")]
-    async fn test_put() {
-        let store = Store::new(vec![]);
-        let filter = put(store.clone());
-
-        let info = AgentInfoSigned::sign(
-            Arc::new(fixt!(KitsuneSpace, Unpredictable)),
-            Arc::new(fixt!(KitsuneAgent, Unpredictable)),
-            u32::MAX / 4,
-            fixt!(UrlList, Empty),
-            0,
-            std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64 + 60_000_000,
-            |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
-        )
-        .await
-        .unwrap();
+    async fn put_request<F>(filter: &F, info: &AgentInfoSigned) -> StatusCode
+    where
+        F: Filter + 'static,
+        F::Extract: warp::Reply + Send,
+    {
         let mut buf = Vec::new();
         rmp_encode(&mut buf, info.clone()).unwrap();
 
-        let res = warp::test::request()
+        warp::test::request()
             .method("POST")
             .header("Content-type", "application/octet")
             .header("X-Op", "put")
+            .remote_addr(([127, 0, 0, 1], 1234).into())
             .body(buf)
-            .reply(&filter)
-            .await;
-        assert_eq!(res.status(), 200);
+            .reply(filter)
+            .await
+            .status()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_put() {
+        let store = Store::new(vec![]);
+        let filter = put(store.clone(), PutRateLimits::default(), true);
+
+        let info = signed_agent_info(
+            Arc::new(fixt!(KitsuneSpace, Unpredictable)),
+            now_ms() + 60_000_000,
+        )
+        .await;
+
+        assert_eq!(put_request(&filter, &info).await, StatusCode::OK);
         assert_eq!(
             *store
                 .all()
@@ -83,4 +139,54 @@ mod tests {
             info
         );
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn put_rejects_invalid_agent_info() {
+        let store = Store::new(vec![]);
+        let filter = put(store.clone(), PutRateLimits::default(), true);
+        let space = Arc::new(fixt!(KitsuneSpace, Unpredictable));
+
+        let expired = signed_agent_info(space.clone(), now_ms() - 1).await;
+        assert_eq!(
+            put_request(&filter, &expired).await,
+            StatusCode::BAD_REQUEST
+        );
+
+        let forged = AgentInfoSigned::sign(
+            space,
+            Arc::new(fixt!(KitsuneAgent, Unpredictable)),
+            u32::MAX / 4,
+            fixt!(UrlList, Empty),
+            now_ms(),
+            now_ms() + 60_000_000,
+            |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
+        )
+        .await
+        .unwrap();
+        assert_eq!(put_request(&filter, &forged).await, StatusCode::BAD_REQUEST);
+        assert!(store.all().is_empty());
+
+        // The signature is only checked if verification is enabled
+        let filter = put(store.clone(), PutRateLimits::default(), false);
+        assert_eq!(put_request(&filter, &forged).await, StatusCode::OK);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn put_is_rate_limited() {
+        let store = Store::new(vec![]);
+        let limits = PutRateLimits::new(std::num::NonZeroU32::new(2), None);
+        let filter = put(store.clone(), limits, true);
+        let space = Arc::new(fixt!(KitsuneSpace, Unpredictable));
+
+        for _ in 0..2 {
+            let info = signed_agent_info(space.clone(), now_ms() + 60_000_000).await;
+            assert_eq!(put_request(&filter, &info).await, StatusCode::OK);
+        }
+        let info = signed_agent_info(space.clone(), now_ms() + 60_000_000).await;
+        assert_eq!(
+            put_request(&filter, &info).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(store.all()[&space].len(), 2);
+    }
 }
@@ -18,7 +18,7 @@ pub(crate) fn random(
 async fn random_info(query: Bytes, store: Store) -> Result<impl warp::Reply, warp::Rejection> {
     let query: RandomQuery =
         rmp_decode(&mut AsRef::<[u8]>::as_ref(&query)).map_err(|_| warp::reject())?;
-    let result = store.random(query);
+    let result = store.random(query).map_err(|_| warp::reject())?;
     let mut buf = Vec::with_capacity(result.len());
     rmp_encode(&mut buf, result).map_err(|_| warp::reject())?;
     RANDOM.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
@@ -30,12 +30,13 @@ mod tests {
     use std::sync::Arc;
 
     use super::*;
+    use crate::test_util::{now_ms, signed_agent_info};
     use fixt::prelude::*;
     use kitsune_p2p::{agent_store::AgentInfoSigned, fixt::*, KitsuneSpace};
     use kitsune_p2p_types::bootstrap::RandomLimit;
 
     async fn put(store: Store, peers: Vec<AgentInfoSigned>) {
-        let filter = crate::put::put(store);
+        let filter = crate::put::put(store, Default::default(), true);
 
         for peer in peers {
             let mut buf = Vec::new();
@@ -59,17 +60,7 @@ mod tests {
         let space: Arc<KitsuneSpace> = Arc::new(fixt!(KitsuneSpace));
         let mut peers = Vec::new();
         for _ in 0..20 {
-            let info = AgentInfoSigned::sign(
-                space.clone(),
-                Arc::new(fixt!(KitsuneAgent, Unpredictable)),
-                u32::MAX / 4,
-                vec!["fake:".into()],
-                0,
-                std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64 + 60_000_000,
-                |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
-            )
-            .await
-            .unwrap();
+            let info = signed_agent_info(space.clone(), now_ms() + 60_000_000).await;
             peers.push(info);
         }
         put(store.clone(), peers.clone()).await;
@@ -0,0 +1,95 @@
+use std::net::IpAddr;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use governor::clock::DefaultClock;
+use governor::state::keyed::DefaultKeyedStateStore;
+use governor::Quota;
+use governor::RateLimiter;
+use kitsune_p2p_types::bin_types::KitsuneSpace;
+
+type KeyedLimiter<K> = RateLimiter<K, DefaultKeyedStateStore<K>, DefaultClock>;
+
+/// Limits on how often agent infos can be put, per source IP address
+/// and per space.
+#[derive(Clone, Default)]
+pub(crate) struct PutRateLimits {
+    per_ip: Option<Arc<KeyedLimiter<IpAddr>>>,
+    per_space: Option<Arc<KeyedLimiter<Arc<KitsuneSpace>>>>,
+}
+
+impl PutRateLimits {
+    /// Limits given in puts per minute. `None` means unlimited.
+    pub fn new(per_ip: Option<NonZeroU32>, per_space: Option<NonZeroU32>) -> Self {
+        let limiter = |n| Arc::new(RateLimiter::keyed(Quota::per_minute(n)));
+        Self {
+            per_ip: per_ip.map(limiter),
+            per_space: per_space.map(limiter),
+        }
+    }
+
+    /// Count a put from this address, returning false if it is over the limit.
+    /// Requests without a known remote address are not limited.
+    pub fn check_ip(&self, addr: Option<IpAddr>) -> bool {
+        match (&self.per_ip, addr) {
+            (Some(limiter), Some(addr)) => limiter.check_key(&addr).is_ok(),
+            _ => true,
+        }
+    }
+
+    /// Count a put to this space, returning false if it is over the limit.
+    pub fn check_space(&self, space: &Arc<KitsuneSpace>) -> bool {
+        match &self.per_space {
+            Some(limiter) => limiter.check_key(space).is_ok(),
+            None => true,
+        }
+    }
+
+    /// Forget the keys which are no longer being limited.
+    pub fn prune(&self) {
+        if let Some(limiter) = &self.per_ip {
+            limiter.retain_recent();
+        }
+        if let Some(limiter) = &self.per_space {
+            limiter.retain_recent();
+        }
+    }
+}
+
+impl std::fmt::Debug for PutRateLimits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PutRateLimits")
+            .field("per_ip", &self.per_ip.is_some())
+            .field("per_space", &self.per_space.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_are_per_key() {
+        let limits = PutRateLimits::new(NonZeroU32::new(2), NonZeroU32::new(1));
+        let ip_1 = Some(IpAddr::from([127, 0, 0, 1]));
+        let ip_2 = Some(IpAddr::from([127, 0, 0, 2]));
+        assert!(limits.check_ip(ip_1));
+        assert!(limits.check_ip(ip_1));
+        assert!(!limits.check_ip(ip_1));
+        assert!(limits.check_ip(ip_2));
+        assert!(limits.check_ip(None));
+
+        let space_1 = Arc::new(KitsuneSpace(vec![1; 36]));
+        let space_2 = Arc::new(KitsuneSpace(vec![2; 36]));
+        assert!(limits.check_space(&space_1));
+        assert!(!limits.check_space(&space_1));
+        assert!(limits.check_space(&space_2));
+
+        let unlimited = PutRateLimits::default();
+        for _ in 0..10 {
+            assert!(unlimited.check_ip(ip_1));
+            assert!(unlimited.check_space(&space_1));
+        }
+    }
+}
@@ -9,86 +9,167 @@ use kitsune_p2p_types::{
 use parking_lot::RwLock;
 use rand::seq::IteratorRandom;
 
+#[cfg(feature = "rusqlite")]
+mod sqlite;
+
 type AgentMap = HashMap<Arc<KitsuneAgent>, AgentInfoSigned>;
 type SpaceMap = HashMap<Arc<KitsuneSpace>, AgentMap>;
 
+pub(crate) type StoreResult<T> = Result<T, String>;
+
 #[derive(Clone, Debug)]
-pub(crate) struct Store(Arc<RwLock<SpaceMap>>, Arc<Vec<String>>);
+pub(crate) struct Store(Arc<Backend>, Arc<Vec<String>>);
+
+/// Where the agent infos are kept.
+#[derive(Debug)]
+enum Backend {
+    /// Lost when the server stops
+    Memory(RwLock<SpaceMap>),
+    /// Persisted in a SQLite database
+    #[cfg(feature = "rusqlite")]
+    Sqlite(sqlite::SqliteStore),
+}
+
+fn now_ms() -> u64 {
+    std::time::UNIX_EPOCH
+        .elapsed()
+        .expect("Bootstrap server time set before epoch")
+        .as_millis() as u64
+}
 
 impl Store {
     pub fn new(proxy_list: Vec<String>) -> Self {
-        Self(Arc::new(RwLock::new(HashMap::new())), Arc::new(proxy_list))
+        Self(
+            Arc::new(Backend::Memory(RwLock::new(HashMap::new()))),
+            Arc::new(proxy_list),
+        )
+    }
+
+    /// Open a store persisted in the SQLite database at this path,
+    /// creating the database if it doesn't exist.
+    #[cfg(feature = "rusqlite")]
+    pub fn open_sqlite(path: &std::path::Path, proxy_list: Vec<String>) -> StoreResult<Self> {
+        Ok(Self(
+            Arc::new(Backend::Sqlite(sqlite::SqliteStore::open(path)?)),
+            Arc::new(proxy_list),
+        ))
     }
 
     pub fn proxy_list(&self) -> Arc<Vec<String>> {
         self.1.clone()
     }
 
-    pub fn prune(&self) {
-        let now = std::time::UNIX_EPOCH
-            .elapsed()
-            .expect("Bootstrap server time set before epoch")
-            .as_millis() as u64;
+    pub fn prune(&self) -> StoreResult<()> {
+        let now = now_ms();
 
-        self.0.write().retain(|_, map| {
-            map.retain(|_, info| info.expires_at_ms >= now);
-            !map.is_empty()
-        });
+        match self.0.as_ref() {
+            Backend::Memory(map) => {
+                map.write().retain(|_, map| {
+                    map.retain(|_, info| info.expires_at_ms >= now);
+                    !map.is_empty()
+                });
+                Ok(())
+            }
+            #[cfg(feature = "rusqlite")]
+            Backend::Sqlite(db) => db.prune(now),
+        }
     }
 
-    pub fn put(&self, info: AgentInfoSigned) {
-        let mut lock = self.0.write();
-        let space_map = lock.entry(info.space.clone()).or_insert_with(HashMap::new);
-        match space_map.entry(info.agent.clone()) {
-            std::collections::hash_map::Entry::Occupied(mut e) => {
-                if info.signed_at_ms > e.get().signed_at_ms {
-                    e.insert(info);
+    pub fn put(&self, info: AgentInfoSigned) -> StoreResult<()> {
+        match self.0.as_ref() {
+            Backend::Memory(map) => {
+                let mut lock = map.write();
+                let space_map = lock.entry(info.space.clone()).or_insert_with(HashMap::new);
+                match space_map.entry(info.agent.clone()) {
+                    std::collections::hash_map::Entry::Occupied(mut e) => {
+                        if info.signed_at_ms > e.get().signed_at_ms {
+                            e.insert(info);
+                        }
+                    }
+                    std::collections::hash_map::Entry::Vacant(e) => {
+                        e.insert(info);
+                    }
                 }
+                Ok(())
             }
-            std::collections::hash_map::Entry::Vacant(e) => {
-                e.insert(info);
-            }
+            #[cfg(feature = "rusqlite")]
+            Backend::Sqlite(db) => db.put(&info),
         }
     }
 
-    pub fn random(&self, query: RandomQuery) -> Vec<Vec<u8>> {
+    pub fn random(&self, query: RandomQuery) -> StoreResult<Vec<Vec<u8>>> {
         // TODO: Max this limit
         let limit = query.limit.0 as usize;
-        let mut rng = rand::thread_rng();
-        let now = std::time::UNIX_EPOCH
-            .elapsed()
-            .expect("Bootstrap server time set before epoch")
-            .as_millis() as u64;
-        self.0
-            .read()
-            .get(query.space.as_ref())
-            .map(|space| {
-                space
-                    .values()
-                    .filter_map(|i| {
-                        if i.expires_at_ms <= now {
-                            return None;
-                        }
-                        if i.url_list.is_empty() {
-                            return None;
-                        }
-                        let mut buf = Vec::new();
-                        match rmp_encode(&mut buf, i) {
-                            Ok(_) => Some(buf),
-                            Err(_) => None,
-                        }
+        let now = now_ms();
+        match self.0.as_ref() {
+            Backend::Memory(map) => {
+                let mut rng = rand::thread_rng();
+                Ok(map
+                    .read()
+                    .get(query.space.as_ref())
+                    .map(|space| {
+                        space
+                            .values()
+                            .filter_map(|i| {
+                                if i.expires_at_ms <= now {
+                                    return None;
+                                }
+                                if i.url_list.is_empty() {
+                                    return None;
+                                }
+                                let mut buf = Vec::new();
+                                match rmp_encode(&mut buf, i) {
+                                    Ok(_) => Some(buf),
+                                    Err(_) => None,
+                                }
+                            })
+                            .choose_multiple(&mut rng, limit)
                     })
-                    .choose_multiple(&mut rng, limit)
-            })
-            .unwrap_or_default()
+                    .unwrap_or_default())
+            }
+            #[cfg(feature = "rusqlite")]
+            Backend::Sqlite(db) => db.random(&query.space, limit, now),
+        }
+    }
+
+    /// The number of agents stored for each space.
+    pub fn agents_per_space(&self) -> StoreResult<Vec<(KitsuneSpace, usize)>> {
+        match self.0.as_ref() {
+            Backend::Memory(map) => Ok(map
+                .read()
+                .iter()
+                .map(|(space, agents)| (space.as_ref().clone(), agents.len()))
+                .collect()),
+            #[cfg(feature = "rusqlite")]
+            Backend::Sqlite(db) => db.agents_per_space(),
+        }
     }
 
-    pub fn clear(&self) {
-        self.0.write().clear()
+    pub fn clear(&self) -> StoreResult<()> {
+        match self.0.as_ref() {
+            Backend::Memory(map) => {
+                map.write().clear();
+                Ok(())
+            }
+            #[cfg(feature = "rusqlite")]
+            Backend::Sqlite(db) => db.clear(),
+        }
     }
 
     #[cfg(test)]
     pub fn all(&self) -> HashMap<Arc<KitsuneSpace>, HashMap<Arc<KitsuneAgent>, AgentInfoSigned>> {
-        self.0.read().clone()
+        match self.0.as_ref() {
+            Backend::Memory(map) => map.read().clone(),
+            #[cfg(feature = "rusqlite")]
+            Backend::Sqlite(db) => {
+                let mut all = SpaceMap::new();
+                for info in db.all().unwrap() {
+                    all.entry(info.space.clone())
+                        .or_default()
+                        .insert(info.agent.clone(), info);
+                }
+                all
+            }
+        }
     }
 }
@@ -0,0 +1,25 @@
+-- Agent infos persisted by the bootstrap server.
+-- Modeled on the conductor's p2p_agent_store table, keyed by space as well
+-- since a single bootstrap server serves many spaces.
+CREATE TABLE IF NOT EXISTS bootstrap_agent_store (
+  -- Primary key
+  space                   BLOB      NOT NULL,
+  agent                   BLOB      NOT NULL,
+
+  -- Encoded binary, as returned by the `random` op
+  encoded                 BLOB      NOT NULL,
+
+  -- Additional queryable fields extracted from encoding
+  signed_at_ms            INTEGER   NOT NULL,
+  expires_at_ms           INTEGER   NOT NULL,
+
+  -- if this record has no urls, it is inactive
+  -- if it *has* urls, it is active, mark it such
+  -- 1 = active, 0 = inactive
+  is_active               INTEGER   NOT NULL,
+
+  PRIMARY KEY (space, agent)
+);
+
+CREATE INDEX IF NOT EXISTS bootstrap_agent_store_expires_at_idx
+  ON bootstrap_agent_store(expires_at_ms ASC);
@@ -0,0 +1,180 @@
+use std::path::Path;
+
+use kitsune_p2p_types::{
+    agent_info::AgentInfoSigned,
+    bin_types::KitsuneSpace,
+    codec::{rmp_decode, rmp_encode},
+};
+use parking_lot::Mutex;
+use rusqlite::{named_params, Connection};
+
+use super::StoreResult;
+
+const SCHEMA: &str = include_str!("schema.sql");
+
+/// Agent infos persisted in a SQLite database,
+/// so that they survive restarts of the bootstrap server.
+#[derive(Debug)]
+pub(super) struct SqliteStore(Mutex<Connection>);
+
+fn err(e: impl std::fmt::Display) -> String {
+    format!("Bootstrap store error: {}", e)
+}
+
+impl SqliteStore {
+    pub fn open(path: &Path) -> StoreResult<Self> {
+        let conn = Connection::open(path).map_err(err)?;
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .map_err(err)?;
+        conn.execute_batch(SCHEMA).map_err(err)?;
+        Ok(Self(Mutex::new(conn)))
+    }
+
+    pub fn prune(&self, now: u64) -> StoreResult<()> {
+        self.0
+            .lock()
+            .execute(
+                "DELETE FROM bootstrap_agent_store WHERE expires_at_ms < :now",
+                named_params! { ":now": now },
+            )
+            .map_err(err)?;
+        Ok(())
+    }
+
+    /// Insert the agent info, unless a more recently signed one is
+    /// already stored for this agent.
+    pub fn put(&self, info: &AgentInfoSigned) -> StoreResult<()> {
+        let mut encoded = Vec::new();
+        rmp_encode(&mut encoded, info).map_err(err)?;
+        self.0
+            .lock()
+            .execute(
+                "
+                INSERT INTO bootstrap_agent_store (
+                  space, agent, encoded, signed_at_ms, expires_at_ms, is_active
+                ) VALUES (
+                  :space, :agent, :encoded, :signed_at_ms, :expires_at_ms, :is_active
+                )
+                ON CONFLICT (space, agent) DO UPDATE SET
+                  encoded = excluded.encoded,
+                  signed_at_ms = excluded.signed_at_ms,
+                  expires_at_ms = excluded.expires_at_ms,
+                  is_active = excluded.is_active
+                WHERE excluded.signed_at_ms > bootstrap_agent_store.signed_at_ms
+                ",
+                named_params! {
+                    ":space": &info.space.0,
+                    ":agent": &info.agent.0,
+                    ":encoded": encoded,
+                    ":signed_at_ms": info.signed_at_ms,
+                    ":expires_at_ms": info.expires_at_ms,
+                    ":is_active": !info.url_list.is_empty(),
+                },
+            )
+            .map_err(err)?;
+        Ok(())
+    }
+
+    pub fn random(
+        &self,
+        space: &KitsuneSpace,
+        limit: usize,
+        now: u64,
+    ) -> StoreResult<Vec<Vec<u8>>> {
+        let conn = self.0.lock();
+        let mut stmt = conn
+            .prepare_cached(
+                "
+                SELECT encoded FROM bootstrap_agent_store
+                WHERE space = :space
+                AND expires_at_ms > :now
+                AND is_active = 1
+                ORDER BY RANDOM()
+                LIMIT :limit
+                ",
+            )
+            .map_err(err)?;
+        let rows = stmt
+            .query_map(
+                named_params! { ":space": &space.0, ":now": now, ":limit": limit },
+                |row| row.get(0),
+            )
+            .map_err(err)?;
+        rows.collect::<Result<_, _>>().map_err(err)
+    }
+
+    pub fn agents_per_space(&self) -> StoreResult<Vec<(KitsuneSpace, usize)>> {
+        let conn = self.0.lock();
+        let mut stmt = conn
+            .prepare_cached("SELECT space, COUNT(*) FROM bootstrap_agent_store GROUP BY space")
+            .map_err(err)?;
+        let rows = stmt
+            .query_map([], |row| Ok((KitsuneSpace(row.get(0)?), row.get(1)?)))
+            .map_err(err)?;
+        rows.collect::<Result<_, _>>().map_err(err)
+    }
+
+    pub fn clear(&self) -> StoreResult<()> {
+        self.0
+            .lock()
+            .execute("DELETE FROM bootstrap_agent_store", [])
+            .map_err(err)?;
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub fn all(&self) -> StoreResult<Vec<AgentInfoSigned>> {
+        let conn = self.0.lock();
+        let mut stmt = conn
+            .prepare("SELECT encoded FROM bootstrap_agent_store")
+            .map_err(err)?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))
+            .map_err(err)?;
+        rows.map(|encoded| {
+            rmp_decode(&mut AsRef::<[u8]>::as_ref(&encoded.map_err(err)?)).map_err(err)
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::super::Store;
+    use crate::test_util::{now_ms, signed_agent_info};
+    use kitsune_p2p_types::bootstrap::{RandomLimit, RandomQuery};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sqlite_store_persists_agent_infos() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bootstrap.sqlite3");
+        let space = Arc::new(kitsune_p2p_types::bin_types::KitsuneSpace(vec![1; 36]));
+
+        let info = signed_agent_info(space.clone(), now_ms() + 60_000_000).await;
+        let expired = signed_agent_info(space.clone(), now_ms() - 1).await;
+        {
+            let store = Store::open_sqlite(&path, vec![]).unwrap();
+            store.put(info.clone()).unwrap();
+            store.put(expired).unwrap();
+            store.prune().unwrap();
+        }
+
+        // Reopening the database finds the agent info again
+        let store = Store::open_sqlite(&path, vec![]).unwrap();
+        assert_eq!(store.all()[&space][&info.agent], info);
+        assert_eq!(
+            store.agents_per_space().unwrap(),
+            vec![(space.as_ref().clone(), 1)]
+        );
+        let query = RandomQuery {
+            space: space.clone(),
+            limit: RandomLimit(10),
+        };
+        assert_eq!(store.random(query).unwrap().len(), 1);
+
+        store.clear().unwrap();
+        assert!(store.all().is_empty());
+    }
+}
@@ -0,0 +1,26 @@
+use crate::{DhtArc, DhtLocation};
+
+/// Holds an agent's storage arc at a fixed size, rather than letting it be
+/// resized according to the density of its peers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArcPin {
+    /// Hold the full DHT, e.g. on an always-on node which anchors the network.
+    Full,
+    /// Hold nothing, e.g. on a node with too few resources to serve others.
+    /// The agent still publishes the data it authors.
+    Empty,
+    /// Hold the arc of this half length starting at the agent's location.
+    HalfLength(u32),
+}
+
+impl ArcPin {
+    /// The pinned arc of an agent at this location.
+    pub fn to_arc(self, loc: DhtLocation) -> DhtArc {
+        match self {
+            ArcPin::Full => DhtArc::full(loc),
+            ArcPin::Empty => DhtArc::empty(loc),
+            ArcPin::HalfLength(half_len) => DhtArc::from_start_and_half_len(loc, half_len),
+        }
+    }
+}
@@ -1,3 +1,6 @@
+mod arc_pin;
+pub use arc_pin::*;
+
 mod defaults;
 pub use defaults::*;
 
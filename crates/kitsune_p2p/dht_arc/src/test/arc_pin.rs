@@ -0,0 +1,20 @@
+use crate::*;
+
+#[test]
+fn pinned_arcs_survive_agent_info_encoding() {
+    // Agent info only carries the half length of the arc, from which the
+    // arc is rebuilt starting at the agent's location.
+    let loc = DhtLocation::new(12345);
+    for pin in [
+        ArcPin::Full,
+        ArcPin::Empty,
+        ArcPin::HalfLength(u32::MAX / 8),
+    ] {
+        let arc = pin.to_arc(loc);
+        let decoded = DhtArc::from_start_and_half_len(loc, arc.half_length());
+        assert_eq!(arc.inner(), decoded.inner(), "{:?}", pin);
+    }
+    assert!(ArcPin::Full.to_arc(loc).is_full());
+    assert!(ArcPin::Empty.to_arc(loc).is_empty());
+    assert_eq!(ArcPin::HalfLength(1000).to_arc(loc).half_length(), 1000);
+}
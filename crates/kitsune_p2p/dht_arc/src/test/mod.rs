@@ -1,6 +1,7 @@
 mod ascii;
 pub use ascii::ascii;
 
+mod arc_pin;
 mod intersection;
 mod test_ascii;
 mod union;
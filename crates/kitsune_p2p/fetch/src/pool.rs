@@ -13,8 +13,10 @@
 use std::sync::Arc;
 use tokio::time::{Duration, Instant};
 
+use holochain_trace::export::prometheus::{register_int_gauge, IntGauge};
 use kitsune_p2p_types::{tx2::tx2_utils::ShareOpen, KAgent, KSpace /*, Tx2Cert*/};
 use linked_hash_map::{Entry, LinkedHashMap};
+use once_cell::sync::Lazy;
 
 use crate::{FetchContext, FetchKey, FetchPoolPush, RoughInt};
 
@@ -24,6 +26,15 @@ pub use pool_reader::*;
 /// Max number of queue items to check on each `next()` poll
 const NUM_ITEMS_PER_POLL: usize = 100;
 
+/// The number of items in all fetch pools
+static FETCH_POOL_SIZE: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "kitsune_fetch_pool_size",
+        "The number of items waiting to be fetched"
+    )
+    .expect("Metric names are unique")
+});
+
 /// A FetchPool tracks a set of [`FetchKey`]s (op hashes or regions) to be fetched,
 /// each of which can have multiple sources associated with it.
 ///
@@ -95,6 +106,12 @@ impl Default for State {
     }
 }
 
+impl Drop for State {
+    fn drop(&mut self) {
+        FETCH_POOL_SIZE.sub(self.queue.len() as i64);
+    }
+}
+
 // TODO: move this to host, but for now, for convenience, we just use this one config
 // for every queue
 struct FetchPoolConfigBitwiseOr;
@@ -163,6 +180,22 @@ impl FetchPool {
             out
         })
     }
+
+    /// Get the items pending for a space, in the order they'll next be fetched,
+    /// so that they can be persisted.
+    pub fn pending(&self, space: &KSpace) -> Vec<FetchPoolPending> {
+        self.state.share_ref(|s| s.pending(space))
+    }
+
+    /// Add items which were pending for a space when they were persisted.
+    pub fn restore(&self, space: KSpace, pending: Vec<FetchPoolPending>) {
+        self.state.share_mut(|s| {
+            for item in pending {
+                s.restore(&*self.config, space.clone(), item);
+            }
+            tracing::debug!("FetchPool (size = {}) items restored", s.queue.len());
+        });
+    }
 }
 
 impl State {
@@ -182,6 +215,7 @@ impl State {
 
         match self.queue.entry(key) {
             Entry::Vacant(e) => {
+                FETCH_POOL_SIZE.inc();
                 let sources = if let Some(author) = author {
                     Sources(vec![SourceRecord::new(source), SourceRecord::agent(author)])
                 } else {
@@ -220,16 +254,64 @@ impl State {
 
     /// When an item has been successfully fetched, we can remove it from the queue.
     pub fn remove(&mut self, key: &FetchKey) -> Option<FetchPoolItem> {
-        self.queue.remove(key)
+        let removed = self.queue.remove(key);
+        if removed.is_some() {
+            FETCH_POOL_SIZE.dec();
+        }
+        removed
+    }
+
+    /// Get the items pending for a space, in queue order.
+    pub fn pending(&self, space: &KSpace) -> Vec<FetchPoolPending> {
+        self.queue
+            .iter()
+            .filter(|(_, v)| &v.space == space)
+            .map(|(k, v)| FetchPoolPending {
+                key: k.clone(),
+                sources: v.sources.0.iter().map(|s| s.source.clone()).collect(),
+                size: v.size,
+                context: v.context,
+            })
+            .collect()
+    }
+
+    /// Add an item which was pending when it was persisted, keeping the order
+    /// of its sources.
+    pub fn restore(&mut self, config: &dyn FetchPoolConfig, space: KSpace, item: FetchPoolPending) {
+        let FetchPoolPending {
+            key,
+            sources,
+            size,
+            context,
+        } = item;
+        // Sources are pushed to the front, so push the last one first.
+        for source in sources.into_iter().rev() {
+            self.push(
+                config,
+                FetchPoolPush {
+                    key: key.clone(),
+                    author: None,
+                    context,
+                    space: space.clone(),
+                    source,
+                    size,
+                },
+            );
+        }
     }
 
     /// Get a string summary of the queue's contents
-    #[cfg(feature = "test_utils")]
     pub fn summary(&self) -> String {
+        self.summary_of(|_| true)
+    }
+
+    /// Get a string summary of the contents of the queue which are included
+    /// by a filter.
+    pub(crate) fn summary_of(&self, include: impl Fn(&FetchPoolItem) -> bool) -> String {
         use human_repr::HumanCount;
 
-        let table = self
-            .queue
+        let items: Vec<_> = self.queue.iter().filter(|(_, v)| include(v)).collect();
+        let table = items
             .iter()
             .map(|(k, v)| {
                 let key = match k {
@@ -253,11 +335,10 @@ impl State {
             })
             .collect::<Vec<_>>()
             .join("\n");
-        format!("{}\n{} items total", table, self.queue.len())
+        format!("{}\n{} items total", table, items.len())
     }
 
     /// The heading to go along with the summary
-    #[cfg(feature = "test_utils")]
     pub fn summary_heading() -> String {
         format!("{:10}  {:>6} {:>6} {}", "key", "#src", "last", "size")
     }
@@ -362,8 +443,25 @@ impl Sources {
     }
 }
 
+/// An item which is pending in the queue, in a form which can be persisted
+/// by the host and restored after a restart.
+///
+/// The times of the last fetches aren't kept, so restored items are fetched
+/// straight away.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FetchPoolPending {
+    /// Description of what to fetch.
+    pub key: FetchKey,
+    /// Known sources from whom we can fetch this item, in the order they'll be tried.
+    pub sources: Vec<FetchSource>,
+    /// The approximate size of the item
+    pub size: Option<RoughInt>,
+    /// Opaque user data specified by the host
+    pub context: Option<FetchContext>,
+}
+
 /// A source to fetch from: either a node, or an agent on a node
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum FetchSource {
     /// An agent on a node
     Agent(KAgent),
@@ -500,6 +598,33 @@ mod tests {
         assert_eq!(q.queue, expected_ready);
     }
 
+    #[test]
+    fn pending_roundtrip() {
+        let c = Config(1, 1);
+        let mut q = State::default();
+        q.push(&c, req(1, ctx(1), source(1)));
+        q.push(&c, req(1, ctx(1), source(2)));
+        q.push(&c, req(2, None, source(3)));
+        q.push(
+            &c,
+            FetchPoolPush {
+                space: space(1),
+                ..req(3, None, source(4))
+            },
+        );
+
+        let pending = q.pending(&space(0));
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].sources, vec![source(2), source(1)]);
+
+        let mut restored = State::default();
+        for item in pending.clone() {
+            restored.restore(&c, space(0), item);
+        }
+        assert_eq!(restored.pending(&space(0)), pending);
+        assert!(restored.pending(&space(1)).is_empty());
+    }
+
     #[tokio::test(start_paused = true)]
     async fn queue_next() {
         let cfg = Config(1, 10);
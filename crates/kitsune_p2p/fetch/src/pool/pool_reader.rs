@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use kitsune_p2p_types::KSpace;
 
-use crate::FetchPool;
+use crate::{FetchPool, State};
 
 /// Read-only access to the queue
 #[derive(Debug, Clone, derive_more::From)]
@@ -23,6 +23,15 @@ impl FetchPoolReader {
             num_ops_to_fetch: count,
         }
     }
+
+    /// Get a string summary of the queue's contents, filtered by space
+    pub fn summary(&self, spaces: HashSet<KSpace>) -> String {
+        let summary = self
+            .0
+            .state
+            .share_ref(|s| s.summary_of(|v| spaces.contains(&v.space)));
+        format!("{}\n{}", State::summary_heading(), summary)
+    }
 }
 
 /// Info about the fetch queue
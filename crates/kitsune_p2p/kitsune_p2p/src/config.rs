@@ -1,7 +1,9 @@
+use crate::KitsuneP2pResult;
 use kitsune_p2p_types::config::{tuning_params_struct, KitsuneP2pTuningParams};
 use kitsune_p2p_types::tx2::tx2_adapter::AdapterFactory;
 use kitsune_p2p_types::tx2::tx2_utils::*;
 use kitsune_p2p_types::*;
+use std::collections::HashMap;
 use url2::Url2;
 
 // TODO - FIXME - holochain bootstrap should not be encoded in kitsune
@@ -12,6 +14,9 @@ pub const BOOTSTRAP_SERVICE_DEFAULT: &str = "https://bootstrap-staging.holo.host
 /// The default development bootstrap service url.
 pub const BOOTSTRAP_SERVICE_DEV: &str = "https://bootstrap-dev.holohost.workers.dev";
 
+/// The gossip modules which may be listed, comma separated, in a gossip strategy.
+pub(crate) const GOSSIP_STRATEGY_MODULES: &[&str] = &["sharded-gossip", "full-sync", "none"];
+
 pub(crate) enum KitsuneP2pTx2Backend {
     #[cfg(feature = "tx2")]
     Mem,
@@ -56,6 +61,11 @@ pub struct KitsuneP2pConfig {
     pub tuning_params: KitsuneP2pTuningParams,
     /// The network used for connecting to other peers
     pub network_type: NetworkType,
+    /// Gossip strategies to use in place of the `gossip_strategy` tuning
+    /// param for particular spaces, keyed by the base64 of the space,
+    /// which for a holochain DNA is its hash without the `uhC0k` prefix.
+    #[serde(default)]
+    pub gossip_strategy_overrides: HashMap<String, String>,
 }
 
 impl Default for KitsuneP2pConfig {
@@ -65,6 +75,7 @@ impl Default for KitsuneP2pConfig {
             bootstrap_service: None,
             tuning_params: KitsuneP2pTuningParams::default(),
             network_type: NetworkType::QuicBootstrap,
+            gossip_strategy_overrides: HashMap::new(),
         }
     }
 }
@@ -78,6 +89,36 @@ fn cnv_bind_to(bind_to: &Option<url2::Url2>) -> TxUrl {
 }
 
 impl KitsuneP2pConfig {
+    /// The gossip strategy to use for a space.
+    pub fn gossip_strategy(&self, space: &bin_types::KitsuneSpace) -> &str {
+        self.gossip_strategy_overrides
+            .get(&space.to_string())
+            .unwrap_or(&self.tuning_params.gossip_strategy)
+    }
+
+    /// Check that the `gossip_strategy` tuning param and every entry in
+    /// `gossip_strategy_overrides` only name known gossip modules.
+    pub fn validate_gossip_strategies(&self) -> KitsuneP2pResult<()> {
+        let strategies = std::iter::once(("tuning_params", &self.tuning_params.gossip_strategy))
+            .chain(
+                self.gossip_strategy_overrides
+                    .iter()
+                    .map(|(space, strategy)| (space.as_str(), strategy)),
+            );
+        for (source, strategy) in strategies {
+            for module in strategy.split(',') {
+                if !GOSSIP_STRATEGY_MODULES.contains(&module) {
+                    return Err(format!(
+                        "unknown gossip strategy {:?} for {}, expected one of {:?}",
+                        module, source, GOSSIP_STRATEGY_MODULES,
+                    )
+                    .into());
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[allow(dead_code)] // because of feature flipping
     pub(crate) fn is_tx2(&self) -> bool {
         #[cfg(feature = "tx2")]
@@ -19,7 +19,12 @@
 //! deterministic hashes associated with each based on the contents, which are sent to the gossip partner.
 //! For regions which mismatch, the ops in those regions will be exchanged between partners. For regions
 //! which match, no data will be transferred.
+//!
+//! Full sync gossip is an alternative to both, for small networks where every node holds everything.
+//! Each round exchanges bloom filters of every agent info and op hash held, with no time windows or
+//! regions. See [`full_sync_gossip`] for details.
 
+pub mod full_sync_gossip;
 pub mod sharded_gossip;
 
 mod common;
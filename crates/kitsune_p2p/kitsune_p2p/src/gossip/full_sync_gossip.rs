@@ -0,0 +1,938 @@
+//! Full sync gossip, for small networks where every node holds everything.
+//!
+//! Sharded gossip splits the DHT into time windows and quantized regions so
+//! that it scales to large networks where each node only holds part of the
+//! data. In a small private network where every agent has a full arc, none
+//! of that is needed. Instead, each round of full sync gossip is a single
+//! exchange of bloom filters of every agent info and op hash two nodes hold
+//! in common:
+//!
+//! 1. The initiator sends its arcs, its local agents and its filters.
+//! 2. The acceptor replies with its own arcs, local agents and filters,
+//!    followed by the agent infos and op hashes missing from the initiator's
+//!    filters.
+//! 3. The initiator replies with the agent infos and op hashes missing from
+//!    the acceptor's filters.
+//!
+//! Missing ops are then fetched through the fetch pool, the same as for
+//! sharded gossip. The size of a round grows with the total amount of data,
+//! so this strategy is only suited to networks of up to a few tens of nodes.
+
+use crate::agent_store::AgentInfoSigned;
+use crate::gossip::sharded_gossip::next_target::{self, Node};
+use crate::gossip::sharded_gossip::{BandwidthThrottle, NodeId};
+use crate::gossip::*;
+use crate::meta_net::*;
+use crate::metrics::MetricsSync;
+use crate::types::event::*;
+use crate::types::gossip::*;
+use crate::{types::*, HostApi};
+use ghost_actor::dependencies::tracing;
+use kitsune_p2p_fetch::{FetchKey, FetchPool, FetchPoolPush, FetchSource, OpHashSized};
+use kitsune_p2p_timestamp::Timestamp;
+use kitsune_p2p_types::codec::Codec;
+use kitsune_p2p_types::config::*;
+use kitsune_p2p_types::dht_arc::{DhtArcRange, DhtArcSet};
+use kitsune_p2p_types::metrics::metric_task;
+use kitsune_p2p_types::tx2::tx2_utils::*;
+use kitsune_p2p_types::*;
+use rand::Rng;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// The most op hashes that are synced in a single round.
+const MAX_OPS: usize = 1_000_000;
+
+/// The most missing op hashes that are sent in a single message.
+/// This keeps messages well under the maximum message size.
+const MAX_OPS_PER_MESSAGE: usize = 100_000;
+
+/// The number of queued incoming messages above which new rounds
+/// are turned away.
+const MAX_INCOMING: usize = 20;
+
+/// The false positive rate of the filters. A false positive means the
+/// remote node won't send an agent info or op hash until a later round.
+const TGT_FP: f64 = 0.01;
+
+type EventSender = futures::channel::mpsc::Sender<event::KitsuneP2pEvent>;
+
+/// Incoming gossip.
+type Incoming = (MetaNetCon, String, FullSyncGossipWire, usize);
+/// Outgoing gossip.
+type Outgoing = (NodeId, HowToConnect, FullSyncGossipWire);
+
+/// The entry point for the full sync gossip strategy.
+pub struct FullSyncGossip {
+    tuning_params: KitsuneP2pTuningParams,
+    space: Arc<KitsuneSpace>,
+    // The endpoint to use for all outgoing comms
+    ep_hnd: MetaNet,
+    evt_sender: EventSender,
+    fetch_pool: FetchPool,
+    /// Bandwidth for incoming and outgoing gossip.
+    bandwidth: Arc<BandwidthThrottle>,
+    /// Metrics that track remote node states and help guide
+    /// the next node to gossip with.
+    metrics: MetricsSync,
+    /// The internal mutable state
+    inner: Share<FullSyncGossipState>,
+    closing: AtomicBool,
+}
+
+impl std::fmt::Debug for FullSyncGossip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FullSyncGossip{...}").finish()
+    }
+}
+
+/// The internal mutable state for [`FullSyncGossip`]
+#[derive(Default)]
+struct FullSyncGossipState {
+    /// The list of agents on this node
+    local_agents: HashSet<Arc<KitsuneAgent>>,
+    /// If Some, we have initiated a round with this node, which ends
+    /// when the round does.
+    initiate_tgt: Option<FullSyncGossipTarget>,
+    /// The rounds in progress, by remote node.
+    rounds: HashMap<NodeId, Round>,
+    incoming: VecDeque<Incoming>,
+    outgoing: VecDeque<Outgoing>,
+}
+
+/// A node we have initiated a round with.
+struct FullSyncGossipTarget {
+    node: Node,
+    /// A random number to resolve concurrent initiates.
+    tie_break: u32,
+    when_initiated: Instant,
+}
+
+/// A round of gossip in progress with a remote node.
+struct Round {
+    /// The remote agents hosted by the remote node
+    remote_agent_list: Vec<AgentInfoSigned>,
+    /// Last moment we had any contact for this round.
+    last_touch: Instant,
+}
+
+impl FullSyncGossipState {
+    fn is_target(&self, cert: &NodeId) -> bool {
+        self.initiate_tgt
+            .as_ref()
+            .map(|tgt| &tgt.node.cert == cert)
+            .unwrap_or(false)
+    }
+
+    /// Start a round with a node.
+    fn start_round(
+        &mut self,
+        cert: NodeId,
+        remote_agent_list: Vec<AgentInfoSigned>,
+        metrics: &MetricsSync,
+    ) {
+        let mut metrics = metrics.write();
+        if !self.is_target(&cert) {
+            metrics.record_accept(&remote_agent_list, GossipModuleType::FullSync);
+        }
+        metrics.start_current_round(&cert, GossipModuleType::FullSync, &remote_agent_list);
+        self.rounds.insert(
+            cert,
+            Round {
+                remote_agent_list,
+                last_touch: Instant::now(),
+            },
+        );
+    }
+
+    /// End the round with a node, along with our initiate if it was our target.
+    fn end_round(&mut self, cert: &NodeId, metrics: &MetricsSync, error: bool) {
+        let round = self.rounds.remove(cert);
+        let tgt = if self.is_target(cert) {
+            self.initiate_tgt.take()
+        } else {
+            None
+        };
+        let mut metrics = metrics.write();
+        match (round, tgt) {
+            (Some(round), _) if error => {
+                metrics.record_error(&round.remote_agent_list, GossipModuleType::FullSync)
+            }
+            (Some(round), _) => {
+                metrics.record_success(&round.remote_agent_list, GossipModuleType::FullSync)
+            }
+            (None, Some(tgt)) if error => {
+                metrics.record_error(&tgt.node.agent_info_list, GossipModuleType::FullSync)
+            }
+            _ => (),
+        }
+        metrics.complete_current_round(cert, error);
+    }
+
+    /// Give up on our initiate with a node that didn't start a round.
+    fn remove_target(&mut self, cert: &NodeId, metrics: &MetricsSync, error: bool) {
+        if self.is_target(cert) && !self.rounds.contains_key(cert) {
+            let tgt = self.initiate_tgt.take().expect("Target was checked");
+            if error {
+                metrics
+                    .write()
+                    .record_error(&tgt.node.agent_info_list, GossipModuleType::FullSync);
+            }
+        }
+    }
+
+    /// End any rounds and initiates which have gone without contact for
+    /// longer than the round timeout.
+    fn record_timeouts(&mut self, round_timeout: Duration, metrics: &MetricsSync) {
+        let timed_out: Vec<_> = self
+            .rounds
+            .iter()
+            .filter(|(_, round)| round.last_touch.elapsed() > round_timeout)
+            .map(|(cert, _)| cert.clone())
+            .collect();
+        for cert in timed_out {
+            tracing::warn!("The node {:?} has timed out their gossip round", cert);
+            self.end_round(&cert, metrics, true);
+        }
+        let expired = self
+            .initiate_tgt
+            .as_ref()
+            .filter(|tgt| tgt.when_initiated.elapsed() > round_timeout)
+            .map(|tgt| tgt.node.cert.clone());
+        if let Some(cert) = expired {
+            tracing::warn!("Tgt expired {:?}", cert);
+            self.remove_target(&cert, metrics, true);
+        }
+    }
+}
+
+impl FullSyncGossip {
+    /// Constructor
+    pub fn new(
+        tuning_params: KitsuneP2pTuningParams,
+        space: Arc<KitsuneSpace>,
+        ep_hnd: MetaNet,
+        evt_sender: EventSender,
+        bandwidth: Arc<BandwidthThrottle>,
+        metrics: MetricsSync,
+        fetch_pool: FetchPool,
+    ) -> Arc<Self> {
+        let this = Arc::new(Self {
+            tuning_params,
+            space,
+            ep_hnd,
+            evt_sender,
+            fetch_pool,
+            bandwidth,
+            metrics,
+            inner: Share::new(Default::default()),
+            closing: AtomicBool::new(false),
+        });
+        metric_task({
+            let this = this.clone();
+            async move {
+                let loop_interval =
+                    Duration::from_millis(this.tuning_params.gossip_loop_iteration_delay_ms as u64);
+                while !this.closing.load(Ordering::Relaxed) {
+                    tokio::time::sleep(loop_interval).await;
+                    this.run_one_iteration().await;
+                }
+                KitsuneResult::Ok(())
+            }
+        });
+        this
+    }
+
+    async fn run_one_iteration(&self) {
+        let _ = self.inner.share_mut(|i, _| {
+            i.record_timeouts(
+                Duration::from_millis(self.tuning_params.gossip_round_timeout_ms as u64),
+                &self.metrics,
+            );
+            Ok(())
+        });
+        match self.try_initiate().await {
+            Ok(Some(outgoing)) => self.push_outgoing([outgoing]),
+            Ok(None) => (),
+            Err(err) => tracing::error!("Gossip failed when trying to initiate with {:?}", err),
+        }
+        while let Some((con, remote_url, msg, bytes)) = self.pop_incoming() {
+            self.bandwidth.incoming_bytes(bytes).await;
+            let peer_cert = con.peer_id();
+            let outgoing = match self.process_incoming(peer_cert.clone(), msg).await {
+                Ok(r) => r,
+                Err(e) => {
+                    tracing::error!("FAILED to process incoming gossip {:?}", e);
+                    self.end_round(&peer_cert, true);
+                    vec![FullSyncGossipWire::error(e.to_string())]
+                }
+            };
+            self.push_outgoing(outgoing.into_iter().map(|msg| {
+                (
+                    peer_cert.clone(),
+                    HowToConnect::Con(con.clone(), remote_url.clone()),
+                    msg,
+                )
+            }));
+        }
+        while let Some((cert, how, msg)) = self.pop_outgoing() {
+            if let Err(err) = self.process_outgoing(how, msg).await {
+                tracing::error!(
+                    "Gossip failed to send outgoing message because of: {:?}",
+                    err
+                );
+                self.end_round(&cert, true);
+            }
+        }
+    }
+
+    fn pop_incoming(&self) -> Option<Incoming> {
+        self.inner
+            .share_mut(|i, _| Ok(i.incoming.pop_front()))
+            .ok()
+            .flatten()
+    }
+
+    fn pop_outgoing(&self) -> Option<Outgoing> {
+        self.inner
+            .share_mut(|i, _| Ok(i.outgoing.pop_front()))
+            .ok()
+            .flatten()
+    }
+
+    fn push_outgoing(&self, outgoing: impl IntoIterator<Item = Outgoing>) {
+        let _ = self.inner.share_mut(|i, _| {
+            i.outgoing.extend(outgoing);
+            Ok(())
+        });
+    }
+
+    fn end_round(&self, cert: &NodeId, error: bool) {
+        let _ = self.inner.share_mut(|i, _| {
+            i.end_round(cert, &self.metrics, error);
+            Ok(())
+        });
+    }
+
+    async fn process_outgoing(
+        &self,
+        how: HowToConnect,
+        gossip: FullSyncGossipWire,
+    ) -> KitsuneResult<()> {
+        let gossip = gossip.encode_vec().map_err(KitsuneError::other)?;
+        let bytes = gossip.len();
+        let gossip = wire::Wire::gossip(
+            self.space.clone(),
+            gossip.into(),
+            GossipModuleType::FullSync,
+        );
+
+        let timeout = self.tuning_params.implicit_timeout();
+
+        self.bandwidth.outgoing_bytes(bytes).await;
+
+        let con = match how {
+            HowToConnect::Con(con, remote_url) => {
+                if con.is_closed() {
+                    self.ep_hnd.get_connection(remote_url, timeout).await?
+                } else {
+                    con
+                }
+            }
+            HowToConnect::Url(url) => self.ep_hnd.get_connection(url, timeout).await?,
+        };
+        con.notify(&gossip, timeout).await?;
+        Ok(())
+    }
+
+    /// Try to initiate a round if we aren't already waiting on one.
+    async fn try_initiate(&self) -> KitsuneResult<Option<Outgoing>> {
+        let (has_target, local_agents) = self
+            .inner
+            .share_mut(|i, _| Ok((i.initiate_tgt.is_some(), i.local_agents.clone())))?;
+        if has_target || local_agents.is_empty() {
+            return Ok(None);
+        }
+
+        let agent_list = self.local_agent_list(&local_agents).await?;
+        let intervals = arc_intervals(&agent_list);
+        let remote_nodes = next_target::remote_nodes_within_arcset(
+            &self.evt_sender,
+            &self.space,
+            Arc::new(intervals.clone().into()),
+            &local_agents,
+        )
+        .await?;
+        let node = match next_target::next_remote_node(
+            remote_nodes,
+            &self.metrics,
+            self.tuning_params.clone(),
+        ) {
+            Some(node) => node,
+            None => return Ok(None),
+        };
+
+        let (agents, ops) = self.filters(intervals.clone().into()).await?;
+        let tie_break = rand::thread_rng().gen();
+        let gossip = FullSyncGossipWire::initiate(intervals, tie_break, agent_list, agents, ops);
+        let outgoing = (
+            node.cert.clone(),
+            HowToConnect::Url(node.url.to_string()),
+            gossip,
+        );
+
+        self.metrics
+            .write()
+            .record_initiate(&node.agent_info_list, GossipModuleType::FullSync);
+        self.inner.share_mut(|i, _| {
+            i.initiate_tgt = Some(FullSyncGossipTarget {
+                node,
+                tie_break,
+                when_initiated: Instant::now(),
+            });
+            Ok(())
+        })?;
+        Ok(Some(outgoing))
+    }
+
+    async fn process_incoming(
+        &self,
+        peer_cert: NodeId,
+        msg: FullSyncGossipWire,
+    ) -> KitsuneResult<Vec<FullSyncGossipWire>> {
+        let r = match msg {
+            FullSyncGossipWire::Initiate(Initiate {
+                intervals,
+                id,
+                agent_list,
+                agents,
+                ops,
+            }) => {
+                self.incoming_initiate(peer_cert, intervals, id, agent_list, agents, ops)
+                    .await?
+            }
+            FullSyncGossipWire::Accept(Accept {
+                intervals,
+                agent_list,
+                agents,
+                ops,
+            }) => {
+                self.incoming_accept(peer_cert, intervals, agent_list, agents, ops)
+                    .await?
+            }
+            FullSyncGossipWire::Missing(Missing {
+                agents,
+                ops,
+                finished,
+            }) => {
+                self.incoming_missing(peer_cert, agents, ops, finished)
+                    .await?;
+                Vec::with_capacity(0)
+            }
+            FullSyncGossipWire::NoAgents(_) => {
+                tracing::warn!("No agents to gossip with on the node {:?}", peer_cert);
+                self.inner.share_mut(|i, _| {
+                    i.remove_target(&peer_cert, &self.metrics, true);
+                    Ok(())
+                })?;
+                Vec::with_capacity(0)
+            }
+            FullSyncGossipWire::AlreadyInProgress(_) => {
+                self.inner.share_mut(|i, _| {
+                    i.remove_target(&peer_cert, &self.metrics, false);
+                    Ok(())
+                })?;
+                Vec::with_capacity(0)
+            }
+            FullSyncGossipWire::Busy(_) => {
+                tracing::warn!("The node {:?} is busy", peer_cert);
+                self.inner.share_mut(|i, _| {
+                    i.remove_target(&peer_cert, &self.metrics, true);
+                    Ok(())
+                })?;
+                Vec::with_capacity(0)
+            }
+            FullSyncGossipWire::Error(Error { message }) => {
+                tracing::warn!("gossiping with: {:?} and got error: {}", peer_cert, message);
+                self.end_round(&peer_cert, true);
+                Vec::with_capacity(0)
+            }
+        };
+        Ok(r)
+    }
+
+    /// Incoming initiate.
+    /// - Send back our filters and everything missing from the remote filters.
+    async fn incoming_initiate(
+        &self,
+        peer_cert: NodeId,
+        remote_intervals: Vec<DhtArcRange>,
+        remote_id: u32,
+        remote_agent_list: Vec<AgentInfoSigned>,
+        remote_agents: Option<PoolBuf>,
+        remote_ops: Option<PoolBuf>,
+    ) -> KitsuneResult<Vec<FullSyncGossipWire>> {
+        let (local_agents, same_as_target, already_in_progress) =
+            self.inner.share_mut(|i, _| {
+                let same_as_target = i
+                    .initiate_tgt
+                    .as_ref()
+                    .filter(|tgt| tgt.node.cert == peer_cert)
+                    .map(|tgt| tgt.tie_break);
+                Ok((
+                    i.local_agents.clone(),
+                    same_as_target,
+                    i.rounds.contains_key(&peer_cert),
+                ))
+            })?;
+
+        if already_in_progress {
+            return Ok(vec![FullSyncGossipWire::already_in_progress()]);
+        }
+
+        // If we initiated with this node at the same time then the
+        // initiate with the higher id goes ahead, and neither does on a tie.
+        if let Some(our_id) = same_as_target {
+            if our_id >= remote_id {
+                return Ok(Vec::with_capacity(0));
+            }
+            self.inner.share_mut(|i, _| {
+                i.initiate_tgt = None;
+                Ok(())
+            })?;
+        }
+
+        if local_agents.is_empty() {
+            return Ok(vec![FullSyncGossipWire::no_agents()]);
+        }
+
+        let agent_list = self.local_agent_list(&local_agents).await?;
+        let intervals = arc_intervals(&agent_list);
+        let arc_set: DhtArcSet = intervals.clone().into();
+        let common_arc_set = arc_set.intersection(&remote_intervals.into());
+
+        let (agents, ops) = self.filters(arc_set).await?;
+        let mut gossip = vec![FullSyncGossipWire::accept(
+            intervals, agent_list, agents, ops,
+        )];
+        self.missing(common_arc_set, remote_agents, remote_ops, &mut gossip)
+            .await?;
+
+        self.inner.share_mut(|i, _| {
+            i.start_round(peer_cert, remote_agent_list, &self.metrics);
+            Ok(())
+        })?;
+        Ok(gossip)
+    }
+
+    /// Incoming accept of our initiate.
+    /// - Send back everything missing from the remote filters.
+    async fn incoming_accept(
+        &self,
+        peer_cert: NodeId,
+        remote_intervals: Vec<DhtArcRange>,
+        remote_agent_list: Vec<AgentInfoSigned>,
+        remote_agents: Option<PoolBuf>,
+        remote_ops: Option<PoolBuf>,
+    ) -> KitsuneResult<Vec<FullSyncGossipWire>> {
+        let (local_agents, accept_is_from_target) = self
+            .inner
+            .share_mut(|i, _| Ok((i.local_agents.clone(), i.is_target(&peer_cert))))?;
+
+        // The other node will have to timeout on this but nodes should
+        // not be sending accepts to nodes that aren't targeting them.
+        if !accept_is_from_target {
+            return Ok(Vec::with_capacity(0));
+        }
+
+        let agent_list = self.local_agent_list(&local_agents).await?;
+        let arc_set: DhtArcSet = arc_intervals(&agent_list).into();
+        let common_arc_set = arc_set.intersection(&remote_intervals.into());
+
+        let mut gossip = Vec::new();
+        self.missing(common_arc_set, remote_agents, remote_ops, &mut gossip)
+            .await?;
+
+        self.inner.share_mut(|i, _| {
+            i.start_round(peer_cert, remote_agent_list, &self.metrics);
+            Ok(())
+        })?;
+        Ok(gossip)
+    }
+
+    /// Incoming agent infos and op hashes we were missing.
+    /// - Add the agents to the peer store and the ops to the fetch pool.
+    /// - The round is over once the last of them has been received.
+    async fn incoming_missing(
+        &self,
+        peer_cert: NodeId,
+        agents: Vec<Arc<AgentInfoSigned>>,
+        ops: Vec<OpHashSized>,
+        finished: bool,
+    ) -> KitsuneResult<()> {
+        let remote_agent_list = self.inner.share_mut(|i, _| {
+            Ok(i.rounds.get_mut(&peer_cert).map(|round| {
+                round.last_touch = Instant::now();
+                round.remote_agent_list.clone()
+            }))
+        })?;
+        let remote_agent_list = match remote_agent_list {
+            Some(list) => list,
+            None => {
+                tracing::warn!(
+                    "Op hashes were received after a round was dropped. {} ops dropped!",
+                    ops.len()
+                );
+                return Ok(());
+            }
+        };
+
+        if !agents.is_empty() {
+            let peer_data = agents.iter().map(|info| (**info).clone()).collect();
+            self.evt_sender
+                .put_agent_info_signed(PutAgentInfoSignedEvt {
+                    space: self.space.clone(),
+                    peer_data,
+                })
+                .await
+                .map_err(KitsuneError::other)?;
+        }
+
+        match remote_agent_list.first() {
+            Some(info) => {
+                let source = FetchSource::Agent(info.agent.clone());
+                for op_hash in ops {
+                    let (hash, size) = op_hash.into_inner();
+                    self.fetch_pool.push(FetchPoolPush {
+                        key: FetchKey::Op(hash),
+                        author: None,
+                        context: None,
+                        space: self.space.clone(),
+                        source: source.clone(),
+                        size,
+                    });
+                }
+            }
+            None if !ops.is_empty() => tracing::warn!(
+                "Op hashes were received for a round with no remote agent(s). {} ops dropped!",
+                ops.len()
+            ),
+            None => (),
+        }
+
+        if finished {
+            self.end_round(&peer_cert, false);
+        }
+        Ok(())
+    }
+
+    /// Add the agent infos and op hashes missing from the remote filters
+    /// to the gossip, in chunks which end with a finished one.
+    async fn missing(
+        &self,
+        common_arc_set: DhtArcSet,
+        remote_agents: Option<PoolBuf>,
+        remote_ops: Option<PoolBuf>,
+        gossip: &mut Vec<FullSyncGossipWire>,
+    ) -> KitsuneResult<()> {
+        let remote_agents = remote_agents.map(|filter| decode_bloom_filter(&filter));
+        let agents: Vec<_> = self
+            .all_agent_info()
+            .await?
+            .into_iter()
+            .filter(|info| {
+                remote_agents.as_ref().map_or(true, |bloom| {
+                    !bloom.check(&MetaOpKey::Agent(info.agent.clone(), info.signed_at_ms))
+                })
+            })
+            .map(Arc::new)
+            .collect();
+
+        let remote_ops = remote_ops.map(|filter| decode_bloom_filter(&filter));
+        let mut hashes = self
+            .op_hashes(common_arc_set, false)
+            .await?
+            .into_iter()
+            .peekable();
+        let mut ops = Vec::new();
+        while hashes.peek().is_some() {
+            for hash in hashes.by_ref().take(100) {
+                if remote_ops
+                    .as_ref()
+                    .map_or(true, |bloom| !bloom.check(&MetaOpKey::Op(hash.clone())))
+                {
+                    ops.push(OpHashSized::new(hash, None));
+                }
+            }
+            // Yield to avoid starving the runtime.
+            tokio::task::yield_now().await;
+        }
+
+        let mut agents = Some(agents);
+        let mut ops = ops.into_iter().peekable();
+        loop {
+            let chunk: Vec<_> = ops.by_ref().take(MAX_OPS_PER_MESSAGE).collect();
+            let finished = ops.peek().is_none();
+            gossip.push(FullSyncGossipWire::missing(
+                agents.take().unwrap_or_default(),
+                chunk,
+                finished,
+            ));
+            if finished {
+                return Ok(());
+            }
+        }
+    }
+
+    /// The filters of all the agent infos we hold, and all the op hashes we
+    /// hold within an arc set, which are none when there's nothing to filter.
+    async fn filters(
+        &self,
+        arc_set: DhtArcSet,
+    ) -> KitsuneResult<(Option<PoolBuf>, Option<PoolBuf>)> {
+        let agents = self.all_agent_info().await?;
+        let agents = (!agents.is_empty()).then(|| {
+            let mut bloom = BloomFilter::new_for_fp_rate(agents.len(), TGT_FP);
+            for info in agents {
+                bloom.set(&MetaOpKey::Agent(info.agent.clone(), info.signed_at_ms));
+            }
+            encode_bloom_filter(&bloom)
+        });
+
+        let hashes = self.op_hashes(arc_set, true).await?;
+        let ops = if hashes.is_empty() {
+            None
+        } else {
+            let mut bloom = BloomFilter::new_for_fp_rate(hashes.len(), TGT_FP);
+            let mut iter = hashes.into_iter().peekable();
+            while iter.peek().is_some() {
+                for hash in iter.by_ref().take(100) {
+                    bloom.set(&MetaOpKey::Op(hash));
+                }
+                // Yield to avoid starving the runtime.
+                tokio::task::yield_now().await;
+            }
+            Some(encode_bloom_filter(&bloom))
+        };
+        Ok((agents, ops))
+    }
+
+    /// Get all the op hashes we hold within an arc set, from any time.
+    async fn op_hashes(
+        &self,
+        arc_set: DhtArcSet,
+        include_limbo: bool,
+    ) -> KitsuneResult<Vec<Arc<KitsuneOpHash>>> {
+        if arc_set.is_empty() {
+            return Ok(Vec::with_capacity(0));
+        }
+        let hashes = self
+            .evt_sender
+            .query_op_hashes(QueryOpHashesEvt {
+                space: self.space.clone(),
+                arc_set,
+                window: Timestamp::MIN..Timestamp::MAX,
+                max_ops: MAX_OPS,
+                include_limbo,
+            })
+            .await
+            .map_err(KitsuneError::other)?
+            .map(|(hashes, _)| hashes)
+            .unwrap_or_default();
+        if hashes.len() >= MAX_OPS {
+            tracing::warn!(
+                "Holding more than {} ops, which is too many to fully sync. Use sharded gossip instead.",
+                MAX_OPS
+            );
+        }
+        Ok(hashes)
+    }
+
+    async fn all_agent_info(&self) -> KitsuneResult<Vec<AgentInfoSigned>> {
+        self.evt_sender
+            .query_agents(QueryAgentsEvt::new(self.space.clone()))
+            .await
+            .map_err(KitsuneError::other)
+    }
+
+    async fn local_agent_list(
+        &self,
+        local_agents: &HashSet<Arc<KitsuneAgent>>,
+    ) -> KitsuneResult<Vec<AgentInfoSigned>> {
+        self.evt_sender
+            .query_agents(
+                QueryAgentsEvt::new(self.space.clone()).by_agents(local_agents.iter().cloned()),
+            )
+            .await
+            .map_err(KitsuneError::other)
+    }
+}
+
+/// The arcs of a list of agents.
+fn arc_intervals(agent_list: &[AgentInfoSigned]) -> Vec<DhtArcRange> {
+    agent_list
+        .iter()
+        .map(|info| DhtArcRange::from(info.storage_arc))
+        .collect()
+}
+
+kitsune_p2p_types::write_codec_enum! {
+    /// FullSyncGossip Wire Protocol Codec
+    codec FullSyncGossipWire {
+        /// Initiate a round of gossip with a remote node
+        Initiate(0x10) {
+            /// The list of arc intervals (equivalent to a [`DhtArcSet`])
+            /// for all local agents
+            intervals.0: Vec<DhtArcRange>,
+            /// A random number to resolve concurrent initiates.
+            id.1: u32,
+            /// List of active local agents represented by this node.
+            agent_list.2: Vec<AgentInfoSigned>,
+            /// The bloom filter of all the agent infos held,
+            /// or none if there are none.
+            agents.3: Option<PoolBuf>,
+            /// The bloom filter of all the op hashes held within the arcs,
+            /// or none if there are none.
+            ops.4: Option<PoolBuf>,
+        },
+
+        /// Accept an incoming round of gossip from a remote node
+        Accept(0x20) {
+            /// The list of arc intervals (equivalent to a [`DhtArcSet`])
+            /// for all local agents
+            intervals.0: Vec<DhtArcRange>,
+            /// List of active local agents represented by this node.
+            agent_list.1: Vec<AgentInfoSigned>,
+            /// The bloom filter of all the agent infos held,
+            /// or none if there are none.
+            agents.2: Option<PoolBuf>,
+            /// The bloom filter of all the op hashes held within the arcs,
+            /// or none if there are none.
+            ops.3: Option<PoolBuf>,
+        },
+
+        /// Agent infos and op hashes that were missing from the remote filters.
+        Missing(0x30) {
+            /// The missing agent infos
+            agents.0: Vec<Arc<AgentInfoSigned>>,
+            /// The missing op hashes
+            ops.1: Vec<OpHashSized>,
+            /// Is this the last of the missing data for this round?
+            finished.2: bool,
+        },
+
+        /// The node you are gossiping with has hit an error condition
+        /// and failed to respond to a request.
+        Error(0xa0) {
+            /// The error message.
+            message.0: String,
+        },
+
+        /// The node currently is gossiping with too many
+        /// other nodes and is too busy to accept your initiate.
+        /// Please try again later.
+        Busy(0xa1) {
+        },
+
+        /// The node you are trying to gossip with has no agents anymore.
+        NoAgents(0xa2) {
+        },
+
+        /// You have sent a stale initiate to a node
+        /// that already has an active round with you.
+        AlreadyInProgress(0xa3) {
+        },
+    }
+}
+
+impl AsGossipModule for FullSyncGossip {
+    fn incoming_gossip(
+        &self,
+        con: MetaNetCon,
+        remote_url: String,
+        gossip_data: Box<[u8]>,
+    ) -> KitsuneResult<()> {
+        let (bytes, gossip) =
+            FullSyncGossipWire::decode_ref(&gossip_data).map_err(KitsuneError::other)?;
+        let new_initiate = matches!(gossip, FullSyncGossipWire::Initiate(_));
+        self.inner.share_mut(move |i, _| {
+            let overloaded = i.incoming.len() > MAX_INCOMING;
+            if overloaded {
+                tracing::warn!(
+                    "Overloaded with incoming gossip.. {} messages",
+                    i.incoming.len()
+                );
+            }
+            // If we are overloaded then return busy to any new initiates.
+            if overloaded && new_initiate {
+                i.outgoing.push_back((
+                    con.peer_id(),
+                    HowToConnect::Con(con, remote_url),
+                    FullSyncGossipWire::busy(),
+                ));
+            } else {
+                i.incoming
+                    .push_back((con, remote_url, gossip, bytes as usize));
+            }
+            Ok(())
+        })
+    }
+
+    fn local_agent_join(&self, a: Arc<KitsuneAgent>) {
+        let _ = self.inner.share_mut(move |i, _| {
+            i.local_agents.insert(a);
+            Ok(())
+        });
+        self.metrics.write().record_force_initiate();
+    }
+
+    fn local_agent_leave(&self, a: Arc<KitsuneAgent>) {
+        let _ = self.inner.share_mut(move |i, _| {
+            i.local_agents.remove(&a);
+            Ok(())
+        });
+    }
+
+    fn close(&self) {
+        self.closing.store(true, Ordering::Relaxed);
+    }
+
+    fn new_integrated_data(&self) {
+        self.metrics.write().record_force_initiate();
+    }
+}
+
+struct FullSyncGossipFactory {
+    bandwidth: Arc<BandwidthThrottle>,
+}
+
+impl AsGossipModuleFactory for FullSyncGossipFactory {
+    fn spawn_gossip_task(
+        &self,
+        tuning_params: KitsuneP2pTuningParams,
+        space: Arc<KitsuneSpace>,
+        ep_hnd: MetaNet,
+        evt_sender: futures::channel::mpsc::Sender<event::KitsuneP2pEvent>,
+        _host: HostApi,
+        metrics: MetricsSync,
+        fetch_pool: FetchPool,
+    ) -> GossipModule {
+        GossipModule(FullSyncGossip::new(
+            tuning_params,
+            space,
+            ep_hnd,
+            evt_sender,
+            self.bandwidth.clone(),
+            metrics,
+            fetch_pool,
+        ))
+    }
+}
+
+/// Create a full sync `GossipModuleFactory`
+pub fn factory(bandwidth: Arc<BandwidthThrottle>) -> GossipModuleFactory {
+    GossipModuleFactory(Arc::new(FullSyncGossipFactory { bandwidth }))
+}
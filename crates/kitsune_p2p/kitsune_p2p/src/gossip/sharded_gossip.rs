@@ -60,7 +60,7 @@ mod state_map;
 mod store;
 
 mod bandwidth;
-mod next_target;
+pub(crate) mod next_target;
 
 // dead_code and unused_imports are allowed here because when compiling this
 // code path due to test_utils, the helper functions defined in this module
@@ -19,64 +19,9 @@ impl ShardedGossipLocal {
         arc_set: Arc<DhtArcSet>,
         local_agents: &HashSet<Arc<KitsuneAgent>>,
     ) -> KitsuneResult<Option<Node>> {
-        let mut remote_nodes: HashMap<Arc<[u8; 32]>, Node> = HashMap::new();
-
-        // Get all the remote nodes in this arc set.
-        let remote_agents_within_arc_set: HashSet<_> =
-            store::agents_within_arcset(&self.evt_sender, &self.space, arc_set.clone())
-                .await?
-                .into_iter()
-                .filter(|(a, _)| !local_agents.contains(a))
-                .map(|(a, _)| a)
-                .collect();
-
-        // Get all the agent info for these remote nodes.
-        for info in store::all_agent_info(&self.evt_sender, &self.space)
-            .await?
-            .into_iter()
-            .filter(|a| {
-                std::time::Duration::from_millis(a.expires_at_ms)
-                    > std::time::UNIX_EPOCH
-                        .elapsed()
-                        .expect("Your system clock is set before UNIX epoch")
-            })
-            .filter(|a| remote_agents_within_arc_set.contains(&a.agent))
-            .filter(|a| !a.storage_arc.is_empty())
-        {
-            // Get an address if there is one.
-            let info = info
-                .url_list
-                .iter()
-                .filter_map(|url| {
-                    kitsune_p2p_proxy::ProxyUrl::from_full(url.as_str())
-                        .map_err(|e| tracing::error!("Failed to parse url {:?}", e))
-                        .ok()
-                        .map(|purl| (info.clone(), purl.digest().0, url.to_string()))
-                })
-                .next();
-
-            // If we found a remote address add this agent to the node
-            // or create the node if it doesn't exist.
-            if let Some((info, cert, url)) = info {
-                match remote_nodes.get_mut::<Arc<[u8; 32]>>(&cert) {
-                    // Add the agent to the node.
-                    Some(node) => node.agent_info_list.push(info),
-                    None => {
-                        // This is a new node.
-                        remote_nodes.insert(
-                            cert.clone(),
-                            Node {
-                                agent_info_list: vec![info],
-                                cert,
-                                url: url.into(),
-                            },
-                        );
-                    }
-                }
-            }
-        }
-
-        let remote_nodes = remote_nodes.into_values().collect();
+        let remote_nodes =
+            remote_nodes_within_arcset(&self.evt_sender, &self.space, arc_set, local_agents)
+                .await?;
         let tuning_params = self.tuning_params.clone();
         // We could clone the metrics store out of the lock here but I don't think
         // the next_remote_node will be that slow so we can just choose the next node inline.
@@ -87,8 +32,75 @@ impl ShardedGossipLocal {
     }
 }
 
+/// Get all the remote nodes with agents within an arc set.
+pub(crate) async fn remote_nodes_within_arcset(
+    evt_sender: &EventSender,
+    space: &Arc<KitsuneSpace>,
+    arc_set: Arc<DhtArcSet>,
+    local_agents: &HashSet<Arc<KitsuneAgent>>,
+) -> KitsuneResult<Vec<Node>> {
+    let mut remote_nodes: HashMap<Arc<[u8; 32]>, Node> = HashMap::new();
+
+    // Get all the remote nodes in this arc set.
+    let remote_agents_within_arc_set: HashSet<_> =
+        store::agents_within_arcset(evt_sender, space, arc_set.clone())
+            .await?
+            .into_iter()
+            .filter(|(a, _)| !local_agents.contains(a))
+            .map(|(a, _)| a)
+            .collect();
+
+    // Get all the agent info for these remote nodes.
+    for info in store::all_agent_info(evt_sender, space)
+        .await?
+        .into_iter()
+        .filter(|a| {
+            std::time::Duration::from_millis(a.expires_at_ms)
+                > std::time::UNIX_EPOCH
+                    .elapsed()
+                    .expect("Your system clock is set before UNIX epoch")
+        })
+        .filter(|a| remote_agents_within_arc_set.contains(&a.agent))
+        .filter(|a| !a.storage_arc.is_empty())
+    {
+        // Get an address if there is one.
+        let info = info
+            .url_list
+            .iter()
+            .filter_map(|url| {
+                kitsune_p2p_proxy::ProxyUrl::from_full(url.as_str())
+                    .map_err(|e| tracing::error!("Failed to parse url {:?}", e))
+                    .ok()
+                    .map(|purl| (info.clone(), purl.digest().0, url.to_string()))
+            })
+            .next();
+
+        // If we found a remote address add this agent to the node
+        // or create the node if it doesn't exist.
+        if let Some((info, cert, url)) = info {
+            match remote_nodes.get_mut::<Arc<[u8; 32]>>(&cert) {
+                // Add the agent to the node.
+                Some(node) => node.agent_info_list.push(info),
+                None => {
+                    // This is a new node.
+                    remote_nodes.insert(
+                        cert.clone(),
+                        Node {
+                            agent_info_list: vec![info],
+                            cert,
+                            url: url.into(),
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(remote_nodes.into_values().collect())
+}
+
 /// Find the next remote node to sync with.
-fn next_remote_node(
+pub(crate) fn next_remote_node(
     mut remote_nodes: Vec<Node>,
     metrics: &MetricsSync,
     tuning_params: KitsuneP2pTuningParams,
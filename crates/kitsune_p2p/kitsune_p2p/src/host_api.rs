@@ -1,17 +1,17 @@
-use kitsune_p2p_fetch::OpHashSized;
+use kitsune_p2p_fetch::{FetchPoolPending, OpHashSized};
 use kitsune_p2p_timestamp::Timestamp;
 use must_future::MustBoxFuture;
 use std::sync::Arc;
 
 use kitsune_p2p_types::{
-    bin_types::KitsuneSpace,
+    bin_types::{KitsuneAgent, KitsuneSpace},
     dependencies::lair_keystore_api,
     dht::{
         region::{Region, RegionCoords},
         region_set::RegionSetLtcs,
         spacetime::Topology,
     },
-    dht_arc::DhtArcSet,
+    dht_arc::{ArcPin, DhtArcSet},
     KOpData, KOpHash,
 };
 
@@ -104,6 +104,43 @@ pub trait KitsuneHost: 'static + Send + Sync {
         .into()
     }
 
+    /// Persist the items pending in the fetch pool for a space, replacing
+    /// any which were persisted before.
+    /// (by default nothing is persisted.)
+    fn put_fetch_pool_pending(
+        &self,
+        space: Arc<KitsuneSpace>,
+        pending: Vec<FetchPoolPending>,
+    ) -> KitsuneHostResult<()> {
+        let _space = space;
+        let _pending = pending;
+        futures::FutureExt::boxed(async move { Ok(()) }).into()
+    }
+
+    /// Get the items which were last persisted as pending in the fetch pool
+    /// for a space, to restore them when the space is joined.
+    fn get_fetch_pool_pending(
+        &self,
+        space: Arc<KitsuneSpace>,
+    ) -> KitsuneHostResult<Vec<FetchPoolPending>> {
+        let _space = space;
+        futures::FutureExt::boxed(async move { Ok(Vec::new()) }).into()
+    }
+
+    /// Get the pin holding a local agent's arc at a fixed size, if any.
+    /// This is checked every time the agent info is signed, and pinned arcs
+    /// are never resized dynamically.
+    /// (by default no arcs are pinned.)
+    fn get_arc_pin(
+        &self,
+        space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+    ) -> KitsuneHostResult<Option<ArcPin>> {
+        let _space = space;
+        let _agent = agent;
+        futures::FutureExt::boxed(async move { Ok(None) }).into()
+    }
+
     /// Get the lair "tag" identifying the id seed to use for crypto signing.
     /// (this is currently only used in tx5/WebRTC if that feature is enabled.)
     fn lair_tag(&self) -> Option<Arc<str>> {
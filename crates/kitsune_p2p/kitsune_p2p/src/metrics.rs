@@ -12,8 +12,10 @@ use crate::gossip::sharded_gossip::RegionDiffs;
 use crate::gossip::sharded_gossip::RoundState;
 use crate::types::event::*;
 use crate::types::*;
+use holochain_trace::export::prometheus::{register_int_counter_vec, IntCounterVec};
 use kitsune_p2p_timestamp::Timestamp;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use once_cell::sync::Lazy;
 
 use num_traits::*;
 
@@ -21,6 +23,17 @@ use num_traits::*;
 /// (currently set to 1 week)
 const HISTORICAL_RECORD_EXPIRE_DURATION_MICROS: i64 = 1000 * 1000 * 60 * 60 * 24 * 7;
 
+/// The number of gossip rounds completed, by gossip type and whether the
+/// round ended in an error, which includes timing out.
+static GOSSIP_ROUNDS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "kitsune_gossip_rounds_total",
+        "The number of gossip rounds completed",
+        &["gossip_type", "outcome"]
+    )
+    .expect("Metric names are unique")
+});
+
 /// Running average that prioritizes memory and cpu efficiency
 /// over strict accuracy.
 /// For metrics where we can't afford the memory of tracking samples
@@ -568,11 +581,37 @@ impl Metrics {
         }
     }
 
+    /// Create node-level info about a current round, for gossip modules which
+    /// don't track a [`RoundState`].
+    pub fn start_current_round(
+        &mut self,
+        peer: &NodeId,
+        gossip_type: GossipModuleType,
+        remote_agent_list: &[AgentInfoSigned],
+    ) {
+        let history = self.node_history.entry(peer.clone()).or_default();
+        history.remote_agents = remote_agent_list.iter().map(|a| a.agent()).collect();
+        history.current_round = Some(CurrentRound::new(
+            nanoid::nanoid!(),
+            gossip_type,
+            Instant::now(),
+        ));
+    }
+
     /// Remove the current round info once it's complete, and put it into the history list
     pub fn complete_current_round(&mut self, node: &NodeId, error: bool) {
         let history = self.node_history.entry(node.clone()).or_default();
         let r = history.current_round.take();
         if let Some(r) = r {
+            let gossip_type = match r.gossip_type {
+                GossipModuleType::ShardedRecent => "recent",
+                GossipModuleType::ShardedHistorical => "historical",
+                GossipModuleType::FullSync => "full_sync",
+            };
+            let outcome = if error { "error" } else { "success" };
+            GOSSIP_ROUNDS
+                .with_label_values(&[gossip_type, outcome])
+                .inc();
             history.completed_rounds.push_back(r.completed(error))
         }
     }
@@ -4,6 +4,7 @@ use crate::HostApi;
 
 mod actor;
 pub(crate) use actor::meta_net;
+pub(crate) use actor::net_bandwidth;
 use actor::*;
 
 #[cfg(any(test, feature = "test_utils"))]
@@ -13,7 +13,6 @@ use crate::*;
 use futures::future::FutureExt;
 use futures::stream::StreamExt;
 use kitsune_p2p_fetch::*;
-use kitsune_p2p_timestamp::Timestamp;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use kitsune_p2p_types::async_lazy::AsyncLazy;
 use kitsune_p2p_types::tx2::tx2_api::*;
@@ -28,6 +27,9 @@ mod bootstrap;
 mod discover;
 pub(crate) mod meta_net;
 use meta_net::*;
+pub(crate) mod net_bandwidth;
+use net_bandwidth::NetBandwidth;
+mod peer_queue;
 mod space;
 use ghost_actor::dependencies::tracing;
 use space::*;
@@ -132,6 +134,8 @@ impl KitsuneP2pActor {
         evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
         host: HostApi,
     ) -> KitsuneP2pResult<Self> {
+        config.validate_gossip_strategies()?;
+
         crate::types::metrics::init();
 
         let metrics = Tx2ApiMetrics::default().set_write_len(|d, l| {
@@ -151,6 +155,8 @@ impl KitsuneP2pActor {
             KitsuneMetrics::count(t, l);
         });
 
+        let bandwidth = Arc::new(NetBandwidth::new(&config.tuning_params));
+
         let mut ep_hnd = None;
         let mut ep_evt = None;
         let mut bootstrap_net = None;
@@ -158,8 +164,14 @@ impl KitsuneP2pActor {
         #[cfg(feature = "tx2")]
         if ep_hnd.is_none() && config.is_tx2() {
             tracing::trace!("tx2");
-            let (h, e) =
-                MetaNet::new_tx2(host.clone(), config.clone(), tls_config, metrics).await?;
+            let (h, e) = MetaNet::new_tx2(
+                host.clone(),
+                config.clone(),
+                tls_config,
+                metrics,
+                bandwidth.clone(),
+            )
+            .await?;
             ep_hnd = Some(h);
             ep_evt = Some(e);
             bootstrap_net = Some(BootstrapNet::Tx2);
@@ -178,6 +190,7 @@ impl KitsuneP2pActor {
                 internal_sender.clone(),
                 evt_sender.clone(),
                 signal_url,
+                bandwidth.clone(),
             )
             .await?;
             ep_hnd = Some(h);
@@ -276,6 +289,25 @@ impl KitsuneP2pActor {
             async move {
                 let fetch_response_queue = &fetch_response_queue;
                 let fetch_pool = &fetch_pool;
+                // When there are bandwidth limits to wait for, incoming
+                // messages are authorized and throttled in a queue per peer
+                // before they get here. Otherwise that's done as they're
+                // handled.
+                let queued = bandwidth.is_limited();
+                let ep_evt = if queued {
+                    let host = host.clone();
+                    peer_queue::spawn_peer_queues(
+                        ep_evt,
+                        Arc::new(move |evt: MetaNetEvt| {
+                            peer_queue::admit(host.clone(), evt).boxed()
+                        }),
+                        bandwidth,
+                        tuning_params.network_peer_queue_len as usize,
+                        tuning_params.concurrent_limit_per_thread,
+                    )
+                } else {
+                    ep_evt
+                };
                 ep_evt
                     .for_each_concurrent(tuning_params.concurrent_limit_per_thread, move |event| {
                         let evt_sender = evt_sender.clone();
@@ -285,6 +317,15 @@ impl KitsuneP2pActor {
                         async move {
                             let evt_sender = &evt_sender;
 
+                            let event = if queued {
+                                event
+                            } else {
+                                match peer_queue::admit_unqueued(host.clone(), event).await {
+                                    Some(event) => event,
+                                    None => return,
+                                }
+                            };
+
                             match event {
                                 MetaNetEvt::Connected { remote_url, con } => {
                                     let _ = i_s.new_con(remote_url, con.clone()).await;
@@ -292,421 +333,379 @@ impl KitsuneP2pActor {
                                 MetaNetEvt::Disconnected { remote_url, con: _ } => {
                                     let _ = i_s.del_con(remote_url).await;
                                 }
-                                MetaNetEvt::Request {
-                                    remote_url: _,
-                                    con,
-                                    data,
-                                    respond,
-                                } => {
-                                    match nodespace_is_authorized(
-                                        &host,
-                                        con.peer_id(),
-                                        data.maybe_space(),
-                                        Timestamp::now(),
-                                    )
-                                    .await
-                                    {
-                                        MetaNetEvtAuth::UnauthorizedIgnore => {}
-                                        MetaNetEvtAuth::UnauthorizedDisconnect => {
-                                            con.close(
-                                                UNAUTHORIZED_DISCONNECT_CODE,
-                                                UNAUTHORIZED_DISCONNECT_REASON,
-                                            )
-                                            .await;
-                                        }
-                                        MetaNetEvtAuth::Authorized => {
-                                            match data {
-                                                wire::Wire::Call(wire::Call {
-                                                    space,
-                                                    to_agent,
-                                                    data,
-                                                    ..
-                                                }) => {
-                                                    let res = match evt_sender
-                                                        .call(space, to_agent, data.into())
-                                                        .await
-                                                    {
-                                                        Err(err) => {
-                                                            let reason = format!("{:?}", err);
-                                                            let fail = wire::Wire::failure(reason);
-                                                            respond(fail).await;
-                                                            return;
-                                                        }
-                                                        Ok(r) => r,
-                                                    };
-                                                    let resp = wire::Wire::call_resp(res.into());
-                                                    respond(resp).await;
-                                                }
-                                                wire::Wire::PeerGet(wire::PeerGet {
-                                                    space,
-                                                    agent,
-                                                }) => {
-                                                    let resp = match host
-                                                        .get_agent_info_signed(
-                                                            GetAgentInfoSignedEvt { space, agent },
-                                                        )
-                                                        .await
-                                                    {
-                                                        Ok(info) => wire::Wire::peer_get_resp(info),
-                                                        Err(err) => wire::Wire::failure(format!(
-                                                            "Error getting agent: {:?}",
-                                                            err,
-                                                        )),
-                                                    };
-                                                    respond(resp).await;
+                                MetaNetEvt::Request { data, respond, .. } => {
+                                    match data {
+                                        wire::Wire::Call(wire::Call {
+                                            space,
+                                            to_agent,
+                                            data,
+                                            ..
+                                        }) => {
+                                            let res = match evt_sender
+                                                .call(space, to_agent, data.into())
+                                                .await
+                                            {
+                                                Err(err) => {
+                                                    let reason = format!("{:?}", err);
+                                                    let fail = wire::Wire::failure(reason);
+                                                    respond(fail).await;
+                                                    return;
                                                 }
-                                                wire::Wire::PeerQuery(wire::PeerQuery {
-                                                    space,
-                                                    basis_loc,
-                                                }) => {
-                                                    // this *does* go over the network...
-                                                    // so we don't want it to be too many
-                                                    const LIMIT: u32 = 8;
-                                                    let query = QueryAgentsEvt::new(space)
-                                                        .near_basis(basis_loc)
-                                                        .limit(LIMIT);
-                                                    let resp = match evt_sender
-                                                        .query_agents(query)
-                                                        .await
-                                                    {
-                                                        Ok(list) => {
-                                                            wire::Wire::peer_query_resp(list)
-                                                        }
-                                                        Err(err) => wire::Wire::failure(format!(
-                                                            "Error querying agents: {:?}",
-                                                            err,
-                                                        )),
-                                                    };
-                                                    respond(resp).await;
+                                                Ok(r) => r,
+                                            };
+                                            let resp = wire::Wire::call_resp(res.into());
+                                            respond(resp).await;
+                                        }
+                                        wire::Wire::PeerGet(wire::PeerGet {
+                                            space,
+                                            agent,
+                                        }) => {
+                                            let resp = match host
+                                                .get_agent_info_signed(
+                                                    GetAgentInfoSignedEvt { space, agent },
+                                                )
+                                                .await
+                                            {
+                                                Ok(info) => wire::Wire::peer_get_resp(info),
+                                                Err(err) => wire::Wire::failure(format!(
+                                                    "Error getting agent: {:?}",
+                                                    err,
+                                                )),
+                                            };
+                                            respond(resp).await;
+                                        }
+                                        wire::Wire::PeerQuery(wire::PeerQuery {
+                                            space,
+                                            basis_loc,
+                                        }) => {
+                                            // this *does* go over the network...
+                                            // so we don't want it to be too many
+                                            const LIMIT: u32 = 8;
+                                            let query = QueryAgentsEvt::new(space)
+                                                .near_basis(basis_loc)
+                                                .limit(LIMIT);
+                                            let resp = match evt_sender
+                                                .query_agents(query)
+                                                .await
+                                            {
+                                                Ok(list) => {
+                                                    wire::Wire::peer_query_resp(list)
                                                 }
-                                                data => unimplemented!("{:?}", data),
-                                            }
+                                                Err(err) => wire::Wire::failure(format!(
+                                                    "Error querying agents: {:?}",
+                                                    err,
+                                                )),
+                                            };
+                                            respond(resp).await;
                                         }
+                                        data => unimplemented!("{:?}", data),
                                     }
                                 }
                                 MetaNetEvt::Notify {
                                     remote_url: url,
                                     con,
                                     data,
+                                    ..
                                 } => {
-                                    match nodespace_is_authorized(
-                                        &host,
-                                        con.peer_id(),
-                                        data.maybe_space(),
-                                        Timestamp::now(),
-                                    )
-                                    .await
-                                    {
-                                        MetaNetEvtAuth::UnauthorizedIgnore => {}
-                                        MetaNetEvtAuth::UnauthorizedDisconnect => {
-                                            con.close(
-                                                UNAUTHORIZED_DISCONNECT_CODE,
-                                                UNAUTHORIZED_DISCONNECT_REASON,
-                                            )
-                                            .await;
-                                        }
-                                        MetaNetEvtAuth::Authorized => {
-                                            match data {
-                                                wire::Wire::DelegateBroadcast(
-                                                    wire::DelegateBroadcast {
+                                    match data {
+                                        wire::Wire::DelegateBroadcast(
+                                            wire::DelegateBroadcast {
+                                                space,
+                                                basis,
+                                                to_agent,
+                                                mod_idx,
+                                                mod_cnt,
+                                                data,
+                                            },
+                                        ) => match data {
+                                            BroadcastData::Publish {
+                                                source,
+                                                op_hash_list,
+                                                context,
+                                            } => {
+                                                if let Err(err) = i_s
+                                                    .incoming_publish(
                                                         space,
-                                                        basis,
                                                         to_agent,
-                                                        mod_idx,
-                                                        mod_cnt,
-                                                        data,
-                                                    },
-                                                ) => match data {
-                                                    BroadcastData::Publish {
                                                         source,
                                                         op_hash_list,
                                                         context,
-                                                    } => {
-                                                        if let Err(err) = i_s
-                                                            .incoming_publish(
-                                                                space,
-                                                                to_agent,
-                                                                source,
-                                                                op_hash_list,
-                                                                context,
-                                                                Some((basis, mod_idx, mod_cnt)),
-                                                            )
-                                                            .await
-                                                        {
-                                                            tracing::warn!(
-                                                    ?err,
-                                                    "failed to handle incoming delegate broadcast"
-                                                );
-                                                        }
-                                                    }
-                                                    data => {
-                                                        // one might be tempted to notify here
-                                                        // as in Broadcast below... but we
-                                                        // notify all relevent agents inside
-                                                        // the space incoming_delegate_broadcast
-                                                        // handler.
-                                                        if let Err(err) = i_s
-                                                            .incoming_delegate_broadcast(
-                                                                space, basis, to_agent, mod_idx,
-                                                                mod_cnt, data,
-                                                            )
-                                                            .await
-                                                        {
-                                                            tracing::warn!(
-                                                    ?err,
-                                                    "failed to handle incoming delegate broadcast"
-                                                );
-                                                        }
-                                                    }
-                                                },
-                                                wire::Wire::Broadcast(wire::Broadcast {
-                                                    space,
-                                                    to_agent,
-                                                    data,
-                                                    ..
-                                                }) => match data {
-                                                    BroadcastData::User(data) => {
-                                                        // TODO: Should we check if the basis is
-                                                        // held before calling notify?
-                                                        if let Err(err) = evt_sender
-                                                            .notify(space, to_agent, data)
-                                                            .await
-                                                        {
-                                                            tracing::warn!(
-                                                        ?err,
-                                                        "error processing incoming broadcast"
-                                                    );
-                                                        }
-                                                    }
-                                                    BroadcastData::AgentInfo(agent_info) => {
-                                                        // TODO: Should we check if the basis is
-                                                        // held before calling put_agent_info_signed?
-                                                        if let Err(err) = evt_sender
-                                                            .put_agent_info_signed(
-                                                                PutAgentInfoSignedEvt {
-                                                                    space,
-                                                                    peer_data: vec![agent_info],
-                                                                },
-                                                            )
-                                                            .await
-                                                        {
-                                                            tracing::warn!(
-                                                    ?err,
-                                                    "error processing incoming agent info broadcast"
-                                                );
-                                                        }
-                                                    }
-                                                    BroadcastData::Publish {
+                                                        Some((basis, mod_idx, mod_cnt)),
+                                                    )
+                                                    .await
+                                                {
+                                                    tracing::warn!(
+                                            ?err,
+                                            "failed to handle incoming delegate broadcast"
+                                        );
+                                                }
+                                            }
+                                            data => {
+                                                // one might be tempted to notify here
+                                                // as in Broadcast below... but we
+                                                // notify all relevent agents inside
+                                                // the space incoming_delegate_broadcast
+                                                // handler.
+                                                if let Err(err) = i_s
+                                                    .incoming_delegate_broadcast(
+                                                        space, basis, to_agent, mod_idx,
+                                                        mod_cnt, data,
+                                                    )
+                                                    .await
+                                                {
+                                                    tracing::warn!(
+                                            ?err,
+                                            "failed to handle incoming delegate broadcast"
+                                        );
+                                                }
+                                            }
+                                        },
+                                        wire::Wire::Broadcast(wire::Broadcast {
+                                            space,
+                                            to_agent,
+                                            data,
+                                            ..
+                                        }) => match data {
+                                            BroadcastData::User(data) => {
+                                                // TODO: Should we check if the basis is
+                                                // held before calling notify?
+                                                if let Err(err) = evt_sender
+                                                    .notify(space, to_agent, data)
+                                                    .await
+                                                {
+                                                    tracing::warn!(
+                                                ?err,
+                                                "error processing incoming broadcast"
+                                            );
+                                                }
+                                            }
+                                            BroadcastData::AgentInfo(agent_info) => {
+                                                // TODO: Should we check if the basis is
+                                                // held before calling put_agent_info_signed?
+                                                if let Err(err) = evt_sender
+                                                    .put_agent_info_signed(
+                                                        PutAgentInfoSignedEvt {
+                                                            space,
+                                                            peer_data: vec![agent_info],
+                                                        },
+                                                    )
+                                                    .await
+                                                {
+                                                    tracing::warn!(
+                                            ?err,
+                                            "error processing incoming agent info broadcast"
+                                        );
+                                                }
+                                            }
+                                            BroadcastData::Publish {
+                                                source,
+                                                op_hash_list,
+                                                context,
+                                            } => {
+                                                if let Err(err) = i_s
+                                                    .incoming_publish(
+                                                        space,
+                                                        to_agent,
                                                         source,
                                                         op_hash_list,
                                                         context,
-                                                    } => {
-                                                        if let Err(err) = i_s
-                                                            .incoming_publish(
-                                                                space,
-                                                                to_agent,
-                                                                source,
-                                                                op_hash_list,
-                                                                context,
-                                                                None,
-                                                            )
-                                                            .await
-                                                        {
-                                                            tracing::warn!(
-                                                        ?err,
-                                                        "failed to handle incoming broadcast"
-                                                    );
-                                                        }
-                                                    }
-                                                },
-                                                wire::Wire::Gossip(wire::Gossip {
-                                                    space,
-                                                    data,
-                                                    module,
-                                                }) => {
-                                                    let data: Vec<u8> = data.into();
-                                                    let data: Box<[u8]> = data.into_boxed_slice();
-                                                    if let Err(e) = i_s
-                                                        .incoming_gossip(
-                                                            space, con, url, data, module,
-                                                        )
-                                                        .await
-                                                    {
-                                                        tracing::warn!(
-                                                    "failed to handle incoming gossip: {:?}",
-                                                    e
-                                                );
-                                                    }
+                                                        None,
+                                                    )
+                                                    .await
+                                                {
+                                                    tracing::warn!(
+                                                ?err,
+                                                "failed to handle incoming broadcast"
+                                            );
                                                 }
-                                                wire::Wire::FetchOp(wire::FetchOp {
-                                                    fetch_list,
-                                                }) => {
-                                                    for (space, key_list) in fetch_list {
-                                                        let mut hashes = Vec::new();
-                                                        let topo = match host
-                                                            .get_topology(space.clone())
-                                                            .await
-                                                        {
-                                                            Err(_) => continue,
-                                                            Ok(topo) => topo,
-                                                        };
-                                                        let mut regions = Vec::new();
-
-                                                        for key in key_list {
-                                                            match key {
-                                                                FetchKey::Region(region_coords) => {
-                                                                    regions.push((
-                                                                        region_coords,
-                                                                        region_coords
-                                                                            .to_bounds(&topo),
-                                                                    ));
-                                                                }
-                                                                FetchKey::Op(op_hash) => {
-                                                                    hashes.push(op_hash);
-                                                                }
-                                                            }
+                                            }
+                                        },
+                                        wire::Wire::Gossip(wire::Gossip {
+                                            space,
+                                            data,
+                                            module,
+                                        }) => {
+                                            let data: Vec<u8> = data.into();
+                                            let data: Box<[u8]> = data.into_boxed_slice();
+                                            if let Err(e) = i_s
+                                                .incoming_gossip(
+                                                    space, con, url, data, module,
+                                                )
+                                                .await
+                                            {
+                                                tracing::warn!(
+                                            "failed to handle incoming gossip: {:?}",
+                                            e
+                                        );
+                                            }
+                                        }
+                                        wire::Wire::FetchOp(wire::FetchOp {
+                                            fetch_list,
+                                        }) => {
+                                            for (space, key_list) in fetch_list {
+                                                let mut hashes = Vec::new();
+                                                let topo = match host
+                                                    .get_topology(space.clone())
+                                                    .await
+                                                {
+                                                    Err(_) => continue,
+                                                    Ok(topo) => topo,
+                                                };
+                                                let mut regions = Vec::new();
+
+                                                for key in key_list {
+                                                    match key {
+                                                        FetchKey::Region(region_coords) => {
+                                                            regions.push((
+                                                                region_coords,
+                                                                region_coords
+                                                                    .to_bounds(&topo),
+                                                            ));
                                                         }
-
-                                                        if !hashes.is_empty() {
-                                                            if let Ok(list) = evt_sender
-                                                        .fetch_op_data(FetchOpDataEvt {
-                                                            space: space.clone(),
-                                                            query: FetchOpDataEvtQuery::Hashes {
-                                                                op_hash_list: hashes,
-                                                                include_limbo: true,
-                                                            },
-                                                        })
-                                                        .await
-                                                    {
-                                                        for (_hash, op) in list {
-                                                            fetch_response_queue.enqueue_op(
-                                                                space.clone(),
-                                                                (con.clone(), url.clone(), None),
-                                                                op,
-                                                            );
+                                                        FetchKey::Op(op_hash) => {
+                                                            hashes.push(op_hash);
                                                         }
                                                     }
-                                                        }
+                                                }
 
-                                                        for (coord, bound) in regions {
-                                                            if let Ok(list) = evt_sender
-                                                        .fetch_op_data(FetchOpDataEvt {
-                                                            space: space.clone(),
-                                                            query: FetchOpDataEvtQuery::Regions(
-                                                                vec![bound],
-                                                            ),
-                                                        })
-                                                        .await
-                                                    {
-                                                        let last_idx = list.len() - 1;
-                                                        for (idx, (_hash, op)) in
-                                                            list.into_iter().enumerate()
-                                                        {
-                                                            fetch_response_queue.enqueue_op(
-                                                                space.clone(),
-                                                                (
-                                                                    con.clone(),
-                                                                    url.clone(),
-                                                                    Some((coord, idx == last_idx)),
-                                                                ),
-                                                                op,
-                                                            );
-                                                        }
-                                                    }
-                                                        }
-                                                    }
+                                                if !hashes.is_empty() {
+                                                    if let Ok(list) = evt_sender
+                                                .fetch_op_data(FetchOpDataEvt {
+                                                    space: space.clone(),
+                                                    query: FetchOpDataEvtQuery::Hashes {
+                                                        op_hash_list: hashes,
+                                                        include_limbo: true,
+                                                    },
+                                                })
+                                                .await
+                                            {
+                                                for (_hash, op) in list {
+                                                    fetch_response_queue.enqueue_op(
+                                                        space.clone(),
+                                                        (con.clone(), url.clone(), None),
+                                                        op,
+                                                    );
                                                 }
-                                                wire::Wire::PushOpData(wire::PushOpData {
-                                                    op_data_list,
-                                                }) => {
-                                                    for (space, op_list) in op_data_list {
-                                                        for op in op_list {
-                                                            // hash the op
-                                                            let op_hash = match host
-                                                                .op_hash(op.op_data.clone())
-                                                                .await
-                                                            {
-                                                                Ok(op_hash) => op_hash,
-                                                                Err(_) => continue,
-                                                            };
-
-                                                            // trigger any delegation
-                                                            // that is pending on
-                                                            // having this data
-                                                            let _ = i_s
-                                                                .resolve_publish_pending_delegates(
-                                                                    space.clone(),
-                                                                    op_hash.clone(),
-                                                                )
-                                                                .await;
-
-                                                            // MAYBE: do something with the
-                                                            //        is_last bool?
-                                                            //        Right now we don't
-                                                            //        really care, because
-                                                            //        if it's a region
-                                                            //        we know it's gossip
-                                                            //        so it's okay if
-                                                            //        the context is
-                                                            //        `None`.
-                                                            let key =
-                                                                if let Some((region, _is_last)) =
-                                                                    op.region
-                                                                {
-                                                                    FetchKey::Region(region)
-                                                                } else {
-                                                                    FetchKey::Op(op_hash.clone())
-                                                                };
-                                                            let fetch_context = fetch_pool
-                                                                .remove(&key)
-                                                                .and_then(|i| i.context);
-
-                                                            // forward the received op
-                                                            let _ = evt_sender
-                                                                .receive_ops(
-                                                                    space.clone(),
-                                                                    vec![op.op_data],
-                                                                    fetch_context,
-                                                                )
-                                                                .await;
-                                                        }
-                                                    }
+                                            }
                                                 }
-                                                wire::Wire::MetricExchange(
-                                                    wire::MetricExchange { space, msgs },
-                                                ) => {
+
+                                                for (coord, bound) in regions {
+                                                    if let Ok(list) = evt_sender
+                                                .fetch_op_data(FetchOpDataEvt {
+                                                    space: space.clone(),
+                                                    query: FetchOpDataEvtQuery::Regions(
+                                                        vec![bound],
+                                                    ),
+                                                })
+                                                .await
+                                            {
+                                                let last_idx = list.len() - 1;
+                                                for (idx, (_hash, op)) in
+                                                    list.into_iter().enumerate()
+                                                {
+                                                    fetch_response_queue.enqueue_op(
+                                                        space.clone(),
+                                                        (
+                                                            con.clone(),
+                                                            url.clone(),
+                                                            Some((coord, idx == last_idx)),
+                                                        ),
+                                                        op,
+                                                    );
+                                                }
+                                            }
+                                                }
+                                            }
+                                        }
+                                        wire::Wire::PushOpData(wire::PushOpData {
+                                            op_data_list,
+                                        }) => {
+                                            for (space, op_list) in op_data_list {
+                                                for op in op_list {
+                                                    // hash the op
+                                                    let op_hash = match host
+                                                        .op_hash(op.op_data.clone())
+                                                        .await
+                                                    {
+                                                        Ok(op_hash) => op_hash,
+                                                        Err(_) => continue,
+                                                    };
+
+                                                    // trigger any delegation
+                                                    // that is pending on
+                                                    // having this data
                                                     let _ = i_s
-                                                        .incoming_metric_exchange(space, msgs)
+                                                        .resolve_publish_pending_delegates(
+                                                            space.clone(),
+                                                            op_hash.clone(),
+                                                        )
+                                                        .await;
+
+                                                    // MAYBE: do something with the
+                                                    //        is_last bool?
+                                                    //        Right now we don't
+                                                    //        really care, because
+                                                    //        if it's a region
+                                                    //        we know it's gossip
+                                                    //        so it's okay if
+                                                    //        the context is
+                                                    //        `None`.
+                                                    let key =
+                                                        if let Some((region, _is_last)) =
+                                                            op.region
+                                                        {
+                                                            FetchKey::Region(region)
+                                                        } else {
+                                                            FetchKey::Op(op_hash.clone())
+                                                        };
+                                                    let fetch_context = fetch_pool
+                                                        .remove(&key)
+                                                        .and_then(|i| i.context);
+
+                                                    // forward the received op
+                                                    let _ = evt_sender
+                                                        .receive_ops(
+                                                            space.clone(),
+                                                            vec![op.op_data],
+                                                            fetch_context,
+                                                        )
                                                         .await;
                                                 }
-                                                wire::Wire::PeerUnsolicited(
-                                                    wire::PeerUnsolicited { peer_list },
-                                                ) => {
-                                                    for peer in peer_list {
-                                                        if let Err(err) = evt_sender
-                                                        .put_agent_info_signed(
-                                                            PutAgentInfoSignedEvt {
-                                                                space: peer.space.clone(),
-                                                                peer_data: vec![peer.clone()],
-                                                            },
-                                                        ).await {
-                                                            tracing::warn!(?err, "error processing incoming agent info unsolicited");
-                                                        }
-                                                    }
-                                                }
-                                                wire::Wire::Failure(_)
-                                                | wire::Wire::Call(_)
-                                                | wire::Wire::CallResp(_)
-                                                | wire::Wire::PeerGet(_)
-                                                | wire::Wire::PeerGetResp(_)
-                                                | wire::Wire::PeerQuery(_)
-                                                | wire::Wire::PeerQueryResp(_) => {
-                                                    tracing::warn!(
-                                                        "received non-notify data in a notify"
-                                                    );
+                                            }
+                                        }
+                                        wire::Wire::MetricExchange(
+                                            wire::MetricExchange { space, msgs },
+                                        ) => {
+                                            let _ = i_s
+                                                .incoming_metric_exchange(space, msgs)
+                                                .await;
+                                        }
+                                        wire::Wire::PeerUnsolicited(
+                                            wire::PeerUnsolicited { peer_list },
+                                        ) => {
+                                            for peer in peer_list {
+                                                if let Err(err) = evt_sender
+                                                .put_agent_info_signed(
+                                                    PutAgentInfoSignedEvt {
+                                                        space: peer.space.clone(),
+                                                        peer_data: vec![peer.clone()],
+                                                    },
+                                                ).await {
+                                                    tracing::warn!(?err, "error processing incoming agent info unsolicited");
                                                 }
                                             }
                                         }
+                                        wire::Wire::Failure(_)
+                                        | wire::Wire::Call(_)
+                                        | wire::Wire::CallResp(_)
+                                        | wire::Wire::PeerGet(_)
+                                        | wire::Wire::PeerGetResp(_)
+                                        | wire::Wire::PeerQuery(_)
+                                        | wire::Wire::PeerQueryResp(_) => {
+                                            tracing::warn!(
+                                                "received non-notify data in a notify"
+                                            );
+                                        }
                                     }
                                 }
                             }
@@ -1137,6 +1136,23 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         .into())
     }
 
+    fn handle_refresh_agent_info(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return unit_ok_fut(),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            let (space_sender, _) = space_sender.await;
+            space_sender.refresh_agent_info(space, agent).await
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_rpc_single(
         &mut self,
         space: Arc<KitsuneSpace>,
@@ -1285,7 +1301,16 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
 
     fn handle_dump_network_stats(&mut self) -> KitsuneP2pHandlerResult<serde_json::Value> {
         let fut = self.ep_hnd.dump_network_stats();
-        Ok(async move { Ok(fut.await?) }.boxed().into())
+        let bandwidth = self.ep_hnd.bandwidth().dump();
+        Ok(async move {
+            let mut stats = fut.await?;
+            if let Some(map) = stats.as_object_mut() {
+                map.insert("bandwidth".into(), bandwidth);
+            }
+            Ok(stats)
+        }
+        .boxed()
+        .into())
     }
 
     fn handle_get_diagnostics(
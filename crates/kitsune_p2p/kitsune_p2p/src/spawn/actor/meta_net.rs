@@ -27,6 +27,7 @@ use kitsune_p2p_types::tx2::tx2_restart_adapter::*;
 #[cfg(feature = "tx2")]
 use kitsune_p2p_types::tx2::*;
 
+use crate::spawn::actor::net_bandwidth::{self, NetBandwidth};
 use crate::spawn::actor::InternalSender;
 use crate::spawn::KitsuneP2pEvent;
 use crate::spawn::PutAgentInfoSignedEvt;
@@ -106,6 +107,9 @@ pub enum MetaNetEvt {
         /// The request data sent by the remote peer.
         data: wire::Wire,
 
+        /// The number of bytes the request took on the wire.
+        bytes: usize,
+
         /// Respond to this request.
         respond: Respond,
     },
@@ -120,6 +124,9 @@ pub enum MetaNetEvt {
 
         /// The request data sent by the remote peer.
         data: wire::Wire,
+
+        /// The number of bytes the notification took on the wire.
+        bytes: usize,
     },
 }
 
@@ -188,12 +195,14 @@ pub async fn nodespace_is_authorized(
 
 pub type MetaNetEvtRecv = futures::channel::mpsc::Receiver<MetaNetEvt>;
 
-type ResStore = Arc<Mutex<HashMap<u64, tokio::sync::oneshot::Sender<wire::Wire>>>>;
+/// Pending requests awaiting a response, which is passed on along with the
+/// number of bytes it took on the wire.
+type ResStore = Arc<Mutex<HashMap<u64, tokio::sync::oneshot::Sender<(usize, wire::Wire)>>>>;
 
 #[derive(Debug, Clone)]
 pub enum MetaNetCon {
     #[cfg(feature = "tx2")]
-    Tx2(Tx2ConHnd<wire::Wire>),
+    Tx2(Tx2ConHnd<wire::Wire>, Arc<NetBandwidth>),
 
     #[cfg(feature = "tx5")]
     Tx5 {
@@ -201,6 +210,7 @@ pub enum MetaNetCon {
         rem_url: tx5::Tx5Url,
         res: ResStore,
         tun: KitsuneP2pTuningParams,
+        bandwidth: Arc<NetBandwidth>,
     },
 }
 
@@ -208,7 +218,7 @@ impl PartialEq for MetaNetCon {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             #[cfg(feature = "tx2")]
-            (MetaNetCon::Tx2(a), MetaNetCon::Tx2(b)) => a == b,
+            (MetaNetCon::Tx2(a, _), MetaNetCon::Tx2(b, _)) => a == b,
             #[cfg(feature = "tx5")]
             (MetaNetCon::Tx5 { ep: a, .. }, MetaNetCon::Tx5 { ep: b, .. }) => a == b,
             _ => false,
@@ -222,7 +232,7 @@ impl MetaNetCon {
     pub async fn close(&self, code: u32, reason: &str) {
         #[cfg(feature = "tx2")]
         {
-            if let MetaNetCon::Tx2(con) = self {
+            if let MetaNetCon::Tx2(con, _) = self {
                 con.close(code, reason).await;
                 return;
             }
@@ -243,7 +253,7 @@ impl MetaNetCon {
     pub fn is_closed(&self) -> bool {
         #[cfg(feature = "tx2")]
         {
-            if let MetaNetCon::Tx2(con) = self {
+            if let MetaNetCon::Tx2(con, _) = self {
                 return con.is_closed();
             }
         }
@@ -265,18 +275,32 @@ impl MetaNetCon {
         let result = (move || async move {
             #[cfg(feature = "tx2")]
             {
-                if let MetaNetCon::Tx2(con) = self {
+                if let MetaNetCon::Tx2(con, bandwidth) = self {
+                    bandwidth.outgoing(self.peer_id(), payload).await;
                     return con.notify(payload, timeout).await;
                 }
             }
 
             #[cfg(feature = "tx5")]
             {
-                if let MetaNetCon::Tx5 { ep, rem_url, .. } = self {
+                if let MetaNetCon::Tx5 {
+                    ep,
+                    rem_url,
+                    bandwidth,
+                    ..
+                } = self
+                {
                     let wire = payload.encode_vec().map_err(KitsuneError::other)?;
                     let wrap = WireWrap::notify(msg_id, WireData(wire));
 
                     let data = wrap.encode_vec().map_err(KitsuneError::other)?;
+                    bandwidth
+                        .outgoing_bytes(
+                            self.peer_id(),
+                            net_bandwidth::wire_space(payload),
+                            data.len(),
+                        )
+                        .await;
                     ep.send(rem_url.clone(), data.as_slice())
                         .await
                         .map_err(KitsuneError::other)?;
@@ -308,8 +332,17 @@ impl MetaNetCon {
         let result = (move || async move {
             #[cfg(feature = "tx2")]
             {
-                if let MetaNetCon::Tx2(con) = self {
-                    return con.request(payload, timeout).await;
+                if let MetaNetCon::Tx2(con, bandwidth) = self {
+                    bandwidth.outgoing(self.peer_id(), payload).await;
+                    let resp = con.request(payload, timeout).await?;
+                    bandwidth
+                        .incoming_bytes(
+                            self.peer_id(),
+                            net_bandwidth::wire_space(payload),
+                            net_bandwidth::encoded_len(&resp),
+                        )
+                        .await;
+                    return Ok(resp);
                 }
             }
 
@@ -319,6 +352,7 @@ impl MetaNetCon {
                     ep,
                     rem_url,
                     res: res_store,
+                    bandwidth,
                     ..
                 } = self
                 {
@@ -334,11 +368,22 @@ impl MetaNetCon {
                     let wire = payload.encode_vec().map_err(KitsuneError::other)?;
                     let wrap = WireWrap::request(msg_id, WireData(wire));
                     let data = wrap.encode_vec().map_err(KitsuneError::other)?;
+                    bandwidth
+                        .outgoing_bytes(
+                            self.peer_id(),
+                            net_bandwidth::wire_space(payload),
+                            data.len(),
+                        )
+                        .await;
 
                     ep.send(rem_url.clone(), data.as_slice())
                         .await
                         .map_err(KitsuneError::other)?;
-                    return r.await.map_err(|_| KitsuneError::other("timeout"));
+                    let (bytes, resp) = r.await.map_err(|_| KitsuneError::other("timeout"))?;
+                    bandwidth
+                        .incoming_bytes(self.peer_id(), net_bandwidth::wire_space(payload), bytes)
+                        .await;
+                    return Ok(resp);
                 }
             }
 
@@ -353,10 +398,29 @@ impl MetaNetCon {
         result
     }
 
+    /// The bandwidth limits and accounting shared by all connections.
+    pub fn bandwidth(&self) -> &Arc<NetBandwidth> {
+        #[cfg(feature = "tx2")]
+        {
+            if let MetaNetCon::Tx2(_, bandwidth) = self {
+                return bandwidth;
+            }
+        }
+
+        #[cfg(feature = "tx5")]
+        {
+            if let MetaNetCon::Tx5 { bandwidth, .. } = self {
+                return bandwidth;
+            }
+        }
+
+        panic!("invalid features");
+    }
+
     pub fn peer_id(&self) -> Arc<[u8; 32]> {
         #[cfg(feature = "tx2")]
         {
-            if let MetaNetCon::Tx2(con) = self {
+            if let MetaNetCon::Tx2(con, _) = self {
                 return con.peer_cert().into();
             }
         }
@@ -378,7 +442,7 @@ impl MetaNetCon {
 pub enum MetaNet {
     /// Tx2 Abstraction
     #[cfg(feature = "tx2")]
-    Tx2(Tx2EpHnd<wire::Wire>),
+    Tx2(Tx2EpHnd<wire::Wire>, Arc<NetBandwidth>),
 
     /// Tx5 Abstraction
     #[cfg(feature = "tx5")]
@@ -387,6 +451,7 @@ pub enum MetaNet {
         url: tx5::Tx5Url,
         res: ResStore,
         tun: KitsuneP2pTuningParams,
+        bandwidth: Arc<NetBandwidth>,
     },
 }
 
@@ -398,6 +463,7 @@ impl MetaNet {
         config: KitsuneP2pConfig,
         tls_config: kitsune_p2p_types::tls::TlsConfig,
         metrics: Tx2ApiMetrics,
+        bandwidth: Arc<NetBandwidth>,
     ) -> KitsuneP2pResult<(Self, MetaNetEvtRecv)> {
         let tuning_params = config.tuning_params.clone();
         let (mut evt_send, evt_recv) =
@@ -507,6 +573,7 @@ impl MetaNet {
         // capture endpoint handle
         let ep_hnd = ep.handle().clone();
 
+        let bandwidth2 = bandwidth.clone();
         tokio::task::spawn(async move {
             let tuning_params = &tuning_params;
             while let Some(evt) = ep.next().await {
@@ -515,7 +582,7 @@ impl MetaNet {
                         if evt_send
                             .send(MetaNetEvt::Connected {
                                 remote_url: url.to_string(),
-                                con: MetaNetCon::Tx2(con),
+                                con: MetaNetCon::Tx2(con, bandwidth2.clone()),
                             })
                             .await
                             .is_err()
@@ -527,7 +594,7 @@ impl MetaNet {
                         if evt_send
                             .send(MetaNetEvt::Connected {
                                 remote_url: url.to_string(),
-                                con: MetaNetCon::Tx2(con),
+                                con: MetaNetCon::Tx2(con, bandwidth2.clone()),
                             })
                             .await
                             .is_err()
@@ -539,7 +606,7 @@ impl MetaNet {
                         if evt_send
                             .send(MetaNetEvt::Disconnected {
                                 remote_url: url.to_string(),
-                                con: MetaNetCon::Tx2(con),
+                                con: MetaNetCon::Tx2(con, bandwidth2.clone()),
                             })
                             .await
                             .is_err()
@@ -554,13 +621,24 @@ impl MetaNet {
                         respond,
                     }) => {
                         let timeout = tuning_params.implicit_timeout();
+                        let bandwidth = bandwidth2.clone();
+                        let peer: Arc<[u8; 32]> = con.peer_cert().into();
+                        let space = data.maybe_space();
+                        // tx2 doesn't expose the length of the frames it
+                        // receives, so re-encode to find it.
+                        let bytes = net_bandwidth::encoded_len(&data);
                         if evt_send
                             .send(MetaNetEvt::Request {
                                 remote_url: url.to_string(),
-                                con: MetaNetCon::Tx2(con),
+                                con: MetaNetCon::Tx2(con, bandwidth2.clone()),
                                 data,
+                                bytes,
                                 respond: Box::new(move |data| {
                                     let out: RespondFut = Box::pin(async move {
+                                        let bytes = data.encode_vec().map(|v| v.len());
+                                        bandwidth
+                                            .outgoing_bytes(peer, space, bytes.unwrap_or_default())
+                                            .await;
                                         let _ = respond.respond(data, timeout).await;
                                     });
                                     out
@@ -573,11 +651,13 @@ impl MetaNet {
                         }
                     }
                     Tx2EpEvent::IncomingNotify(Tx2EpIncomingNotify { con, url, data, .. }) => {
+                        let bytes = net_bandwidth::encoded_len(&data);
                         if evt_send
                             .send(MetaNetEvt::Notify {
                                 remote_url: url.to_string(),
-                                con: MetaNetCon::Tx2(con),
+                                con: MetaNetCon::Tx2(con, bandwidth2.clone()),
                                 data,
+                                bytes,
                             })
                             .await
                             .is_err()
@@ -590,7 +670,7 @@ impl MetaNet {
             }
         });
 
-        Ok((MetaNet::Tx2(ep_hnd), evt_recv))
+        Ok((MetaNet::Tx2(ep_hnd, bandwidth), evt_recv))
     }
 
     /// Construct abstraction with tx5 backend.
@@ -601,6 +681,7 @@ impl MetaNet {
         kitsune_internal_sender: ghost_actor::GhostSender<crate::spawn::Internal>,
         evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
         signal_url: String,
+        bandwidth: Arc<NetBandwidth>,
     ) -> KitsuneP2pResult<(Self, MetaNetEvtRecv)> {
         let (mut evt_send, evt_recv) =
             futures::channel::mpsc::channel(tuning_params.concurrent_limit_per_thread);
@@ -680,6 +761,7 @@ impl MetaNet {
         let ep_hnd2 = ep_hnd.clone();
         let res_store2 = res_store.clone();
         let tuning_params2 = tuning_params.clone();
+        let bandwidth2 = bandwidth.clone();
         tokio::task::spawn(async move {
             while let Some(evt) = ep_evt.recv().await {
                 let evt = match evt {
@@ -700,6 +782,7 @@ impl MetaNet {
                                     rem_url: rem_cli_url,
                                     res: res_store2.clone(),
                                     tun: tuning_params2.clone(),
+                                    bandwidth: bandwidth2.clone(),
                                 },
                             })
                             .await
@@ -717,6 +800,7 @@ impl MetaNet {
                                     rem_url: rem_cli_url,
                                     res: res_store2.clone(),
                                     tun: tuning_params2.clone(),
+                                    bandwidth: bandwidth2.clone(),
                                 },
                             })
                             .await
@@ -730,7 +814,8 @@ impl MetaNet {
                         data,
                         permit,
                     } => {
-                        tracing::trace!(%rem_cli_url, byte_count=?data.remaining(), "received bytes");
+                        let byte_count = data.remaining();
+                        tracing::trace!(%rem_cli_url, %byte_count, "received bytes");
 
                         match WireWrap::decode(&mut bytes::Buf::reader(data)) {
                             Ok(WireWrap::Notify(Notify { msg_id, data })) => {
@@ -745,8 +830,10 @@ impl MetaNet {
                                                     rem_url: rem_cli_url,
                                                     res: res_store2.clone(),
                                                     tun: tuning_params2.clone(),
+                                                    bandwidth: bandwidth2.clone(),
                                                 },
                                                 data,
+                                                bytes: byte_count,
                                             })
                                             .await
                                             .is_err()
@@ -765,6 +852,8 @@ impl MetaNet {
                                     Ok((_, data)) => {
                                         let ep_hnd = ep_hnd2.clone();
                                         let rem_cli_url2 = rem_cli_url.clone();
+                                        let bandwidth = bandwidth2.clone();
+                                        let space = data.maybe_space();
                                         let respond: Respond = Box::new(move |data| {
                                             let out: RespondFut = Box::pin(async move {
                                                 let wire = match data.encode_vec() {
@@ -777,6 +866,15 @@ impl MetaNet {
                                                     Ok(data) => data,
                                                     Err(_) => return,
                                                 };
+                                                if let Some(id) = rem_cli_url2.id() {
+                                                    bandwidth
+                                                        .outgoing_bytes(
+                                                            Arc::new(id.0),
+                                                            space,
+                                                            data.len(),
+                                                        )
+                                                        .await;
+                                                }
                                                 let _ = ep_hnd
                                                     .send(rem_cli_url2, data.as_slice())
                                                     .await;
@@ -791,8 +889,10 @@ impl MetaNet {
                                                     rem_url: rem_cli_url,
                                                     res: res_store2.clone(),
                                                     tun: tuning_params2.clone(),
+                                                    bandwidth: bandwidth2.clone(),
                                                 },
                                                 data,
+                                                bytes: byte_count,
                                                 respond,
                                             })
                                             .await
@@ -811,7 +911,7 @@ impl MetaNet {
                                 if let Some(s) = res_store2.lock().remove(&msg_id) {
                                     match wire::Wire::decode_ref(&data) {
                                         Ok((_, data)) => {
-                                            let _ = s.send(data);
+                                            let _ = s.send((byte_count, data));
                                         }
                                         Err(err) => {
                                             tracing::error!(?err, "decoding error");
@@ -840,6 +940,7 @@ impl MetaNet {
                 url: cli_url,
                 res: res_store,
                 tun: tuning_params,
+                bandwidth,
             },
             evt_recv,
         ))
@@ -848,7 +949,7 @@ impl MetaNet {
     pub fn local_addr(&self) -> KitsuneResult<String> {
         #[cfg(feature = "tx2")]
         {
-            if let MetaNet::Tx2(ep) = self {
+            if let MetaNet::Tx2(ep, _) = self {
                 return ep.local_addr().map(|s| s.to_string());
             }
         }
@@ -863,10 +964,29 @@ impl MetaNet {
         panic!("invalid features");
     }
 
+    /// The bandwidth limits and accounting shared by all connections.
+    pub fn bandwidth(&self) -> &Arc<NetBandwidth> {
+        #[cfg(feature = "tx2")]
+        {
+            if let MetaNet::Tx2(_, bandwidth) = self {
+                return bandwidth;
+            }
+        }
+
+        #[cfg(feature = "tx5")]
+        {
+            if let MetaNet::Tx5 { bandwidth, .. } = self {
+                return bandwidth;
+            }
+        }
+
+        panic!("invalid features");
+    }
+
     pub fn local_id(&self) -> Arc<[u8; 32]> {
         #[cfg(feature = "tx2")]
         {
-            if let MetaNet::Tx2(ep) = self {
+            if let MetaNet::Tx2(ep, _) = self {
                 return ep.local_cert().into();
             }
         }
@@ -916,7 +1036,7 @@ impl MetaNet {
     pub async fn close(&self, code: u32, reason: &str) {
         #[cfg(feature = "tx2")]
         {
-            if let MetaNet::Tx2(ep) = self {
+            if let MetaNet::Tx2(ep, _) = self {
                 ep.close(code, reason).await;
                 return;
             }
@@ -932,20 +1052,28 @@ impl MetaNet {
     ) -> KitsuneResult<MetaNetCon> {
         #[cfg(feature = "tx2")]
         {
-            if let MetaNet::Tx2(ep) = self {
+            if let MetaNet::Tx2(ep, bandwidth) = self {
                 let con = ep.get_connection(remote_url, timeout).await?;
-                return Ok(MetaNetCon::Tx2(con));
+                return Ok(MetaNetCon::Tx2(con, bandwidth.clone()));
             }
         }
 
         #[cfg(feature = "tx5")]
         {
-            if let MetaNet::Tx5 { ep, res, tun, .. } = self {
+            if let MetaNet::Tx5 {
+                ep,
+                res,
+                tun,
+                bandwidth,
+                ..
+            } = self
+            {
                 return Ok(MetaNetCon::Tx5 {
                     ep: ep.clone(),
                     rem_url: tx5::Tx5Url::new(remote_url).map_err(KitsuneError::other)?,
                     res: res.clone(),
                     tun: tun.clone(),
+                    bandwidth: bandwidth.clone(),
                 });
             }
         }
@@ -960,7 +1088,7 @@ impl MetaNet {
 
         #[cfg(feature = "tx2")]
         {
-            if let MetaNet::Tx2(ep) = self {
+            if let MetaNet::Tx2(ep, _) = self {
                 let mut res = ep.debug();
                 if let Some(map) = res.as_object_mut() {
                     map.insert("backend".into(), "tx2-quic".into());
@@ -0,0 +1,301 @@
+//! Accounting and rate limiting of all the bytes sent to and received from
+//! remote peers, whatever the kind of message: gossip, publish, fetch
+//! responses and remote calls alike.
+
+use crate::gossip::sharded_gossip::BandwidthThrottle;
+use crate::*;
+use kitsune_p2p_types::config::KitsuneP2pTuningParams;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+type KSpace = Arc<KitsuneSpace>;
+type PeerId = Arc<[u8; 32]>;
+
+/// Bytes and messages exchanged with a single peer in a single space.
+#[derive(Debug, Default, Clone, Copy, serde::Serialize)]
+struct Usage {
+    inbound_bytes: u64,
+    inbound_messages: u64,
+    outbound_bytes: u64,
+    outbound_messages: u64,
+}
+
+impl Usage {
+    fn add(&mut self, other: &Usage) {
+        self.inbound_bytes += other.inbound_bytes;
+        self.inbound_messages += other.inbound_messages;
+        self.outbound_bytes += other.outbound_bytes;
+        self.outbound_messages += other.outbound_messages;
+    }
+}
+
+/// Rate limits all traffic of an endpoint to a total budget, and the
+/// traffic with each peer to a per-peer budget, and records how many
+/// bytes were exchanged with each peer in each space.
+///
+/// A message first waits for its peer's budget and only then for the
+/// total budget, so a peer which sends or requests a lot queues behind
+/// its own limit rather than starving the other peers of the total.
+///
+/// The limits and usage of a peer are dropped when it disconnects, and
+/// its usage is folded into the total.
+pub struct NetBandwidth {
+    total: BandwidthThrottle,
+    limited: bool,
+    peer_inbound_mbps: f64,
+    peer_outbound_mbps: f64,
+    burst_ratio: f64,
+    peers: Mutex<HashMap<PeerId, Arc<BandwidthThrottle>>>,
+    usage: Mutex<HashMap<(Option<KSpace>, PeerId), Usage>>,
+    disconnected: Mutex<Usage>,
+}
+
+impl std::fmt::Debug for NetBandwidth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NetBandwidth").finish()
+    }
+}
+
+impl NetBandwidth {
+    /// Create the limits from the `network_*` tuning params.
+    pub fn new(tuning_params: &KitsuneP2pTuningParams) -> Self {
+        Self {
+            total: BandwidthThrottle::new(
+                tuning_params.network_inbound_target_mbps,
+                tuning_params.network_outbound_target_mbps,
+                tuning_params.network_burst_ratio,
+            ),
+            limited: [
+                tuning_params.network_inbound_target_mbps,
+                tuning_params.network_outbound_target_mbps,
+                tuning_params.network_peer_inbound_target_mbps,
+                tuning_params.network_peer_outbound_target_mbps,
+            ]
+            .iter()
+            .any(|mbps| *mbps > 0.0),
+            peer_inbound_mbps: tuning_params.network_peer_inbound_target_mbps,
+            peer_outbound_mbps: tuning_params.network_peer_outbound_target_mbps,
+            burst_ratio: tuning_params.network_burst_ratio,
+            peers: Mutex::new(HashMap::new()),
+            usage: Mutex::new(HashMap::new()),
+            disconnected: Mutex::new(Usage::default()),
+        }
+    }
+
+    /// Whether any of the limits are set.
+    pub fn is_limited(&self) -> bool {
+        self.limited
+    }
+
+    /// Wait until there's enough bandwidth to send this many bytes of a
+    /// message to this peer.
+    pub async fn outgoing_bytes(&self, peer: PeerId, space: Option<KSpace>, bytes: usize) {
+        if self.limited {
+            if let Some(throttle) = self.peer_throttle(&peer) {
+                throttle.outgoing_bytes(bytes).await;
+            }
+            self.total.outgoing_bytes(bytes).await;
+        }
+        let mut usage = self.usage.lock();
+        let usage = usage.entry((space, peer)).or_default();
+        usage.outbound_bytes += bytes as u64;
+        usage.outbound_messages += 1;
+    }
+
+    /// Wait until there's enough bandwidth to receive this many bytes of a
+    /// message from this peer.
+    pub async fn incoming_bytes(&self, peer: PeerId, space: Option<KSpace>, bytes: usize) {
+        if self.limited {
+            if let Some(throttle) = self.peer_throttle(&peer) {
+                throttle.incoming_bytes(bytes).await;
+            }
+            self.total.incoming_bytes(bytes).await;
+        }
+        let mut usage = self.usage.lock();
+        let usage = usage.entry((space, peer)).or_default();
+        usage.inbound_bytes += bytes as u64;
+        usage.inbound_messages += 1;
+    }
+
+    /// Wait until there's enough bandwidth to send this message to this peer,
+    /// for transports which don't expose how many bytes they send.
+    pub async fn outgoing(&self, peer: PeerId, payload: &wire::Wire) {
+        let bytes = encoded_len(payload);
+        self.outgoing_bytes(peer, wire_space(payload), bytes).await;
+    }
+
+    /// Forget the limits and usage of a peer which has disconnected. Its
+    /// usage still counts towards the total.
+    pub fn remove_peer(&self, peer: &PeerId) {
+        self.peers.lock().remove(peer);
+        let mut disconnected = self.disconnected.lock();
+        self.usage.lock().retain(|(_, p), usage| {
+            if p != peer {
+                return true;
+            }
+            disconnected.add(usage);
+            false
+        });
+    }
+
+    /// Dump the bytes exchanged so far, in total and by space and peer.
+    /// Messages which aren't about any one space are listed under "none",
+    /// and peers which have disconnected are only counted in the total.
+    pub fn dump(&self) -> serde_json::Value {
+        let mut total = *self.disconnected.lock();
+        let mut spaces = serde_json::Map::new();
+        for ((space, peer), usage) in self.usage.lock().iter() {
+            total.add(usage);
+            let space = space
+                .as_ref()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| "none".to_string());
+            let peer = base64::encode_config(&peer[..], base64::URL_SAFE_NO_PAD);
+            if let serde_json::Value::Object(peers) = spaces
+                .entry(space)
+                .or_insert_with(|| serde_json::Value::Object(Default::default()))
+            {
+                peers.insert(peer, serde_json::json!(usage));
+            }
+        }
+        serde_json::json!({
+            "total": total,
+            "spaces": spaces,
+        })
+    }
+
+    fn peer_throttle(&self, peer: &PeerId) -> Option<Arc<BandwidthThrottle>> {
+        if self.peer_inbound_mbps <= 0.0 && self.peer_outbound_mbps <= 0.0 {
+            return None;
+        }
+        Some(
+            self.peers
+                .lock()
+                .entry(peer.clone())
+                .or_insert_with(|| {
+                    Arc::new(BandwidthThrottle::new(
+                        self.peer_inbound_mbps,
+                        self.peer_outbound_mbps,
+                        self.burst_ratio,
+                    ))
+                })
+                .clone(),
+        )
+    }
+}
+
+pub(crate) fn encoded_len(data: &wire::Wire) -> usize {
+    data.encode_vec().map(|v| v.len()).unwrap_or_default()
+}
+
+/// The space a message is about. Fetches and their responses can list
+/// several spaces, but in practice only ever carry one.
+pub(crate) fn wire_space(data: &wire::Wire) -> Option<KSpace> {
+    match data {
+        wire::Wire::FetchOp(wire::FetchOp { fetch_list }) => {
+            fetch_list.first().map(|(space, _)| space.clone())
+        }
+        wire::Wire::PushOpData(wire::PushOpData { op_data_list }) => {
+            op_data_list.first().map(|(space, _)| space.clone())
+        }
+        data => data.maybe_space(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn usage_is_recorded_by_space_and_peer() {
+        let bandwidth = NetBandwidth::new(&Default::default());
+        let space = Arc::new(KitsuneSpace(vec![1; 36]));
+        let alice: PeerId = Arc::new([1; 32]);
+        let bob: PeerId = Arc::new([2; 32]);
+
+        bandwidth
+            .outgoing_bytes(alice.clone(), Some(space.clone()), 100)
+            .await;
+        bandwidth
+            .outgoing_bytes(alice.clone(), Some(space.clone()), 50)
+            .await;
+        bandwidth
+            .incoming_bytes(bob.clone(), Some(space.clone()), 10)
+            .await;
+        bandwidth.incoming_bytes(bob.clone(), None, 5).await;
+
+        let dump = bandwidth.dump();
+        assert_eq!(dump["total"]["outbound_bytes"], 150);
+        assert_eq!(dump["total"]["inbound_bytes"], 15);
+        assert_eq!(dump["total"]["inbound_messages"], 2);
+
+        let alice = base64::encode_config(&alice[..], base64::URL_SAFE_NO_PAD);
+        let bob = base64::encode_config(&bob[..], base64::URL_SAFE_NO_PAD);
+        let in_space = &dump["spaces"][space.to_string()];
+        assert_eq!(in_space[&alice]["outbound_messages"], 2);
+        assert_eq!(in_space[&alice]["inbound_bytes"], 0);
+        assert_eq!(in_space[&bob]["inbound_bytes"], 10);
+        assert_eq!(dump["spaces"]["none"][&bob]["inbound_bytes"], 5);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn disconnected_peers_only_count_in_the_total() {
+        let bandwidth = NetBandwidth::new(&Default::default());
+        let space = Arc::new(KitsuneSpace(vec![1; 36]));
+        let alice: PeerId = Arc::new([1; 32]);
+        let bob: PeerId = Arc::new([2; 32]);
+
+        bandwidth
+            .outgoing_bytes(alice.clone(), Some(space.clone()), 100)
+            .await;
+        bandwidth.outgoing_bytes(alice.clone(), None, 20).await;
+        bandwidth
+            .incoming_bytes(bob.clone(), Some(space.clone()), 10)
+            .await;
+        bandwidth.remove_peer(&alice);
+
+        let dump = bandwidth.dump();
+        assert_eq!(dump["total"]["outbound_bytes"], 120);
+        assert_eq!(dump["total"]["outbound_messages"], 2);
+        assert_eq!(dump["total"]["inbound_bytes"], 10);
+
+        let alice = base64::encode_config(&alice[..], base64::URL_SAFE_NO_PAD);
+        let bob = base64::encode_config(&bob[..], base64::URL_SAFE_NO_PAD);
+        let in_space = &dump["spaces"][space.to_string()];
+        assert!(in_space.get(&alice).is_none());
+        assert_eq!(in_space[&bob]["inbound_bytes"], 10);
+        assert!(dump["spaces"].get("none").is_none());
+        assert!(bandwidth.usage.lock().keys().all(|(_, p)| p[0] != 1));
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn each_peer_is_limited_separately() {
+        let mut tuning_params =
+            kitsune_p2p_types::config::tuning_params_struct::KitsuneP2pTuningParams::default();
+        tuning_params.network_peer_outbound_target_mbps = 0.1;
+        tuning_params.network_burst_ratio = 1.0;
+        let bandwidth = NetBandwidth::new(&Arc::new(tuning_params));
+        let alice: PeerId = Arc::new([1; 32]);
+        let bob: PeerId = Arc::new([2; 32]);
+
+        // Use up alice's whole burst of 0.1 megabits.
+        bandwidth.outgoing_bytes(alice.clone(), None, 12_500).await;
+
+        // Sending more to alice has to wait...
+        let to_alice = tokio::time::timeout(
+            std::time::Duration::from_millis(100),
+            bandwidth.outgoing_bytes(alice.clone(), None, 12_500),
+        )
+        .await;
+        assert!(to_alice.is_err());
+
+        // ...but bob is unaffected.
+        let to_bob = tokio::time::timeout(
+            std::time::Duration::from_millis(100),
+            bandwidth.outgoing_bytes(bob.clone(), None, 12_500),
+        )
+        .await;
+        assert!(to_bob.is_ok());
+    }
+}
@@ -0,0 +1,482 @@
+//! Per peer queues for incoming requests and notifications.
+//!
+//! Before it is handled, each incoming message is checked against the
+//! host's blocks and then waits for its peer's share of the inbound
+//! bandwidth. When any `network_*` limit is set, both happen in a queue of
+//! the peer's own, so a throttled peer only delays its own messages and
+//! never holds one of the `concurrent_limit_per_thread` slots that all
+//! peers share for handling them. When a peer's queue is full, its further
+//! messages are dropped.
+//!
+//! A peer's connection events pass through its queue as well, so that its
+//! messages are always handled between the `Connected` and the
+//! `Disconnected` of the connection they arrived on.
+
+use super::meta_net::*;
+use super::net_bandwidth::{self, NetBandwidth};
+use super::{UNAUTHORIZED_DISCONNECT_CODE, UNAUTHORIZED_DISCONNECT_REASON};
+use crate::*;
+use futures::future::BoxFuture;
+use futures::sink::SinkExt;
+use futures::stream::{Stream, StreamExt};
+use kitsune_p2p_timestamp::Timestamp;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc::error::TrySendError;
+
+type PeerId = Arc<[u8; 32]>;
+
+/// What the queues need to know about an incoming event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PeerEvtKind {
+    Connected,
+    Disconnected,
+    Message,
+}
+
+/// An incoming event which can be queued by the peer it came from.
+pub(crate) trait PeerEvt: Send + 'static {
+    /// The peer the event came from.
+    fn peer(&self) -> PeerId;
+
+    /// Whether this is a connection event or a message.
+    fn kind(&self) -> PeerEvtKind;
+
+    /// Give up on a message because its peer's queue is full.
+    fn drop_message(self);
+}
+
+/// Decide whether a queued message should be handled, waiting for as long
+/// as it takes to be let through.
+pub(crate) type Admit<E> = Arc<dyn Fn(E) -> BoxFuture<'static, Option<E>> + Send + Sync>;
+
+/// The queue of each peer, with an id to tell a peer's queues apart when it
+/// reconnects before its old queue has drained.
+struct Queues<E> {
+    next_id: u64,
+    by_peer: HashMap<PeerId, (u64, tokio::sync::mpsc::Sender<E>)>,
+}
+
+type SharedQueues<E> = Arc<parking_lot::Mutex<Queues<E>>>;
+
+/// Pass incoming events through a queue per peer, returning the events
+/// which are ready to be handled.
+pub(crate) fn spawn_peer_queues<E: PeerEvt>(
+    mut evts: impl Stream<Item = E> + Send + Unpin + 'static,
+    admit: Admit<E>,
+    bandwidth: Arc<NetBandwidth>,
+    queue_len: usize,
+    buffer: usize,
+) -> futures::channel::mpsc::Receiver<E> {
+    let (ready, ready_recv) = futures::channel::mpsc::channel(buffer);
+    let queues: SharedQueues<E> = Arc::new(parking_lot::Mutex::new(Queues {
+        next_id: 0,
+        by_peer: HashMap::new(),
+    }));
+    tokio::task::spawn(async move {
+        while let Some(mut evt) = evts.next().await {
+            let peer = evt.peer();
+            loop {
+                let (id, queue) = {
+                    let mut queues_lock = queues.lock();
+                    let Queues { next_id, by_peer } = &mut *queues_lock;
+                    by_peer
+                        .entry(peer.clone())
+                        .or_insert_with(|| {
+                            let id = *next_id;
+                            *next_id += 1;
+                            let queue = spawn_peer_queue(
+                                peer.clone(),
+                                id,
+                                queues.clone(),
+                                admit.clone(),
+                                bandwidth.clone(),
+                                ready.clone(),
+                                queue_len,
+                            );
+                            (id, queue)
+                        })
+                        .clone()
+                };
+
+                let closed = match (evt.kind(), queue.try_send(evt)) {
+                    (_, Ok(())) => None,
+                    (_, Err(TrySendError::Closed(evt))) => Some(evt),
+                    (PeerEvtKind::Message, Err(TrySendError::Full(evt))) => {
+                        evt.drop_message();
+                        None
+                    }
+                    // Connection events are never dropped, so wait for room.
+                    (_, Err(TrySendError::Full(evt))) => queue.send(evt).await.err().map(|e| e.0),
+                };
+
+                // The queue has been retired since we looked it up, so
+                // give the event to a new one.
+                evt = match closed {
+                    None => break,
+                    Some(evt) => evt,
+                };
+                if ready.is_closed() {
+                    return;
+                }
+                let mut queues = queues.lock();
+                if queues.by_peer.get(&peer).map(|(i, _)| *i) == Some(id) {
+                    queues.by_peer.remove(&peer);
+                }
+            }
+        }
+    });
+    ready_recv
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_peer_queue<E: PeerEvt>(
+    peer: PeerId,
+    id: u64,
+    queues: SharedQueues<E>,
+    admit: Admit<E>,
+    bandwidth: Arc<NetBandwidth>,
+    mut ready: futures::channel::mpsc::Sender<E>,
+    queue_len: usize,
+) -> tokio::sync::mpsc::Sender<E> {
+    let (queue, mut queue_recv) = tokio::sync::mpsc::channel(queue_len);
+    tokio::task::spawn(async move {
+        let mut next = None;
+        loop {
+            let evt = match next.take() {
+                Some(evt) => evt,
+                None => match queue_recv.recv().await {
+                    Some(evt) => evt,
+                    None => return,
+                },
+            };
+
+            let kind = evt.kind();
+            if !forward(&admit, &mut ready, evt).await {
+                return;
+            }
+            if kind != PeerEvtKind::Disconnected {
+                continue;
+            }
+
+            // Once everything queued before the disconnect has been handled,
+            // retire the queue, unless the peer has already reconnected
+            // and is using it again.
+            let mut queues_lock = queues.lock();
+            if let Ok(evt) = queue_recv.try_recv() {
+                next = Some(evt);
+                continue;
+            }
+            if queues_lock.by_peer.get(&peer).map(|(i, _)| *i) == Some(id) {
+                queues_lock.by_peer.remove(&peer);
+                bandwidth.remove_peer(&peer);
+            }
+            queue_recv.close();
+            drop(queues_lock);
+
+            // Anything sent just before the queue was retired is still
+            // handled, and counts towards the peer's new connection.
+            while let Ok(evt) = queue_recv.try_recv() {
+                if !forward(&admit, &mut ready, evt).await {
+                    return;
+                }
+            }
+            return;
+        }
+    });
+    queue
+}
+
+/// Pass an event on to be handled, if it's admitted. Returns false once
+/// nothing is being handled anymore.
+async fn forward<E: PeerEvt>(
+    admit: &Admit<E>,
+    ready: &mut futures::channel::mpsc::Sender<E>,
+    evt: E,
+) -> bool {
+    let evt = match evt.kind() {
+        PeerEvtKind::Message => match admit(evt).await {
+            Some(evt) => evt,
+            None => return true,
+        },
+        PeerEvtKind::Connected | PeerEvtKind::Disconnected => evt,
+    };
+    ready.send(evt).await.is_ok()
+}
+
+impl PeerEvt for MetaNetEvt {
+    fn peer(&self) -> PeerId {
+        self.con().peer_id()
+    }
+
+    fn kind(&self) -> PeerEvtKind {
+        match self {
+            MetaNetEvt::Connected { .. } => PeerEvtKind::Connected,
+            MetaNetEvt::Disconnected { .. } => PeerEvtKind::Disconnected,
+            MetaNetEvt::Request { .. } | MetaNetEvt::Notify { .. } => PeerEvtKind::Message,
+        }
+    }
+
+    fn drop_message(self) {
+        tracing::warn!(
+            peer = ?self.con().peer_id(),
+            "dropping a message from a peer with a full queue"
+        );
+        if let MetaNetEvt::Request { respond, .. } = self {
+            tokio::task::spawn(respond(wire::Wire::failure(
+                "too many queued messages".to_string(),
+            )));
+        }
+    }
+}
+
+/// Check an incoming message against the host's blocks, then wait for its
+/// peer's share of the inbound bandwidth and count it. Other events are
+/// passed through as they are.
+pub(crate) async fn admit(host: HostApi, evt: MetaNetEvt) -> Option<MetaNetEvt> {
+    let (data, bytes) = match &evt {
+        MetaNetEvt::Request { data, bytes, .. } | MetaNetEvt::Notify { data, bytes, .. } => {
+            (data, *bytes)
+        }
+        MetaNetEvt::Connected { .. } | MetaNetEvt::Disconnected { .. } => return Some(evt),
+    };
+    let con = evt.con();
+    let peer = con.peer_id();
+
+    match nodespace_is_authorized(&host, peer.clone(), data.maybe_space(), Timestamp::now()).await {
+        MetaNetEvtAuth::UnauthorizedIgnore => return None,
+        MetaNetEvtAuth::UnauthorizedDisconnect => {
+            con.close(UNAUTHORIZED_DISCONNECT_CODE, UNAUTHORIZED_DISCONNECT_REASON)
+                .await;
+            return None;
+        }
+        MetaNetEvtAuth::Authorized => (),
+    }
+
+    con.bandwidth()
+        .incoming_bytes(peer, net_bandwidth::wire_space(data), bytes)
+        .await;
+    Some(evt)
+}
+
+/// Admit an event as it arrives, for when there are no bandwidth limits to
+/// queue for, forgetting the usage of peers which disconnect.
+pub(crate) async fn admit_unqueued(host: HostApi, evt: MetaNetEvt) -> Option<MetaNetEvt> {
+    if let MetaNetEvt::Disconnected { con, .. } = &evt {
+        con.bandwidth().remove_peer(&con.peer_id());
+    }
+    admit(host, evt).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::FutureExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    struct TestEvt {
+        peer: PeerId,
+        kind: PeerEvtKind,
+        n: u32,
+        dropped: Arc<AtomicUsize>,
+    }
+
+    impl PeerEvt for TestEvt {
+        fn peer(&self) -> PeerId {
+            self.peer.clone()
+        }
+
+        fn kind(&self) -> PeerEvtKind {
+            self.kind
+        }
+
+        fn drop_message(self) {
+            self.dropped.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    struct Test {
+        evts: futures::channel::mpsc::Sender<TestEvt>,
+        ready: futures::channel::mpsc::Receiver<TestEvt>,
+        bandwidth: Arc<NetBandwidth>,
+        gates: Arc<parking_lot::Mutex<HashMap<PeerId, Arc<tokio::sync::Semaphore>>>>,
+        dropped: Arc<AtomicUsize>,
+    }
+
+    impl Test {
+        /// Queues which only admit a peer's messages once its gate is
+        /// opened, standing in for its bandwidth limit.
+        fn new(queue_len: usize) -> Self {
+            let (evts, evts_recv) = futures::channel::mpsc::channel(16);
+            let bandwidth = Arc::new(NetBandwidth::new(&Default::default()));
+            let gates: Arc<parking_lot::Mutex<HashMap<PeerId, Arc<tokio::sync::Semaphore>>>> =
+                Default::default();
+            let admit: Admit<TestEvt> = Arc::new({
+                let gates = gates.clone();
+                move |evt: TestEvt| {
+                    let gate = gates.lock().get(&evt.peer).cloned();
+                    async move {
+                        if let Some(gate) = gate {
+                            gate.acquire().await.unwrap().forget();
+                        }
+                        Some(evt)
+                    }
+                    .boxed()
+                }
+            });
+            let ready = spawn_peer_queues(evts_recv, admit, bandwidth.clone(), queue_len, 16);
+            Self {
+                evts,
+                ready,
+                bandwidth,
+                gates,
+                dropped: Default::default(),
+            }
+        }
+
+        fn close_gate(&self, peer: &PeerId) -> Arc<tokio::sync::Semaphore> {
+            let gate = Arc::new(tokio::sync::Semaphore::new(0));
+            self.gates.lock().insert(peer.clone(), gate.clone());
+            gate
+        }
+
+        async fn send(&mut self, peer: &PeerId, kind: PeerEvtKind, n: u32) {
+            let evt = TestEvt {
+                peer: peer.clone(),
+                kind,
+                n,
+                dropped: self.dropped.clone(),
+            };
+            self.evts.send(evt).await.unwrap();
+        }
+
+        async fn next(&mut self) -> Option<(PeerId, PeerEvtKind, u32)> {
+            tokio::time::timeout(Duration::from_millis(100), self.ready.next())
+                .await
+                .ok()
+                .flatten()
+                .map(|evt| (evt.peer, evt.kind, evt.n))
+        }
+
+        fn has_usage(&self, peer: &PeerId) -> bool {
+            let peer = base64::encode_config(&peer[..], base64::URL_SAFE_NO_PAD);
+            self.bandwidth.dump()["spaces"]["none"].get(&peer).is_some()
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn a_throttled_peer_does_not_hold_up_others() {
+        let mut test = Test::new(4);
+        let alice: PeerId = Arc::new([1; 32]);
+        let bob: PeerId = Arc::new([2; 32]);
+        let alice_gate = test.close_gate(&alice);
+
+        test.send(&alice, PeerEvtKind::Message, 0).await;
+        test.send(&bob, PeerEvtKind::Message, 0).await;
+        test.send(&bob, PeerEvtKind::Message, 1).await;
+
+        assert_eq!(
+            test.next().await,
+            Some((bob.clone(), PeerEvtKind::Message, 0))
+        );
+        assert_eq!(test.next().await, Some((bob, PeerEvtKind::Message, 1)));
+        assert_eq!(test.next().await, None);
+
+        alice_gate.add_permits(1);
+        assert_eq!(test.next().await, Some((alice, PeerEvtKind::Message, 0)));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn messages_are_dropped_when_a_queue_is_full() {
+        let mut test = Test::new(2);
+        let alice: PeerId = Arc::new([1; 32]);
+        let alice_gate = test.close_gate(&alice);
+
+        // The first message is taken off the queue to wait at the gate.
+        test.send(&alice, PeerEvtKind::Message, 0).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        for n in 1..4 {
+            test.send(&alice, PeerEvtKind::Message, n).await;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(test.dropped.load(Ordering::SeqCst), 1);
+
+        // Connection events wait for room instead.
+        test.send(&alice, PeerEvtKind::Disconnected, 0).await;
+        alice_gate.add_permits(3);
+        for n in 0..3 {
+            assert_eq!(
+                test.next().await,
+                Some((alice.clone(), PeerEvtKind::Message, n))
+            );
+        }
+        assert_eq!(
+            test.next().await,
+            Some((alice, PeerEvtKind::Disconnected, 0))
+        );
+        assert_eq!(test.dropped.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn a_disconnect_is_handled_after_the_queued_messages() {
+        let mut test = Test::new(4);
+        let alice: PeerId = Arc::new([1; 32]);
+        let alice_gate = test.close_gate(&alice);
+        test.bandwidth.incoming_bytes(alice.clone(), None, 10).await;
+
+        test.send(&alice, PeerEvtKind::Message, 0).await;
+        test.send(&alice, PeerEvtKind::Disconnected, 0).await;
+        assert_eq!(test.next().await, None);
+        assert!(test.has_usage(&alice));
+
+        alice_gate.add_permits(1);
+        assert_eq!(
+            test.next().await,
+            Some((alice.clone(), PeerEvtKind::Message, 0))
+        );
+        assert_eq!(
+            test.next().await,
+            Some((alice.clone(), PeerEvtKind::Disconnected, 0))
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!test.has_usage(&alice));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn a_reconnected_peer_is_not_forgotten_by_its_old_queue() {
+        let mut test = Test::new(4);
+        let alice: PeerId = Arc::new([1; 32]);
+        let alice_gate = test.close_gate(&alice);
+
+        test.send(&alice, PeerEvtKind::Message, 0).await;
+        test.send(&alice, PeerEvtKind::Disconnected, 0).await;
+        test.send(&alice, PeerEvtKind::Connected, 1).await;
+        alice_gate.add_permits(1);
+        assert_eq!(
+            test.next().await,
+            Some((alice.clone(), PeerEvtKind::Message, 0))
+        );
+        assert_eq!(
+            test.next().await,
+            Some((alice.clone(), PeerEvtKind::Disconnected, 0))
+        );
+        assert_eq!(
+            test.next().await,
+            Some((alice.clone(), PeerEvtKind::Connected, 1))
+        );
+
+        // The new connection's usage outlives the old connection's queue.
+        test.bandwidth.incoming_bytes(alice.clone(), None, 10).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(test.has_usage(&alice));
+
+        test.send(&alice, PeerEvtKind::Disconnected, 1).await;
+        assert_eq!(
+            test.next().await,
+            Some((alice.clone(), PeerEvtKind::Disconnected, 1))
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!test.has_usage(&alice));
+    }
+}
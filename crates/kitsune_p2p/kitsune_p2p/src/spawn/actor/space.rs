@@ -17,6 +17,10 @@ use url2::Url2;
 /// (currently once per hour)
 const HISTORICAL_METRIC_RECORD_FREQ_MS: u64 = 1000 * 60 * 60;
 
+/// How often to persist the items pending in the fetch pool
+/// (currently once per minute)
+const FETCH_POOL_PERSIST_FREQ_MS: u64 = 1000 * 60;
+
 mod metric_exchange;
 use metric_exchange::*;
 
@@ -192,6 +196,7 @@ impl SpaceInternalHandler for Space {
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
         let dynamic_arcs = self.config.tuning_params.gossip_dynamic_arcs;
         let internal_sender = self.i_s.clone();
+        let host = self.ro_inner.host_api.clone();
         Ok(async move {
             let urls = vec![TxUrl::from(ep_hnd.local_addr()?)];
             let mut peer_data = Vec::with_capacity(agent_list.len());
@@ -209,6 +214,7 @@ impl SpaceInternalHandler for Space {
                     mdns_handles: &mut mdns_handles,
                     bootstrap_service: &bootstrap_service,
                     dynamic_arcs,
+                    host: &host,
                 };
                 peer_data.push(update_single_agent_info(input).await?);
             }
@@ -238,6 +244,7 @@ impl SpaceInternalHandler for Space {
         let bootstrap_service = self.config.bootstrap_service.clone();
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
         let dynamic_arcs = self.config.tuning_params.gossip_dynamic_arcs;
+        let host = self.ro_inner.host_api.clone();
         let arc = self.get_agent_arc(&agent);
 
         Ok(async move {
@@ -255,6 +262,7 @@ impl SpaceInternalHandler for Space {
                 mdns_handles: &mut mdns_handles,
                 bootstrap_service: &bootstrap_service,
                 dynamic_arcs,
+                host: &host,
             };
             let peer_data = vec![update_single_agent_info(input).await?];
             internal_sender
@@ -627,6 +635,7 @@ struct UpdateAgentInfoInput<'borrow> {
     mdns_handles: &'borrow mut HashMap<Vec<u8>, Arc<AtomicBool>>,
     bootstrap_service: &'borrow Option<Url2>,
     dynamic_arcs: bool,
+    host: &'borrow HostApi,
 }
 
 async fn update_arc_length(
@@ -665,9 +674,16 @@ async fn update_single_agent_info(
         mdns_handles,
         bootstrap_service,
         dynamic_arcs,
+        host,
     } = input;
 
-    if dynamic_arcs {
+    let arc_pin = host
+        .get_arc_pin(space.clone(), agent.clone())
+        .await
+        .map_err(KitsuneP2pError::other)?;
+    if let Some(arc_pin) = arc_pin {
+        arc = arc_pin.to_arc(agent.get_loc());
+    } else if dynamic_arcs {
         update_arc_length(evt_sender, space.clone(), &mut arc).await?;
     }
 
@@ -704,6 +720,15 @@ async fn update_single_agent_info(
 
     tracing::debug!(?agent_info_signed);
 
+    // Store our own agent info directly, since the local broadcast only
+    // reaches it if a local arc covers the agent, which an empty one won't.
+    evt_sender
+        .put_agent_info_signed(PutAgentInfoSignedEvt {
+            space: space.clone(),
+            peer_data: vec![agent_info_signed.clone()],
+        })
+        .await?;
+
     // Push to the network as well
     match network_type {
         NetworkType::QuicMdns => {
@@ -749,6 +774,19 @@ impl ghost_actor::GhostControlHandler for Space {
             let _ = &self;
             self.ro_inner.metric_exchange.write().shutdown();
 
+            // Save the fetch pool one last time, so that fetches added since
+            // the last periodic save are not lost when the space stops.
+            let space = self.ro_inner.space.clone();
+            let pending = self.ro_inner.fetch_pool.pending(&space);
+            if let Err(err) = self
+                .ro_inner
+                .host_api
+                .put_fetch_pool_pending(space, pending)
+                .await
+            {
+                tracing::error!(?err, "failed to persist the fetch pool");
+            }
+
             use futures::sink::SinkExt;
             // this is a curtesy, ok if fails
             let _ = self.evt_sender.close().await;
@@ -852,6 +890,18 @@ impl KitsuneP2pHandler for Space {
         self.publish_leave_agent_info(agent)
     }
 
+    fn handle_refresh_agent_info(
+        &mut self,
+        _space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+    ) -> KitsuneP2pHandlerResult<()> {
+        if !self.local_joined_agents.contains_key(&agent) {
+            return unit_ok_fut();
+        }
+        let fut = self.i_s.update_single_agent_info(agent);
+        Ok(async move { fut.await }.boxed().into())
+    }
+
     fn handle_rpc_single(
         &mut self,
         space: Arc<KitsuneSpace>,
@@ -1376,6 +1426,35 @@ impl Space {
             });
         }
 
+        {
+            let space = space.clone();
+            let host = host_api.clone();
+            let fetch_pool = fetch_pool.clone();
+            let i_s = i_s.clone();
+            tokio::task::spawn(async move {
+                match host.get_fetch_pool_pending(space.clone()).await {
+                    Ok(pending) => fetch_pool.restore(space.clone(), pending),
+                    Err(err) => tracing::error!(?err, "failed to restore the fetch pool"),
+                }
+
+                loop {
+                    use ghost_actor::GhostControlSender;
+                    tokio::time::sleep(std::time::Duration::from_millis(
+                        FETCH_POOL_PERSIST_FREQ_MS,
+                    ))
+                    .await;
+                    if !i_s.ghost_actor_is_active() {
+                        break;
+                    }
+
+                    let pending = fetch_pool.pending(&space);
+                    if let Err(err) = host.put_fetch_pool_pending(space.clone(), pending).await {
+                        tracing::error!(?err, "failed to persist the fetch pool");
+                    }
+                }
+            });
+        }
+
         let metric_exchange = MetricExchangeSync::spawn(
             space.clone(),
             config.tuning_params.clone(),
@@ -1384,8 +1463,7 @@ impl Space {
         );
 
         let gossip_mod = config
-            .tuning_params
-            .gossip_strategy
+            .gossip_strategy(&space)
             .split(',')
             .flat_map(|module| match module {
                 "sharded-gossip" => {
@@ -1408,9 +1486,15 @@ impl Space {
                     }
                     gossips
                 }
+                "full-sync" => vec![(
+                    GossipModuleType::FullSync,
+                    crate::gossip::full_sync_gossip::factory(bandwidth_throttles.recent()),
+                )],
                 "none" => vec![],
                 _ => {
-                    panic!("unknown gossip strategy: {}", module);
+                    // Strategies are validated when the config is loaded.
+                    tracing::error!(%module, "unknown gossip strategy, not gossiping it");
+                    vec![]
                 }
             })
             .map(|(module, factory)| {
@@ -1,7 +1,9 @@
 //! An in-memory network for sharded kitsune tests.
 
+use crate::gossip::full_sync_gossip::FullSyncGossip;
 use crate::gossip::sharded_gossip::{BandwidthThrottle, GossipType, ShardedGossip};
 use crate::meta_net::*;
+use crate::net_bandwidth::NetBandwidth;
 use crate::test_util::spawn_handler;
 use crate::types::gossip::*;
 use crate::types::wire;
@@ -9,6 +11,7 @@ use futures::stream::StreamExt;
 use ghost_actor::dependencies::tracing;
 use ghost_actor::GhostResult;
 use itertools::Itertools;
+use kitsune_p2p_fetch::{FetchKey, FetchPool};
 use kitsune_p2p_proxy::tx2::{tx2_proxy, ProxyConfig};
 use kitsune_p2p_timestamp::Timestamp;
 use kitsune_p2p_types::agent_info::agent_info_helper::{AgentInfoEncode, AgentMetaInfoEncode};
@@ -60,23 +63,23 @@ pub struct Switchboard {
     pub(super) strat: ArqStrat,
     pub(super) topology: Topology,
     inner: Share<SwitchboardState>,
-    gossip_type: GossipType,
+    gossip_type: GossipModuleType,
 }
 
 impl Switchboard {
-    /// Constructor. Only works for one GossipType at a time.
+    /// Constructor. Only works for one gossip module type at a time.
     // MAYBE: if it's desirable to test multiple gossip loops running at the
     //   same time on the same state, another method could be exposed to take
     //   an already instantiated `Share<SwitchboardState>`, which will cause
     //   both gossip loops to share the same state.
     //   Or, this could be modified to take a list of GossipTypes, so that
     //   multiple loops will be created internally.
-    pub fn new(topology: Topology, gossip_type: GossipType) -> Self {
+    pub fn new(topology: Topology, gossip_type: impl Into<GossipModuleType>) -> Self {
         Self {
             strat: ArqStrat::default(),
             topology,
             inner: Share::new(SwitchboardState::default()),
-            gossip_type,
+            gossip_type: gossip_type.into(),
         }
     }
 
@@ -132,19 +135,35 @@ impl Switchboard {
         let (evt_sender, handler_task) = spawn_handler(evt_handler.clone()).await;
 
         let bandwidth = Arc::new(BandwidthThrottle::new(1000.0, 1000.0, 10.0));
-
-        let gossip = ShardedGossip::new(
-            tuning_params,
-            space.clone(),
-            MetaNet::Tx2(ep_hnd.clone()),
-            evt_sender,
-            host_api,
-            self.gossip_type,
-            bandwidth,
-            Default::default(),
-            kitsune_p2p_fetch::FetchPool::new_bitwise_or(),
-        );
-        let gossip_module = GossipModule(gossip.clone());
+        let net_bandwidth = Arc::new(NetBandwidth::new(&tuning_params));
+        let fetch_pool = FetchPool::new_bitwise_or();
+
+        let gossip = match self.gossip_type {
+            GossipModuleType::FullSync => GossipModule(FullSyncGossip::new(
+                tuning_params,
+                space.clone(),
+                MetaNet::Tx2(ep_hnd.clone(), net_bandwidth.clone()),
+                evt_sender,
+                bandwidth,
+                Default::default(),
+                fetch_pool.clone(),
+            )),
+            sharded => GossipModule(ShardedGossip::new(
+                tuning_params,
+                space.clone(),
+                MetaNet::Tx2(ep_hnd.clone(), net_bandwidth.clone()),
+                evt_sender,
+                host_api,
+                match sharded {
+                    GossipModuleType::ShardedRecent => GossipType::Recent,
+                    _ => GossipType::Historical,
+                },
+                bandwidth,
+                Default::default(),
+                fetch_pool.clone(),
+            )),
+        };
+        let gossip_module = gossip.clone();
 
         let ep_task = metric_task(async move {
             while let Some(evt) = ep.next().await {
@@ -161,7 +180,7 @@ impl Switchboard {
                                 let data: Box<[u8]> = data.into_boxed_slice();
 
                                 gossip_module.incoming_gossip(
-                                    MetaNetCon::Tx2(con),
+                                    MetaNetCon::Tx2(con, net_bandwidth.clone()),
                                     url.to_string(),
                                     data,
                                 )?
@@ -190,6 +209,7 @@ impl Switchboard {
                     remote_agents: HashMap::new(),
                     ops: HashMap::new(),
                     gossip,
+                    fetch_pool,
                 },
             );
         });
@@ -467,6 +487,24 @@ impl SwitchboardState {
             .collect()
     }
 
+    /// Get all ops a node has been told to fetch, in terms of their Loc8 location.
+    ///
+    /// The switchboard doesn't fetch ops, so use this to make assertions about
+    /// what ops gossip has found to be missing.
+    pub fn get_ops_to_fetch_loc8(&mut self, node_ep: &NodeEp) -> BTreeSet<Loc8> {
+        self.nodes
+            .get(node_ep)
+            .unwrap()
+            .fetch_pool
+            .get_items_to_fetch()
+            .into_iter()
+            .filter_map(|(key, _, _, _)| match key {
+                FetchKey::Op(hash) => Some(hash.get_loc().as_loc8().to_unsigned()),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub(super) fn node_for_local_agent_loc8(&self, loc8: Loc8) -> Option<&NodeEntry> {
         self.nodes
             .values()
@@ -608,7 +646,9 @@ pub struct NodeEntry {
     /// The ops held by this node.
     /// Other data for this op can be found in SwitchboardSpace::ops
     pub(super) ops: HashMap<Loc8, NodeOpEntry>,
-    pub(super) gossip: Arc<ShardedGossip>,
+    pub(super) gossip: GossipModule,
+    /// The ops gossip has found to be missing from this node.
+    pub(super) fetch_pool: FetchPool,
 }
 
 impl NodeEntry {
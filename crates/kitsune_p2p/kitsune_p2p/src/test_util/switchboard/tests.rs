@@ -7,7 +7,8 @@ use kitsune_p2p_types::{
 use rand::Rng;
 
 use crate::{
-    gossip::sharded_gossip::GossipType, test_util::switchboard::switchboard_state::SwitchboardAgent,
+    gossip::sharded_gossip::GossipType,
+    test_util::switchboard::switchboard_state::SwitchboardAgent, types::gossip::GossipModuleType,
 };
 
 use super::super::switchboard_state::Switchboard;
@@ -382,10 +383,92 @@ async fn sharded_4way_historical() {
     });
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn full_sync_3way_missing_ops_are_fetched() {
+    holochain_trace::test_run().ok();
+    let topo = Topology::standard_epoch_full();
+    let sb = Switchboard::new(topo.clone(), GossipModuleType::FullSync);
+
+    let [n1, n2, n3] = sb.add_nodes(tuning_params()).await;
+
+    let a1 = SwitchboardAgent::full(1);
+    let a2 = SwitchboardAgent::full(2);
+    let a3 = SwitchboardAgent::full(3);
+
+    sb.share(|sb| {
+        sb.add_local_agent(&n1, &a1);
+        sb.add_local_agent(&n2, &a2);
+        sb.add_local_agent(&n3, &a3);
+
+        sb.add_ops_now(&n1, true, [10, 20, 30]);
+        sb.add_ops_now(&n2, true, [-10, -20, -30]);
+        // Full sync gossip isn't limited to recent ops.
+        sb.add_ops_timed(&n3, true, [(40, Timestamp::from_micros(1))]);
+
+        sb.exchange_all_peer_info();
+    });
+
+    // let gossip do its thing
+    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+    sb.share(|sb| {
+        assert_eq!(
+            (
+                sb.get_ops_to_fetch_loc8(&n1),
+                sb.get_ops_to_fetch_loc8(&n2),
+                sb.get_ops_to_fetch_loc8(&n3)
+            ),
+            (
+                Loc8::set([-30, -20, -10, 40]),
+                Loc8::set([10, 20, 30, 40]),
+                Loc8::set([-30, -20, -10, 10, 20, 30]),
+            )
+        );
+    });
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn full_sync_transitive_peer_gossip() {
+    holochain_trace::test_run().ok();
+    let topo = Topology::standard_epoch_full();
+    let sb = Switchboard::new(topo.clone(), GossipModuleType::FullSync);
+
+    let [n1, n2, n3] = sb.add_nodes(tuning_params()).await;
+
+    let a1 = SwitchboardAgent::full(1);
+    let a2 = SwitchboardAgent::full(2);
+    let a3 = SwitchboardAgent::full(3);
+
+    sb.share(|sb| {
+        sb.add_local_agent(&n1, &a1);
+        sb.add_local_agent(&n2, &a2);
+        sb.add_local_agent(&n3, &a3);
+
+        // 1 -> 2 -> 3
+        // (but neither 1 nor 3 know about each other)
+        sb.inject_peer_info(&n1, [&a2]);
+        sb.inject_peer_info(&n2, [&a3]);
+    });
+
+    // let gossip do its thing
+    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+    let agent_locs: Vec<_> = vec![a1.loc, a2.loc, a3.loc];
+
+    sb.share(|sb| {
+        sb.print_peer_lists();
+        assert_eq!(
+            (&sb.all_peers(&n1), &sb.all_peers(&n2), &sb.all_peers(&n3)),
+            (&agent_locs, &agent_locs, &agent_locs)
+        );
+    });
+}
+
 /// Set tuning params such that many rounds of gossip happen during the test,
 /// to mitigate the false-positive rate inherent to the bloom filters.
 fn tuning_params() -> KitsuneP2pTuningParams {
     let mut tp = kitsune_p2p_types::config::tuning_params_struct::KitsuneP2pTuningParams::default();
     tp.gossip_peer_on_success_next_gossip_delay_ms = 1;
+    tp.gossip_loop_iteration_delay_ms = 10;
     std::sync::Arc::new(tp)
 }
@@ -106,6 +106,10 @@ ghost_actor::ghost_chan! {
         /// Withdraw this space/agent pair from this network.
         fn leave(space: KSpace, agent: KAgent) -> ();
 
+        /// Re-sign and publish the agent info of a joined space/agent pair,
+        /// e.g. to pick up a change in its arc pin from the host.
+        fn refresh_agent_info(space: KSpace, agent: KAgent) -> ();
+
         /// Make a request of a single remote agent, expecting a response.
         /// The remote side will receive a "Call" event.
         fn rpc_single(space: KSpace, to_agent: KAgent, payload: Payload, timeout_ms: OptU64) -> Vec<u8>;
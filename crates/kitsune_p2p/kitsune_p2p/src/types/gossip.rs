@@ -14,6 +14,8 @@ pub enum GossipModuleType {
     ShardedRecent,
     /// Historical sharded gossip.
     ShardedHistorical,
+    /// Full sync gossip, for small networks where every node holds everything.
+    FullSync,
 }
 
 /// Represents an interchangeable gossip strategy module
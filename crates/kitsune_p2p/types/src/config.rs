@@ -80,12 +80,18 @@ pub mod tuning_params_struct {
     }
 
     mk_tune! {
-        /// Gossip strategy to use. [Default: "sharded-gossip"]
+        /// Gossip strategy to use, one of "sharded-gossip", "full-sync"
+        /// or "none". [Default: "sharded-gossip"]
         gossip_strategy: String = "sharded-gossip".to_string(),
 
         /// Delay between gossip loop iteration. [Default: 1s]
         gossip_loop_iteration_delay_ms: u32 = 1000,
 
+        /// How long a gossip round can go without contact from the remote
+        /// node before it is abandoned. Used by the "full-sync" gossip
+        /// strategy. [Default: 1 minute]
+        gossip_round_timeout_ms: u32 = 1000 * 60,
+
         /// The gossip loop will attempt to rate-limit output
         /// to this count megabits per second. [Default: 100.0]
         gossip_outbound_target_mbps: f64 = 100.0,
@@ -113,6 +119,40 @@ pub mod tuning_params_struct {
         /// seconds to "refill"). [Default: 100.0]
         gossip_burst_ratio: f64 = 100.0,
 
+        /// All outbound traffic, including gossip, publishes, fetch
+        /// responses and remote calls, will be rate-limited to this
+        /// count megabits per second. 0.0 means no limit. [Default: 0.0]
+        network_outbound_target_mbps: f64 = 0.0,
+
+        /// All inbound traffic, including gossip, publishes, fetch
+        /// responses and remote calls, will be rate-limited to this
+        /// count megabits per second. 0.0 means no limit. [Default: 0.0]
+        network_inbound_target_mbps: f64 = 0.0,
+
+        /// Outbound traffic to any single peer will be rate-limited
+        /// to this count megabits per second, so that one peer can't
+        /// use up the whole `network_outbound_target_mbps`.
+        /// 0.0 means no limit. [Default: 0.0]
+        network_peer_outbound_target_mbps: f64 = 0.0,
+
+        /// Inbound traffic from any single peer will be rate-limited
+        /// to this count megabits per second, so that one peer can't
+        /// use up the whole `network_inbound_target_mbps`.
+        /// 0.0 means no limit. [Default: 0.0]
+        network_peer_inbound_target_mbps: f64 = 0.0,
+
+        /// The excess capacity accomodated before enacting the `network_*`
+        /// rate limits, expressed as a ratio of the limit, as for
+        /// `gossip_burst_ratio`. This must be large enough to let
+        /// the largest message through. [Default: 100.0]
+        network_burst_ratio: f64 = 100.0,
+
+        /// How many incoming messages from a single peer can wait for
+        /// its share of the inbound bandwidth before further messages from
+        /// it are dropped. Only used when a `network_*` limit is set.
+        /// [Default: 64]
+        network_peer_queue_len: u32 = 64,
+
         /// How long should we hold off talking to a peer
         /// we've previously spoken successfully to.
         /// [Default: 1 minute]
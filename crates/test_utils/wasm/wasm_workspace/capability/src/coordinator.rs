@@ -50,7 +50,8 @@ pub fn unblock_agent(target: AgentPubKey) -> ExternResult<()> {
 fn cap_grant_entry(secret: CapSecret) -> ExternResult<CapGrantEntry> {
     let mut fns = BTreeSet::new();
     let this_zome = zome_info()?.name;
-    fns.insert((this_zome, "needs_cap_claim".into()));
+    fns.insert((this_zome.clone(), "needs_cap_claim".into()));
+    fns.insert((this_zome, "cap_info".into()));
     let functions = GrantedFunctions::Listed(fns);
     Ok(CapGrantEntry {
         tag: "".into(),
@@ -108,6 +109,32 @@ fn try_cap_claim(cap_for: crate::CapFor) -> ExternResult<ZomeCallResponse> {
     )
 }
 
+#[hdk_extern]
+fn try_cap_info(cap_for: crate::CapFor) -> ExternResult<ZomeCallResponse> {
+    call_remote(
+        cap_for.1,
+        zome_info()?.name,
+        "cap_info".to_string().into(),
+        Some(cap_for.0),
+        &(),
+    )
+}
+
+#[hdk_extern]
+fn cap_grants(input: CapGrantsInput) -> ExternResult<Vec<CapGrantInfo>> {
+    capability_grants(input)
+}
+
+#[hdk_extern]
+fn cap_claims(input: CapClaimsInput) -> ExternResult<Vec<CapClaim>> {
+    capability_claims(input)
+}
+
+#[hdk_extern]
+fn cap_info(_: ()) -> ExternResult<CapabilityInfo> {
+    capability_info()
+}
+
 #[hdk_extern]
 fn send_assigned_cap_claim(agent: AgentPubKey) -> ExternResult<()> {
     let tag = String::from("has_cap_claim");
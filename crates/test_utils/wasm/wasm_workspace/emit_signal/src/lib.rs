@@ -6,6 +6,14 @@ fn emit(_: ()) -> ExternResult<()> {
     Ok(())
 }
 
+#[hdk_extern]
+fn stream(count: u32) -> ExternResult<u32> {
+    for i in 0..count {
+        stream_chunk(i)?;
+    }
+    Ok(count)
+}
+
 #[hdk_extern]
 fn signal_others(signal: RemoteSignal) -> ExternResult<()> {
     remote_signal(&signal.signal, signal.agents)
@@ -153,6 +153,11 @@ fn get_external_links(_: ()) -> ExternResult<Vec<Link>> {
     hdk::prelude::get_links(external()?, LinkTypes::SomeLinks, None)
 }
 
+#[hdk_extern]
+fn get_links_filtered(filter: GetLinksFilter) -> ExternResult<Vec<Link>> {
+    hdk::prelude::get_links_filtered(base()?, LinkTypes::SomeLinks, None, filter)
+}
+
 #[hdk_extern]
 fn get_link_details(_: ()) -> ExternResult<LinkDetails> {
     hdk::prelude::get_link_details(base()?, LinkTypes::SomeLinks, None)
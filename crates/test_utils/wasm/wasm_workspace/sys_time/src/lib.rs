@@ -5,6 +5,11 @@ fn sys_time(_: ()) -> ExternResult<Timestamp> {
     hdk::prelude::sys_time()
 }
 
+#[hdk_extern]
+fn sleep(millis: u64) -> ExternResult<()> {
+    hdk::prelude::sleep(std::time::Duration::from_millis(millis))
+}
+
 #[cfg(all(test, feature = "mock"))]
 pub mod test {
     use hdk::prelude::*;